@@ -1,6 +1,10 @@
 //! A wrapper around graph types that enforces an acyclicity invariant.
 
-use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec,
+    vec::Vec,
+};
 use core::{
     cell::RefCell,
     cmp::Ordering,
@@ -13,12 +17,12 @@ use crate::stable_graph::StableDiGraph;
 use crate::{
     Direction,
     adj::IndexType,
-    algo::Cycle,
+    algo::{Cycle, CycleWitness},
     data::{Build, Create, DataMap, DataMapMut},
     graph::NodeIndex,
     prelude::DiGraph,
     visit::{
-        Control, Data, DfsEvent, EdgeCount, EdgeIndexable, GetAdjacencyMatrix, GraphBase,
+        Control, Data, DfsEvent, EdgeCount, EdgeIndexable, EdgeRef, GetAdjacencyMatrix, GraphBase,
         GraphProp, IntoEdgeReferences, IntoEdges, IntoEdgesDirected, IntoNeighbors,
         IntoNeighborsDirected, IntoNodeIdentifiers, IntoNodeReferences, NodeCompactIndexable,
         NodeCount, NodeIndexable, Reversed, Time, Visitable, dfs_visitor,
@@ -62,7 +66,8 @@ pub use order_map::TopologicalPosition;
 /// [`Build::update_edge`] methods will panic if it is attempted to add an edge
 /// that would create a cycle. The [`Build::add_edge`] on the other hand method
 /// will return `None` if the edge cannot be added (either it already exists on
-/// a graph type that does not support it or would create a cycle).
+/// a graph type that does not support it or would create a cycle). Use
+/// [`Acyclic::closing_path`] to find the cycle that an edge would create.
 #[derive(Clone, Debug)]
 pub struct Acyclic<G: Visitable> {
     /// The underlying graph, accessible through the `inner` method.
@@ -241,6 +246,79 @@ where
         }
     }
 
+    /// Find the path that an edge `a -> b` would close into a cycle.
+    ///
+    /// If adding the edge would fail with [`AcyclicEdgeError::Cycle`] or
+    /// [`AcyclicEdgeError::SelfLoop`], returns the cycle that it would create,
+    /// from `b` to `a` and back. Its edges are `Some` of the edges along the
+    /// path, followed by `None` for the edge `a -> b` that is not in the graph.
+    /// Returns `None` if the edge can be added.
+    ///
+    /// **Panics** if `a` or `b` are not found.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::{acyclic::Acyclic, data::Build, prelude::*};
+    ///
+    /// let mut dag = Acyclic::<DiGraph<&str, ()>>::new();
+    /// let a = dag.add_node("a");
+    /// let b = dag.add_node("b");
+    /// let c = dag.add_node("c");
+    /// let ab = dag.try_add_edge(a, b, ()).unwrap();
+    /// let bc = dag.try_add_edge(b, c, ()).unwrap();
+    ///
+    /// assert!(dag.try_add_edge(c, a, ()).is_err());
+    /// let cycle = dag.closing_path(c, a).unwrap();
+    /// assert_eq!(cycle.nodes(), [a, b, c]);
+    /// assert_eq!(cycle.edges(), [Some(ab), Some(bc), None]);
+    /// assert!(dag.closing_path(a, c).is_none());
+    /// ```
+    pub fn closing_path(
+        &self,
+        a: G::NodeId,
+        b: G::NodeId,
+    ) -> Option<CycleWitness<G::NodeId, Option<G::EdgeId>>>
+    where
+        G::NodeId: IndexType,
+        for<'a> &'a G: IntoEdges + GraphBase<NodeId = G::NodeId, EdgeId = G::EdgeId>,
+    {
+        if a == b {
+            return Some(CycleWitness::new(vec![a], vec![None]));
+        }
+        // Only nodes positioned between `b` and `a` can be on a path from `b` to `a`.
+        let max_order = self.get_position(a);
+        if self.get_position(b) > max_order {
+            return None;
+        }
+        let mut previous = BTreeMap::new();
+        let mut stack = vec![b];
+        while let Some(v) = stack.pop() {
+            for edge in self.graph.edges(v) {
+                let w = edge.target();
+                if w == b || previous.contains_key(&w) || self.get_position(w) > max_order {
+                    continue;
+                }
+                previous.insert(w, (v, edge.id()));
+                if w == a {
+                    let (mut nodes, mut edges) = (vec![a], Vec::new());
+                    let mut at = a;
+                    while let Some(&(before, id)) = previous.get(&at) {
+                        nodes.push(before);
+                        edges.push(Some(id));
+                        at = before;
+                    }
+                    nodes.reverse();
+                    edges.reverse();
+                    // The edge `a -> b` closes the cycle.
+                    edges.push(None);
+                    return Some(CycleWitness::new(nodes, edges));
+                }
+                stack.push(w);
+            }
+        }
+        None
+    }
+
     /// Update the ordering of the nodes in the order map resulting from adding an
     /// edge a -> b.
     ///
@@ -809,6 +887,48 @@ mod tests {
         assert!(acyclic.add_edge(d, a, ()).is_none());
     }
 
+    #[test]
+    fn test_closing_path() {
+        // a -> b -> c -> d and a -> d
+        let mut acyclic = Acyclic::<DiGraph<(), ()>>::new();
+        let nodes: Vec<_> = (0..5).map(|_| acyclic.add_node(())).collect();
+        let (a, b, c, d, e) = (nodes[0], nodes[1], nodes[2], nodes[3], nodes[4]);
+        let ab = acyclic.try_add_edge(a, b, ()).unwrap();
+        let bc = acyclic.try_add_edge(b, c, ()).unwrap();
+        let cd = acyclic.try_add_edge(c, d, ()).unwrap();
+        acyclic.try_add_edge(a, d, ()).unwrap();
+
+        for &x in &nodes {
+            for &y in &nodes {
+                let path = acyclic.closing_path(x, y);
+                assert_eq!(path.is_some(), !acyclic.is_valid_edge(x, y));
+                if let Some(cycle) = path {
+                    let (path_nodes, path_edges) = (cycle.nodes(), cycle.edges());
+                    assert_eq!(path_nodes.first(), Some(&y));
+                    assert_eq!(path_nodes.last(), Some(&x));
+                    assert_eq!(path_edges.len(), path_nodes.len());
+                    assert_eq!(path_edges.last(), Some(&None));
+                    for (i, &edge) in path_edges[..path_edges.len() - 1].iter().enumerate() {
+                        let (source, target) =
+                            acyclic.inner().edge_endpoints(edge.unwrap()).unwrap();
+                        assert_eq!((source, target), (path_nodes[i], path_nodes[i + 1]));
+                    }
+                }
+            }
+        }
+
+        let cycle = acyclic.closing_path(d, b).unwrap();
+        assert_eq!(cycle.nodes(), [b, c, d]);
+        assert_eq!(cycle.edges(), [Some(bc), Some(cd), None]);
+        assert_eq!(
+            acyclic.closing_path(c, a).unwrap().edges(),
+            [Some(ab), Some(bc), None]
+        );
+        let cycle = acyclic.closing_path(e, e).unwrap();
+        assert_eq!((cycle.nodes(), cycle.edges()), (&[e][..], &[None][..]));
+        assert!(acyclic.closing_path(e, a).is_none());
+    }
+
     #[cfg(feature = "stable_graph")]
     #[test]
     fn test_acyclic_graph_add_remove() {
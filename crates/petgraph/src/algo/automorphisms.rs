@@ -0,0 +1,241 @@
+//! The [automorphism group][1] of a graph: the permutations of its nodes that map the graph
+//! onto itself.
+//!
+//! The group is found by the search of [`canonical_form`](crate::algo::canonical_form), which
+//! records an automorphism whenever two leaves of its search tree relabel the graph the same
+//! way, and these automorphisms generate the whole group.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Graph_automorphism
+
+use alloc::{vec, vec::Vec};
+use core::hash::Hash;
+
+use hashbrown::HashMap;
+
+use super::{canonical::Search, node_positions};
+use crate::{
+    data::DataMap,
+    unionfind::UnionFind,
+    visit::{EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable},
+};
+
+/// The automorphism group of a graph, returned by [`automorphisms`] and
+/// [`automorphisms_matching`].
+#[derive(Clone, Debug)]
+pub struct Automorphisms<N> {
+    /// Automorphisms that generate the group, none of which is the identity.
+    ///
+    /// Each one is a `Vec` indexed by [`NodeIndexable::to_index`], which gives the image of
+    /// every node. Indices that are not in use map to themselves.
+    pub generators: Vec<Vec<N>>,
+    /// The orbit of every node, numbered from zero in the order of
+    /// [`IntoNodeIdentifiers::node_identifiers`]. Two nodes are in the same orbit if and only if
+    /// some automorphism maps one to the other.
+    pub orbits: HashMap<N, usize>,
+    /// The number of orbits.
+    pub orbit_count: usize,
+}
+
+/// Compute generators of the automorphism group of `graph`, and the orbits of its nodes.
+///
+/// Parallel edges and self-loops are taken into account. See [`automorphisms_matching`] to
+/// only allow automorphisms that preserve node and edge weights.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+///
+/// # Returns
+/// * [`Automorphisms`]: the generators and the orbits.
+///
+/// # Complexity
+/// * Time complexity: exponential in the worst case, but usually close to **O(|V|²(|V| + |E|) log
+///   |V|)**.
+/// * Auxiliary space: **O(|V|(|V| + |E|))**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::automorphisms, prelude::*};
+///
+/// // A square a - b - c - d - a, with a tail e on a.
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 0), (0, 4)]);
+///
+/// let group = automorphisms(&g);
+/// // The only symmetry is the reflection that swaps b and d.
+/// assert_eq!(group.generators.len(), 1);
+/// let swap = &group.generators[0];
+/// assert_eq!(swap[1], NodeIndex::new(3));
+/// assert_eq!(swap[0], NodeIndex::new(0));
+///
+/// assert_eq!(group.orbit_count, 4);
+/// assert_eq!(
+///     group.orbits[&NodeIndex::new(1)],
+///     group.orbits[&NodeIndex::new(3)]
+/// );
+/// ```
+pub fn automorphisms<G>(graph: G) -> Automorphisms<G::NodeId>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    G::NodeId: Eq + Hash,
+{
+    let nodes = graph.node_identifiers().collect::<Vec<_>>();
+    let edges = graph.edge_references().map(|e| (e, 0)).collect();
+    search(graph, nodes.iter().map(|_| 0).collect(), edges)
+}
+
+/// Compute generators of the group of automorphisms of `graph` that preserve node and edge
+/// weights, and the orbits of its nodes.
+///
+/// An automorphism must map every node to a node whose weight matches according to
+/// `node_match`, and every edge to an edge whose weight matches according to `edge_match`, like
+/// in [`is_isomorphic_matching`](crate::algo::is_isomorphic_matching). Both closures should be
+/// equivalence relations.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+/// * `node_match`: closure that returns whether two node weights match.
+/// * `edge_match`: closure that returns whether two edge weights match.
+///
+/// # Returns
+/// * [`Automorphisms`]: the generators and the orbits.
+///
+/// # Complexity
+/// * Time complexity: exponential in the worst case, but usually close to **O(|V|²(|V| + |E|) log
+///   |V| + (|V| + |E|)k)**.
+/// * Auxiliary space: **O(|V|(|V| + |E|))**.
+///
+/// where **|V|** is the number of nodes, **|E|** is the number of edges and **k** is the number
+/// of classes of matching weights.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::automorphisms_matching, prelude::*};
+///
+/// // A triangle with one node of another element.
+/// let g = UnGraph::<&str, ()>::from_edges([(0, 1), (1, 2), (2, 0)])
+///     .map(|v, _| ["C", "C", "N"][v.index()], |_, _| ());
+///
+/// let group = automorphisms_matching(&g, |a, b| a == b, |_, _| true);
+/// assert_eq!(group.generators.len(), 1);
+/// assert_eq!(group.orbit_count, 2);
+/// assert_eq!(
+///     group.orbits[&NodeIndex::new(0)],
+///     group.orbits[&NodeIndex::new(1)]
+/// );
+/// assert_ne!(
+///     group.orbits[&NodeIndex::new(0)],
+///     group.orbits[&NodeIndex::new(2)]
+/// );
+/// ```
+pub fn automorphisms_matching<G, NM, EM>(
+    graph: G,
+    node_match: NM,
+    edge_match: EM,
+) -> Automorphisms<G::NodeId>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp + DataMap,
+    G::NodeId: Eq + Hash,
+    NM: FnMut(&G::NodeWeight, &G::NodeWeight) -> bool,
+    EM: FnMut(&G::EdgeWeight, &G::EdgeWeight) -> bool,
+{
+    let nodes = graph.node_identifiers().collect::<Vec<_>>();
+    let node_weights = nodes
+        .iter()
+        .map(|&v| graph.node_weight(v).unwrap())
+        .collect::<Vec<_>>();
+    let node_classes = classes(&node_weights, node_match);
+
+    let edges = graph.edge_references().collect::<Vec<_>>();
+    let edge_weights = edges.iter().map(|e| e.weight()).collect::<Vec<_>>();
+    let edge_classes = classes(&edge_weights, edge_match);
+
+    search(
+        graph,
+        node_classes,
+        edges.into_iter().zip(edge_classes).collect(),
+    )
+}
+
+/// Sort `items` into classes of items that match, and return the class of every item.
+fn classes<T: ?Sized, M>(items: &[&T], mut same: M) -> Vec<usize>
+where
+    M: FnMut(&T, &T) -> bool,
+{
+    let mut representatives = Vec::<usize>::new();
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            representatives
+                .iter()
+                .position(|&r| same(items[r], item))
+                .unwrap_or_else(|| {
+                    representatives.push(i);
+                    representatives.len() - 1
+                })
+        })
+        .collect()
+}
+
+/// Search the automorphisms of `graph` that preserve the classes of the nodes, given in the
+/// order of [`IntoNodeIdentifiers::node_identifiers`], and the classes of the edges.
+fn search<G>(
+    graph: G,
+    node_classes: Vec<usize>,
+    edges: Vec<(G::EdgeRef, usize)>,
+) -> Automorphisms<G::NodeId>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    G::NodeId: Eq + Hash,
+{
+    let (nodes, index) = node_positions(graph);
+    let edges = edges
+        .into_iter()
+        .map(|(e, class)| {
+            let a = index[graph.to_index(e.source())];
+            let b = index[graph.to_index(e.target())];
+            (a, b, class)
+        })
+        .collect();
+
+    let mut search = Search::new(graph.is_directed(), edges, nodes.len());
+    search.search(node_classes);
+
+    let mut orbits = UnionFind::new(nodes.len());
+    let generators = search
+        .generators
+        .into_iter()
+        .map(|gamma| {
+            let mut generator = (0..graph.node_bound())
+                .map(|i| graph.from_index(i))
+                .collect::<Vec<_>>();
+            for (v, &w) in gamma.iter().enumerate() {
+                orbits.union(v, w);
+                generator[graph.to_index(nodes[v])] = nodes[w];
+            }
+            generator
+        })
+        .collect();
+
+    let mut numbers = vec![usize::MAX; nodes.len()];
+    let mut orbit_count = 0;
+    let orbits = nodes
+        .iter()
+        .enumerate()
+        .map(|(v, &node)| {
+            let root = orbits.find(v);
+            if numbers[root] == usize::MAX {
+                numbers[root] = orbit_count;
+                orbit_count += 1;
+            }
+            (node, numbers[root])
+        })
+        .collect();
+
+    Automorphisms {
+        generators,
+        orbits,
+        orbit_count,
+    }
+}
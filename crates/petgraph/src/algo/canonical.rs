@@ -0,0 +1,425 @@
+//! Canonical labeling of graphs, by individualization and refinement in the style of
+//! [nauty and Traces][1].
+//!
+//! A canonical labeling orders the nodes of a graph so that isomorphic graphs, and only
+//! isomorphic graphs, look the same once their nodes are renamed to their positions in the
+//! order. This makes it possible to compare graphs with `==`, or to store them in a hash set.
+//!
+//! The search starts from the partition of the nodes by label and refines it until nodes in
+//! the same cell have the same number of neighbors in every cell. If some cell still has more
+//! than one node, each of them is singled out in turn, and the partition refined again, which
+//! builds a search tree whose leaves order all nodes. The canonical labeling is the leaf whose
+//! relabeled edges are smallest. Leaves with equal edges reveal automorphisms of the graph,
+//! which let the search skip the branches that they map to branches already explored.
+//!
+//! [1]: https://doi.org/10.1016/j.jsc.2013.09.003
+
+use alloc::{vec, vec::Vec};
+use core::cmp::Ordering;
+
+use crate::{
+    algo::node_positions,
+    unionfind::UnionFind,
+    visit::{EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable},
+};
+
+/// The canonical form of a graph, returned by [`canonical_form`].
+///
+/// Nodes are numbered by their positions in the canonical labeling, so the canonical forms of
+/// two graphs are equal if and only if the graphs are isomorphic, with matching labels.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CanonicalForm<N, E> {
+    /// Whether the graph is directed.
+    pub directed: bool,
+    /// The labels of the nodes, in canonical order.
+    pub node_labels: Vec<N>,
+    /// The edges as `(source, target, label)`, sorted. The endpoints of undirected edges are
+    /// stored with the smaller position first.
+    pub edges: Vec<(usize, usize, E)>,
+}
+
+/// Compute the canonical form of `graph`.
+///
+/// Two graphs have the same canonical form if and only if they are isomorphic, with an
+/// isomorphism that preserves the labels of the nodes and edges. Parallel edges and self-loops
+/// are taken into account.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+/// * `node_label`: closure that returns the label of a node; use `|_| ()` for no labels.
+/// * `edge_label`: closure that returns the label of an edge; use `|_| ()` for no labels.
+///
+/// # Returns
+/// * [`CanonicalForm`]: the labels and the edges of `graph`, with the nodes numbered in the order
+///   of [`canonical_labeling`].
+///
+/// # Complexity
+/// * Time complexity: exponential in the worst case, but usually close to **O(|V|(|V| + |E|) log
+///   |V|)**.
+/// * Auxiliary space: **O(|V|(|V| + |E|))**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::canonical_form, prelude::*};
+///
+/// // A triangle with a tail, twice, with the nodes in different orders.
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (2, 3)]);
+/// let h = UnGraph::<(), ()>::from_edges([(3, 2), (2, 0), (0, 3), (1, 0)]);
+/// // A cycle.
+/// let k = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 0)]);
+///
+/// let form = |g: &UnGraph<(), ()>| canonical_form(g, |_| (), |_| ());
+/// assert_eq!(form(&g), form(&h));
+/// assert_ne!(form(&g), form(&k));
+/// ```
+pub fn canonical_form<G, NL, EL, N, E>(
+    graph: G,
+    node_label: NL,
+    edge_label: EL,
+) -> CanonicalForm<N, E>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    NL: FnMut(G::NodeId) -> N,
+    EL: FnMut(G::EdgeRef) -> E,
+    N: Ord,
+    E: Ord,
+{
+    let (_, node_labels, edges, positions) = canonize(graph, node_label, edge_label);
+
+    let mut node_labels = node_labels.into_iter().map(Some).collect::<Vec<_>>();
+    let mut order = vec![0; positions.len()];
+    for (v, &position) in positions.iter().enumerate() {
+        order[position] = v;
+    }
+    let node_labels = order
+        .into_iter()
+        .map(|v| node_labels[v].take().unwrap())
+        .collect();
+
+    let mut edges = edges
+        .into_iter()
+        .map(|(a, b, label)| {
+            let (a, b) = relabel(graph.is_directed(), &positions, a, b);
+            (a, b, label)
+        })
+        .collect::<Vec<_>>();
+    edges.sort_unstable();
+
+    CanonicalForm {
+        directed: graph.is_directed(),
+        node_labels,
+        edges,
+    }
+}
+
+/// Compute a canonical labeling of `graph`, that is an order of its nodes that is the same for
+/// isomorphic graphs, up to automorphisms.
+///
+/// See [`canonical_form`], which renames the nodes of the graph according to this order.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+/// * `node_label`: closure that returns the label of a node; use `|_| ()` for no labels.
+/// * `edge_label`: closure that returns the label of an edge; use `|_| ()` for no labels.
+///
+/// # Returns
+/// * `Vec<G::NodeId>`: the nodes of `graph`, in canonical order.
+///
+/// # Complexity
+/// * Time complexity: exponential in the worst case, but usually close to **O(|V|(|V| + |E|) log
+///   |V|)**.
+/// * Auxiliary space: **O(|V|(|V| + |E|))**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::canonical_labeling, prelude::*};
+///
+/// // Two directed paths a -> b -> c, with the nodes in different orders.
+/// let g = DiGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+/// let h = DiGraph::<(), ()>::from_edges([(2, 0), (1, 2)]);
+///
+/// let g_order = canonical_labeling(&g, |_| (), |_| ());
+/// let h_order = canonical_labeling(&h, |_| (), |_| ());
+/// // The canonical labelings map the paths onto each other.
+/// assert_eq!(
+///     g_order.iter().map(|v| v.index()).collect::<Vec<_>>(),
+///     [0, 1, 2]
+/// );
+/// assert_eq!(
+///     h_order.iter().map(|v| v.index()).collect::<Vec<_>>(),
+///     [1, 2, 0]
+/// );
+/// ```
+pub fn canonical_labeling<G, NL, EL, N, E>(
+    graph: G,
+    node_label: NL,
+    edge_label: EL,
+) -> Vec<G::NodeId>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    NL: FnMut(G::NodeId) -> N,
+    EL: FnMut(G::EdgeRef) -> E,
+    N: Ord,
+    E: Ord,
+{
+    let (nodes, _, _, positions) = canonize(graph, node_label, edge_label);
+    let mut order = nodes.clone();
+    for (v, &position) in positions.iter().enumerate() {
+        order[position] = nodes[v];
+    }
+    order
+}
+
+/// Return the nodes of `graph`, their labels, the edges between them as indices into the
+/// nodes, and the canonical position of every node.
+#[allow(clippy::type_complexity)]
+fn canonize<G, NL, EL, N, E>(
+    graph: G,
+    mut node_label: NL,
+    mut edge_label: EL,
+) -> (Vec<G::NodeId>, Vec<N>, Vec<(usize, usize, E)>, Vec<usize>)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    NL: FnMut(G::NodeId) -> N,
+    EL: FnMut(G::EdgeRef) -> E,
+    N: Ord,
+    E: Ord,
+{
+    let (nodes, index) = node_positions(graph);
+    let node_labels = nodes.iter().map(|&v| node_label(v)).collect::<Vec<_>>();
+    let edges = graph
+        .edge_references()
+        .map(|e| {
+            let a = index[graph.to_index(e.source())];
+            let b = index[graph.to_index(e.target())];
+            (a, b, edge_label(e))
+        })
+        .collect::<Vec<_>>();
+
+    let colors = ranks(&node_labels);
+    let edge_colors = ranks(&edges.iter().map(|(_, _, label)| label).collect::<Vec<_>>());
+    let colored_edges = edges
+        .iter()
+        .zip(edge_colors)
+        .map(|(&(a, b, _), color)| (a, b, color))
+        .collect();
+
+    let mut search = Search::new(graph.is_directed(), colored_edges, nodes.len());
+    search.search(colors);
+    let positions = search.best.unwrap().positions;
+    (nodes, node_labels, edges, positions)
+}
+
+/// Return the rank of every value among the distinct values.
+pub(crate) fn ranks<T: Ord>(values: &[T]) -> Vec<usize> {
+    let mut order = (0..values.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| values[a].cmp(&values[b]));
+    let mut ranks = vec![0; values.len()];
+    for i in 1..order.len() {
+        let (previous, v) = (order[i - 1], order[i]);
+        ranks[v] = ranks[previous] + usize::from(values[previous] != values[v]);
+    }
+    ranks
+}
+
+fn relabel(directed: bool, positions: &[usize], a: usize, b: usize) -> (usize, usize) {
+    let (a, b) = (positions[a], positions[b]);
+    if directed || a <= b { (a, b) } else { (b, a) }
+}
+
+/// A leaf of the search tree.
+#[derive(Clone)]
+struct Leaf {
+    /// The nodes individualized on the way to the leaf.
+    path: Vec<usize>,
+    /// The position of every node.
+    positions: Vec<usize>,
+    /// The relabeled edges, sorted.
+    certificate: Vec<(usize, usize, usize)>,
+}
+
+/// The search tree of individualization and refinement, over nodes `0..n`, with colored nodes
+/// and edges.
+///
+/// The automorphisms that it finds generate the automorphism group of the colored graph.
+pub(crate) struct Search {
+    directed: bool,
+    edges: Vec<(usize, usize, usize)>,
+    /// The neighbors of every node, with a code for the color and the direction of the edge.
+    adjacency: Vec<Vec<(usize, usize)>>,
+    path: Vec<usize>,
+    first: Option<Leaf>,
+    best: Option<Leaf>,
+    /// The automorphisms found so far, as maps from nodes to nodes.
+    pub(crate) generators: Vec<Vec<usize>>,
+}
+
+impl Search {
+    pub(crate) fn new(
+        directed: bool,
+        edges: Vec<(usize, usize, usize)>,
+        node_count: usize,
+    ) -> Self {
+        let mut adjacency = vec![Vec::new(); node_count];
+        for &(a, b, color) in &edges {
+            if directed {
+                adjacency[a].push((2 * color, b));
+                adjacency[b].push((2 * color + 1, a));
+            } else {
+                adjacency[a].push((color, b));
+                adjacency[b].push((color, a));
+            }
+        }
+        Search {
+            directed,
+            edges,
+            adjacency,
+            path: Vec::new(),
+            first: None,
+            best: None,
+            generators: Vec::new(),
+        }
+    }
+
+    /// Search the subtree below the partition `colors`, at the end of `self.path`.
+    ///
+    /// Return the depth to jump back to, if an automorphism shows that the rest of the subtree
+    /// of the node at that depth is equivalent to a part of the tree already explored.
+    pub(crate) fn search(&mut self, mut colors: Vec<usize>) -> Option<usize> {
+        let cells = self.refine(&mut colors);
+        let node_count = colors.len();
+        let depth = self.path.len();
+
+        // Branch on the first cell with more than one node.
+        let mut sizes = vec![0usize; cells];
+        colors.iter().for_each(|&c| sizes[c] += 1);
+        let Some(cell) = sizes.iter().position(|&size| size > 1) else {
+            return self.leaf(colors);
+        };
+
+        let mut explored = Vec::<usize>::new();
+        let mut orbits = UnionFind::new(node_count);
+        let mut generators = 0;
+        for v in (0..node_count).filter(|&v| colors[v] == cell) {
+            if generators < self.generators.len() {
+                // Only automorphisms that fix the path map the children onto each other.
+                orbits = UnionFind::new(node_count);
+                for gamma in &self.generators {
+                    if self.path.iter().all(|&u| gamma[u] == u) {
+                        for (u, &w) in gamma.iter().enumerate() {
+                            orbits.union(u, w);
+                        }
+                    }
+                }
+                generators = self.generators.len();
+            }
+            if explored.iter().any(|&w| orbits.equiv(v, w)) {
+                continue;
+            }
+            explored.push(v);
+
+            let child = colors
+                .iter()
+                .enumerate()
+                .map(|(u, &c)| 2 * c + usize::from(c == cell && u != v))
+                .collect();
+            self.path.push(v);
+            let jump = self.search(child);
+            self.path.pop();
+            match jump {
+                Some(level) if level < depth => return Some(level),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Refine `colors` until it is equitable, and number the cells from zero, in an order
+    /// that depends only on the order of the cells of `colors`. Return the number of cells.
+    fn refine(&self, colors: &mut Vec<usize>) -> usize {
+        let node_count = colors.len();
+        let mut cells = None;
+        loop {
+            let signatures = (0..node_count)
+                .map(|v| {
+                    let mut neighbors = self.adjacency[v]
+                        .iter()
+                        .map(|&(code, u)| (code, colors[u]))
+                        .collect::<Vec<_>>();
+                    neighbors.sort_unstable();
+                    (colors[v], neighbors)
+                })
+                .collect::<Vec<_>>();
+            let mut order = (0..node_count).collect::<Vec<_>>();
+            order.sort_by(|&a, &b| signatures[a].cmp(&signatures[b]));
+
+            let mut count = 0;
+            for i in 0..order.len() {
+                if i > 0 && signatures[order[i - 1]] != signatures[order[i]] {
+                    count += 1;
+                }
+                colors[order[i]] = count;
+            }
+            let count = if node_count == 0 { 0 } else { count + 1 };
+            if cells == Some(count) {
+                return count;
+            }
+            cells = Some(count);
+        }
+    }
+
+    fn leaf(&mut self, positions: Vec<usize>) -> Option<usize> {
+        let mut certificate = self
+            .edges
+            .iter()
+            .map(|&(a, b, color)| {
+                let (a, b) = relabel(self.directed, &positions, a, b);
+                (a, b, color)
+            })
+            .collect::<Vec<_>>();
+        certificate.sort_unstable();
+        let leaf = Leaf {
+            path: self.path.clone(),
+            positions,
+            certificate,
+        };
+
+        let Some(first) = &self.first else {
+            self.best = Some(leaf.clone());
+            self.first = Some(leaf);
+            return None;
+        };
+        if first.certificate == leaf.certificate {
+            return Some(Self::automorphism(&mut self.generators, first, &leaf));
+        }
+        let best = self.best.as_ref().unwrap();
+        match leaf.certificate.cmp(&best.certificate) {
+            Ordering::Equal => Some(Self::automorphism(&mut self.generators, best, &leaf)),
+            Ordering::Less => {
+                self.best = Some(leaf);
+                None
+            }
+            Ordering::Greater => None,
+        }
+    }
+
+    /// Record the automorphism that maps `leaf` to `other`, which have the same certificate,
+    /// and return the depth of their last common ancestor.
+    fn automorphism(generators: &mut Vec<Vec<usize>>, other: &Leaf, leaf: &Leaf) -> usize {
+        let mut inverse = vec![0; other.positions.len()];
+        for (v, &position) in other.positions.iter().enumerate() {
+            inverse[position] = v;
+        }
+        generators.push(leaf.positions.iter().map(|&p| inverse[p]).collect());
+        other
+            .path
+            .iter()
+            .zip(&leaf.path)
+            .take_while(|(a, b)| a == b)
+            .count()
+    }
+}
@@ -0,0 +1,644 @@
+//! Centrality measures, which rank the nodes or edges of a graph by how central they are.
+//!
+//! * [`betweenness_centrality`] and [`edge_betweenness_centrality`] count the shortest paths
+//!   through every node or edge with [Brandes' algorithm][1], exactly or from a sample of sources.
+//! * [`closeness_centrality`] and [`harmonic_centrality`] measure how close a node is to all other
+//!   nodes.
+//!
+//! All of them compute shortest paths with Dijkstra's algorithm from every source, with edge
+//! costs given by the same kind of closure as [`dijkstra`][crate::algo::dijkstra()]. Use `|_| 1`
+//! for unweighted graphs. The work for different sources is independent, so with the `rayon`
+//! feature, every function has a `parallel_` variant that divides the sources among threads.
+//!
+//! Scores are returned as a `Vec<f64>` indexed by [`NodeIndexable::to_index`] or
+//! [`EdgeIndexable::to_index`], with a score of zero for indices that are not in use.
+//!
+//! [1]: https://doi.org/10.1080/0022250X.2001.9990249
+
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+
+use crate::{
+    algo::Measure,
+    scored::MinScored,
+    visit::{EdgeIndexable, EdgeRef, GraphProp, IntoEdges, IntoNodeIdentifiers, NodeIndexable},
+};
+
+/// Compute the [betweenness centrality][1] of every node with [Brandes' algorithm][2].
+///
+/// The betweenness of a node **v** is the sum over all pairs of other nodes **s** and **t** of
+/// the fraction of shortest paths from **s** to **t** that pass through **v**. In an undirected
+/// graph, every pair of nodes is counted once.
+///
+/// If `sources` is given, only the shortest paths from those nodes are counted, and the scores
+/// are scaled by **|V| / k**, where **k** is the number of sources. A uniformly random sample of
+/// distinct nodes gives an unbiased estimate of the betweenness, which is much faster to compute
+/// on large graphs.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+/// * `normalized`: whether to divide the scores by the number of pairs of other nodes: **(|V| -
+///   1)(|V| - 2)**, or half of that for undirected graphs.
+/// * `sources`: the distinct nodes to count shortest paths from, or `None` for all nodes.
+/// * `edge_cost`: closure that returns the cost of an edge, which must be positive.
+///
+/// # Returns
+/// * `Vec<f64>`: the betweenness of every node, indexed by [`NodeIndexable::to_index`].
+///
+/// # Complexity
+/// * Time complexity: **O(k (|V| + |E|) log |V|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes, **|E|** is the number of edges and **k** is the number
+/// of sources.
+///
+/// [1]: https://en.wikipedia.org/wiki/Betweenness_centrality
+/// [2]: https://doi.org/10.1080/0022250X.2001.9990249
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::betweenness_centrality, prelude::*};
+///
+/// // Two triangles joined by a bridge between `c` and `d`.
+/// let mut g = UnGraph::<(), u32>::new_undirected();
+/// let [a, b, c, d, e, f] = [(); 6].map(|_| g.add_node(()));
+/// g.extend_with_edges(&[
+///     (a, b, 1),
+///     (b, c, 1),
+///     (c, a, 1),
+///     (c, d, 1),
+///     (d, e, 1),
+///     (e, f, 1),
+///     (f, d, 1),
+/// ]);
+///
+/// let betweenness = betweenness_centrality(&g, false, None, |e| *e.weight());
+/// // The paths from `a`, `b` and `c` to `d`, `e` and `f` all cross the bridge.
+/// assert_eq!(betweenness[c.index()], 6.);
+/// assert_eq!(betweenness[a.index()], 0.);
+///
+/// // Counting the paths from half of the nodes gives an estimate.
+/// let sample = betweenness_centrality(&g, false, Some(&[a, c, e]), |e| *e.weight());
+/// assert!((sample[d.index()] - 6.).abs() <= 1.);
+/// ```
+pub fn betweenness_centrality<G, F, K>(
+    graph: G,
+    normalized: bool,
+    sources: Option<&[G::NodeId]>,
+    mut edge_cost: F,
+) -> Vec<f64>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let (node_count, sources) = source_indices(graph, sources);
+    let mut scores = vec![0.; graph.node_bound()];
+    let mut paths = ShortestPaths::new(graph.node_bound());
+    for &s in &sources {
+        paths.search(graph, s, true, &mut edge_cost);
+        paths.accumulate(s, |v, delta| scores[v] += delta, |_, _| {});
+    }
+    let scale = node_scale(graph, node_count, sources.len(), normalized);
+    scores.iter_mut().for_each(|score| *score *= scale);
+    scores
+}
+
+/// Compute the betweenness centrality of every edge with [Brandes' algorithm][1].
+///
+/// The betweenness of an edge **e** is the sum over all pairs of nodes **s** and **t** of the
+/// fraction of shortest paths from **s** to **t** that use **e**. In an undirected graph, every
+/// pair of nodes is counted once.
+///
+/// If `sources` is given, only the shortest paths from those nodes are counted, and the scores
+/// are scaled by **|V| / k**, where **k** is the number of sources, as in
+/// [`betweenness_centrality`].
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+/// * `normalized`: whether to divide the scores by the number of pairs of nodes: **|V|(|V| - 1)**,
+///   or half of that for undirected graphs.
+/// * `sources`: the distinct nodes to count shortest paths from, or `None` for all nodes.
+/// * `edge_cost`: closure that returns the cost of an edge, which must be positive.
+///
+/// # Returns
+/// * `Vec<f64>`: the betweenness of every edge, indexed by [`EdgeIndexable::to_index`].
+///
+/// # Complexity
+/// * Time complexity: **O(k (|V| + |E|) log |V|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes, **|E|** is the number of edges and **k** is the number
+/// of sources.
+///
+/// [1]: https://doi.org/10.1016/j.socnet.2007.11.001
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::edge_betweenness_centrality, prelude::*};
+///
+/// // A square with a diagonal that is as long as two sides.
+/// let mut g = UnGraph::<(), u32>::new_undirected();
+/// let [a, b, c, d] = [(); 4].map(|_| g.add_node(()));
+/// g.extend_with_edges(&[(a, b, 1), (b, c, 1), (c, d, 1), (d, a, 1), (a, c, 2)]);
+///
+/// let betweenness = edge_betweenness_centrality(&g, false, None, |e| *e.weight());
+/// // The edge `ab` is on the only shortest path between `a` and `b`, on one of the two between
+/// // `b` and `d`, and on one of the three between `a` and `c`.
+/// let ab = g.find_edge(a, b).unwrap();
+/// assert_eq!(betweenness[ab.index()], 1. + 1. / 2. + 1. / 3.);
+/// ```
+pub fn edge_betweenness_centrality<G, F, K>(
+    graph: G,
+    normalized: bool,
+    sources: Option<&[G::NodeId]>,
+    mut edge_cost: F,
+) -> Vec<f64>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + EdgeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let (node_count, sources) = source_indices(graph, sources);
+    let mut scores = vec![0.; graph.edge_bound()];
+    let mut paths = ShortestPaths::new(graph.node_bound());
+    for &s in &sources {
+        paths.search(graph, s, true, &mut edge_cost);
+        paths.accumulate(
+            s,
+            |_, _| {},
+            |e, delta| scores[EdgeIndexable::to_index(&graph, e)] += delta,
+        );
+    }
+    let scale = edge_scale(graph, node_count, sources.len(), normalized);
+    scores.iter_mut().for_each(|score| *score *= scale);
+    scores
+}
+
+/// Compute the [closeness centrality][1] of every node.
+///
+/// The closeness of a node **u** that reaches **r** nodes, including itself, is
+/// **(r - 1) / Σ d(u, v)**, where the sum is over the nodes **v** that **u** reaches. It is scaled
+/// by **(r - 1) / (|V| - 1)**, as proposed by Wasserman and Faust, so that nodes that reach only
+/// a few others close by are not ranked highest. In a connected graph, this is the inverse of the
+/// average distance to the other nodes. Nodes that reach no other node have a closeness of zero.
+///
+/// The distances are measured from **u**; use [`Reversed`](crate::visit::Reversed) to measure
+/// them to **u** in a directed graph instead.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+/// * `edge_cost`: closure that returns the cost of an edge, which must be non-negative.
+///
+/// # Returns
+/// * `Vec<f64>`: the closeness of every node, indexed by [`NodeIndexable::to_index`].
+///
+/// # Complexity
+/// * Time complexity: **O(|V| (|V| + |E|) log |V|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Closeness_centrality
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::closeness_centrality, prelude::*};
+///
+/// // A path a - b - c and a separate node d.
+/// let mut g = UnGraph::<(), u32>::new_undirected();
+/// let [a, b, c, d] = [(); 4].map(|_| g.add_node(()));
+/// g.extend_with_edges(&[(a, b, 1), (b, c, 1)]);
+///
+/// let closeness = closeness_centrality(&g, |e| *e.weight());
+/// // `b` reaches two nodes at distance one, out of three other nodes.
+/// assert_eq!(closeness[b.index()], 2. / 2. * 2. / 3.);
+/// assert_eq!(closeness[a.index()], 2. / 3. * 2. / 3.);
+/// assert_eq!(closeness[d.index()], 0.);
+/// ```
+pub fn closeness_centrality<G, F, K>(graph: G, mut edge_cost: F) -> Vec<f64>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy + Into<f64>,
+{
+    let (node_count, sources) = source_indices(graph, None);
+    let mut scores = vec![0.; graph.node_bound()];
+    let mut paths = ShortestPaths::new(graph.node_bound());
+    for &s in &sources {
+        paths.search(graph, s, false, &mut edge_cost);
+        scores[s] = paths.closeness(node_count);
+    }
+    scores
+}
+
+/// Compute the [harmonic centrality][1] of every node.
+///
+/// The harmonic centrality of a node **u** is **Σ 1 / d(u, v)** over all other nodes **v**, where
+/// the nodes that **u** does not reach add nothing. Unlike closeness, it needs no correction for
+/// graphs that are not connected.
+///
+/// The distances are measured from **u**; use [`Reversed`](crate::visit::Reversed) to measure
+/// them to **u** in a directed graph instead.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+/// * `edge_cost`: closure that returns the cost of an edge, which must be positive.
+///
+/// # Returns
+/// * `Vec<f64>`: the harmonic centrality of every node, indexed by [`NodeIndexable::to_index`].
+///
+/// # Complexity
+/// * Time complexity: **O(|V| (|V| + |E|) log |V|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Closeness_centrality#In_disconnected_graphs
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::harmonic_centrality, prelude::*};
+///
+/// // A path a → b → c.
+/// let mut g = DiGraph::<(), f64>::new();
+/// let [a, b, c] = [(); 3].map(|_| g.add_node(()));
+/// g.extend_with_edges(&[(a, b, 1.), (b, c, 3.)]);
+///
+/// let harmonic = harmonic_centrality(&g, |e| *e.weight());
+/// assert_eq!(harmonic, [1. + 1. / 4., 1. / 3., 0.]);
+/// ```
+pub fn harmonic_centrality<G, F, K>(graph: G, mut edge_cost: F) -> Vec<f64>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy + Into<f64>,
+{
+    let (_, sources) = source_indices(graph, None);
+    let mut scores = vec![0.; graph.node_bound()];
+    let mut paths = ShortestPaths::new(graph.node_bound());
+    for &s in &sources {
+        paths.search(graph, s, false, &mut edge_cost);
+        scores[s] = paths.harmonic(s);
+    }
+    scores
+}
+
+/// Compute the betweenness centrality of every node in parallel.
+///
+/// See [`betweenness_centrality`].
+#[cfg(feature = "rayon")]
+pub fn parallel_betweenness_centrality<G, F, K>(
+    graph: G,
+    normalized: bool,
+    sources: Option<&[G::NodeId]>,
+    edge_cost: F,
+) -> Vec<f64>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + GraphProp + Sync,
+    G::EdgeId: Send,
+    F: Fn(G::EdgeRef) -> K + Sync,
+    K: Measure + Copy + Send,
+{
+    let (node_count, sources) = source_indices(graph, sources);
+    let mut scores = parallel_sum(
+        graph.node_bound(),
+        graph.node_bound(),
+        &sources,
+        |paths, s, scores| {
+            paths.search(graph, s, true, &mut &edge_cost);
+            paths.accumulate(s, |v, delta| scores[v] += delta, |_, _| {});
+        },
+    );
+    let scale = node_scale(graph, node_count, sources.len(), normalized);
+    scores.iter_mut().for_each(|score| *score *= scale);
+    scores
+}
+
+/// Compute the betweenness centrality of every edge in parallel.
+///
+/// See [`edge_betweenness_centrality`].
+#[cfg(feature = "rayon")]
+pub fn parallel_edge_betweenness_centrality<G, F, K>(
+    graph: G,
+    normalized: bool,
+    sources: Option<&[G::NodeId]>,
+    edge_cost: F,
+) -> Vec<f64>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + EdgeIndexable + GraphProp + Sync,
+    G::EdgeId: Send,
+    F: Fn(G::EdgeRef) -> K + Sync,
+    K: Measure + Copy + Send,
+{
+    let (node_count, sources) = source_indices(graph, sources);
+    let mut scores = parallel_sum(
+        graph.node_bound(),
+        graph.edge_bound(),
+        &sources,
+        |paths, s, scores| {
+            paths.search(graph, s, true, &mut &edge_cost);
+            paths.accumulate(
+                s,
+                |_, _| {},
+                |e, delta| scores[EdgeIndexable::to_index(&graph, e)] += delta,
+            );
+        },
+    );
+    let scale = edge_scale(graph, node_count, sources.len(), normalized);
+    scores.iter_mut().for_each(|score| *score *= scale);
+    scores
+}
+
+/// Compute the closeness centrality of every node in parallel.
+///
+/// See [`closeness_centrality`].
+#[cfg(feature = "rayon")]
+pub fn parallel_closeness_centrality<G, F, K>(graph: G, edge_cost: F) -> Vec<f64>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + Sync,
+    G::EdgeId: Send,
+    F: Fn(G::EdgeRef) -> K + Sync,
+    K: Measure + Copy + Into<f64> + Send,
+{
+    let (node_count, sources) = source_indices(graph, None);
+    parallel_map(graph.node_bound(), &sources, |paths, s| {
+        paths.search(graph, s, false, &mut &edge_cost);
+        paths.closeness(node_count)
+    })
+}
+
+/// Compute the harmonic centrality of every node in parallel.
+///
+/// See [`harmonic_centrality`].
+#[cfg(feature = "rayon")]
+pub fn parallel_harmonic_centrality<G, F, K>(graph: G, edge_cost: F) -> Vec<f64>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + Sync,
+    G::EdgeId: Send,
+    F: Fn(G::EdgeRef) -> K + Sync,
+    K: Measure + Copy + Into<f64> + Send,
+{
+    let (_, sources) = source_indices(graph, None);
+    parallel_map(graph.node_bound(), &sources, |paths, s| {
+        paths.search(graph, s, false, &mut &edge_cost);
+        paths.harmonic(s)
+    })
+}
+
+/// Add up the scores that `run` computes for every source, with a [`ShortestPaths`] for every
+/// thread.
+#[cfg(feature = "rayon")]
+fn parallel_sum<K, E>(
+    node_bound: usize,
+    score_count: usize,
+    sources: &[usize],
+    run: impl Fn(&mut ShortestPaths<K, E>, usize, &mut [f64]) + Sync,
+) -> Vec<f64>
+where
+    K: Measure + Copy + Send,
+    E: Copy + Send,
+{
+    use rayon::prelude::*;
+
+    sources
+        .par_iter()
+        .fold(
+            || (ShortestPaths::new(node_bound), vec![0.; score_count]),
+            |(mut paths, mut scores), &s| {
+                run(&mut paths, s, &mut scores);
+                (paths, scores)
+            },
+        )
+        .map(|(_, scores)| scores)
+        .reduce(
+            || vec![0.; score_count],
+            |mut a, b| {
+                a.iter_mut().zip(b).for_each(|(a, b)| *a += b);
+                a
+            },
+        )
+}
+
+/// Compute the score of every source with `run`, with a [`ShortestPaths`] for every thread.
+#[cfg(feature = "rayon")]
+fn parallel_map<K, E>(
+    node_bound: usize,
+    sources: &[usize],
+    run: impl Fn(&mut ShortestPaths<K, E>, usize) -> f64 + Sync,
+) -> Vec<f64>
+where
+    K: Measure + Copy + Send,
+    E: Copy + Send,
+{
+    use rayon::prelude::*;
+
+    let source_scores = sources
+        .par_iter()
+        .map_init(|| ShortestPaths::new(node_bound), |paths, &s| run(paths, s))
+        .collect::<Vec<_>>();
+    let mut scores = vec![0.; node_bound];
+    for (&s, score) in sources.iter().zip(source_scores) {
+        scores[s] = score;
+    }
+    scores
+}
+
+/// Return the number of nodes, and the indices of the `sources`, or of all nodes.
+fn source_indices<G>(graph: G, sources: Option<&[G::NodeId]>) -> (usize, Vec<usize>)
+where
+    G: IntoNodeIdentifiers + NodeIndexable,
+{
+    let nodes = graph
+        .node_identifiers()
+        .map(|v| graph.to_index(v))
+        .collect::<Vec<_>>();
+    match sources {
+        Some(sources) => (
+            nodes.len(),
+            sources.iter().map(|&v| graph.to_index(v)).collect(),
+        ),
+        None => (nodes.len(), nodes),
+    }
+}
+
+/// The factor to scale node betweenness by.
+fn node_scale<G: GraphProp>(graph: G, n: usize, k: usize, normalized: bool) -> f64 {
+    if normalized && n > 2 {
+        sample_scale(n, k) / ((n - 1) * (n - 2)) as f64
+    } else {
+        sample_scale(n, k) * pair_scale(graph, normalized)
+    }
+}
+
+/// The factor to scale edge betweenness by.
+fn edge_scale<G: GraphProp>(graph: G, n: usize, k: usize, normalized: bool) -> f64 {
+    if normalized && n > 1 {
+        sample_scale(n, k) / (n * (n - 1)) as f64
+    } else {
+        sample_scale(n, k) * pair_scale(graph, normalized)
+    }
+}
+
+/// The factor to count every pair of nodes once.
+///
+/// Paths in undirected graphs are found from both of their ends. Normalizing these double counts
+/// by all pairs is the same as normalizing single counts by half the pairs, so they are only
+/// halved without normalization.
+fn pair_scale<G: GraphProp>(graph: G, normalized: bool) -> f64 {
+    if graph.is_directed() || normalized {
+        1.
+    } else {
+        0.5
+    }
+}
+
+/// The factor to extrapolate from `k` sources to all `n` nodes.
+fn sample_scale(n: usize, k: usize) -> f64 {
+    if k == 0 {
+        return 0.;
+    }
+    n as f64 / k as f64
+}
+
+/// Shortest paths from a single source, found with Dijkstra's algorithm, with the number of
+/// shortest paths to every node and the edges into it on them, as needed by Brandes' algorithm.
+/// The buffers are reused for every source.
+struct ShortestPaths<K, E> {
+    distance: Vec<Option<K>>,
+    settled: Vec<bool>,
+    /// The number of shortest paths from the source.
+    paths: Vec<f64>,
+    /// The dependency of the source on every node.
+    dependency: Vec<f64>,
+    /// The edges into every node on shortest paths, with their source.
+    predecessors: Vec<Vec<(usize, E)>>,
+    /// The reached nodes in order of distance.
+    order: Vec<usize>,
+    touched: Vec<usize>,
+    heap: BinaryHeap<MinScored<K, usize>>,
+}
+
+impl<K, E> ShortestPaths<K, E>
+where
+    K: Measure + Copy,
+    E: Copy,
+{
+    fn new(node_bound: usize) -> Self {
+        ShortestPaths {
+            distance: vec![None; node_bound],
+            settled: vec![false; node_bound],
+            paths: vec![0.; node_bound],
+            dependency: vec![0.; node_bound],
+            predecessors: vec![Vec::new(); node_bound],
+            order: Vec::new(),
+            touched: Vec::new(),
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    /// Find the shortest paths from `s`, counting them if `count_paths` is set.
+    fn search<G, F>(&mut self, graph: G, s: usize, count_paths: bool, edge_cost: &mut F)
+    where
+        G: IntoEdges<EdgeId = E> + NodeIndexable,
+        F: FnMut(G::EdgeRef) -> K,
+    {
+        for v in self.touched.drain(..) {
+            self.distance[v] = None;
+            self.settled[v] = false;
+            self.paths[v] = 0.;
+            self.dependency[v] = 0.;
+            self.predecessors[v].clear();
+        }
+        self.order.clear();
+
+        self.distance[s] = Some(K::default());
+        self.paths[s] = 1.;
+        self.touched.push(s);
+        self.heap.push(MinScored(K::default(), s));
+        while let Some(MinScored(d, v)) = self.heap.pop() {
+            if self.settled[v] {
+                continue;
+            }
+            self.settled[v] = true;
+            self.order.push(v);
+            for edge in graph.edges(graph.from_index(v)) {
+                let w = graph.to_index(edge.target());
+                if self.settled[w] {
+                    continue;
+                }
+                let next = d + edge_cost(edge);
+                match self.distance[w] {
+                    Some(old) if next == old => {
+                        if count_paths {
+                            self.paths[w] += self.paths[v];
+                            self.predecessors[w].push((v, edge.id()));
+                        }
+                    }
+                    Some(old) if old < next => {}
+                    old => {
+                        if old.is_none() {
+                            self.touched.push(w);
+                        }
+                        self.distance[w] = Some(next);
+                        self.heap.push(MinScored(next, w));
+                        if count_paths {
+                            self.paths[w] = self.paths[v];
+                            self.predecessors[w].clear();
+                            self.predecessors[w].push((v, edge.id()));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pass the dependency of the source `s` on every other node and edge to `on_node` and
+    /// `on_edge`, from the farthest nodes in.
+    fn accumulate(
+        &mut self,
+        s: usize,
+        mut on_node: impl FnMut(usize, f64),
+        mut on_edge: impl FnMut(E, f64),
+    ) {
+        for &w in self.order.iter().rev() {
+            for &(v, e) in &self.predecessors[w] {
+                let share = self.paths[v] / self.paths[w] * (1. + self.dependency[w]);
+                self.dependency[v] += share;
+                on_edge(e, share);
+            }
+            if w != s {
+                on_node(w, self.dependency[w]);
+            }
+        }
+    }
+
+    /// The closeness of the source, out of `node_count` nodes.
+    fn closeness(&self, node_count: usize) -> f64
+    where
+        K: Into<f64>,
+    {
+        let total = self
+            .order
+            .iter()
+            .map(|&v| self.distance[v].unwrap().into())
+            .sum::<f64>();
+        let reached = (self.order.len() - 1) as f64;
+        if total > 0. {
+            reached / total * reached / (node_count - 1) as f64
+        } else {
+            0.
+        }
+    }
+
+    /// The harmonic centrality of the source `s`.
+    fn harmonic(&self, s: usize) -> f64
+    where
+        K: Into<f64>,
+    {
+        self.order
+            .iter()
+            .filter(|&&v| v != s)
+            .map(|&v| 1. / self.distance[v].unwrap().into())
+            .sum()
+    }
+}
@@ -0,0 +1,430 @@
+//! Recognition and triangulation of [chordal graphs][1].
+//!
+//! A graph is chordal if every cycle of length at least four has a chord, an edge between two
+//! nodes that are not consecutive on the cycle. Equivalently, the graph has a *perfect
+//! elimination ordering*: an order of the nodes in which every node forms a clique with its
+//! neighbors that come after it.
+//!
+//! * [`is_chordal`] and [`perfect_elimination_ordering`] recognize chordal graphs with [maximum
+//!   cardinality search][2], the latter with a chordless cycle as witness if the graph is not
+//!   chordal.
+//! * [`minimal_triangulation`] adds an inclusion-minimal set of edges that makes a graph chordal.
+//!
+//! All functions treat the graph as undirected and ignore self-loops and parallel edges.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Chordal_graph
+//! [2]: https://doi.org/10.1137/0213035
+
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+use crate::{
+    algo::{cycles::CycleWitness, node_positions},
+    visit::{
+        EdgeRef, IntoEdges, IntoNeighbors, IntoNodeIdentifiers, MaxCardinalitySearch, NodeIndexable,
+    },
+};
+
+/// Return `true` if the undirected graph `graph` is [chordal][1].
+///
+/// Use [`perfect_elimination_ordering`] to also get an elimination ordering of a chordal graph, or
+/// a chordless cycle of a graph that is not chordal.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+///
+/// # Returns
+/// * `true` if every cycle of length at least four has a chord, `false` otherwise.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Chordal_graph
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::is_chordal, prelude::*};
+///
+/// // A square is not chordal, until one of its diagonals is added.
+/// let mut g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 0)]);
+/// assert!(!is_chordal(&g));
+///
+/// g.add_edge(0.into(), 2.into(), ());
+/// assert!(is_chordal(&g));
+/// ```
+pub fn is_chordal<G>(graph: G) -> bool
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    elimination_violation(graph).is_ok()
+}
+
+/// Compute a perfect elimination ordering of an undirected graph, or find a chordless cycle.
+///
+/// In a perfect elimination ordering, the neighbors of every node that come after it in the
+/// ordering form a clique. A graph has one if and only if it is [chordal][1]. The ordering is the
+/// reverse of a [`MaxCardinalitySearch`] and is checked with the algorithm of
+/// [Tarjan and Yannakakis][2].
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+///
+/// # Returns
+/// * `Ok`: a perfect elimination ordering of all nodes, if the graph is chordal.
+/// * `Err`: a [`CycleWitness`] with the nodes and edges of a cycle of length at least four that has
+///   no chord, if the graph is not chordal.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Chordal_graph
+/// [2]: https://doi.org/10.1137/0213035
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::perfect_elimination_ordering, prelude::*};
+///
+/// // Two triangles that share the edge b - c.
+/// let mut g = UnGraph::<&str, ()>::new_undirected();
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// let c = g.add_node("c");
+/// let d = g.add_node("d");
+/// g.extend_with_edges(&[(a, b), (a, c), (b, c), (b, d), (c, d)]);
+/// let order = perfect_elimination_ordering(&g).unwrap();
+/// assert_eq!(order.len(), 4);
+///
+/// // Without the shared edge, a - b - d - c - a is a chordless cycle.
+/// g.remove_edge(g.find_edge(b, c).unwrap());
+/// let cycle = perfect_elimination_ordering(&g).unwrap_err();
+/// assert_eq!(cycle.len(), 4);
+/// ```
+pub fn perfect_elimination_ordering<G>(
+    graph: G,
+) -> Result<Vec<G::NodeId>, CycleWitness<G::NodeId, G::EdgeId>>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    match elimination_violation(graph) {
+        Ok(order) => Ok(order),
+        Err(violation) => Err(chordless_cycle(graph, violation)),
+    }
+}
+
+/// A node `v` and two of its neighbors after it in the elimination ordering that are not adjacent:
+/// `follower`, the first of those neighbors, and `other`.
+struct Violation {
+    position: Vec<usize>,
+    v: usize,
+    follower: usize,
+    other: usize,
+}
+
+/// Compute the reverse of a maximum cardinality search and check whether it is a perfect
+/// elimination ordering.
+fn elimination_violation<G>(graph: G) -> Result<Vec<G::NodeId>, Violation>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let mut order = Vec::new();
+    let mut search = MaxCardinalitySearch::new(graph);
+    while let Some(v) = search.next(graph) {
+        order.push(v);
+    }
+    order.reverse();
+
+    let mut position = vec![usize::MAX; graph.node_bound()];
+    for (i, &v) in order.iter().enumerate() {
+        position[graph.to_index(v)] = i;
+    }
+
+    // Every later neighbor of a node must be adjacent to its follower, the first later neighbor.
+    // Collect these requirements by follower, then check them all at once.
+    let mut required = vec![Vec::new(); graph.node_bound()];
+    let mut later = Vec::new();
+    for &v in &order {
+        let iv = graph.to_index(v);
+        later.clear();
+        later.extend(
+            graph
+                .neighbors(v)
+                .map(|w| graph.to_index(w))
+                .filter(|&w| position[w] > position[iv]),
+        );
+        let Some(&follower) = later.iter().min_by_key(|&&w| position[w]) else {
+            continue;
+        };
+        for &w in &later {
+            if w != follower {
+                required[follower].push((w, iv));
+            }
+        }
+    }
+
+    let mut mark = vec![usize::MAX; graph.node_bound()];
+    for &f in &order {
+        let follower = graph.to_index(f);
+        if required[follower].is_empty() {
+            continue;
+        }
+        for w in graph.neighbors(f) {
+            mark[graph.to_index(w)] = follower;
+        }
+        if let Some(&(other, v)) = required[follower].iter().find(|r| mark[r.0] != follower) {
+            return Err(Violation {
+                position,
+                v,
+                follower,
+                other,
+            });
+        }
+    }
+    Ok(order)
+}
+
+/// Build a chordless cycle through the nodes of a violation of the elimination ordering.
+///
+/// A shortest path from the follower to the other neighbor that avoids the remaining neighbors of
+/// `v` closes a chordless cycle with `v`. Because the ordering comes from a maximum cardinality
+/// search, such a path is expected among the nodes after `v`. Should there be none, every node
+/// and pair of its neighbors is tried instead.
+fn chordless_cycle<G>(graph: G, violation: Violation) -> CycleWitness<G::NodeId, G::EdgeId>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    let Violation {
+        position,
+        v,
+        follower,
+        other,
+    } = violation;
+
+    let path = chordless_path(graph, v, follower, other, |x| position[x] > position[v])
+        .map(|path| (v, path))
+        .or_else(|| {
+            // A graph that is not chordal has a chordless cycle, through some node `x` and two of
+            // its neighbors.
+            let mut adjacent = vec![false; graph.node_bound()];
+            graph.node_identifiers().find_map(|x| {
+                let neighbors = graph
+                    .neighbors(x)
+                    .map(|y| graph.to_index(y))
+                    .filter(|&y| y != graph.to_index(x))
+                    .collect::<Vec<_>>();
+                neighbors.iter().enumerate().find_map(|(i, &a)| {
+                    adjacent.fill(false);
+                    for y in graph.neighbors(graph.from_index(a)) {
+                        adjacent[graph.to_index(y)] = true;
+                    }
+                    neighbors[i + 1..]
+                        .iter()
+                        .filter(|&&b| b != a && !adjacent[b])
+                        .find_map(|&b| chordless_path(graph, graph.to_index(x), a, b, |_| true))
+                        .map(|path| (graph.to_index(x), path))
+                })
+            })
+        });
+    let (v, path) = path.expect("a graph that is not chordal has a chordless cycle");
+
+    let nodes = core::iter::once(v)
+        .chain(path)
+        .map(|x| graph.from_index(x))
+        .collect::<Vec<_>>();
+    let edges = (0..nodes.len())
+        .map(|i| {
+            let (a, b) = (nodes[i], nodes[(i + 1) % nodes.len()]);
+            graph
+                .edges(a)
+                .find(|e| e.target() == b)
+                .expect("consecutive nodes of the cycle are adjacent")
+                .id()
+        })
+        .collect();
+    CycleWitness::new(nodes, edges)
+}
+
+/// Find a shortest path from `a` to `b`, two neighbors of `v`, through `allowed` nodes that are
+/// not adjacent to `v`. Together with `v`, the path forms a chordless cycle if `a` and `b` are not
+/// adjacent.
+fn chordless_path<G>(
+    graph: G,
+    v: usize,
+    a: usize,
+    b: usize,
+    allowed: impl Fn(usize) -> bool,
+) -> Option<Vec<usize>>
+where
+    G: IntoEdges + NodeIndexable,
+{
+    let mut blocked = vec![false; graph.node_bound()];
+    blocked[v] = true;
+    for w in graph.neighbors(graph.from_index(v)) {
+        blocked[graph.to_index(w)] = true;
+    }
+    blocked[a] = false;
+    blocked[b] = false;
+
+    // Search backwards from `b`, so that the path can be read from `a`.
+    let mut parent = vec![usize::MAX; graph.node_bound()];
+    parent[b] = b;
+    let mut queue = VecDeque::from([b]);
+    while let Some(x) = queue.pop_front() {
+        if x == a {
+            break;
+        }
+        for y in graph.neighbors(graph.from_index(x)) {
+            let y = graph.to_index(y);
+            if parent[y] == usize::MAX && !blocked[y] && allowed(y) {
+                parent[y] = x;
+                queue.push_back(y);
+            }
+        }
+    }
+    if parent[a] == usize::MAX {
+        return None;
+    }
+    let mut path = vec![a];
+    while *path.last().unwrap() != b {
+        path.push(parent[*path.last().unwrap()]);
+    }
+    Some(path)
+}
+
+/// Compute a [minimal triangulation][1] of an undirected graph with the MCS-M algorithm.
+///
+/// A triangulation adds *fill* edges to a graph to make it chordal. It is minimal if removing any
+/// fill edge makes the graph not chordal again. Minimal triangulations are not necessarily of
+/// minimum size, which is NP-hard to find.
+///
+/// MCS-M numbers the nodes from last to first like a [`MaxCardinalitySearch`], but a node also
+/// counts a numbered node as a neighbor if they are connected by a path through unnumbered nodes
+/// of smaller weight, and such nodes become adjacent in the triangulation. See
+/// [Berry, Blair, Heggernes and Peyton][2].
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+///
+/// # Returns
+/// * A perfect elimination ordering of the triangulated graph, which contains all nodes.
+/// * The fill edges. There are none if and only if the graph is chordal.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| · (|V| + |E|))**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://doi.org/10.1016/j.dam.2005.05.010
+/// [2]: https://doi.org/10.1007/s00453-004-1084-3
+///
+/// # Example
+/// ```rust
+/// use petgraph::{
+///     algo::{is_chordal, minimal_triangulation},
+///     prelude::*,
+/// };
+///
+/// // A cycle of length five needs two chords.
+/// let mut g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+/// let (order, fill) = minimal_triangulation(&g);
+/// assert_eq!(order.len(), 5);
+/// assert_eq!(fill.len(), 2);
+///
+/// for (a, b) in fill {
+///     g.add_edge(a, b, ());
+/// }
+/// assert!(is_chordal(&g));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn minimal_triangulation<G>(graph: G) -> (Vec<G::NodeId>, Vec<(G::NodeId, G::NodeId)>)
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let (nodes, positions) = node_positions(graph);
+    let n = nodes.len();
+    let adjacency = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| {
+            let mut neighbors = graph
+                .neighbors(v)
+                .map(|w| positions[graph.to_index(w)])
+                .filter(|&w| w != i)
+                .collect::<Vec<_>>();
+            neighbors.sort_unstable();
+            neighbors.dedup();
+            neighbors
+        })
+        .collect::<Vec<_>>();
+
+    let mut weight = vec![0; n];
+    let mut numbered = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    let mut fill = Vec::new();
+
+    // The largest weight of the interior nodes of the best path to every node, plus one, so that
+    // direct neighbors have cost zero.
+    let mut cost = vec![usize::MAX; n];
+    let mut expanded = vec![false; n];
+    let mut buckets = vec![Vec::new(); n + 1];
+    let mut touched = Vec::new();
+    let mut reached = Vec::new();
+
+    for _ in 0..n {
+        let v = (0..n)
+            .filter(|&x| !numbered[x])
+            .max_by_key(|&x| (weight[x], core::cmp::Reverse(x)))
+            .unwrap();
+        numbered[v] = true;
+
+        for &w in &adjacency[v] {
+            if !numbered[w] {
+                cost[w] = 0;
+                buckets[0].push(w);
+                touched.push(w);
+            }
+        }
+        for c in 0..=n {
+            while let Some(x) = buckets[c].pop() {
+                if cost[x] != c || expanded[x] {
+                    continue;
+                }
+                expanded[x] = true;
+                // The path to `x` only goes through nodes of smaller weight.
+                if c <= weight[x] {
+                    reached.push(x);
+                }
+                let next = c.max(weight[x] + 1);
+                for &y in &adjacency[x] {
+                    if !numbered[y] && !expanded[y] && next < cost[y] {
+                        if cost[y] == usize::MAX {
+                            touched.push(y);
+                        }
+                        cost[y] = next;
+                        buckets[next].push(y);
+                    }
+                }
+            }
+        }
+
+        for &x in &reached {
+            weight[x] += 1;
+            if adjacency[v].binary_search(&x).is_err() {
+                fill.push((nodes[v], nodes[x]));
+            }
+        }
+        reached.clear();
+        for x in touched.drain(..) {
+            cost[x] = usize::MAX;
+            expanded[x] = false;
+        }
+        order.push(nodes[v]);
+    }
+    order.reverse();
+    (order, fill)
+}
@@ -1,11 +1,15 @@
-use alloc::{collections::BinaryHeap, vec};
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
 use core::hash::Hash;
 
 use hashbrown::{HashMap, HashSet};
 
 use crate::{
+    algo::{node_positions, simple_adjacency},
     scored::MaxScored,
-    visit::{IntoEdges, IntoNodeIdentifiers, NodeIndexable, VisitMap, Visitable},
+    visit::{
+        EdgeRef, IntoEdgeReferences, IntoEdges, IntoNodeIdentifiers, NodeIndexable, VisitMap,
+        Visitable,
+    },
 };
 
 /// [DStatur algorithm][1] to properly color a non weighted undirected graph.
@@ -100,3 +104,571 @@ where
 
     (colored, max_color + 1)
 }
+
+/// Compute a coloring with the minimum number of colors, the [chromatic number][1] of the graph.
+///
+/// This is an exact [DSatur branch and bound][2]: like [`dsatur_coloring`], it colors the node
+/// with the most distinctly colored neighbors next, but it tries every possible color and
+/// backtracks, pruning colorings that cannot use fewer colors than the best one found so far. The
+/// nodes of a large clique are colored first, and the search stops as soon as a coloring uses as
+/// many colors as the clique has nodes.
+///
+/// Finding the chromatic number is NP-hard. With a `node_budget`, the search stops after that
+/// many search steps once a first coloring is found, and returns the best coloring found so far,
+/// which may not be minimal.
+///
+/// The graph is treated as undirected. Self-loops are ignored.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `node_budget`: the maximum number of search steps, or `None` to search until a minimum
+///   coloring is found.
+///
+/// # Returns
+/// Returns a tuple of:
+/// * [`struct@hashbrown::HashMap`] that associates to each `NodeId` its color.
+/// * `usize`: the number of used colors.
+///
+/// # Complexity
+/// * Time complexity: exponential in **|V|** in the worst case, and **O(b · |V|)** with a budget of
+///   **b** steps once a first coloring is found.
+/// * Auxiliary space: **O(|V| · Δ + |E|)**.
+///
+/// where **|V|** is the number of nodes, **|E|** is the number of edges and **Δ** is the maximum
+/// degree.
+///
+/// [1]: https://en.wikipedia.org/wiki/Graph_coloring#Chromatic_number
+/// [2]: https://doi.org/10.1145/359094.359101
+///
+/// # Example
+/// ```rust
+/// use petgraph::{
+///     algo::{dsatur_coloring, exact_coloring},
+///     prelude::*,
+/// };
+///
+/// // A crown graph: DSatur may need more than two colors, but it is bipartite.
+/// let mut g = UnGraph::<(), ()>::default();
+/// for a in 0..5 {
+///     for b in 0..5 {
+///         if a != b {
+///             g.extend_with_edges([(a, 5 + b)]);
+///         }
+///     }
+/// }
+/// let (coloring, colors) = exact_coloring(&g, None);
+/// assert_eq!(colors, 2);
+/// assert!(g.edge_indices().all(|e| {
+///     let (a, b) = g.edge_endpoints(e).unwrap();
+///     coloring[&a] != coloring[&b]
+/// }));
+/// assert!(dsatur_coloring(&g).1 >= colors);
+/// ```
+pub fn exact_coloring<G>(graph: G, node_budget: Option<usize>) -> (HashMap<G::NodeId, usize>, usize)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let (nodes, adjacency) = simple_adjacency(graph);
+    let n = nodes.len();
+    if n == 0 {
+        return (HashMap::new(), 0);
+    }
+    let max_degree = adjacency.iter().map(Vec::len).max().unwrap_or(0);
+    let clique = greedy_clique(&adjacency);
+
+    let mut search = ColoringSearch {
+        adjacency: &adjacency,
+        colors: vec![NO_COLOR; n],
+        neighbor_colors: vec![vec![0; max_degree + 1]; n],
+        saturation: vec![0; n],
+        // No coloring uses more than `max_degree + 1` colors.
+        best: max_degree + 2,
+        best_colors: Vec::new(),
+        lower_bound: clique.len(),
+        budget: node_budget,
+    };
+    for (color, &v) in clique.iter().enumerate() {
+        search.assign(v, color);
+    }
+    search.search(clique.len(), clique.len());
+
+    let coloring = nodes
+        .into_iter()
+        .zip(search.best_colors)
+        .collect::<HashMap<_, _>>();
+    (coloring, search.best)
+}
+
+/// Marks a node without a color.
+const NO_COLOR: usize = usize::MAX;
+
+/// The state of the branch and bound search of [`exact_coloring`].
+struct ColoringSearch<'a> {
+    adjacency: &'a [Vec<usize>],
+    colors: Vec<usize>,
+    /// The number of neighbors of every node with every color.
+    neighbor_colors: Vec<Vec<u32>>,
+    /// The number of distinct colors of the neighbors of every node.
+    saturation: Vec<usize>,
+    best: usize,
+    best_colors: Vec<usize>,
+    lower_bound: usize,
+    budget: Option<usize>,
+}
+
+impl ColoringSearch<'_> {
+    fn assign(&mut self, v: usize, color: usize) {
+        self.colors[v] = color;
+        for &w in &self.adjacency[v] {
+            if self.neighbor_colors[w][color] == 0 {
+                self.saturation[w] += 1;
+            }
+            self.neighbor_colors[w][color] += 1;
+        }
+    }
+
+    fn unassign(&mut self, v: usize, color: usize) {
+        self.colors[v] = NO_COLOR;
+        for &w in &self.adjacency[v] {
+            self.neighbor_colors[w][color] -= 1;
+            if self.neighbor_colors[w][color] == 0 {
+                self.saturation[w] -= 1;
+            }
+        }
+    }
+
+    /// Extend the coloring of `colored` nodes with `used` colors. Return `true` to stop the
+    /// search, because a coloring is optimal or the budget is exhausted.
+    fn search(&mut self, colored: usize, used: usize) -> bool {
+        if !self.best_colors.is_empty() {
+            match &mut self.budget {
+                Some(0) => return true,
+                Some(budget) => *budget -= 1,
+                None => {}
+            }
+        }
+        if colored == self.colors.len() {
+            self.best = used;
+            self.best_colors.clone_from(&self.colors);
+            return used == self.lower_bound;
+        }
+
+        let v = (0..self.colors.len())
+            .filter(|&v| self.colors[v] == NO_COLOR)
+            .max_by_key(|&v| (self.saturation[v], self.adjacency[v].len()))
+            .unwrap();
+        // Only try one new color, and only colorings with fewer colors than the best one.
+        for color in 0..(used + 1).min(self.best - 1) {
+            if self.neighbor_colors[v][color] != 0 {
+                continue;
+            }
+            self.assign(v, color);
+            let stop = self.search(colored + 1, used.max(color + 1));
+            self.unassign(v, color);
+            if stop {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// The order in which [`greedy_coloring`] colors the nodes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GreedyOrdering {
+    /// Color the nodes by decreasing degree, as in the [Welsh–Powell algorithm][1].
+    ///
+    /// [1]: https://doi.org/10.1093/comjnl/10.1.85
+    LargestFirst,
+    /// Repeatedly remove a node of minimum degree from the graph, and color the nodes in the
+    /// reverse order of their removal, as proposed by [Matula and Beck][1]. This uses at most
+    /// **d + 1** colors, where **d** is the [degeneracy][2] of the graph.
+    ///
+    /// [1]: https://doi.org/10.1145/2402.322385
+    /// [2]: https://en.wikipedia.org/wiki/Degeneracy_(graph_theory)
+    SmallestLast,
+    /// Color the nodes by decreasing [core number][1], the largest **k** such that the node
+    /// belongs to a subgraph in which all nodes have degree at least **k**, and by decreasing
+    /// degree within a core. This colors the densest parts of the graph first.
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Degeneracy_(graph_theory)#k-Cores
+    Degeneracy,
+}
+
+/// Color the nodes of a graph greedily in the given order.
+///
+/// Every node gets the smallest color that none of its neighbors have. The number of colors
+/// depends on the [`GreedyOrdering`], but is never more than the maximum degree plus one.
+///
+/// The graph is treated as undirected. Self-loops are ignored.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `ordering`: the order in which the nodes are colored.
+///
+/// # Returns
+/// Returns a tuple of:
+/// * [`struct@hashbrown::HashMap`] that associates to each `NodeId` its color.
+/// * `usize`: the number of used colors.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| log |V| + |E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{
+///     algo::{GreedyOrdering, greedy_coloring, is_proper_coloring},
+///     prelude::*,
+/// };
+///
+/// // A tree has degeneracy one, so the smallest-last ordering colors it with two colors.
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (2, 3), (2, 4), (4, 5), (4, 6)]);
+/// let (coloring, colors) = greedy_coloring(&g, GreedyOrdering::SmallestLast);
+/// assert_eq!(colors, 2);
+/// assert!(is_proper_coloring(&g, &coloring));
+/// ```
+pub fn greedy_coloring<G>(graph: G, ordering: GreedyOrdering) -> (HashMap<G::NodeId, usize>, usize)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let (nodes, adjacency) = simple_adjacency(graph);
+    let n = nodes.len();
+    let degree = |v: &usize| adjacency[*v].len();
+    let order = match ordering {
+        GreedyOrdering::LargestFirst => {
+            let mut order = (0..n).collect::<Vec<_>>();
+            order.sort_by_key(|v| core::cmp::Reverse(degree(v)));
+            order
+        }
+        GreedyOrdering::SmallestLast => {
+            let (mut order, _) = degeneracy_ordering(&adjacency);
+            order.reverse();
+            order
+        }
+        GreedyOrdering::Degeneracy => {
+            let (_, core_numbers) = degeneracy_ordering(&adjacency);
+            let mut order = (0..n).collect::<Vec<_>>();
+            order.sort_by_key(|v| core::cmp::Reverse((core_numbers[*v], degree(v))));
+            order
+        }
+    };
+
+    let mut colors = vec![NO_COLOR; n];
+    // The last node whose neighbors used every color.
+    let mut taken_by = vec![usize::MAX; n + 1];
+    let mut count = 0;
+    for v in order {
+        for &w in &adjacency[v] {
+            if colors[w] != NO_COLOR {
+                taken_by[colors[w]] = v;
+            }
+        }
+        let color = (0..).find(|&c| taken_by[c] != v).unwrap();
+        colors[v] = color;
+        count = count.max(color + 1);
+    }
+    (nodes.into_iter().zip(colors).collect(), count)
+}
+
+/// Color the edges of a graph with at most **Δ + 1** colors, where **Δ** is its maximum degree.
+///
+/// Every edge gets a color, such that edges with a common endpoint have different colors. By
+/// [Vizing's theorem][1], every simple graph has such a coloring with **Δ + 1** colors, and the
+/// [algorithm of Misra and Gries][2] finds one. Some graphs need only **Δ** colors, but deciding
+/// this is NP-hard.
+///
+/// The graph is treated as undirected. Self-loops cannot be colored and are ignored. Parallel
+/// edges get colors that are not used at either endpoint yet, so that the coloring is proper, but
+/// may need more than **Δ + 1** colors.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+///
+/// # Returns
+/// Returns a tuple of:
+/// * [`struct@hashbrown::HashMap`] that associates to each `EdgeId` its color.
+/// * `usize`: the number of used colors.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E| · |V|)**.
+/// * Auxiliary space: **O(|V| · Δ + |E|)**.
+///
+/// where **|V|** is the number of nodes, **|E|** is the number of edges and **Δ** is the maximum
+/// degree.
+///
+/// [1]: https://en.wikipedia.org/wiki/Vizing%27s_theorem
+/// [2]: https://doi.org/10.1016/0020-0190(92)90041-S
+///
+/// # Example
+/// ```rust
+/// use petgraph::{
+///     algo::{is_proper_edge_coloring, misra_gries_edge_coloring},
+///     prelude::*,
+/// };
+///
+/// // The Petersen graph has maximum degree three, but needs four colors.
+/// let mut g = UnGraph::<(), ()>::from_edges((0..5).map(|i| (i, (i + 1) % 5)));
+/// g.extend_with_edges((0..5).map(|i| (i, i + 5)));
+/// g.extend_with_edges((0..5).map(|i| (i + 5, (i + 2) % 5 + 5)));
+/// let (coloring, colors) = misra_gries_edge_coloring(&g);
+/// assert_eq!(colors, 4);
+/// assert!(is_proper_edge_coloring(&g, &coloring));
+/// ```
+pub fn misra_gries_edge_coloring<G>(graph: G) -> (HashMap<G::EdgeId, usize>, usize)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::EdgeId: Eq + Hash,
+{
+    let (nodes, positions) = node_positions(graph);
+    let n = nodes.len();
+
+    // Split the edges into a simple graph and the remaining parallel edges.
+    let mut simple = HashSet::new();
+    let mut simple_edges = Vec::new();
+    let mut parallel_edges = Vec::new();
+    let mut degree = vec![0; n];
+    for edge in graph.edge_references() {
+        let a = positions[graph.to_index(edge.source())];
+        let b = positions[graph.to_index(edge.target())];
+        if a == b {
+            continue;
+        }
+        if simple.insert((a.min(b), a.max(b))) {
+            simple_edges.push((a, b, edge.id()));
+            degree[a] += 1;
+            degree[b] += 1;
+        } else {
+            parallel_edges.push((a, b, edge.id()));
+        }
+    }
+    let palette = degree.iter().max().map_or(0, |&d| d + 1);
+
+    // The neighbor of every node along the edge of every color.
+    let mut at = vec![vec![NO_COLOR; palette]; n];
+    let free = |at: &[Vec<usize>], x: usize| at[x].iter().position(|&y| y == NO_COLOR).unwrap();
+    let mut fan = Vec::new();
+    for &(u, v, _) in &simple_edges {
+        // A maximal fan of `u` starting at `v`: the color of the edge to every next node of the
+        // fan is free at the previous node.
+        fan.clear();
+        fan.push(v);
+        loop {
+            let last = *fan.last().unwrap();
+            let next = (0..palette).find_map(|c| {
+                let w = at[u][c];
+                (w != NO_COLOR && at[last][c] == NO_COLOR && !fan.contains(&w)).then_some(w)
+            });
+            match next {
+                Some(w) => fan.push(w),
+                None => break,
+            }
+        }
+
+        let c = free(&at, u);
+        let d = free(&at, *fan.last().unwrap());
+
+        // Invert the path from `u` whose edges are colored d and c alternately.
+        let mut path = vec![u];
+        let mut color = d;
+        while let Some(&x) = path.last() {
+            let y = at[x][color];
+            if y == NO_COLOR {
+                break;
+            }
+            path.push(y);
+            color = if color == d { c } else { d };
+        }
+        let mut color = d;
+        for pair in path.windows(2) {
+            at[pair[0]][color] = NO_COLOR;
+            at[pair[1]][color] = NO_COLOR;
+            color = if color == d { c } else { d };
+        }
+        let mut color = c;
+        for pair in path.windows(2) {
+            at[pair[0]][color] = pair[1];
+            at[pair[1]][color] = pair[0];
+            color = if color == d { c } else { d };
+        }
+
+        // Rotate the prefix of the fan up to a node where d is free, and color its last edge d.
+        let end = fan.iter().position(|&w| at[w][d] == NO_COLOR).unwrap();
+        for i in 0..end {
+            let (x, y) = (fan[i], fan[i + 1]);
+            let shifted = at[u].iter().position(|&w| w == y).unwrap();
+            at[u][shifted] = x;
+            at[y][shifted] = NO_COLOR;
+            at[x][shifted] = u;
+        }
+        at[u][d] = fan[end];
+        at[fan[end]][d] = u;
+    }
+
+    let mut coloring = HashMap::with_capacity(simple_edges.len() + parallel_edges.len());
+    let mut count = 0;
+    for &(a, b, id) in &simple_edges {
+        let color = at[a].iter().position(|&w| w == b).unwrap();
+        coloring.insert(id, color);
+        count = count.max(color + 1);
+    }
+    for &(a, b, id) in &parallel_edges {
+        let color = (0..)
+            .find(|&c| {
+                at[a].get(c).is_none_or(|&w| w == NO_COLOR)
+                    && at[b].get(c).is_none_or(|&w| w == NO_COLOR)
+            })
+            .unwrap();
+        for x in [a, b] {
+            if at[x].len() <= color {
+                at[x].resize(color + 1, NO_COLOR);
+            }
+        }
+        at[a][color] = b;
+        at[b][color] = a;
+        coloring.insert(id, color);
+        count = count.max(color + 1);
+    }
+    (coloring, count)
+}
+
+/// Return `true` if `coloring` is a proper coloring of the nodes of `graph`.
+///
+/// A coloring is proper if every node has a color, and the endpoints of every edge have
+/// different colors. Self-loops are ignored.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `coloring`: a color for every node.
+///
+/// # Returns
+/// * `true` if the coloring is proper, `false` otherwise.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(1)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use hashbrown::HashMap;
+/// use petgraph::{algo::is_proper_coloring, prelude::*};
+///
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+/// let (a, b, c) = (NodeIndex::new(0), NodeIndex::new(1), NodeIndex::new(2));
+/// let mut coloring = HashMap::from_iter([(a, 0), (b, 1), (c, 0)]);
+/// assert!(is_proper_coloring(&g, &coloring));
+///
+/// coloring.insert(b, 0);
+/// assert!(!is_proper_coloring(&g, &coloring));
+/// ```
+pub fn is_proper_coloring<G>(graph: G, coloring: &HashMap<G::NodeId, usize>) -> bool
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers,
+    G::NodeId: Eq + Hash,
+{
+    graph.node_identifiers().all(|v| coloring.contains_key(&v))
+        && graph.edge_references().all(|edge| {
+            edge.source() == edge.target() || coloring[&edge.source()] != coloring[&edge.target()]
+        })
+}
+
+/// Return `true` if `coloring` is a proper coloring of the edges of `graph`.
+///
+/// An edge coloring is proper if every edge that is not a self-loop has a color, and edges with a
+/// common endpoint have different colors.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `coloring`: a color for every edge.
+///
+/// # Returns
+/// * `true` if the edge coloring is proper, `false` otherwise.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(|E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+pub fn is_proper_edge_coloring<G>(graph: G, coloring: &HashMap<G::EdgeId, usize>) -> bool
+where
+    G: IntoEdgeReferences + NodeIndexable,
+    G::EdgeId: Eq + Hash,
+{
+    let mut seen = HashSet::new();
+    graph.edge_references().all(|edge| {
+        let (a, b) = (graph.to_index(edge.source()), graph.to_index(edge.target()));
+        a == b
+            || coloring
+                .get(&edge.id())
+                .is_some_and(|&color| seen.insert((a, color)) && seen.insert((b, color)))
+    })
+}
+
+/// Find a large clique greedily, starting from every node.
+fn greedy_clique(adjacency: &[Vec<usize>]) -> Vec<usize> {
+    let mut best = Vec::new();
+    for v in 0..adjacency.len() {
+        if adjacency[v].len() < best.len() {
+            continue;
+        }
+        let mut candidates = adjacency[v].clone();
+        candidates.sort_by_key(|&w| core::cmp::Reverse(adjacency[w].len()));
+        let mut clique = vec![v];
+        for w in candidates {
+            if clique
+                .iter()
+                .all(|&x| adjacency[w].binary_search(&x).is_ok())
+            {
+                clique.push(w);
+            }
+        }
+        if clique.len() > best.len() {
+            best = clique;
+        }
+    }
+    best
+}
+
+/// Repeatedly remove a node of minimum degree. Return the nodes in the order of their removal,
+/// and the core number of every node.
+fn degeneracy_ordering(adjacency: &[Vec<usize>]) -> (Vec<usize>, Vec<usize>) {
+    let n = adjacency.len();
+    let mut degree = adjacency.iter().map(Vec::len).collect::<Vec<_>>();
+    let mut buckets = vec![Vec::new(); n];
+    for v in 0..n {
+        buckets[degree[v]].push(v);
+    }
+    let mut removed = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    let mut core_numbers = vec![0; n];
+    let mut core = 0;
+    let mut smallest = 0;
+    while order.len() < n {
+        // Entries are not removed from the buckets when the degree changes, so skip stale ones.
+        let Some(v) = buckets[smallest].pop() else {
+            smallest += 1;
+            continue;
+        };
+        if removed[v] || degree[v] != smallest {
+            continue;
+        }
+        removed[v] = true;
+        core = core.max(smallest);
+        core_numbers[v] = core;
+        order.push(v);
+        for &w in &adjacency[v] {
+            if !removed[w] {
+                degree[w] -= 1;
+                buckets[degree[w]].push(w);
+                smallest = smallest.min(degree[w]);
+            }
+        }
+    }
+    (order, core_numbers)
+}
@@ -0,0 +1,660 @@
+//! Community detection, which partitions the nodes of a graph into densely connected groups.
+//!
+//! * [`louvain_communities`] and [`leiden_communities`] greedily optimize the [modularity][1] of
+//!   the partition, with [the Louvain method][2] and [the Leiden algorithm][3]. The Leiden
+//!   algorithm refines the communities before merging them, which guarantees that every community
+//!   is connected.
+//! * [`label_propagation_communities`] lets every node adopt the most common community among its
+//!   neighbors, with [asynchronous label propagation][4]. It is faster, but does not optimize any
+//!   score.
+//! * [`modularity`] scores a given partition.
+//!
+//! All of them ignore edge directions, and weigh every edge by an `edge_weight` closure, which
+//! must return a non-negative weight; use `|_| 1.` for unweighted graphs. A partition maps every
+//! node to its community, numbered from zero, like the colorings of
+//! [`greedy_coloring`](crate::algo::greedy_coloring). The algorithms visit nodes in a random
+//! order, drawn from a `seed`, so that their results are reproducible.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Modularity_(networks)
+//! [2]: https://doi.org/10.1088/1742-5468/2008/10/P10008
+//! [3]: https://doi.org/10.1038/s41598-019-41695-z
+//! [4]: https://doi.org/10.1103/PhysRevE.76.036106
+
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use core::hash::Hash;
+
+use hashbrown::HashMap;
+
+use crate::{
+    algo::node_positions,
+    visit::{EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable},
+};
+
+/// Compute the [modularity][1] of a partition of the nodes of `graph`.
+///
+/// The modularity of a partition is **Σ (L_c / m - γ (d_c / 2m)²)** over every community **c**,
+/// where **L_c** is the total weight of the edges within **c**, **d_c** is the total weighted
+/// degree of its nodes, **m** is the total weight of all edges and **γ** is the `resolution`.
+/// With a resolution of one, it is the fraction of the edge weight within communities, minus the
+/// fraction expected if the edges were placed at random. A higher resolution favours smaller
+/// communities.
+///
+/// Edge directions are ignored, and a self-loop counts twice towards the degree of its node.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+/// * `communities`: the community of every node.
+/// * `resolution`: the weight of the expected fraction, usually one.
+/// * `edge_weight`: closure that returns the weight of an edge, which must be non-negative.
+///
+/// # Returns
+/// * `f64`: the modularity of the partition, or zero if the edges have no weight.
+///
+/// # Panics
+/// Panics if a node with an edge has no community.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(|V|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Modularity_(networks)
+///
+/// # Example
+/// ```rust
+/// use hashbrown::HashMap;
+/// use petgraph::{algo::modularity, prelude::*};
+///
+/// // Two triangles joined by an edge.
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3)]);
+/// let communities = g
+///     .node_indices()
+///     .map(|v| (v, v.index() / 3))
+///     .collect::<HashMap<_, _>>();
+///
+/// // Six of the seven edges are within a community, and both have half of the degree.
+/// let expected = 6. / 7. - 2. * 0.5 * 0.5;
+/// assert!((modularity(&g, &communities, 1., |_| 1.) - expected).abs() < 1e-12);
+/// ```
+pub fn modularity<G, F>(
+    graph: G,
+    communities: &HashMap<G::NodeId, usize>,
+    resolution: f64,
+    mut edge_weight: F,
+) -> f64
+where
+    G: IntoEdgeReferences,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    let mut internal = 0.;
+    let mut total = 0.;
+    let mut degrees = HashMap::<usize, f64>::new();
+    for edge in graph.edge_references() {
+        let weight = edge_weight(edge);
+        let (a, b) = (communities[&edge.source()], communities[&edge.target()]);
+        total += weight;
+        *degrees.entry(a).or_default() += weight;
+        *degrees.entry(b).or_default() += weight;
+        if a == b {
+            internal += weight;
+        }
+    }
+    if total == 0. {
+        return 0.;
+    }
+    let expected = degrees
+        .values()
+        .map(|degree| degree / (2. * total) * (degree / (2. * total)))
+        .sum::<f64>();
+    internal / total - resolution * expected
+}
+
+/// Find communities with the [Louvain method][1].
+///
+/// Starting with every node in its own community, the Louvain method moves nodes to the
+/// neighboring community that increases the [`modularity`] most, until no move does. It then
+/// merges every community into a single node, and repeats on the smaller graph until no node
+/// moves. Nodes are visited in a random order drawn from `seed`.
+///
+/// The communities are found greedily, so their modularity is not necessarily the highest
+/// possible. They may also be disconnected; use [`leiden_communities`] to avoid that.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph, whose edge directions are ignored.
+/// * `resolution`: the resolution of the modularity, usually one. A higher resolution gives smaller
+///   communities.
+/// * `seed`: the seed of the random order of the nodes.
+/// * `edge_weight`: closure that returns the weight of an edge, which must be non-negative.
+///
+/// # Returns
+/// Returns a tuple of:
+/// * [`struct@hashbrown::HashMap`] that associates to each `NodeId` its community.
+/// * `usize`: the number of communities.
+///
+/// # Complexity
+/// * Time complexity: **O(k(|V| + |E|))** for **k** moves in the first pass, and usually close to
+///   linear in practice.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://doi.org/10.1088/1742-5468/2008/10/P10008
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::louvain_communities, prelude::*};
+///
+/// // Two triangles joined by an edge.
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3)]);
+///
+/// let (communities, count) = louvain_communities(&g, 1., 42, |_| 1.);
+/// assert_eq!(count, 2);
+/// let community = |v| communities[&NodeIndex::new(v)];
+/// assert!(community(0) == community(1) && community(1) == community(2));
+/// assert!(community(3) == community(4) && community(4) == community(5));
+/// assert_ne!(community(0), community(3));
+/// ```
+pub fn louvain_communities<G, F>(
+    graph: G,
+    resolution: f64,
+    seed: u64,
+    edge_weight: F,
+) -> (HashMap<G::NodeId, usize>, usize)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    let (mut network, nodes) = Network::new(graph, edge_weight);
+    let scale = network.scale(resolution);
+    let mut rng = SplitMix64(seed);
+    // The node of the current network that contains every node of the graph.
+    let mut membership = (0..nodes.len()).collect::<Vec<_>>();
+    loop {
+        let mut partition = (0..network.len()).collect::<Vec<_>>();
+        if !network.move_nodes(&mut partition, scale, &mut rng) {
+            break;
+        }
+        let count = renumber(&mut partition);
+        membership.iter_mut().for_each(|v| *v = partition[*v]);
+        network = network.aggregate(&partition, count);
+    }
+    communities(&nodes, membership)
+}
+
+/// Find communities with the [Leiden algorithm][1].
+///
+/// The Leiden algorithm improves on [`louvain_communities`]. After moving nodes between
+/// communities, it splits every community into well-connected parts, by merging nodes only with
+/// parts that they are well connected to. It then merges every part into a single node, which
+/// starts in the community of the part, and repeats on the smaller graph until no node moves.
+/// This guarantees that every community is connected. Nodes are visited in a random order drawn
+/// from `seed`.
+///
+/// Nodes always merge with the part that increases the [`modularity`] most, rather than with a
+/// random part as in the original algorithm.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph, whose edge directions are ignored.
+/// * `resolution`: the resolution of the modularity, usually one. A higher resolution gives smaller
+///   communities.
+/// * `seed`: the seed of the random order of the nodes.
+/// * `edge_weight`: closure that returns the weight of an edge, which must be non-negative.
+///
+/// # Returns
+/// Returns a tuple of:
+/// * [`struct@hashbrown::HashMap`] that associates to each `NodeId` its community.
+/// * `usize`: the number of communities.
+///
+/// # Complexity
+/// * Time complexity: **O(k(|V| + |E|))** for **k** moves in the first pass, and usually close to
+///   linear in practice.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://doi.org/10.1038/s41598-019-41695-z
+///
+/// # Example
+/// ```rust
+/// use petgraph::{
+///     algo::{leiden_communities, modularity},
+///     prelude::*,
+/// };
+///
+/// // Three cliques of four nodes in a ring.
+/// let mut g = UnGraph::<(), f64>::new_undirected();
+/// let nodes = (0..12).map(|_| g.add_node(())).collect::<Vec<_>>();
+/// for clique in nodes.chunks(4) {
+///     for (i, &a) in clique.iter().enumerate() {
+///         for &b in &clique[i + 1..] {
+///             g.add_edge(a, b, 1.);
+///         }
+///     }
+/// }
+/// g.extend_with_edges(&[
+///     (nodes[3], nodes[4], 1.),
+///     (nodes[7], nodes[8], 1.),
+///     (nodes[11], nodes[0], 1.),
+/// ]);
+///
+/// let (communities, count) = leiden_communities(&g, 1., 7, |e| *e.weight());
+/// assert_eq!(count, 3);
+/// assert!(nodes.chunks(4).all(|clique| {
+///     clique
+///         .iter()
+///         .all(|v| communities[v] == communities[&clique[0]])
+/// }));
+/// assert!(modularity(&g, &communities, 1., |e| *e.weight()) > 0.5);
+/// ```
+pub fn leiden_communities<G, F>(
+    graph: G,
+    resolution: f64,
+    seed: u64,
+    edge_weight: F,
+) -> (HashMap<G::NodeId, usize>, usize)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    let (mut network, nodes) = Network::new(graph, edge_weight);
+    let scale = network.scale(resolution);
+    let mut rng = SplitMix64(seed);
+    // The node of the current network that contains every node of the graph.
+    let mut membership = (0..nodes.len()).collect::<Vec<_>>();
+    let mut partition = (0..network.len()).collect::<Vec<_>>();
+    loop {
+        network.move_nodes(&mut partition, scale, &mut rng);
+        if renumber(&mut partition) == network.len() {
+            break;
+        }
+        let mut refined = network.refine(&partition, scale, &mut rng);
+        let refined_count = renumber(&mut refined);
+        if refined_count == network.len() {
+            break;
+        }
+        // Every part starts out in the community that it was refined from.
+        let mut next = vec![0; refined_count];
+        for (v, &part) in refined.iter().enumerate() {
+            next[part] = partition[v];
+        }
+        membership.iter_mut().for_each(|v| *v = refined[*v]);
+        network = network.aggregate(&refined, refined_count);
+        partition = next;
+    }
+    membership.iter_mut().for_each(|v| *v = partition[*v]);
+    communities(&nodes, membership)
+}
+
+/// Find communities with [asynchronous label propagation][1].
+///
+/// Every node starts in its own community. Then, in a random order drawn from `seed`, every node
+/// joins the community with the highest total edge weight among its neighbors, picked at random
+/// if several are tied. This repeats until every node is in one of the heaviest communities
+/// among its neighbors.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph, whose edge directions are ignored.
+/// * `seed`: the seed of the random order of the nodes and of the choice between tied communities.
+/// * `edge_weight`: closure that returns the weight of an edge, which must be non-negative.
+///
+/// # Returns
+/// Returns a tuple of:
+/// * [`struct@hashbrown::HashMap`] that associates to each `NodeId` its community.
+/// * `usize`: the number of communities.
+///
+/// # Complexity
+/// * Time complexity: **O(k(|V| + |E|))** for **k** rounds, which is usually small.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://doi.org/10.1103/PhysRevE.76.036106
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::label_propagation_communities, prelude::*};
+///
+/// // Two cliques of four nodes joined by an edge, and an isolated node.
+/// let mut g = UnGraph::<(), ()>::from_edges([
+///     (0, 1),
+///     (0, 2),
+///     (0, 3),
+///     (1, 2),
+///     (1, 3),
+///     (2, 3),
+///     (4, 5),
+///     (4, 6),
+///     (4, 7),
+///     (5, 6),
+///     (5, 7),
+///     (6, 7),
+///     (3, 4),
+/// ]);
+/// g.add_node(());
+///
+/// let (communities, count) = label_propagation_communities(&g, 3, |_| 1.);
+/// assert_eq!(count, 3);
+/// assert!((0..4).all(|v| communities[&NodeIndex::new(v)] == communities[&NodeIndex::new(0)]));
+/// assert!((4..8).all(|v| communities[&NodeIndex::new(v)] == communities[&NodeIndex::new(4)]));
+/// ```
+pub fn label_propagation_communities<G, F>(
+    graph: G,
+    seed: u64,
+    edge_weight: F,
+) -> (HashMap<G::NodeId, usize>, usize)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    let (network, nodes) = Network::new(graph, edge_weight);
+    let mut rng = SplitMix64(seed);
+    let mut labels = (0..network.len()).collect::<Vec<_>>();
+    let mut weights = vec![0.; network.len()];
+    let mut neighbors = Vec::new();
+    let mut heaviest = Vec::new();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for v in rng.permutation(network.len()) {
+            for &(u, weight) in &network.adjacency[v] {
+                if weights[labels[u]] == 0. {
+                    neighbors.push(labels[u]);
+                }
+                weights[labels[u]] += weight;
+            }
+            let max = neighbors
+                .iter()
+                .map(|&label| weights[label])
+                .fold(0., f64::max);
+            heaviest.extend(
+                neighbors
+                    .iter()
+                    .copied()
+                    .filter(|&label| weights[label] == max),
+            );
+            if !heaviest.is_empty() && !heaviest.contains(&labels[v]) {
+                labels[v] = heaviest[rng.below(heaviest.len())];
+                changed = true;
+            }
+            neighbors.drain(..).for_each(|label| weights[label] = 0.);
+            heaviest.clear();
+        }
+    }
+    communities(&nodes, labels)
+}
+
+/// The graph that the communities are computed on, with nodes numbered from zero and edges
+/// in both directions.
+struct Network {
+    /// The neighbors of every node and the weight of the edge to them, without self-loops or
+    /// edges of weight zero.
+    adjacency: Vec<Vec<(usize, f64)>>,
+    /// The weighted degree of every node, where self-loops count twice.
+    degree: Vec<f64>,
+}
+
+impl Network {
+    /// Number the nodes of `graph` and collect its edges.
+    fn new<G, F>(graph: G, mut edge_weight: F) -> (Self, Vec<G::NodeId>)
+    where
+        G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+        F: FnMut(G::EdgeRef) -> f64,
+    {
+        let (nodes, index) = node_positions(graph);
+        let mut network = Network {
+            adjacency: vec![Vec::new(); nodes.len()],
+            degree: vec![0.; nodes.len()],
+        };
+        for edge in graph.edge_references() {
+            let weight = edge_weight(edge);
+            let a = index[graph.to_index(edge.source())];
+            let b = index[graph.to_index(edge.target())];
+            network.degree[a] += weight;
+            network.degree[b] += weight;
+            if a != b && weight > 0. {
+                network.adjacency[a].push((b, weight));
+                network.adjacency[b].push((a, weight));
+            }
+        }
+        (network, nodes)
+    }
+
+    fn len(&self) -> usize {
+        self.degree.len()
+    }
+
+    /// The factor of the expected weight between two nodes, from the product of their degrees.
+    fn scale(&self, resolution: f64) -> f64 {
+        let total = self.degree.iter().sum::<f64>();
+        if total > 0. { resolution / total } else { 0. }
+    }
+
+    /// Move nodes to the community that increases the modularity most until no move does,
+    /// revisiting the neighbors of every node that moved. Return whether any node moved.
+    ///
+    /// Communities are numbered below the number of nodes.
+    fn move_nodes(&self, partition: &mut [usize], scale: f64, rng: &mut SplitMix64) -> bool {
+        let mut totals = vec![0.; self.len()];
+        let mut sizes = vec![0; self.len()];
+        for (v, &community) in partition.iter().enumerate() {
+            totals[community] += self.degree[v];
+            sizes[community] += 1;
+        }
+        let mut empty = (0..self.len())
+            .filter(|&community| sizes[community] == 0)
+            .collect::<Vec<_>>();
+        let mut queue = VecDeque::from(rng.permutation(self.len()));
+        let mut queued = vec![true; self.len()];
+        let mut weights = vec![0.; self.len()];
+        let mut neighbors = Vec::new();
+        let mut moved = false;
+        while let Some(v) = queue.pop_front() {
+            queued[v] = false;
+            for &(u, weight) in &self.adjacency[v] {
+                if weights[partition[u]] == 0. {
+                    neighbors.push(partition[u]);
+                }
+                weights[partition[u]] += weight;
+            }
+            let current = partition[v];
+            sizes[current] -= 1;
+            totals[current] = if sizes[current] == 0 {
+                0.
+            } else {
+                totals[current] - self.degree[v]
+            };
+
+            let gain =
+                |community: usize| weights[community] - scale * totals[community] * self.degree[v];
+            let mut best = current;
+            let mut best_gain = gain(current);
+            for &community in &neighbors {
+                if gain(community) > best_gain {
+                    best = community;
+                    best_gain = gain(community);
+                }
+            }
+            // The node is better off alone. Some community is empty, as it is in none.
+            if best_gain < 0. {
+                best = empty.pop().unwrap();
+            }
+
+            partition[v] = best;
+            sizes[best] += 1;
+            totals[best] += self.degree[v];
+            if sizes[current] == 0 {
+                empty.push(current);
+            }
+            if best != current {
+                moved = true;
+                for &(u, _) in &self.adjacency[v] {
+                    if partition[u] != best && !queued[u] {
+                        queued[u] = true;
+                        queue.push_back(u);
+                    }
+                }
+            }
+            neighbors
+                .drain(..)
+                .for_each(|community| weights[community] = 0.);
+        }
+        moved
+    }
+
+    /// Split every community of `partition` into parts, by merging every node that is well
+    /// connected to the rest of its community into the well-connected part of it that increases
+    /// the modularity most. Return the part of every node.
+    fn refine(&self, partition: &[usize], scale: f64, rng: &mut SplitMix64) -> Vec<usize> {
+        let mut totals = vec![0.; self.len()];
+        for (v, &community) in partition.iter().enumerate() {
+            totals[community] += self.degree[v];
+        }
+        // The weight of the edges from every node to the rest of its community.
+        let external = (0..self.len())
+            .map(|v| {
+                self.adjacency[v]
+                    .iter()
+                    .filter(|&&(u, _)| partition[u] == partition[v])
+                    .map(|&(_, weight)| weight)
+                    .sum::<f64>()
+            })
+            .collect::<Vec<_>>();
+        // Every part is named after its first node, and starts out as only that node.
+        let mut parts = (0..self.len()).collect::<Vec<_>>();
+        let mut alone = vec![true; self.len()];
+        let mut part_totals = self.degree.clone();
+        let mut part_external = external.clone();
+        let mut weights = vec![0.; self.len()];
+        let mut neighbors = Vec::new();
+        for v in rng.permutation(self.len()) {
+            let community_total = totals[partition[v]];
+            let degree = self.degree[v];
+            if !alone[v] || external[v] < scale * degree * (community_total - degree) {
+                continue;
+            }
+            for &(u, weight) in &self.adjacency[v] {
+                if partition[u] == partition[v] {
+                    if weights[parts[u]] == 0. {
+                        neighbors.push(parts[u]);
+                    }
+                    weights[parts[u]] += weight;
+                }
+            }
+            let mut best = v;
+            let mut best_gain = 0.;
+            for &part in &neighbors {
+                let well_connected = part_external[part]
+                    >= scale * part_totals[part] * (community_total - part_totals[part]);
+                let gain = weights[part] - scale * degree * part_totals[part];
+                if well_connected && gain > best_gain {
+                    best = part;
+                    best_gain = gain;
+                }
+            }
+            if best != v {
+                parts[v] = best;
+                alone[v] = false;
+                alone[best] = false;
+                part_totals[best] += degree;
+                part_external[best] += external[v] - 2. * weights[best];
+            }
+            neighbors.drain(..).for_each(|part| weights[part] = 0.);
+        }
+        parts
+    }
+
+    /// Merge the nodes of every community of `partition`, numbered below `count`, into a single
+    /// node.
+    fn aggregate(&self, partition: &[usize], count: usize) -> Network {
+        let mut members = vec![Vec::new(); count];
+        for (v, &community) in partition.iter().enumerate() {
+            members[community].push(v);
+        }
+        let mut network = Network {
+            adjacency: Vec::with_capacity(count),
+            degree: vec![0.; count],
+        };
+        let mut weights = vec![0.; count];
+        let mut neighbors = Vec::new();
+        for (community, members) in members.iter().enumerate() {
+            for &v in members {
+                network.degree[community] += self.degree[v];
+                for &(u, weight) in &self.adjacency[v] {
+                    let other = partition[u];
+                    if other != community {
+                        if weights[other] == 0. {
+                            neighbors.push(other);
+                        }
+                        weights[other] += weight;
+                    }
+                }
+            }
+            network.adjacency.push(
+                neighbors
+                    .drain(..)
+                    .map(|other| (other, core::mem::take(&mut weights[other])))
+                    .collect(),
+            );
+        }
+        network
+    }
+}
+
+/// Renumber the communities of `partition` from zero, in the order of their first node, and
+/// return their number.
+fn renumber(partition: &mut [usize]) -> usize {
+    let mut numbers = vec![usize::MAX; partition.len()];
+    let mut count = 0;
+    for community in partition.iter_mut() {
+        if numbers[*community] == usize::MAX {
+            numbers[*community] = count;
+            count += 1;
+        }
+        *community = numbers[*community];
+    }
+    count
+}
+
+/// Map every node to its community, given by its index in `nodes`.
+fn communities<N>(nodes: &[N], mut partition: Vec<usize>) -> (HashMap<N, usize>, usize)
+where
+    N: Copy + Eq + Hash,
+{
+    let count = renumber(&mut partition);
+    let communities = nodes.iter().copied().zip(partition).collect();
+    (communities, count)
+}
+
+/// A [SplitMix64][1] pseudorandom number generator, so that the results only depend on the seed.
+///
+/// [1]: https://doi.org/10.1145/2714064.2660195
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number below `bound`.
+    fn below(&mut self, bound: usize) -> usize {
+        ((self.next() as u128 * bound as u128) >> 64) as usize
+    }
+
+    /// The numbers below `len` in a random order.
+    fn permutation(&mut self, len: usize) -> Vec<usize> {
+        let mut permutation = (0..len).collect::<Vec<_>>();
+        for i in (1..len).rev() {
+            permutation.swap(i, self.below(i + 1));
+        }
+        permutation
+    }
+}
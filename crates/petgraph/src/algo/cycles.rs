@@ -0,0 +1,980 @@
+//! Cycle enumeration and cycle bases.
+//!
+//! * [`elementary_circuits`] lazily enumerates the elementary circuits of a directed graph with
+//!   [Johnson's algorithm][1].
+//! * [`toposort_or_cycle`], [`find_cycle_directed`] and [`find_cycle_undirected`] return a
+//!   [`CycleWitness`] with the nodes and edges of a cycle, if there is one.
+//! * [`fundamental_cycle_basis`] and [`minimum_cycle_basis`] compute a [cycle basis][2] of an
+//!   undirected graph.
+//!
+//! Cycles are reported as sequences of edges, so parallel edges give rise to distinct cycles.
+//!
+//! [1]: https://doi.org/10.1137/0204007
+//! [2]: https://en.wikipedia.org/wiki/Cycle_basis
+
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+use core::mem;
+
+use fixedbitset::FixedBitSet;
+use hashbrown::{HashMap, HashSet};
+
+use crate::{
+    algo::{Cycle, Measure, node_positions},
+    scored::MinScored,
+    unionfind::UnionFind,
+    visit::{EdgeRef, IntoEdgeReferences, IntoEdges, IntoNodeIdentifiers, NodeIndexable},
+};
+
+/// Enumerate the elementary circuits of a directed graph using [Johnson's algorithm][1].
+///
+/// An elementary circuit is a closed walk that does not visit any node twice. Every circuit is
+/// reported exactly once, starting from an edge that leaves its first node in the order of
+/// [`IntoNodeIdentifiers`]. A self-loop is a circuit of length one.
+///
+/// The number of circuits can grow exponentially with the size of the graph. The iterator is
+/// lazy, so [`Iterator::take`] can be used to look at the first few only.
+///
+/// If `max_length` is given, circuits are found with a depth-first search that is pruned by the
+/// distance back to the first node of the circuit, instead of Johnson's blocking scheme, which is
+/// not compatible with a length bound. This is faster when the bound is small compared to the
+/// length of the circuits in the graph.
+///
+/// # Arguments
+/// * `graph`: a directed graph.
+/// * `min_length`: the minimum number of edges of the circuits.
+/// * `max_length`: the maximum number of edges of the circuits (optional).
+///
+/// # Returns
+/// An iterator over the circuits of the graph that have at least `min_length` and at most
+/// `max_length` edges. Each circuit is a `Vec<G::EdgeRef>` of its edges in order: the target of
+/// each edge is the source of the next one, and the target of the last edge is the source of the
+/// first one.
+///
+/// # Complexity
+/// * Time complexity: **O((|V| + |E|)(c + 1))** without a length bound, where **c** is the number
+///   of circuits.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://doi.org/10.1137/0204007
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::elementary_circuits, prelude::*};
+///
+/// // a → b → c → a, b → a and a self-loop at c.
+/// let mut g = DiGraph::<&str, ()>::new();
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// let c = g.add_node("c");
+/// g.extend_with_edges(&[(a, b), (b, c), (c, a), (b, a), (c, c)]);
+///
+/// let mut circuits = elementary_circuits(&g, 0, None)
+///     .map(|circuit| circuit.iter().map(|e| g[e.source()]).collect::<String>())
+///     .collect::<Vec<_>>();
+/// circuits.sort();
+/// assert_eq!(circuits, ["ab", "abc", "c"]);
+///
+/// assert_eq!(elementary_circuits(&g, 2, Some(2)).count(), 1);
+/// ```
+pub fn elementary_circuits<G>(
+    graph: G,
+    min_length: usize,
+    max_length: Option<usize>,
+) -> ElementaryCircuits<G>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+{
+    let (nodes, positions) = node_positions(graph);
+    let node_count = nodes.len();
+    let mut adjacency = vec![Vec::new(); node_count];
+    let mut reverse = vec![Vec::new(); node_count];
+    for edge in graph.edge_references() {
+        let a = positions[graph.to_index(edge.source())];
+        let b = positions[graph.to_index(edge.target())];
+        adjacency[a].push((b, edge));
+        reverse[b].push(a);
+    }
+
+    let mut circuits = ElementaryCircuits {
+        adjacency,
+        reverse,
+        components: Vec::new(),
+        min_length,
+        max_length,
+        start: 0,
+        component: Vec::new(),
+        in_component: vec![true; node_count],
+        visited: vec![false; node_count],
+        blocked: vec![false; node_count],
+        blocked_by: vec![Vec::new(); node_count],
+        distance: vec![usize::MAX; node_count],
+        stack: Vec::new(),
+        path: Vec::new(),
+    };
+    let nodes = (0..node_count).collect::<Vec<_>>();
+    circuits.push_components(&nodes);
+    circuits.in_component.fill(false);
+    circuits
+}
+
+/// An iterator over the elementary circuits of a directed graph.
+///
+/// See [`elementary_circuits`].
+#[derive(Clone)]
+pub struct ElementaryCircuits<G>
+where
+    G: IntoEdgeReferences,
+{
+    adjacency: Vec<Vec<(usize, G::EdgeRef)>>,
+    reverse: Vec<Vec<usize>>,
+    /// Strongly connected components whose circuits are still to be enumerated.
+    components: Vec<Vec<usize>>,
+    min_length: usize,
+    max_length: Option<usize>,
+    /// The least node of the current component; the circuits through it are enumerated.
+    start: usize,
+    component: Vec<usize>,
+    in_component: Vec<bool>,
+    visited: Vec<bool>,
+    blocked: Vec<bool>,
+    blocked_by: Vec<Vec<usize>>,
+    /// The distance to `start` within the component, only used with a length bound.
+    distance: Vec<usize>,
+    /// The nodes of the current path, the next edge to explore from them and whether a circuit
+    /// was found through them.
+    stack: Vec<(usize, usize, bool)>,
+    path: Vec<G::EdgeRef>,
+}
+
+impl<G> ElementaryCircuits<G>
+where
+    G: IntoEdgeReferences,
+{
+    /// Push the strongly connected components of the subgraph induced by `nodes` that contain a
+    /// circuit. The nodes must be marked in `in_component`.
+    fn push_components(&mut self, nodes: &[usize]) {
+        // Kosaraju's algorithm: order the nodes by finishing time, then collect the nodes that
+        // reach each root in the reverse order.
+        let mut order = Vec::with_capacity(nodes.len());
+        let mut stack = Vec::new();
+        for &root in nodes {
+            if self.visited[root] {
+                continue;
+            }
+            self.visited[root] = true;
+            stack.push((root, 0));
+            while let Some(&mut (v, ref mut next)) = stack.last_mut() {
+                if let Some(&(w, _)) = self.adjacency[v].get(*next) {
+                    *next += 1;
+                    if self.in_component[w] && !self.visited[w] {
+                        self.visited[w] = true;
+                        stack.push((w, 0));
+                    }
+                } else {
+                    order.push(v);
+                    stack.pop();
+                }
+            }
+        }
+
+        for &v in nodes {
+            self.visited[v] = false;
+        }
+        let mut stack = Vec::new();
+        for &root in order.iter().rev() {
+            if self.visited[root] {
+                continue;
+            }
+            self.visited[root] = true;
+            stack.push(root);
+            let mut component = Vec::new();
+            while let Some(v) = stack.pop() {
+                component.push(v);
+                for &w in &self.reverse[v] {
+                    if self.in_component[w] && !self.visited[w] {
+                        self.visited[w] = true;
+                        stack.push(w);
+                    }
+                }
+            }
+            let cyclic = component.len() > 1
+                || self.adjacency[component[0]]
+                    .iter()
+                    .any(|&(w, _)| w == component[0]);
+            if cyclic {
+                self.components.push(component);
+            }
+        }
+        for &v in nodes {
+            self.visited[v] = false;
+        }
+    }
+
+    /// Finish the search for circuits through the current start node and begin the next one.
+    /// Returns `false` if there are no components left.
+    fn next_search(&mut self) -> bool {
+        let component = mem::take(&mut self.component);
+        if !component.is_empty() {
+            for &v in &component {
+                self.blocked[v] = false;
+                self.blocked_by[v].clear();
+                self.distance[v] = usize::MAX;
+            }
+            // All circuits through the start node are known, look at the rest of the component.
+            self.in_component[self.start] = false;
+            let rest = component
+                .into_iter()
+                .filter(|&v| v != self.start)
+                .collect::<Vec<_>>();
+            self.push_components(&rest);
+            for &v in &rest {
+                self.in_component[v] = false;
+            }
+        }
+
+        let Some(component) = self.components.pop() else {
+            return false;
+        };
+        for &v in &component {
+            self.in_component[v] = true;
+        }
+        self.start = component.iter().copied().min().unwrap();
+        if self.max_length.is_some() {
+            self.distance[self.start] = 0;
+            let mut queue = vec![self.start];
+            let mut i = 0;
+            while let Some(&v) = queue.get(i) {
+                i += 1;
+                for &w in &self.reverse[v] {
+                    if self.in_component[w] && self.distance[w] == usize::MAX {
+                        self.distance[w] = self.distance[v] + 1;
+                        queue.push(w);
+                    }
+                }
+            }
+        }
+        self.blocked[self.start] = true;
+        self.stack.push((self.start, 0, false));
+        self.component = component;
+        true
+    }
+
+    fn unblock(&mut self, v: usize) {
+        let mut stack = vec![v];
+        while let Some(u) = stack.pop() {
+            if self.blocked[u] {
+                self.blocked[u] = false;
+                stack.append(&mut self.blocked_by[u]);
+            }
+        }
+    }
+}
+
+impl<G> Iterator for ElementaryCircuits<G>
+where
+    G: IntoEdgeReferences,
+{
+    type Item = Vec<G::EdgeRef>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(&(v, next, found)) = self.stack.last() else {
+                if !self.next_search() {
+                    return None;
+                }
+                continue;
+            };
+
+            if let Some(&(w, edge)) = self.adjacency[v].get(next) {
+                self.stack.last_mut().unwrap().1 += 1;
+                if !self.in_component[w] {
+                    continue;
+                }
+                let length = self.path.len() + 1;
+                if w == self.start {
+                    self.stack.last_mut().unwrap().2 = true;
+                    if length >= self.min_length && self.max_length.is_none_or(|m| length <= m) {
+                        let mut circuit = self.path.clone();
+                        circuit.push(edge);
+                        return Some(circuit);
+                    }
+                } else if !self.blocked[w]
+                    && self
+                        .max_length
+                        .is_none_or(|m| length.saturating_add(self.distance[w]) <= m)
+                {
+                    self.path.push(edge);
+                    self.blocked[w] = true;
+                    self.stack.push((w, 0, false));
+                }
+                continue;
+            }
+
+            self.stack.pop();
+            if self.max_length.is_some() {
+                // Without blocking, a node is only blocked while it is on the path.
+                self.blocked[v] = false;
+            } else if found {
+                self.unblock(v);
+            } else {
+                for &(w, _) in &self.adjacency[v] {
+                    if self.in_component[w] && !self.blocked_by[w].contains(&v) {
+                        self.blocked_by[w].push(v);
+                    }
+                }
+            }
+            if let Some(parent) = self.stack.last_mut() {
+                parent.2 |= found;
+                self.path.pop();
+            }
+        }
+    }
+}
+
+/// A cycle found in a graph, given by its nodes and edges.
+///
+/// The cycle is a closed walk: the `i`-th edge leads from the `i`-th node to the next one, and
+/// the last edge leads back to the first node. A self-loop is a cycle with one node and one edge.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CycleWitness<N, E> {
+    nodes: Vec<N>,
+    edges: Vec<E>,
+}
+
+impl<N, E> CycleWitness<N, E> {
+    /// Create a witness from the nodes of a cycle and the edges between consecutive nodes, the
+    /// last one closing the cycle.
+    pub(crate) fn new(nodes: Vec<N>, edges: Vec<E>) -> Self {
+        debug_assert_eq!(nodes.len(), edges.len());
+        CycleWitness { nodes, edges }
+    }
+
+    /// Return the nodes of the cycle, in order.
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// Return the edges of the cycle, in order.
+    pub fn edges(&self) -> &[E] {
+        &self.edges
+    }
+
+    /// Return the number of edges of the cycle.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Return `true` if the cycle has no edges, which never happens for a cycle found in a graph.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Return the nodes and the edges of the cycle.
+    pub fn into_parts(self) -> (Vec<N>, Vec<E>) {
+        (self.nodes, self.edges)
+    }
+}
+
+impl<N: Copy, E> From<CycleWitness<N, E>> for Cycle<N> {
+    fn from(witness: CycleWitness<N, E>) -> Self {
+        Cycle(witness.nodes[0])
+    }
+}
+
+/// Perform a topological sort of a directed graph, or find a cycle.
+///
+/// This is like [`toposort`][crate::algo::toposort], but returns the complete cycle instead of
+/// a single node on it if the graph is not acyclic, which makes for more helpful error messages
+/// ("a depends on b depends on a").
+///
+/// # Arguments
+/// * `g`: a directed graph.
+///
+/// # Returns
+/// * `Ok`: a vector of nodes in topological order: each node is ordered before its successors.
+/// * `Err`: a [`CycleWitness`] with the nodes and edges of a cycle, if the graph is not acyclic.
+///   Self-loops are also cycles.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(|V|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::toposort_or_cycle, prelude::*};
+///
+/// let mut g = DiGraph::<&str, ()>::new();
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// let c = g.add_node("c");
+/// g.extend_with_edges(&[(a, b), (b, c)]);
+/// assert_eq!(toposort_or_cycle(&g), Ok(vec![a, b, c]));
+///
+/// let ca = g.add_edge(c, a, ());
+/// let cycle = toposort_or_cycle(&g).unwrap_err();
+/// assert_eq!(cycle.nodes(), [a, b, c]);
+/// assert_eq!(cycle.edges()[2], ca);
+///
+/// let names = cycle.nodes().iter().map(|&v| g[v]).collect::<Vec<_>>();
+/// assert_eq!(names.join(" depends on "), "a depends on b depends on c");
+/// ```
+pub fn toposort_or_cycle<G>(g: G) -> Result<Vec<G::NodeId>, CycleWitness<G::NodeId, G::EdgeId>>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    // The position on the stack of every node that is on it, or one of the markers below.
+    const NEW: usize = usize::MAX;
+    const FINISHED: usize = usize::MAX - 1;
+
+    let mut state = vec![NEW; g.node_bound()];
+    let mut finished = Vec::new();
+    let mut stack: Vec<(G::NodeId, G::Edges, Option<G::EdgeId>)> = Vec::new();
+    for root in g.node_identifiers() {
+        if state[g.to_index(root)] != NEW {
+            continue;
+        }
+        state[g.to_index(root)] = 0;
+        stack.push((root, g.edges(root), None));
+        while let Some((v, edges, _)) = stack.last_mut() {
+            let v = *v;
+            let Some(edge) = edges.next() else {
+                state[g.to_index(v)] = FINISHED;
+                finished.push(v);
+                stack.pop();
+                continue;
+            };
+            let w = edge.target();
+            match state[g.to_index(w)] {
+                NEW => {
+                    state[g.to_index(w)] = stack.len();
+                    stack.push((w, g.edges(w), Some(edge.id())));
+                }
+                FINISHED => {}
+                position => {
+                    // A back edge: the nodes from `w` up to `v` form a cycle.
+                    let nodes = stack[position..].iter().map(|s| s.0).collect();
+                    let edges = stack[position + 1..]
+                        .iter()
+                        .filter_map(|s| s.2)
+                        .chain(Some(edge.id()))
+                        .collect();
+                    return Err(CycleWitness { nodes, edges });
+                }
+            }
+        }
+    }
+    finished.reverse();
+    Ok(finished)
+}
+
+/// Find a cycle in a directed graph.
+///
+/// This is like [`is_cyclic_directed`][crate::algo::is_cyclic_directed], but returns the cycle
+/// it finds.
+///
+/// # Arguments
+/// * `g`: a directed graph.
+///
+/// # Returns
+/// * `Some`: a [`CycleWitness`] with the nodes and edges of a cycle.
+/// * `None`: if the graph is acyclic.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(|V|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+pub fn find_cycle_directed<G>(g: G) -> Option<CycleWitness<G::NodeId, G::EdgeId>>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    toposort_or_cycle(g).err()
+}
+
+/// Find a cycle in an undirected graph.
+///
+/// This is like [`is_cyclic_undirected`][crate::algo::is_cyclic_undirected], but returns the
+/// cycle it finds. The graph is treated as if undirected, so two parallel edges form a cycle.
+///
+/// # Arguments
+/// * `g`: an undirected graph.
+///
+/// # Returns
+/// * `Some`: a [`CycleWitness`] with the nodes and edges of a cycle. The edges may point in either
+///   direction.
+/// * `None`: if the graph is a forest.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(|V|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::find_cycle_undirected, prelude::*};
+///
+/// let mut g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (1, 4)]);
+/// assert!(find_cycle_undirected(&g).is_none());
+///
+/// g.add_edge(3.into(), 1.into(), ());
+/// let cycle = find_cycle_undirected(&g).unwrap();
+/// assert_eq!(cycle.len(), 3);
+/// assert!(cycle.nodes().contains(&1.into()));
+/// ```
+pub fn find_cycle_undirected<G>(g: G) -> Option<CycleWitness<G::NodeId, G::EdgeId>>
+where
+    G: IntoEdgeReferences + NodeIndexable,
+{
+    let mut forest = UnionFind::new(g.node_bound());
+    let mut tree = vec![Vec::new(); g.node_bound()];
+    for edge in g.edge_references() {
+        let (a, b) = (g.to_index(edge.source()), g.to_index(edge.target()));
+        if forest.union(a, b) {
+            tree[a].push((b, edge.id()));
+            tree[b].push((a, edge.id()));
+            continue;
+        }
+
+        // The edge closes a cycle with the path from `b` to `a` in the forest.
+        let mut previous = vec![None; g.node_bound()];
+        let mut queue = vec![b];
+        let mut i = 0;
+        while let Some(&v) = queue.get(i) {
+            i += 1;
+            if v == a {
+                break;
+            }
+            for &(w, id) in &tree[v] {
+                if w != b && previous[w].is_none() {
+                    previous[w] = Some((v, id));
+                    queue.push(w);
+                }
+            }
+        }
+        let (mut nodes, mut edges) = (vec![a], vec![edge.id()]);
+        let mut at = a;
+        while let Some((before, id)) = previous[at] {
+            nodes.push(before);
+            edges.push(id);
+            at = before;
+        }
+        // Walk the cycle from `a` over the edge to `b`, then through the forest back to `a`.
+        nodes[1..].reverse();
+        edges[1..].reverse();
+        let nodes = nodes.into_iter().map(|i| g.from_index(i)).collect();
+        return Some(CycleWitness { nodes, edges });
+    }
+    None
+}
+
+/// Compute a [fundamental cycle basis][1] of an undirected graph.
+///
+/// Every edge that is not part of a spanning forest of the graph closes a cycle with the path in
+/// the forest that connects its endpoints. These cycles form a basis of the cycle space of the
+/// graph: every cycle is the symmetric difference of some of them.
+///
+/// The graph is treated as if undirected.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+///
+/// # Returns
+/// A `Vec` of **|E| - |V| + c** cycles, where **c** is the number of connected components. Each
+/// cycle is a `Vec<G::EdgeRef>` of its edges in order around the cycle; consecutive edges share
+/// an endpoint, but the edges may point in either direction.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| |E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Cycle_basis#Fundamental_cycles
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::fundamental_cycle_basis, prelude::*};
+///
+/// // Two triangles sharing the edge b - c.
+/// //
+/// //     b
+/// //   / | \
+/// //  a  |  d
+/// //   \ | /
+/// //     c
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)]);
+///
+/// let basis = fundamental_cycle_basis(&g);
+/// assert_eq!(basis.len(), 2);
+/// assert!(
+///     basis
+///         .iter()
+///         .all(|cycle| cycle.len() == 3 || cycle.len() == 4)
+/// );
+/// ```
+pub fn fundamental_cycle_basis<G>(graph: G) -> Vec<Vec<G::EdgeRef>>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+{
+    let edges = undirected_edges(graph);
+    let forest = edges.spanning_forest();
+    forest
+        .non_tree
+        .iter()
+        .map(|&i| {
+            let (a, b) = edges.endpoints[i];
+            // Walk over the edge from a to b, then up from b and down to a.
+            let (mut up, mut down) = (vec![i], Vec::new());
+            let (mut x, mut y) = (b, a);
+            while x != y {
+                if forest.depth[x] >= forest.depth[y] {
+                    let (parent, edge) = forest.parent[x];
+                    up.push(edge);
+                    x = parent;
+                } else {
+                    let (parent, edge) = forest.parent[y];
+                    down.push(edge);
+                    y = parent;
+                }
+            }
+            up.extend(down.into_iter().rev());
+            up.into_iter().map(|i| edges.edges[i]).collect()
+        })
+        .collect()
+}
+
+/// Compute a [minimum weight cycle basis][1] of an undirected graph using de Pina's algorithm.
+///
+/// A minimum weight cycle basis is a basis of the cycle space of the graph, such that the total
+/// cost of the edges of its cycles is as small as possible. The algorithm maintains a set of
+/// support vectors and repeatedly picks the cheapest cycle that is not orthogonal to the next
+/// one, found with shortest path searches in a doubled graph ([de Pina 1995][2], [Kavitha et al.
+/// 2004][3]).
+///
+/// The graph is treated as if undirected. Edge costs must be non-negative.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `edge_cost`: closure that returns the non-negative cost of an edge.
+///
+/// # Returns
+/// A `Vec` of **|E| - |V| + c** cycles, where **c** is the number of connected components. Each
+/// cycle is a `Vec<G::EdgeRef>` of its edges in order around the cycle; consecutive edges share
+/// an endpoint, but the edges may point in either direction.
+///
+/// # Complexity
+/// * Time complexity: **O(|E|² |V| log |V|)**.
+/// * Auxiliary space: **O(|E|² + |V|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Cycle_basis#Minimum_weight_bases
+/// [2]: https://research.tue.nl/en/publications/applications-of-shortest-path-methods
+/// [3]: https://doi.org/10.1007/978-3-540-24749-4_19
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::minimum_cycle_basis, prelude::*};
+///
+/// // A square a - b - c - d with the diagonal a - c.
+/// let g =
+///     UnGraph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 0, 1), (0, 2, 1)]);
+///
+/// let basis = minimum_cycle_basis(&g, |e| *e.weight());
+/// // Two triangles, rather than a triangle and the square.
+/// assert_eq!(basis.len(), 2);
+/// assert!(basis.iter().all(|cycle| cycle.len() == 3));
+/// ```
+pub fn minimum_cycle_basis<G, F, K>(graph: G, mut edge_cost: F) -> Vec<Vec<G::EdgeRef>>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let edges = undirected_edges(graph);
+    let costs = edges
+        .edges
+        .iter()
+        .map(|&e| edge_cost(e))
+        .collect::<Vec<_>>();
+    let non_tree = edges.spanning_forest().non_tree;
+    let dimension = non_tree.len();
+    let mut coordinate = vec![usize::MAX; edges.edges.len()];
+    for (j, &i) in non_tree.iter().enumerate() {
+        coordinate[i] = j;
+    }
+
+    let mut support = (0..dimension)
+        .map(|j| {
+            let mut s = FixedBitSet::with_capacity(dimension);
+            s.insert(j);
+            s
+        })
+        .collect::<Vec<_>>();
+    let mut basis = Vec::with_capacity(dimension);
+    for i in 0..dimension {
+        let odd = |edge: usize| coordinate[edge] != usize::MAX && support[i][coordinate[edge]];
+        let cycle = edges.shortest_odd_cycle(&costs, odd, support[i].ones().map(|j| non_tree[j]));
+
+        let mut vector = FixedBitSet::with_capacity(dimension);
+        for &edge in &cycle {
+            if coordinate[edge] != usize::MAX {
+                vector.insert(coordinate[edge]);
+            }
+        }
+        let (done, rest) = support.split_at_mut(i + 1);
+        for s in rest {
+            if s.intersection_count(&vector) % 2 == 1 {
+                s.symmetric_difference_with(&done[i]);
+            }
+        }
+        basis.push(edges.cyclic_order(&cycle));
+    }
+    basis
+}
+
+/// Collect the edges of `graph`, with the positions of their endpoints.
+fn undirected_edges<G>(graph: G) -> UndirectedEdges<G::EdgeRef>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+{
+    let (nodes, positions) = node_positions(graph);
+    let node_count = nodes.len();
+    let edges = graph.edge_references().collect::<Vec<_>>();
+    let mut endpoints = Vec::with_capacity(edges.len());
+    let mut adjacency = vec![Vec::new(); node_count];
+    for (i, edge) in edges.iter().enumerate() {
+        let a = positions[graph.to_index(edge.source())];
+        let b = positions[graph.to_index(edge.target())];
+        endpoints.push((a, b));
+        adjacency[a].push((b, i));
+        if a != b {
+            adjacency[b].push((a, i));
+        }
+    }
+    UndirectedEdges {
+        edges,
+        endpoints,
+        adjacency,
+    }
+}
+
+/// A spanning forest: the parent of every node with the connecting edge, and its depth.
+struct SpanningForest {
+    parent: Vec<(usize, usize)>,
+    depth: Vec<usize>,
+    non_tree: Vec<usize>,
+}
+
+/// The edges of a graph, with their endpoints and incident edges as positions.
+struct UndirectedEdges<E> {
+    edges: Vec<E>,
+    endpoints: Vec<(usize, usize)>,
+    /// The neighbors of every node, with the connecting edge.
+    adjacency: Vec<Vec<(usize, usize)>>,
+}
+
+impl<E> UndirectedEdges<E>
+where
+    E: Copy,
+{
+    fn spanning_forest(&self) -> SpanningForest {
+        let node_count = self.adjacency.len();
+        let mut forest = UnionFind::new(node_count);
+        let mut tree = vec![Vec::new(); node_count];
+        let mut non_tree = Vec::new();
+        for (i, &(a, b)) in self.endpoints.iter().enumerate() {
+            if forest.union(a, b) {
+                tree[a].push((b, i));
+                tree[b].push((a, i));
+            } else {
+                non_tree.push(i);
+            }
+        }
+
+        let mut parent = vec![(usize::MAX, usize::MAX); node_count];
+        let mut depth = vec![usize::MAX; node_count];
+        let mut stack = Vec::new();
+        for root in 0..node_count {
+            if depth[root] != usize::MAX {
+                continue;
+            }
+            depth[root] = 0;
+            stack.push(root);
+            while let Some(v) = stack.pop() {
+                for &(w, edge) in &tree[v] {
+                    if depth[w] == usize::MAX {
+                        depth[w] = depth[v] + 1;
+                        parent[w] = (v, edge);
+                        stack.push(w);
+                    }
+                }
+            }
+        }
+        SpanningForest {
+            parent,
+            depth,
+            non_tree,
+        }
+    }
+
+    /// Find the cheapest closed walk that uses an odd number of `odd` edges and passes through
+    /// an endpoint of one of `through`, and return the edges of a simple cycle in it that also
+    /// uses an odd number of `odd` edges.
+    fn shortest_odd_cycle<K>(
+        &self,
+        costs: &[K],
+        odd: impl Fn(usize) -> bool,
+        through: impl Iterator<Item = usize>,
+    ) -> Vec<usize>
+    where
+        K: Measure + Copy,
+    {
+        let node_count = self.adjacency.len();
+        let mut sources = through
+            .flat_map(|edge| {
+                let (a, b) = self.endpoints[edge];
+                [a, b]
+            })
+            .collect::<Vec<_>>();
+        sources.sort_unstable();
+        sources.dedup();
+
+        // Search the graph with two copies of every node, `2 * v` and `2 * v + 1`, where odd
+        // edges cross between the copies. A path from `2 * v` to `2 * v + 1` is an odd walk.
+        let mut best: Option<(K, Vec<usize>)> = None;
+        let mut distance = vec![None; 2 * node_count];
+        let mut previous = vec![(usize::MAX, usize::MAX); 2 * node_count];
+        let mut heap = BinaryHeap::new();
+        for source in sources {
+            distance.fill(None);
+            heap.clear();
+            let (from, to) = (2 * source, 2 * source + 1);
+            distance[from] = Some(K::default());
+            heap.push(MinScored(K::default(), from));
+            while let Some(MinScored(cost, state)) = heap.pop() {
+                if distance[state].is_some_and(|d| d < cost) {
+                    continue;
+                }
+                if best.as_ref().is_some_and(|b| b.0 <= cost) {
+                    break;
+                }
+                if state == to {
+                    let mut walk = Vec::new();
+                    let mut at = to;
+                    while at != from {
+                        let (before, edge) = previous[at];
+                        walk.push(edge);
+                        at = before;
+                    }
+                    best = Some((cost, walk));
+                    break;
+                }
+                let (v, side) = (state / 2, state % 2);
+                for &(w, edge) in &self.adjacency[v] {
+                    let next = 2 * w + (side ^ usize::from(odd(edge)));
+                    let next_cost = cost + costs[edge];
+                    if distance[next].is_none_or(|d| next_cost < d) {
+                        distance[next] = Some(next_cost);
+                        previous[next] = (state, edge);
+                        heap.push(MinScored(next_cost, next));
+                    }
+                }
+            }
+        }
+
+        let mut walk = best.map(|b| b.1).unwrap_or_default();
+        walk.sort_unstable();
+        let mut edges = Vec::with_capacity(walk.len());
+        for edge in walk {
+            if edges.last() == Some(&edge) {
+                edges.pop();
+            } else {
+                edges.push(edge);
+            }
+        }
+        // With edges of cost zero, the walk can pass through a node twice. Its edges then form
+        // several cycles, and one of them with an odd number of `odd` edges costs no more.
+        self.split_cycles(&edges)
+            .into_iter()
+            .find(|cycle| cycle.iter().filter(|&&edge| odd(edge)).count() % 2 == 1)
+            .unwrap_or_default()
+    }
+
+    /// Split a set of edges in which every node has an even degree into simple cycles.
+    fn split_cycles(&self, edges: &[usize]) -> Vec<Vec<usize>> {
+        let mut incident = HashMap::<usize, Vec<usize>>::new();
+        for &edge in edges {
+            let (a, b) = self.endpoints[edge];
+            incident.entry(a).or_default().push(edge);
+            if a != b {
+                incident.entry(b).or_default().push(edge);
+            }
+        }
+        let mut used = HashSet::with_capacity(edges.len());
+        let mut cycles = Vec::new();
+        for &first in edges {
+            if used.contains(&first) {
+                continue;
+            }
+            // Walk along unused edges, and cut off a cycle whenever the walk gets back to a node
+            // on its path.
+            let start = self.endpoints[first].0;
+            let mut path = vec![start];
+            let mut path_edges = Vec::new();
+            let mut on_path = HashMap::new();
+            on_path.insert(start, 0);
+            while let Some(&at) = path.last() {
+                let Some(edge) = incident[&at].iter().copied().find(|e| !used.contains(e)) else {
+                    break;
+                };
+                used.insert(edge);
+                path_edges.push(edge);
+                let (a, b) = self.endpoints[edge];
+                let next = if at == a { b } else { a };
+                if let Some(&i) = on_path.get(&next) {
+                    for v in path.drain(i + 1..) {
+                        on_path.remove(&v);
+                    }
+                    cycles.push(path_edges.split_off(i));
+                } else {
+                    on_path.insert(next, path.len());
+                    path.push(next);
+                }
+            }
+        }
+        cycles
+    }
+
+    /// Order a set of edges that forms a cycle into a walk around it.
+    fn cyclic_order(&self, cycle: &[usize]) -> Vec<E> {
+        let mut incident = HashMap::<usize, Vec<usize>>::new();
+        for &edge in cycle {
+            let (a, b) = self.endpoints[edge];
+            incident.entry(a).or_default().push(edge);
+            incident.entry(b).or_default().push(edge);
+        }
+        let mut used = HashSet::with_capacity(cycle.len());
+        let mut order = Vec::with_capacity(cycle.len());
+        for &first in cycle {
+            if used.contains(&first) {
+                continue;
+            }
+            let mut at = self.endpoints[first].0;
+            let mut edge = Some(first);
+            while let Some(e) = edge {
+                used.insert(e);
+                order.push(self.edges[e]);
+                let (a, b) = self.endpoints[e];
+                at = if at == a { b } else { a };
+                edge = incident[&at].iter().copied().find(|e| !used.contains(e));
+            }
+        }
+        order
+    }
+}
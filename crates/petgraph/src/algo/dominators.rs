@@ -11,15 +11,35 @@
 //! The node **A** is said to be the *immediate dominator* of a node **B** iff it
 //! strictly dominates **B** and there does not exist any node **C** where **A**
 //! dominates **C** and **C** dominates **B**.
+//!
+//! # Post-Dominance, Dominance Frontiers and Control Dependence
+//!
+//! A node **A** *post-dominates* a node **B** iff every path from **B** to an exit
+//! of the graph contains **A**. These are the dominators of the reversed graph,
+//! rooted at the exits; see [`post_dominators`].
+//!
+//! The *dominance frontier* of a node **A** is the set of nodes **B** such that **A**
+//! dominates a predecessor of **B**, but does not strictly dominate **B**. These are
+//! the places where the definitions of a variable at **A** meet other definitions,
+//! and where SSA construction places phi nodes; see [`DominanceFrontiers`].
+//!
+//! A node **B** is *control dependent* on a node **A** iff **A** has a successor
+//! that **B** post-dominates, but **B** does not strictly post-dominate **A**: one
+//! branch out of **A** always reaches **B**, and another may avoid it.
 
 use alloc::{vec, vec::Vec};
 use core::{cmp::Ordering, hash::Hash};
 
 use hashbrown::{HashMap, HashSet, hash_map::Iter};
 
-use crate::visit::{DfsPostOrder, GraphBase, IntoNeighbors, Visitable, Walker};
+use crate::visit::{
+    DfsPostOrder, GraphBase, IntoNeighbors, IntoNodeIdentifiers, NodeIndexable, Visitable, Walker,
+};
 
 /// The dominance relation for some graph and root.
+///
+/// The roots of the dominator tree map to themselves. There is a single root, except for the
+/// post-dominators of a graph with several exits, see [`post_dominators`].
 #[derive(Debug, Clone)]
 pub struct Dominators<N>
 where
@@ -34,6 +54,9 @@ where
     N: Copy + Eq + Hash,
 {
     /// Get the root node used to construct these dominance relations.
+    ///
+    /// For the post-dominators of a graph with several exits, this is the first
+    /// exit.
     pub fn root(&self) -> N {
         self.root
     }
@@ -41,13 +64,13 @@ where
     /// Get the immediate dominator of the given node.
     ///
     /// Returns `None` for any node that is not reachable from the root, and for
-    /// the root itself.
+    /// the root itself. For post-dominators with several exits, it also returns
+    /// `None` for the nodes whose immediate post-dominator is the virtual exit.
     pub fn immediate_dominator(&self, node: N) -> Option<N> {
-        if node == self.root {
-            None
-        } else {
-            self.dominators.get(&node).cloned()
-        }
+        self.dominators
+            .get(&node)
+            .copied()
+            .filter(|&dominator| dominator != node)
     }
 
     /// Iterate over the given node's strict dominators.
@@ -89,6 +112,206 @@ where
             node,
         }
     }
+
+    /// Compute the [dominance frontiers][0] of all nodes reachable from the root.
+    ///
+    /// `graph` must be the graph these dominators were computed for. The
+    /// dominance frontiers of post-dominators, for the reversed graph, are the
+    /// post-dominance frontiers.
+    ///
+    /// This is the algorithm of Cooper et al: for every edge into a node **B**,
+    /// it walks up the dominator tree from the source of the edge to the
+    /// immediate dominator of **B**, and adds **B** to the frontier of every
+    /// node on the way.
+    ///
+    /// # Complexity
+    /// * Time complexity: **O(|E| · d)**, where **d** is the depth of the dominator tree, and
+    ///   **O(|V| + |E|)** plus the size of the frontiers in practice.
+    /// * Auxiliary space: **O(|V|)** plus the size of the frontiers.
+    ///
+    /// where **|V|** is the number of nodes and **|E|** is the number of edges.
+    ///
+    /// [0]: http://www.hipersoft.rice.edu/grads/publications/dom14.pdf
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::{algo::dominators, prelude::*};
+    ///
+    /// // An if-then-else: both branches meet at `join`.
+    /// let mut graph = DiGraph::<(), ()>::new();
+    /// let entry = graph.add_node(());
+    /// let then = graph.add_node(());
+    /// let other = graph.add_node(());
+    /// let join = graph.add_node(());
+    /// graph.extend_with_edges([(entry, then), (entry, other), (then, join), (other, join)]);
+    ///
+    /// let frontiers = dominators::simple_fast(&graph, entry).dominance_frontiers(&graph);
+    /// assert_eq!(
+    ///     frontiers.frontier(then).unwrap().collect::<Vec<_>>(),
+    ///     [join]
+    /// );
+    /// assert_eq!(frontiers.frontier(entry).unwrap().count(), 0);
+    ///
+    /// // A definition in `then` needs a phi node in `join`.
+    /// assert!(frontiers.iterated_frontier([then]).contains(&join));
+    /// ```
+    pub fn dominance_frontiers<G>(&self, graph: G) -> DominanceFrontiers<N>
+    where
+        G: IntoNeighbors<NodeId = N>,
+    {
+        let mut frontiers = self
+            .dominators
+            .keys()
+            .map(|&node| (node, HashSet::new()))
+            .collect::<HashMap<_, _>>();
+        for &node in self.dominators.keys() {
+            for successor in graph.neighbors(node) {
+                self.walk_to_immediate_dominator(node, successor, |runner| {
+                    frontiers.get_mut(&runner).unwrap().insert(successor);
+                });
+            }
+        }
+        DominanceFrontiers { frontiers }
+    }
+
+    /// Compute the control dependences of a graph, with these as its
+    /// post-dominators from [`post_dominators`].
+    ///
+    /// A node **B** is control dependent on a node **A** iff **A** has a
+    /// successor that **B** post-dominates, but **B** does not strictly
+    /// post-dominate **A**. The result maps every node **A** that can reach an
+    /// exit to the nodes that are control dependent on it, which are the
+    /// successors of **A** in the control-dependence graph. Nodes that are not
+    /// control dependent on any node are executed whenever the graph is.
+    ///
+    /// This is the algorithm of [Ferrante et al][0], which walks the
+    /// post-dominator tree from the target of every edge.
+    ///
+    /// # Complexity
+    /// * Time complexity: **O(|E| · d)**, where **d** is the depth of the post-dominator tree.
+    /// * Auxiliary space: **O(|V|)** plus the size of the result.
+    ///
+    /// where **|V|** is the number of nodes and **|E|** is the number of edges.
+    ///
+    /// [0]: https://doi.org/10.1145/24039.24041
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::{algo::dominators, prelude::*};
+    ///
+    /// // `body` only runs if the branch in `test` takes it.
+    /// let mut graph = DiGraph::<(), ()>::new();
+    /// let test = graph.add_node(());
+    /// let body = graph.add_node(());
+    /// let exit = graph.add_node(());
+    /// graph.extend_with_edges([(test, body), (test, exit), (body, exit)]);
+    ///
+    /// let post_doms = dominators::post_dominators(&graph, [exit]);
+    /// let dependences = post_doms.control_dependences(&graph);
+    /// assert_eq!(dependences[&test].iter().collect::<Vec<_>>(), [&body]);
+    /// assert!(dependences[&body].is_empty());
+    /// ```
+    pub fn control_dependences<G>(&self, graph: G) -> HashMap<N, HashSet<N>>
+    where
+        G: IntoNeighbors<NodeId = N>,
+    {
+        let mut dependences = self
+            .dominators
+            .keys()
+            .map(|&node| (node, HashSet::new()))
+            .collect::<HashMap<_, HashSet<_>>>();
+        for &node in self.dominators.keys() {
+            for successor in graph.neighbors(node) {
+                if !self.dominators.contains_key(&successor) {
+                    continue;
+                }
+                // In the reversed graph, `node` is a successor of `successor`.
+                self.walk_to_immediate_dominator(successor, node, |runner| {
+                    dependences.get_mut(&node).unwrap().insert(runner);
+                });
+            }
+        }
+        dependences
+    }
+
+    /// Call `f` on the nodes of the dominator tree from `start` up to the
+    /// immediate dominator of `node`, excluding the latter.
+    fn walk_to_immediate_dominator(&self, start: N, node: N, mut f: impl FnMut(N)) {
+        if !self.dominators.contains_key(&node) {
+            return;
+        }
+        let stop = self.immediate_dominator(node);
+        let mut runner = Some(start);
+        while let Some(current) = runner {
+            if Some(current) == stop {
+                break;
+            }
+            f(current);
+            runner = self.immediate_dominator(current);
+        }
+    }
+}
+
+/// The dominance frontiers of the nodes of a graph.
+///
+/// See [`Dominators::dominance_frontiers`].
+#[derive(Debug, Clone)]
+pub struct DominanceFrontiers<N>
+where
+    N: Copy + Eq + Hash,
+{
+    frontiers: HashMap<N, HashSet<N>>,
+}
+
+impl<N> DominanceFrontiers<N>
+where
+    N: Copy + Eq + Hash,
+{
+    /// Iterate over the dominance frontier of the given node.
+    ///
+    /// If the given node is not reachable from the root, then `None` is
+    /// returned.
+    pub fn frontier(&self, node: N) -> Option<impl Iterator<Item = N> + '_> {
+        self.frontiers
+            .get(&node)
+            .map(|frontier| frontier.iter().copied())
+    }
+
+    /// Compute the iterated dominance frontier of a set of nodes.
+    ///
+    /// This is the limit of taking the dominance frontier of the nodes and
+    /// the frontier found so far. For the nodes that define a variable, these
+    /// are the nodes that need a phi node for it in [SSA form][0]. Nodes that
+    /// are not reachable from the root are ignored.
+    ///
+    /// # Complexity
+    /// * Time complexity: **O(|V|)** plus the size of the visited frontiers.
+    /// * Auxiliary space: **O(|V|)**.
+    ///
+    /// where **|V|** is the number of nodes.
+    ///
+    /// [0]: https://en.wikipedia.org/wiki/Static_single-assignment_form
+    pub fn iterated_frontier<I>(&self, nodes: I) -> HashSet<N>
+    where
+        I: IntoIterator<Item = N>,
+    {
+        let mut result = HashSet::new();
+        let mut visited = HashSet::new();
+        let mut stack = nodes
+            .into_iter()
+            .filter(|node| self.frontiers.contains_key(node))
+            .collect::<Vec<_>>();
+        visited.extend(stack.iter().copied());
+        while let Some(node) = stack.pop() {
+            for &next in &self.frontiers[&node] {
+                result.insert(next);
+                if visited.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        result
+    }
 }
 
 /// Iterator for a node's dominators.
@@ -316,6 +539,236 @@ where
     (post_order, predecessor_sets)
 }
 
+/// Compute the dominators of a graph with the [Lengauer-Tarjan algorithm][0].
+///
+/// This finds the same dominators as [`simple_fast`], with a better worst
+/// case, which makes it the better choice for large control-flow graphs with
+/// deep nesting or many back edges. This is the simple version of the
+/// algorithm, with path compression but without balancing.
+///
+/// # Arguments
+/// * `graph`: a control-flow graph.
+/// * `root`: the *root* node of the `graph`.
+///
+/// # Returns
+/// * `Dominators`: the dominance relation for given `graph` and `root` represented by
+///   [`struct@Dominators`].
+///
+/// # Complexity
+/// * Time complexity: **O(|E| log |V|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [0]: https://doi.org/10.1145/357062.357071
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::dominators, prelude::*};
+///
+/// // A loop whose header dominates its body.
+/// let graph = DiGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 1), (1, 3)]);
+/// let doms = dominators::lengauer_tarjan(&graph, 0.into());
+/// assert_eq!(doms.immediate_dominator(2.into()), Some(1.into()));
+/// assert_eq!(doms.immediate_dominator(3.into()), Some(1.into()));
+/// ```
+pub fn lengauer_tarjan<G>(graph: G, root: G::NodeId) -> Dominators<G::NodeId>
+where
+    G: IntoNeighbors + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let (vertex, idom) = lengauer_tarjan_indices(graph.to_index(root), |v, successors| {
+        successors.extend(
+            graph
+                .neighbors(graph.from_index(v))
+                .map(|w| graph.to_index(w)),
+        );
+    });
+    Dominators {
+        root,
+        dominators: vertex
+            .iter()
+            .zip(&idom)
+            .map(|(&v, &dominator)| (graph.from_index(v), graph.from_index(vertex[dominator])))
+            .collect(),
+    }
+}
+
+/// Compute the post-dominators of a graph.
+///
+/// The post-dominators are the dominators of the reversed graph, rooted at
+/// the exits. With several exits, the root is a virtual exit that succeeds
+/// all of them and is not part of the result: the exits, and the nodes that
+/// no single node post-dominates, have no immediate post-dominator. Only the
+/// nodes that can reach an exit are post-dominated; the others, like the nodes
+/// of an infinite loop, are treated as unreachable.
+///
+/// The exits are usually the nodes without successors, like the return blocks
+/// of a function, which are given by
+/// [`externals`](crate::graph::Graph::externals) with
+/// [`Outgoing`](crate::Direction::Outgoing).
+///
+/// # Arguments
+/// * `graph`: a control-flow graph.
+/// * `exits`: the exit nodes of the `graph`.
+///
+/// # Returns
+/// * `Dominators`: the post-dominance relation, whose [`root`](Dominators::root) is the first exit.
+///
+/// # Panics
+/// * If `exits` is empty.
+///
+/// # Complexity
+/// * Time complexity: **O(|E| log |V|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::dominators, prelude::*};
+///
+/// // Two returns, and a node that reaches both.
+/// let graph = DiGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3), (0, 3)]);
+/// let exits = graph.externals(Outgoing).collect::<Vec<_>>();
+/// let post_doms = dominators::post_dominators(&graph, exits);
+/// assert_eq!(post_doms.immediate_dominator(0.into()), None);
+/// assert_eq!(post_doms.immediate_dominator(2.into()), None);
+///
+/// // With a single exit, it post-dominates everything.
+/// let post_doms = dominators::post_dominators(&graph, [3.into()]);
+/// assert_eq!(post_doms.immediate_dominator(0.into()), Some(3.into()));
+/// assert_eq!(post_doms.immediate_dominator(2.into()), None);
+/// ```
+pub fn post_dominators<G, I>(graph: G, exits: I) -> Dominators<G::NodeId>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+    I: IntoIterator<Item = G::NodeId>,
+{
+    let exits = exits.into_iter().collect::<Vec<_>>();
+    let root = *exits.first().expect("a graph needs an exit");
+    let virtual_exit = graph.node_bound();
+    let mut predecessors = vec![Vec::new(); virtual_exit + 1];
+    for node in graph.node_identifiers() {
+        for successor in graph.neighbors(node) {
+            predecessors[graph.to_index(successor)].push(graph.to_index(node));
+        }
+    }
+    predecessors[virtual_exit].extend(exits.iter().map(|&exit| graph.to_index(exit)));
+
+    let (vertex, idom) = lengauer_tarjan_indices(virtual_exit, |v, successors| {
+        successors.extend_from_slice(&predecessors[v]);
+    });
+    Dominators {
+        root,
+        dominators: vertex
+            .iter()
+            .zip(&idom)
+            .skip(1)
+            .map(|(&v, &dominator)| {
+                // The nodes below the virtual exit are roots.
+                let dominator = if dominator == 0 { v } else { vertex[dominator] };
+                (graph.from_index(v), graph.from_index(dominator))
+            })
+            .collect(),
+    }
+}
+
+/// Compute the dominator tree of the nodes reachable from `root` with the
+/// Lengauer-Tarjan algorithm, where `successors` appends the successors of a
+/// node. Return the reachable nodes in depth-first order, and the immediate
+/// dominator of each as a position in that order. The root is first, and is
+/// its own immediate dominator.
+fn lengauer_tarjan_indices<F>(root: usize, mut successors: F) -> (Vec<usize>, Vec<usize>)
+where
+    F: FnMut(usize, &mut Vec<usize>),
+{
+    // Number the nodes in depth-first order, and remember their successors.
+    let mut number = HashMap::new();
+    let mut vertex = vec![root];
+    let mut parent = vec![UNDEFINED];
+    let mut adjacency = vec![Vec::new()];
+    number.insert(root, 0);
+    successors(root, &mut adjacency[0]);
+    let mut stack = vec![(0, 0)];
+    while let Some((v, next)) = stack.last_mut() {
+        let v = *v;
+        let Some(&w) = adjacency[v].get(*next) else {
+            stack.pop();
+            continue;
+        };
+        *next += 1;
+        if number.contains_key(&w) {
+            continue;
+        }
+        let w_number = vertex.len();
+        number.insert(w, w_number);
+        vertex.push(w);
+        parent.push(v);
+        let mut w_successors = Vec::new();
+        successors(w, &mut w_successors);
+        adjacency.push(w_successors);
+        stack.push((w_number, 0));
+    }
+    let length = vertex.len();
+    let mut predecessors = vec![Vec::new(); length];
+    for (v, successors) in adjacency.iter().enumerate() {
+        for w in successors {
+            predecessors[number[w]].push(v);
+        }
+    }
+    drop(adjacency);
+
+    // From here on, nodes are their depth-first numbers.
+    let mut semi = (0..length).collect::<Vec<_>>();
+    let mut label = (0..length).collect::<Vec<_>>();
+    let mut ancestor = vec![UNDEFINED; length];
+    let mut idom = vec![0; length];
+    let mut bucket = vec![Vec::new(); length];
+    let mut path = Vec::new();
+    let mut eval = |v: usize, ancestor: &mut [usize], label: &mut [usize], semi: &[usize]| {
+        if ancestor[v] == UNDEFINED {
+            return v;
+        }
+        // Compress the path to the root of the forest, from the top down.
+        let mut current = v;
+        while ancestor[ancestor[current]] != UNDEFINED {
+            path.push(current);
+            current = ancestor[current];
+        }
+        while let Some(current) = path.pop() {
+            let a = ancestor[current];
+            if semi[label[a]] < semi[label[current]] {
+                label[current] = label[a];
+            }
+            ancestor[current] = ancestor[a];
+        }
+        label[v]
+    };
+
+    for w in (1..length).rev() {
+        for &v in &predecessors[w] {
+            let u = eval(v, &mut ancestor, &mut label, &semi);
+            if semi[u] < semi[w] {
+                semi[w] = semi[u];
+            }
+        }
+        bucket[semi[w]].push(w);
+        ancestor[w] = parent[w];
+        for v in core::mem::take(&mut bucket[parent[w]]) {
+            let u = eval(v, &mut ancestor, &mut label, &semi);
+            idom[v] = if semi[u] < semi[v] { u } else { parent[w] };
+        }
+    }
+    for w in 1..length {
+        if idom[w] != semi[w] {
+            idom[w] = idom[idom[w]];
+        }
+    }
+    (vertex, idom)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
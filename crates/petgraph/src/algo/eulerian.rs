@@ -0,0 +1,454 @@
+//! Eulerian paths and circuits, and the Chinese postman problem.
+//!
+//! An [*Eulerian path*][1] (or trail) is a walk that traverses every edge of a graph exactly once.
+//! An *Eulerian circuit* is an Eulerian path that starts and ends at the same node. Parallel edges
+//! and self-loops are supported: every edge is traversed exactly once, however many edges connect
+//! the same pair of nodes.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Eulerian_path
+
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+use core::ops::Div;
+
+use crate::{
+    algo::{BoundedMeasure, matching::min_weight_perfect_matching_indices},
+    scored::MinScored,
+    visit::{
+        EdgeIndexable, EdgeRef, GraphProp, IntoEdgeReferences, IntoEdges, IntoNodeIdentifiers,
+        NodeIndexable,
+    },
+};
+
+/// An edge as seen from one of its endpoints: the edge slot, the edge reference leaving the node
+/// and the index of the node at the other end.
+type Arc<E> = (usize, E, usize);
+
+/// Return `true` if the graph has an Eulerian circuit.
+///
+/// A graph without edges has an (empty) Eulerian circuit.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+///
+/// # Returns
+/// * `true`: if there is a closed walk that traverses every edge exactly once.
+/// * `false`: otherwise.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+pub fn has_eulerian_circuit<G>(graph: G) -> bool
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + EdgeIndexable + GraphProp,
+{
+    eulerian_circuit(graph).is_some()
+}
+
+/// Return `true` if the graph has an Eulerian path.
+///
+/// Every graph with an Eulerian circuit also has an Eulerian path.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+///
+/// # Returns
+/// * `true`: if there is a walk that traverses every edge exactly once.
+/// * `false`: otherwise.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+pub fn has_eulerian_path<G>(graph: G) -> bool
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + EdgeIndexable + GraphProp,
+{
+    eulerian_path(graph).is_some()
+}
+
+/// Find an Eulerian circuit using [Hierholzer's algorithm][1].
+///
+/// A directed graph has an Eulerian circuit iff every node has equal in- and out-degree and all
+/// edges belong to a single strongly connected component. An undirected graph has one iff every
+/// node has an even degree and all edges belong to a single connected component.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+///
+/// # Returns
+/// * `Some(Vec<G::EdgeRef>)`: the edges of the circuit, in the order in which they are traversed.
+///   The source of each edge is the node the walk leaves from, also for undirected graphs, and the
+///   target of the last edge is the source of the first one. The circuit is empty if the graph has
+///   no edges.
+/// * `None`: if the graph has no Eulerian circuit.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Eulerian_path#Hierholzer's_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::eulerian::eulerian_circuit, prelude::*};
+///
+/// // A multigraph with three parallel edges between b and c, and a self-loop at a:
+/// //
+/// //    __
+/// //   |  |
+/// //    a
+/// //   / \
+/// //  b ≡ c
+/// let mut g = UnGraph::<(), ()>::new_undirected();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// let c = g.add_node(());
+/// g.extend_with_edges(&[(a, b), (b, c), (c, b), (b, c), (c, a), (a, a)]);
+///
+/// let circuit = eulerian_circuit(&g).unwrap();
+/// assert_eq!(circuit.len(), g.edge_count());
+/// for pair in circuit.windows(2) {
+///     assert_eq!(pair[0].target(), pair[1].source());
+/// }
+/// assert_eq!(circuit.last().unwrap().target(), circuit[0].source());
+///
+/// g.add_edge(a, b, ());
+/// assert!(eulerian_circuit(&g).is_none());
+/// ```
+pub fn eulerian_circuit<G>(graph: G) -> Option<Vec<G::EdgeRef>>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + EdgeIndexable + GraphProp,
+{
+    let (out_degree, in_degree, edge_count) = degrees(graph);
+    let balanced = if graph.is_directed() {
+        out_degree == in_degree
+    } else {
+        out_degree.iter().all(|d| d % 2 == 0)
+    };
+    if !balanced {
+        return None;
+    }
+    let Some(start) = out_degree.iter().position(|&d| d > 0) else {
+        return Some(Vec::new());
+    };
+
+    let adjacency = adjacency(graph);
+    let circuit = hierholzer(&adjacency, EdgeIndexable::edge_bound(&graph), start);
+    (circuit.len() == edge_count).then_some(circuit)
+}
+
+/// Find an Eulerian path using [Hierholzer's algorithm][1].
+///
+/// A directed graph has an Eulerian path iff at most one node has one more outgoing than incoming
+/// edge (the start), at most one node has one more incoming than outgoing edge (the end), all
+/// other nodes have equal in- and out-degree, and all edges are in a single weakly connected
+/// component. An undirected graph has one iff zero or two nodes have an odd degree and all edges
+/// are in a single connected component.
+///
+/// If the graph has an Eulerian circuit, a circuit is returned.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+///
+/// # Returns
+/// * `Some(Vec<G::EdgeRef>)`: the edges of the path, in the order in which they are traversed. The
+///   source of each edge is the node the walk leaves from, also for undirected graphs. The path is
+///   empty if the graph has no edges.
+/// * `None`: if the graph has no Eulerian path.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Eulerian_path#Hierholzer's_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::eulerian::eulerian_path, prelude::*};
+///
+/// // A de Bruijn style graph of the 2-mers of "ATGGCGTGCA".
+/// let mut g = DiGraphMap::<&str, ()>::new();
+/// let kmers = ["AT", "TG", "GG", "GC", "CG", "GT", "TG", "GC", "CA"];
+/// for pair in kmers.windows(2) {
+///     g.add_edge(pair[0], pair[1], ());
+/// }
+///
+/// let path = eulerian_path(&g).unwrap();
+/// assert_eq!(path.len(), g.edge_count());
+/// assert_eq!(path[0].0, "AT");
+/// assert_eq!(path.last().unwrap().1, "CA");
+/// ```
+pub fn eulerian_path<G>(graph: G) -> Option<Vec<G::EdgeRef>>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + EdgeIndexable + GraphProp,
+{
+    let (out_degree, in_degree, edge_count) = degrees(graph);
+    let start = if graph.is_directed() {
+        let mut start = None;
+        let mut has_end = false;
+        for (i, (&out_d, &in_d)) in out_degree.iter().zip(&in_degree).enumerate() {
+            if out_d == in_d + 1 && start.is_none() {
+                start = Some(i);
+            } else if in_d == out_d + 1 && !has_end {
+                has_end = true;
+            } else if out_d != in_d {
+                return None;
+            }
+        }
+        start
+    } else {
+        let mut odd = out_degree.iter().enumerate().filter(|(_, d)| *d % 2 == 1);
+        let start = odd.next().map(|(i, _)| i);
+        if odd.count() > 1 {
+            return None;
+        }
+        start
+    };
+    let Some(start) = start.or_else(|| out_degree.iter().position(|&d| d > 0)) else {
+        return Some(Vec::new());
+    };
+
+    let adjacency = adjacency(graph);
+    let path = hierholzer(&adjacency, EdgeIndexable::edge_bound(&graph), start);
+    (path.len() == edge_count).then_some(path)
+}
+
+/// Solve the [*Chinese postman problem*][1] (route inspection problem) for an undirected graph.
+///
+/// Find a shortest closed walk that traverses every edge at least once. Edges on shortest paths
+/// between the nodes of odd degree are duplicated, paired up with a minimum weight perfect
+/// matching (see
+/// [`minimum_weight_perfect_matching`](crate::algo::minimum_weight_perfect_matching)),
+/// and an Eulerian circuit of the resulting multigraph is returned.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `edge_cost`: closure that returns the cost of traversing a particular edge. All costs must be
+///   non-negative.
+///
+/// # Returns
+/// * `Some((cost, walk))`: the total cost of the walk, and the edges of the walk in traversal
+///   order. The source of each edge is the node the walk leaves from. Edges that are traversed
+///   several times appear several times.
+/// * `None`: if the edges of the graph are not all in a single connected component.
+///
+/// # Panics
+/// * If the graph is directed.
+///
+/// # Complexity
+/// * Time complexity: **O(|V|³ + k(|E| + |V|log|V|))**.
+/// * Auxiliary space: **O(|V|² + |E|)**.
+///
+/// where **|V|** is the number of nodes, **|E|** is the number of edges and **k** is the number
+/// of nodes of odd degree.
+///
+/// [1]: https://en.wikipedia.org/wiki/Chinese_postman_problem
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::eulerian::chinese_postman, prelude::*};
+///
+/// // a ---1--- b
+/// // |         |
+/// // 1         1
+/// // |         |
+/// // d ---1--- c ---5--- e
+/// //  \                 /
+/// //   -------2--------
+/// let mut g = UnGraph::<(), u32>::new_undirected();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// let c = g.add_node(());
+/// let d = g.add_node(());
+/// let e = g.add_node(());
+/// g.extend_with_edges(&[
+///     (a, b, 1),
+///     (b, c, 1),
+///     (c, d, 1),
+///     (d, a, 1),
+///     (c, e, 5),
+///     (d, e, 2),
+/// ]);
+///
+/// // The odd nodes c and d are joined by duplicating the edge between them.
+/// let (cost, walk) = chinese_postman(&g, |e| *e.weight()).unwrap();
+/// assert_eq!(cost, 12);
+/// assert_eq!(walk.len(), 7);
+/// assert_eq!(walk.last().unwrap().target(), walk[0].source());
+/// ```
+pub fn chinese_postman<G, F, K>(graph: G, mut edge_cost: F) -> Option<(K, Vec<G::EdgeRef>)>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + EdgeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> K,
+    K: BoundedMeasure + Copy + Div<K, Output = K>,
+{
+    assert!(
+        !graph.is_directed(),
+        "chinese_postman only supports undirected graphs"
+    );
+    let (degree, _, edge_count) = degrees(graph);
+    let Some(start) = degree.iter().position(|&d| d > 0) else {
+        return Some((K::default(), Vec::new()));
+    };
+
+    let mut adjacency = adjacency(graph);
+    let mut slot_count = EdgeIndexable::edge_bound(&graph);
+    let mut slot_cost = vec![K::default(); slot_count];
+    let mut total_cost = K::default();
+    for edge in graph.edge_references() {
+        let cost = edge_cost(edge);
+        slot_cost[EdgeIndexable::to_index(&graph, edge.id())] = cost;
+        total_cost = total_cost + cost;
+    }
+
+    // Pair up the odd nodes along shortest paths.
+    let odd = (0..degree.len())
+        .filter(|&i| degree[i] % 2 == 1)
+        .collect::<Vec<_>>();
+    let trees = odd
+        .iter()
+        .map(|&source| shortest_path_tree(&adjacency, &slot_cost, source))
+        .collect::<Vec<_>>();
+    let mut pair_edges = Vec::new();
+    for (i, (dist, _)) in trees.iter().enumerate() {
+        for (j, &target) in odd.iter().enumerate().skip(i + 1) {
+            if let Some(d) = dist[target] {
+                pair_edges.push((i, j, d));
+            }
+        }
+    }
+    let mate = min_weight_perfect_matching_indices(odd.len(), &pair_edges)?;
+
+    // Duplicate the edges on the shortest path between every matched pair.
+    let mut copies = Vec::new();
+    for (i, &j) in mate.iter().enumerate().filter(|&(i, &j)| i < j) {
+        let (dist, pred) = &trees[i];
+        total_cost = total_cost + dist[odd[j]].unwrap();
+        let mut node = odd[j];
+        while let Some((slot, prev)) = pred[node] {
+            copies.push((slot, prev, node));
+            node = prev;
+        }
+    }
+    for (slot, a, b) in copies {
+        for (from, to) in [(a, b), (b, a)] {
+            let &(_, edge, _) = adjacency[from].iter().find(|arc| arc.0 == slot).unwrap();
+            adjacency[from].push((slot_count, edge, to));
+        }
+        slot_count += 1;
+    }
+
+    let walk = hierholzer(&adjacency, slot_count, start);
+    let walk_length = edge_count + slot_count - EdgeIndexable::edge_bound(&graph);
+    (walk.len() == walk_length).then_some((total_cost, walk))
+}
+
+/// Return the out-degree and in-degree of every node, and the number of edges. For undirected
+/// graphs, both are the degree, where self-loops count twice.
+fn degrees<G>(graph: G) -> (Vec<usize>, Vec<usize>, usize)
+where
+    G: IntoEdgeReferences + NodeIndexable + GraphProp,
+{
+    let mut out_degree = vec![0; graph.node_bound()];
+    let mut in_degree = vec![0; graph.node_bound()];
+    let mut edge_count = 0;
+    for edge in graph.edge_references() {
+        let (a, b) = (graph.to_index(edge.source()), graph.to_index(edge.target()));
+        out_degree[a] += 1;
+        in_degree[b] += 1;
+        if !graph.is_directed() {
+            out_degree[b] += 1;
+            in_degree[a] += 1;
+        }
+        edge_count += 1;
+    }
+    (out_degree, in_degree, edge_count)
+}
+
+/// The edges leaving every node, indexed by node index.
+fn adjacency<G>(graph: G) -> Vec<Vec<Arc<G::EdgeRef>>>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + EdgeIndexable,
+{
+    let mut adjacency = vec![Vec::new(); graph.node_bound()];
+    for node in graph.node_identifiers() {
+        adjacency[NodeIndexable::to_index(&graph, node)] = graph
+            .edges(node)
+            .map(|edge| {
+                (
+                    EdgeIndexable::to_index(&graph, edge.id()),
+                    edge,
+                    NodeIndexable::to_index(&graph, edge.target()),
+                )
+            })
+            .collect();
+    }
+    adjacency
+}
+
+/// Walk from `start`, traversing every edge slot reachable from it at most once, and splice
+/// sub-circuits into the walk until no unused edges remain on it.
+fn hierholzer<E: Copy>(adjacency: &[Vec<Arc<E>>], slot_count: usize, start: usize) -> Vec<E> {
+    let mut used = vec![false; slot_count];
+    let mut cursor = vec![0; adjacency.len()];
+    let mut walk = Vec::new();
+    let mut stack: Vec<(usize, Option<E>)> = vec![(start, None)];
+
+    while let Some(&(node, _)) = stack.last() {
+        let arcs = &adjacency[node];
+        while cursor[node] < arcs.len() && used[arcs[cursor[node]].0] {
+            cursor[node] += 1;
+        }
+        if let Some(&(slot, edge, next)) = arcs.get(cursor[node]) {
+            used[slot] = true;
+            stack.push((next, Some(edge)));
+        } else if let Some((_, Some(edge))) = stack.pop() {
+            walk.push(edge);
+        }
+    }
+
+    walk.reverse();
+    walk
+}
+
+/// Dijkstra's algorithm over the adjacency lists. Returns the distance from `source` and the
+/// edge slot and node that precede every reached node on a shortest path.
+#[allow(clippy::type_complexity)]
+fn shortest_path_tree<E, K>(
+    adjacency: &[Vec<Arc<E>>],
+    slot_cost: &[K],
+    source: usize,
+) -> (Vec<Option<K>>, Vec<Option<(usize, usize)>>)
+where
+    K: BoundedMeasure + Copy,
+{
+    let mut dist = vec![None; adjacency.len()];
+    let mut pred = vec![None; adjacency.len()];
+    let mut visited = vec![false; adjacency.len()];
+    let mut heap = BinaryHeap::new();
+    dist[source] = Some(K::default());
+    heap.push(MinScored(K::default(), source));
+
+    while let Some(MinScored(d, node)) = heap.pop() {
+        if visited[node] {
+            continue;
+        }
+        visited[node] = true;
+        for &(slot, _, next) in &adjacency[node] {
+            let next_dist = d + slot_cost[slot];
+            if !visited[next] && dist[next].is_none_or(|old| next_dist < old) {
+                dist[next] = Some(next_dist);
+                pred[next] = Some((slot, node));
+                heap.push(MinScored(next_dist, next));
+            }
+        }
+    }
+    (dist, pred)
+}
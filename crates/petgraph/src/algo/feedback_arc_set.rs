@@ -1,13 +1,23 @@
-use alloc::{collections::VecDeque, vec::Vec};
-use core::ops::{Index, IndexMut};
+use alloc::{
+    collections::{BinaryHeap, VecDeque},
+    vec,
+    vec::Vec,
+};
+use core::{
+    cmp::Ordering,
+    ops::{Index, IndexMut, Sub},
+};
 
 use hashbrown::HashMap;
 
 use self::linked_list::{LinkedList, LinkedListEntry};
 use crate::{
     Directed,
+    algo::{Measure, tarjan_scc},
     graph::{GraphIndex, NodeIndex},
-    visit::{EdgeRef, GraphProp, IntoEdgeReferences},
+    visit::{
+        EdgeRef, GraphProp, IntoEdgeReferences, IntoNeighbors, IntoNodeIdentifiers, NodeIndexable,
+    },
 };
 
 /// Finds a [feedback arc set]: a set of edges in the given directed graph, which when
@@ -86,6 +96,375 @@ where
         .filter(move |e| node_seq[&e.source().index()] >= node_seq[&e.target().index()])
 }
 
+/// Finds a [feedback arc set] of small total weight: a set of edges in the given directed graph,
+/// which when removed, make the graph acyclic.
+///
+/// Only edges within a strongly connected component (found with [`tarjan_scc`]) can be part of a
+/// cycle, so every component is handled on its own. Within a component, this is the heuristic of
+/// [`greedy_feedback_arc_set`] with weights: sinks are placed last and sources first, and
+/// otherwise the node with the largest difference between the weights of its outgoing and its
+/// incoming edges is placed next. The edges that point backwards in the resulting order are the
+/// feedback arc set.
+///
+/// Loops (edges to and from the same node) are always included in the returned set.
+///
+/// # Arguments
+/// * `g`: a directed graph.
+/// * `edge_weight`: closure that returns the weight of an edge, which must not be negative.
+///
+/// # Returns
+/// * `Vec`: the edge references `G::EdgeRef` in the feedback arc set.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E| log |E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [feedback arc set]: https://en.wikipedia.org/wiki/Feedback_arc_set
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::weighted_feedback_arc_set, graph::DiGraph};
+///
+/// // Two cycles that share a light edge.
+/// let g = DiGraph::<(), u32>::from_edges([(0, 1, 5), (1, 2, 1), (2, 0, 5), (2, 3, 5), (3, 1, 5)]);
+/// let fas = weighted_feedback_arc_set(&g, |e| *e.weight());
+/// assert_eq!(fas.len(), 1);
+/// assert_eq!(*fas[0].weight(), 1);
+/// ```
+pub fn weighted_feedback_arc_set<G, F, K>(g: G, mut edge_weight: F) -> Vec<G::EdgeRef>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + IntoNeighbors + NodeIndexable,
+    G: GraphProp<EdgeType = Directed>,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy + Sub<K, Output = K>,
+{
+    let mut fas = Vec::new();
+    for component in component_edges(g, &mut edge_weight, &mut fas) {
+        let order = weighted_node_sequence(&component);
+        component.backward_edges(&order, &mut fas);
+    }
+    fas
+}
+
+/// Finds a minimum [feedback arc set]: a set of edges of minimum total weight in the given
+/// directed graph, which when removed, make the graph acyclic.
+///
+/// Every strongly connected component (found with [`tarjan_scc`]) is handled on its own. Removing
+/// a feedback arc set leaves an acyclic graph, whose topological order has the removed edges
+/// pointing backwards, so this finds the order of the nodes of the component with the lightest
+/// backward edges, by dynamic programming over the subsets of the nodes that come first.
+///
+/// Finding a minimum feedback arc set is NP-hard, and the running time grows exponentially with
+/// the size of the largest component, so this is only practical for components of up to about 20
+/// nodes.
+///
+/// Loops (edges to and from the same node) are always included in the returned set.
+///
+/// # Arguments
+/// * `g`: a directed graph.
+/// * `edge_weight`: closure that returns the weight of an edge, which must not be negative.
+/// * `max_component_size`: the size of the largest strongly connected component to solve.
+///
+/// # Returns
+/// * `Some(Vec)`: the edge references `G::EdgeRef` in a minimum feedback arc set.
+/// * `None`: if a strongly connected component has more than `max_component_size` nodes.
+///
+/// # Panics
+/// Panics if `max_component_size` is not less than the number of bits of `usize`.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E| + 2^|C| |C|²)**.
+/// * Auxiliary space: **O(|V| + |E| + 2^|C|)**.
+///
+/// where **|V|** is the number of nodes, **|E|** is the number of edges and **|C|** is the number
+/// of nodes of the largest strongly connected component.
+///
+/// [feedback arc set]: https://en.wikipedia.org/wiki/Feedback_arc_set
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::min_feedback_arc_set, graph::DiGraph};
+///
+/// // A tournament on four nodes, with one edge against the order 0, 1, 2, 3.
+/// let g = DiGraph::<(), f64>::from_edges([
+///     (0, 1, 1.0),
+///     (0, 2, 1.0),
+///     (3, 0, 0.5),
+///     (1, 2, 1.0),
+///     (1, 3, 1.0),
+///     (2, 3, 1.0),
+/// ]);
+/// let fas = min_feedback_arc_set(&g, |e| *e.weight(), 16).unwrap();
+/// assert_eq!(fas.len(), 1);
+/// assert_eq!(*fas[0].weight(), 0.5);
+///
+/// // The component is too large.
+/// assert!(min_feedback_arc_set(&g, |e| *e.weight(), 3).is_none());
+/// ```
+pub fn min_feedback_arc_set<G, F, K>(
+    g: G,
+    mut edge_weight: F,
+    max_component_size: usize,
+) -> Option<Vec<G::EdgeRef>>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + IntoNeighbors + NodeIndexable,
+    G: GraphProp<EdgeType = Directed>,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    assert!(
+        max_component_size < usize::BITS as usize,
+        "components of {max_component_size} nodes are too large to solve exactly"
+    );
+    let mut fas = Vec::new();
+    let components = component_edges(g, &mut edge_weight, &mut fas);
+    if components.iter().any(|c| c.size > max_component_size) {
+        return None;
+    }
+    for component in components {
+        let order = exact_node_sequence(&component);
+        component.backward_edges(&order, &mut fas);
+    }
+    Some(fas)
+}
+
+/// The edges within a strongly connected component, between the positions of their endpoints in
+/// the component.
+struct Component<E, K> {
+    size: usize,
+    edges: Vec<(usize, usize, K, E)>,
+}
+
+impl<E: Copy, K> Component<E, K> {
+    /// Add the edges that point backwards in the `order` of the nodes to `fas`.
+    fn backward_edges(&self, order: &[usize], fas: &mut Vec<E>) {
+        fas.extend(
+            self.edges
+                .iter()
+                .filter(|&&(a, b, _, _)| order[a] > order[b])
+                .map(|&(_, _, _, e)| e),
+        );
+    }
+}
+
+/// Split the edges of `g` by strongly connected component, and add the loops to `loops`. The
+/// edges between components are left out.
+fn component_edges<G, F, K>(
+    g: G,
+    edge_weight: &mut F,
+    loops: &mut Vec<G::EdgeRef>,
+) -> Vec<Component<G::EdgeRef, K>>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + IntoNeighbors + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+{
+    let mut component = vec![usize::MAX; g.node_bound()];
+    let mut position = vec![0; g.node_bound()];
+    let mut components = Vec::new();
+    for scc in tarjan_scc(g) {
+        if scc.len() > 1 {
+            for (i, &v) in scc.iter().enumerate() {
+                component[g.to_index(v)] = components.len();
+                position[g.to_index(v)] = i;
+            }
+            components.push(Component {
+                size: scc.len(),
+                edges: Vec::new(),
+            });
+        }
+    }
+    for edge in g.edge_references() {
+        let (a, b) = (g.to_index(edge.source()), g.to_index(edge.target()));
+        if a == b {
+            loops.push(edge);
+        } else if component[a] != usize::MAX && component[a] == component[b] {
+            components[component[a]].edges.push((
+                position[a],
+                position[b],
+                edge_weight(edge),
+                edge,
+            ));
+        }
+    }
+    components
+}
+
+/// The weighted version of [`good_node_sequence`]: the position of every node of the component
+/// in an order with light backward edges.
+fn weighted_node_sequence<E, K>(component: &Component<E, K>) -> Vec<usize>
+where
+    K: Measure + Copy + Sub<K, Output = K>,
+{
+    let n = component.size;
+    let mut out_edges = vec![Vec::new(); n];
+    let mut in_edges = vec![Vec::new(); n];
+    let mut out_weight = vec![K::default(); n];
+    let mut in_weight = vec![K::default(); n];
+    for &(a, b, weight, _) in &component.edges {
+        out_edges[a].push((b, weight));
+        in_edges[b].push((a, weight));
+        out_weight[a] = out_weight[a] + weight;
+        in_weight[b] = in_weight[b] + weight;
+    }
+    let mut out_degree = out_edges.iter().map(Vec::len).collect::<Vec<_>>();
+    let mut in_degree = in_edges.iter().map(Vec::len).collect::<Vec<_>>();
+
+    // A heap of the nodes by delta weight, whose outdated entries are skipped.
+    let mut stamp = vec![0usize; n];
+    let mut heap = (0..n)
+        .map(|v| DeltaWeight {
+            out_weight: out_weight[v],
+            in_weight: in_weight[v],
+            node: v,
+            stamp: 0,
+        })
+        .collect::<BinaryHeap<_>>();
+
+    let mut removed = vec![false; n];
+    let mut first = Vec::with_capacity(n);
+    let mut last = Vec::new();
+    let mut pending = (0..n).collect::<Vec<_>>();
+    while first.len() + last.len() < n {
+        let v = if let Some(v) = pending.pop() {
+            if removed[v] {
+                continue;
+            }
+            if out_degree[v] == 0 {
+                last.push(v);
+            } else if in_degree[v] == 0 {
+                first.push(v);
+            } else {
+                continue;
+            }
+            v
+        } else {
+            let v = loop {
+                let top = heap.pop().unwrap();
+                if !removed[top.node] && stamp[top.node] == top.stamp {
+                    break top.node;
+                }
+            };
+            first.push(v);
+            v
+        };
+
+        removed[v] = true;
+        for &(u, weight) in &out_edges[v] {
+            if !removed[u] {
+                in_degree[u] -= 1;
+                in_weight[u] = in_weight[u] - weight;
+                stamp[u] += 1;
+                heap.push(DeltaWeight {
+                    out_weight: out_weight[u],
+                    in_weight: in_weight[u],
+                    node: u,
+                    stamp: stamp[u],
+                });
+                pending.push(u);
+            }
+        }
+        for &(u, weight) in &in_edges[v] {
+            if !removed[u] {
+                out_degree[u] -= 1;
+                out_weight[u] = out_weight[u] - weight;
+                stamp[u] += 1;
+                heap.push(DeltaWeight {
+                    out_weight: out_weight[u],
+                    in_weight: in_weight[u],
+                    node: u,
+                    stamp: stamp[u],
+                });
+                pending.push(u);
+            }
+        }
+    }
+
+    let mut order = vec![0; n];
+    for (i, v) in first.into_iter().chain(last.into_iter().rev()).enumerate() {
+        order[v] = i;
+    }
+    order
+}
+
+/// A node in the heap of [`weighted_node_sequence`], ordered by the difference between the
+/// weights of its outgoing and incoming edges, which is compared without subtracting.
+struct DeltaWeight<K> {
+    out_weight: K,
+    in_weight: K,
+    node: usize,
+    stamp: usize,
+}
+
+impl<K: Measure + Copy> Ord for DeltaWeight<K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.out_weight + other.in_weight)
+            .partial_cmp(&(other.out_weight + self.in_weight))
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+impl<K: Measure + Copy> PartialOrd for DeltaWeight<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Measure + Copy> PartialEq for DeltaWeight<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<K: Measure + Copy> Eq for DeltaWeight<K> {}
+
+/// The position of every node of the component in an order with the lightest backward edges.
+fn exact_node_sequence<E, K>(component: &Component<E, K>) -> Vec<usize>
+where
+    K: Measure + Copy,
+{
+    let n = component.size;
+    // The total weight of the edges between two nodes.
+    let mut weight = vec![vec![None::<K>; n]; n];
+    for &(a, b, w, _) in &component.edges {
+        weight[a][b] = Some(weight[a][b].map_or(w, |total| total + w));
+    }
+
+    // `best[set]` is the weight of the backward edges of the best order of `set`, whose last node
+    // is `last[set]`.
+    let full = (1usize << n) - 1;
+    let mut best = vec![None::<K>; full + 1];
+    let mut last = vec![0; full + 1];
+    best[0] = Some(K::default());
+    for set in 0..full {
+        let cost = best[set].unwrap();
+        for v in (0..n).filter(|&v| set & (1 << v) == 0) {
+            let mut next = cost;
+            for u in (0..n).filter(|&u| set & (1 << u) != 0) {
+                if let Some(w) = weight[v][u] {
+                    next = next + w;
+                }
+            }
+            let extended = set | (1 << v);
+            if best[extended].is_none_or(|b| next < b) {
+                best[extended] = Some(next);
+                last[extended] = v;
+            }
+        }
+    }
+
+    let mut order = vec![0; n];
+    let mut set = full;
+    for position in (0..n).rev() {
+        let v = last[set];
+        order[v] = position;
+        set &= !(1 << v);
+    }
+    order
+}
+
 fn good_node_sequence(
     edge_refs: impl Iterator<Item = (NodeIndex<usize>, NodeIndex<usize>)>,
 ) -> HashMap<usize, usize> {
@@ -0,0 +1,353 @@
+//! Feedback vertex sets: sets of nodes whose removal makes a directed graph acyclic.
+//!
+//! Finding a minimum feedback vertex set is NP-hard, so these are heuristics. Both handle every
+//! strongly connected component on its own, repeatedly drop the nodes without incoming or
+//! outgoing edges, which are on no cycle, and finally put back the chosen nodes that are not
+//! needed to break a cycle.
+
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+use core::{cmp::Reverse, ops::Sub};
+
+use crate::{
+    Directed,
+    algo::{Measure, tarjan_scc},
+    visit::{
+        EdgeRef, GraphProp, IntoEdgeReferences, IntoNeighbors, IntoNodeIdentifiers, NodeIndexable,
+    },
+};
+
+/// Finds a small [feedback vertex set]: a set of nodes in the given directed graph, which when
+/// removed, make the graph acyclic.
+///
+/// Within every strongly connected component, this repeatedly picks the node with the most
+/// pairs of incoming and outgoing edges, that is with the largest product of its in-degree and
+/// out-degree, which is on the most cycles of length two through it.
+///
+/// Nodes with loops (edges to and from the same node) are always included in the returned set.
+///
+/// # Arguments
+/// * `g`: a directed graph.
+///
+/// # Returns
+/// * `Vec`: the nodes in the feedback vertex set. No node can be left out without leaving a cycle.
+///
+/// # Complexity
+/// * Time complexity: **O(|E| log |E| + |F| (|V| + |E|))**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes, **|E|** is the number of edges and **|F|** is the number
+/// of nodes in the feedback vertex set.
+///
+/// [feedback vertex set]: https://en.wikipedia.org/wiki/Feedback_vertex_set
+///
+/// # Example
+/// ```rust
+/// use petgraph::{
+///     algo::greedy_feedback_vertex_set,
+///     graph::{DiGraph, NodeIndex},
+/// };
+///
+/// // Three cycles through node 0.
+/// let g = DiGraph::<(), ()>::from_edges([(0, 1), (1, 0), (0, 2), (2, 3), (3, 0), (4, 0), (0, 4)]);
+/// assert_eq!(greedy_feedback_vertex_set(&g), [NodeIndex::new(0)]);
+/// ```
+pub fn greedy_feedback_vertex_set<G>(g: G) -> Vec<G::NodeId>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + IntoNeighbors + NodeIndexable,
+    G: GraphProp<EdgeType = Directed>,
+{
+    let mut fvs = Vec::new();
+    for component in components(g, &mut fvs) {
+        let mut residual = Residual::new(&component);
+        let n = component.nodes.len();
+        let product = |r: &Residual, v: usize| r.in_degree[v] * r.out_degree[v];
+        let mut stamp = vec![0usize; n];
+        let mut heap = BinaryHeap::new();
+        let mut chosen = Vec::new();
+        residual.reduce();
+        loop {
+            // Refresh the heap entries of the nodes whose degrees changed.
+            for v in core::mem::take(&mut residual.changed) {
+                stamp[v] += 1;
+                heap.push((product(&residual, v), Reverse(v), stamp[v]));
+            }
+            let v = loop {
+                match heap.pop() {
+                    Some((_, Reverse(v), s)) if !residual.removed[v] && s == stamp[v] => {
+                        break Some(v);
+                    }
+                    Some(_) => {}
+                    None => break None,
+                }
+            };
+            let Some(v) = v else {
+                break;
+            };
+            chosen.push(v);
+            residual.remove(v);
+            residual.reduce();
+        }
+        component.minimize(&mut chosen);
+        fvs.extend(chosen.into_iter().map(|v| component.nodes[v]));
+    }
+    fvs
+}
+
+/// Finds a [feedback vertex set] of small total weight: a set of nodes in the given directed
+/// graph, which when removed, make the graph acyclic.
+///
+/// This uses the [local ratio technique][1]: within every strongly connected component, it
+/// repeatedly finds a cycle, and lowers the weights of its nodes by the smallest weight among
+/// them, which takes the nodes whose weight drops to zero into the set.
+///
+/// Nodes with loops (edges to and from the same node) are always included in the returned set.
+///
+/// # Arguments
+/// * `g`: a directed graph.
+/// * `node_weight`: closure that returns the weight of a node, which must not be negative.
+///
+/// # Returns
+/// * `Vec`: the nodes in the feedback vertex set. No node can be left out without leaving a cycle.
+///
+/// # Complexity
+/// * Time complexity: **O(|V|² + |F| (|V| + |E|))**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes, **|E|** is the number of edges and **|F|** is the number
+/// of nodes in the feedback vertex set.
+///
+/// [feedback vertex set]: https://en.wikipedia.org/wiki/Feedback_vertex_set
+/// [1]: https://doi.org/10.1145/1008731.1008733
+///
+/// # Example
+/// ```rust
+/// use petgraph::{
+///     algo::weighted_feedback_vertex_set,
+///     graph::{DiGraph, NodeIndex},
+/// };
+///
+/// // Two cycles through the expensive node 0, which can also be broken at 1 and 3.
+/// let g = DiGraph::<u32, ()>::from_edges([(0, 1), (1, 2), (2, 0), (0, 3), (3, 4), (4, 0)]);
+/// let weights = [10, 1, 5, 2, 5];
+/// let mut fvs = weighted_feedback_vertex_set(&g, |v| weights[v.index()]);
+/// fvs.sort();
+/// assert_eq!(fvs, [NodeIndex::new(1), NodeIndex::new(3)]);
+/// ```
+pub fn weighted_feedback_vertex_set<G, F, K>(g: G, mut node_weight: F) -> Vec<G::NodeId>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + IntoNeighbors + NodeIndexable,
+    G: GraphProp<EdgeType = Directed>,
+    F: FnMut(G::NodeId) -> K,
+    K: Measure + Copy + Sub<K, Output = K>,
+{
+    let mut fvs = Vec::new();
+    for component in components(g, &mut fvs) {
+        let mut residual = Residual::new(&component);
+        let n = component.nodes.len();
+        let mut weight = component
+            .nodes
+            .iter()
+            .map(|&v| node_weight(v))
+            .collect::<Vec<_>>();
+        let mut next_out = vec![0; n];
+        let mut on_path = vec![usize::MAX; n];
+        let mut path = Vec::new();
+        let mut start = 0;
+        let mut chosen = Vec::new();
+        residual.reduce();
+        loop {
+            while start < n && residual.removed[start] {
+                start += 1;
+            }
+            if start == n {
+                break;
+            }
+            // Every remaining node has an outgoing edge, so a walk finds a cycle.
+            let mut v = start;
+            while on_path[v] == usize::MAX {
+                on_path[v] = path.len();
+                path.push(v);
+                while residual.removed[component.out_edges[v][next_out[v]]] {
+                    next_out[v] += 1;
+                }
+                v = component.out_edges[v][next_out[v]];
+            }
+            let cycle = &path[on_path[v]..];
+            let lightest = cycle
+                .iter()
+                .map(|&c| weight[c])
+                .fold(weight[v], |a, b| if b < a { b } else { a });
+            let first = chosen.len();
+            for &c in cycle {
+                weight[c] = weight[c] - lightest;
+                if !(weight[c] > K::default()) {
+                    chosen.push(c);
+                }
+            }
+            for &c in &path {
+                on_path[c] = usize::MAX;
+            }
+            path.clear();
+            for &c in &chosen[first..] {
+                residual.remove(c);
+            }
+            residual.reduce();
+        }
+        component.minimize(&mut chosen);
+        fvs.extend(chosen.into_iter().map(|v| component.nodes[v]));
+    }
+    fvs
+}
+
+/// A strongly connected component, with the edges between the positions of their endpoints.
+struct Component<N> {
+    nodes: Vec<N>,
+    out_edges: Vec<Vec<usize>>,
+    in_edges: Vec<Vec<usize>>,
+}
+
+impl<N> Component<N> {
+    /// Leave out the nodes of `chosen` that are not needed to break every cycle, trying the last
+    /// chosen nodes first.
+    fn minimize(&self, chosen: &mut Vec<usize>) {
+        let mut in_set = vec![false; self.nodes.len()];
+        for &v in chosen.iter() {
+            in_set[v] = true;
+        }
+        for i in (0..chosen.len()).rev() {
+            let v = chosen[i];
+            in_set[v] = false;
+            if self.is_acyclic_without(&in_set) {
+                chosen.remove(i);
+            } else {
+                in_set[v] = true;
+            }
+        }
+    }
+
+    /// Check whether the component is acyclic without the nodes of `removed`, with Kahn's
+    /// algorithm.
+    fn is_acyclic_without(&self, removed: &[bool]) -> bool {
+        let n = self.nodes.len();
+        let mut in_degree = vec![0; n];
+        for v in (0..n).filter(|&v| !removed[v]) {
+            for &u in &self.out_edges[v] {
+                in_degree[u] += 1;
+            }
+        }
+        let mut stack = (0..n)
+            .filter(|&v| !removed[v] && in_degree[v] == 0)
+            .collect::<Vec<_>>();
+        let mut visited = 0;
+        while let Some(v) = stack.pop() {
+            visited += 1;
+            for &u in &self.out_edges[v] {
+                in_degree[u] -= 1;
+                if in_degree[u] == 0 && !removed[u] {
+                    stack.push(u);
+                }
+            }
+        }
+        visited == removed.iter().filter(|&&r| !r).count()
+    }
+}
+
+/// The remaining graph of a component, while nodes are removed.
+struct Residual<'a> {
+    out_edges: &'a [Vec<usize>],
+    in_edges: &'a [Vec<usize>],
+    out_degree: Vec<usize>,
+    in_degree: Vec<usize>,
+    removed: Vec<bool>,
+    /// The nodes whose degrees changed, to check for removal.
+    pending: Vec<usize>,
+    /// The nodes whose degrees changed, for the heap of [`greedy_feedback_vertex_set`].
+    changed: Vec<usize>,
+}
+
+impl<'a> Residual<'a> {
+    fn new<N>(component: &'a Component<N>) -> Self {
+        let n = component.nodes.len();
+        Residual {
+            out_edges: &component.out_edges,
+            in_edges: &component.in_edges,
+            out_degree: component.out_edges.iter().map(Vec::len).collect(),
+            in_degree: component.in_edges.iter().map(Vec::len).collect(),
+            removed: vec![false; n],
+            pending: (0..n).collect(),
+            changed: (0..n).collect(),
+        }
+    }
+
+    fn remove(&mut self, v: usize) {
+        self.removed[v] = true;
+        for &u in &self.out_edges[v] {
+            if !self.removed[u] {
+                self.in_degree[u] -= 1;
+                self.pending.push(u);
+                self.changed.push(u);
+            }
+        }
+        for &u in &self.in_edges[v] {
+            if !self.removed[u] {
+                self.out_degree[u] -= 1;
+                self.pending.push(u);
+                self.changed.push(u);
+            }
+        }
+    }
+
+    /// Remove the nodes without incoming or outgoing edges, which are on no cycle.
+    fn reduce(&mut self) {
+        while let Some(v) = self.pending.pop() {
+            if !self.removed[v] && (self.in_degree[v] == 0 || self.out_degree[v] == 0) {
+                self.remove(v);
+            }
+        }
+    }
+}
+
+/// Split `g` into its strongly connected components with cycles, and add the nodes with loops to
+/// `loops`, leaving them out of the components.
+fn components<G>(g: G, loops: &mut Vec<G::NodeId>) -> Vec<Component<G::NodeId>>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + IntoNeighbors + NodeIndexable,
+{
+    let mut looped = vec![false; g.node_bound()];
+    for edge in g.edge_references() {
+        if edge.source() == edge.target() && !looped[g.to_index(edge.source())] {
+            looped[g.to_index(edge.source())] = true;
+            loops.push(edge.source());
+        }
+    }
+
+    let mut component = vec![usize::MAX; g.node_bound()];
+    let mut position = vec![0; g.node_bound()];
+    let mut components = Vec::new();
+    for scc in tarjan_scc(g) {
+        let nodes = scc
+            .into_iter()
+            .filter(|&v| !looped[g.to_index(v)])
+            .collect::<Vec<_>>();
+        if nodes.len() > 1 {
+            for (i, &v) in nodes.iter().enumerate() {
+                component[g.to_index(v)] = components.len();
+                position[g.to_index(v)] = i;
+            }
+            components.push(Component {
+                out_edges: vec![Vec::new(); nodes.len()],
+                in_edges: vec![Vec::new(); nodes.len()],
+                nodes,
+            });
+        }
+    }
+    for edge in g.edge_references() {
+        let (a, b) = (g.to_index(edge.source()), g.to_index(edge.target()));
+        if a != b && component[a] != usize::MAX && component[a] == component[b] {
+            let c = &mut components[component[a]];
+            c.out_edges[position[a]].push(position[b]);
+            c.in_edges[position[b]].push(position[a]);
+        }
+    }
+    components
+}
@@ -2,12 +2,16 @@ use alloc::{vec, vec::Vec};
 use core::convert::TryFrom;
 
 use self::{
+    homomorphism::Homomorphisms,
+    matching::{GraphMatcher, Mode},
     semantic::{EdgeMatcher, NoSemanticMatch, NodeMatcher},
     state::Vf2State,
 };
+use super::canonical::{Search, ranks};
 use crate::{
     Incoming, Outgoing,
     data::DataMap,
+    unionfind::UnionFind,
     visit::{
         EdgeCount, EdgeRef, GetAdjacencyMatrix, GraphBase, GraphProp, IntoEdgesDirected,
         IntoNeighborsDirected, NodeCompactIndexable,
@@ -257,6 +261,17 @@ mod semantic {
 mod matching {
     use super::*;
 
+    /// The kind of mapping to search for.
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    pub enum Mode {
+        /// A bijection that preserves edges and non-edges.
+        Isomorphism,
+        /// An injection that preserves edges and non-edges.
+        InducedSubgraph,
+        /// An injection that preserves edges.
+        Monomorphism,
+    }
+
     #[derive(Copy, Clone, PartialEq, Debug)]
     enum OpenList {
         Out,
@@ -286,6 +301,7 @@ mod matching {
         nodes: (G0::NodeId, G1::NodeId),
         node_match: &mut NM,
         edge_match: &mut EM,
+        mode: Mode,
     ) -> bool
     where
         G0: GetAdjacencyMatrix + GraphProp + NodeCompactIndexable + IntoNeighborsDirected,
@@ -308,8 +324,11 @@ mod matching {
             };
         }
 
+        // In a monomorphism, edges of G1 need not be edges of G0.
+        let induced = mode != Mode::Monomorphism;
+
         macro_rules! r_succ {
-            ($j:tt) => {{
+            ($j:tt, $check:expr) => {{
                 let mut succ_count = 0;
                 for n_neigh in field!(st, $j)
                     .graph
@@ -322,7 +341,7 @@ mod matching {
                     } else {
                         field!(st, 1 - $j).graph.to_index(field!(nodes, 1 - $j))
                     };
-                    if m_neigh == usize::MAX {
+                    if m_neigh == usize::MAX || !$check {
                         continue;
                     }
                     let has_edge = field!(st, 1 - $j).graph.is_adjacent(
@@ -339,7 +358,7 @@ mod matching {
         }
 
         macro_rules! r_pred {
-            ($j:tt) => {{
+            ($j:tt, $check:expr) => {{
                 let mut pred_count = 0;
                 for n_neigh in field!(st, $j)
                     .graph
@@ -348,7 +367,7 @@ mod matching {
                     pred_count += 1;
                     // the self loop case is handled in outgoing
                     let m_neigh = field!(st, $j).mapping[field!(st, $j).graph.to_index(n_neigh)];
-                    if m_neigh == usize::MAX {
+                    if m_neigh == usize::MAX || !$check {
                         continue;
                     }
                     let has_edge = field!(st, 1 - $j).graph.is_adjacent(
@@ -381,11 +400,11 @@ mod matching {
         // R_new: Equal for G0, G1: Ñ n Pred(G, n); both Succ and Pred,
         //      Ñ is G0 - M - Tin - Tout
         // last attempt to add these did not speed up any of the testcases
-        if r_succ!(0) > r_succ!(1) {
+        if r_succ!(0, true) > r_succ!(1, induced) {
             return false;
         }
         // R_pred
-        if st.0.graph.is_directed() && r_pred!(0) > r_pred!(1) {
+        if st.0.graph.is_directed() && r_pred!(0, true) > r_pred!(1, induced) {
             return false;
         }
 
@@ -457,11 +476,37 @@ mod matching {
             }
 
             edge_feasibility!(0);
-            edge_feasibility!(1);
+            if induced {
+                edge_feasibility!(1);
+            }
         }
         true
     }
 
+    /// Return whether mapping `nodes.0` to `nodes.1` respects `order`, given the nodes already
+    /// mapped.
+    fn is_ordered<G0, G1>(
+        st: &(Vf2State<'_, G0>, Vf2State<'_, G1>),
+        nodes: (G0::NodeId, G1::NodeId),
+        order: &[(usize, usize)],
+    ) -> bool
+    where
+        G0: GetAdjacencyMatrix + GraphProp + NodeCompactIndexable + IntoNeighborsDirected,
+        G1: GetAdjacencyMatrix + GraphProp + NodeCompactIndexable + IntoNeighborsDirected,
+    {
+        let (from, to) = (st.0.graph.to_index(nodes.0), st.1.graph.to_index(nodes.1));
+        let mapping = &st.0.mapping;
+        order.iter().all(|&(a, b)| {
+            if a == from {
+                mapping[b] == usize::MAX || to < mapping[b]
+            } else if b == from {
+                mapping[a] == usize::MAX || mapping[a] < to
+            } else {
+                true
+            }
+        })
+    }
+
     fn next_candidate<G0, G1>(
         st: &mut (Vf2State<'_, G0>, Vf2State<'_, G1>),
     ) -> Option<(G0::NodeId, G1::NodeId, OpenList)>
@@ -559,7 +604,8 @@ mod matching {
         st: &mut (Vf2State<'_, G0>, Vf2State<'_, G1>),
         node_match: &mut NM,
         edge_match: &mut EM,
-        match_subgraph: bool,
+        mode: Mode,
+        order: &[(usize, usize)],
         stack: &mut Vec<Frame<G0, G1>>,
     ) -> Option<Vec<usize>>
     where
@@ -607,16 +653,18 @@ mod matching {
                     }
                 },
                 Frame::Inner { nodes, open_list } => {
-                    if is_feasible(st, nodes, node_match, edge_match) {
+                    if is_ordered(st, nodes, order)
+                        && is_feasible(st, nodes, node_match, edge_match, mode)
+                    {
                         push_state(st, nodes);
                         if st.0.is_complete() {
                             result = Some(st.0.mapping.clone());
                         }
                         // Check cardinalities of Tin, Tout sets
-                        if (!match_subgraph
+                        if (mode == Mode::Isomorphism
                             && st.0.out_size == st.1.out_size
                             && st.0.ins_size == st.1.ins_size)
-                            || (match_subgraph
+                            || (mode != Mode::Isomorphism
                                 && st.0.out_size <= st.1.out_size
                                 && st.0.ins_size <= st.1.ins_size)
                         {
@@ -664,7 +712,10 @@ mod matching {
         st: (Vf2State<'a, G0>, Vf2State<'b, G1>),
         node_match: &'c mut NM,
         edge_match: &'c mut EM,
-        match_subgraph: bool,
+        mode: Mode,
+        /// Pairs `(a, b)` of nodes of G0 such that `a` must be mapped to a smaller index than
+        /// `b`, to break the symmetries of G0.
+        order: Vec<(usize, usize)>,
         stack: Vec<Frame<G0, G1>>,
         // if this is `Some(iter)` we're overriding any calls to `isomorphisms()` with calls to
         // `iter` instead. that is, we return the single known mapping once.
@@ -691,7 +742,7 @@ mod matching {
             g1: &'b G1,
             node_match: &'c mut NM,
             edge_match: &'c mut EM,
-            match_subgraph: bool,
+            mode: Mode,
         ) -> Self {
             let stack = vec![Frame::Outer];
             let st = (Vf2State::new(g0), Vf2State::new(g1));
@@ -706,11 +757,19 @@ mod matching {
                 st,
                 node_match,
                 edge_match,
-                match_subgraph,
+                mode,
+                order: Vec::new(),
                 stack,
                 iter_override,
             }
         }
+
+        /// Only return mappings that map `a` to a smaller index than `b`, for every pair
+        /// `(a, b)` in `order`.
+        pub fn with_order(mut self, order: Vec<(usize, usize)>) -> Self {
+            self.order = order;
+            self
+        }
     }
 
     impl<G0, G1, NM, EM> Iterator for GraphMatcher<'_, '_, '_, G0, G1, NM, EM>
@@ -739,7 +798,8 @@ mod matching {
                 &mut self.st,
                 self.node_match,
                 self.edge_match,
-                self.match_subgraph,
+                self.mode,
+                &self.order,
                 &mut self.stack,
             )
         }
@@ -788,6 +848,175 @@ mod matching {
     }
 }
 
+mod homomorphism {
+    use super::*;
+
+    /// A backtracking search for the maps from the nodes of G0 to the nodes of G1 that map every
+    /// edge to an edge.
+    pub struct Homomorphisms<'a, G0, G1, NM, EM>
+    where
+        G0: GetAdjacencyMatrix,
+        G1: GetAdjacencyMatrix,
+    {
+        g0: &'a G0,
+        g1: &'a G1,
+        adjacency_matrix: G1::AdjMatrix,
+        node_match: &'a mut NM,
+        edge_match: &'a mut EM,
+        /// The nodes of G0 in the order they are mapped, so that every node but the first of
+        /// each component has an earlier neighbor.
+        order: Vec<usize>,
+        /// The current map from G0 to G1, `usize::MAX` for no mapping.
+        mapping: Vec<usize>,
+        /// The next node of G1 to try, for every depth.
+        next: Vec<usize>,
+        depth: usize,
+        done: bool,
+    }
+
+    impl<'a, G0, G1, NM, EM> Homomorphisms<'a, G0, G1, NM, EM>
+    where
+        G0: NodeCompactIndexable + GetAdjacencyMatrix + GraphProp + IntoNeighborsDirected,
+        G1: NodeCompactIndexable + GetAdjacencyMatrix + GraphProp + IntoNeighborsDirected,
+        NM: NodeMatcher<G0, G1>,
+        EM: EdgeMatcher<G0, G1>,
+    {
+        pub fn new(g0: &'a G0, g1: &'a G1, node_match: &'a mut NM, edge_match: &'a mut EM) -> Self {
+            let node_count = g0.node_count();
+            let mut order = Vec::with_capacity(node_count);
+            let mut visited = vec![false; node_count];
+            for root in 0..node_count {
+                if visited[root] {
+                    continue;
+                }
+                visited[root] = true;
+                let start = order.len();
+                order.push(root);
+                let mut i = start;
+                while i < order.len() {
+                    let v = g0.from_index(order[i]);
+                    for u in g0
+                        .neighbors_directed(v, Outgoing)
+                        .chain(g0.neighbors_directed(v, Incoming))
+                    {
+                        let u = g0.to_index(u);
+                        if !visited[u] {
+                            visited[u] = true;
+                            order.push(u);
+                        }
+                    }
+                    i += 1;
+                }
+            }
+            Homomorphisms {
+                g0,
+                g1,
+                adjacency_matrix: g1.adjacency_matrix(),
+                node_match,
+                edge_match,
+                order,
+                mapping: vec![usize::MAX; node_count],
+                next: vec![0; node_count],
+                depth: 0,
+                done: false,
+            }
+        }
+
+        /// Return whether `from` can be mapped to `to`, given the nodes already mapped.
+        fn is_feasible(&mut self, from: usize, to: usize) -> bool {
+            let (g0, g1) = (self.g0, self.g1);
+            let nodes = (g0.from_index(from), g1.from_index(to));
+            if NM::enabled() && !self.node_match.eq(g0, g1, nodes.0, nodes.1) {
+                return false;
+            }
+            for n_neigh in g0.neighbors_directed(nodes.0, Outgoing) {
+                // `from` itself is not mapped yet, so handle the self loop case here.
+                let m_neigh = if n_neigh == nodes.0 {
+                    to
+                } else {
+                    self.mapping[g0.to_index(n_neigh)]
+                };
+                if m_neigh == usize::MAX {
+                    continue;
+                }
+                let m_neigh = g1.from_index(m_neigh);
+                if !g1.is_adjacent(&self.adjacency_matrix, nodes.1, m_neigh)
+                    || (EM::enabled()
+                        && !self
+                            .edge_match
+                            .eq(g0, g1, (nodes.0, n_neigh), (nodes.1, m_neigh)))
+                {
+                    return false;
+                }
+            }
+            if g0.is_directed() {
+                for n_neigh in g0.neighbors_directed(nodes.0, Incoming) {
+                    // the self loop case is handled in outgoing
+                    let m_neigh = self.mapping[g0.to_index(n_neigh)];
+                    if n_neigh == nodes.0 || m_neigh == usize::MAX {
+                        continue;
+                    }
+                    let m_neigh = g1.from_index(m_neigh);
+                    if !g1.is_adjacent(&self.adjacency_matrix, m_neigh, nodes.1)
+                        || (EM::enabled()
+                            && !self
+                                .edge_match
+                                .eq(g0, g1, (n_neigh, nodes.0), (m_neigh, nodes.1)))
+                    {
+                        return false;
+                    }
+                }
+            }
+            true
+        }
+    }
+
+    impl<G0, G1, NM, EM> Iterator for Homomorphisms<'_, G0, G1, NM, EM>
+    where
+        G0: NodeCompactIndexable + GetAdjacencyMatrix + GraphProp + IntoNeighborsDirected,
+        G1: NodeCompactIndexable + GetAdjacencyMatrix + GraphProp + IntoNeighborsDirected,
+        NM: NodeMatcher<G0, G1>,
+        EM: EdgeMatcher<G0, G1>,
+    {
+        type Item = Vec<usize>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.done {
+                return None;
+            }
+            if self.order.is_empty() {
+                // The empty map is the only homomorphism from the empty graph.
+                self.done = true;
+                return Some(Vec::new());
+            }
+            loop {
+                let from = self.order[self.depth];
+                self.mapping[from] = usize::MAX;
+                let found = (self.next[self.depth]..self.g1.node_count())
+                    .find(|&to| self.is_feasible(from, to));
+                match found {
+                    Some(to) => {
+                        self.mapping[from] = to;
+                        self.next[self.depth] = to + 1;
+                        if self.depth + 1 == self.order.len() {
+                            return Some(self.mapping.clone());
+                        }
+                        self.depth += 1;
+                    }
+                    None => {
+                        self.next[self.depth] = 0;
+                        if self.depth == 0 {
+                            self.done = true;
+                            return None;
+                        }
+                        self.depth -= 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Return `true` if the graphs `g0` and `g1` are isomorphic.
 ///
 /// Using the VF2 algorithm, only matching graph syntactically (graph
@@ -814,9 +1043,15 @@ where
         return false;
     }
 
-    self::matching::GraphMatcher::new(&g0, &g1, &mut NoSemanticMatch, &mut NoSemanticMatch, false)
-        .next()
-        .is_some()
+    GraphMatcher::new(
+        &g0,
+        &g1,
+        &mut NoSemanticMatch,
+        &mut NoSemanticMatch,
+        Mode::Isomorphism,
+    )
+    .next()
+    .is_some()
 }
 
 /// Return `true` if the graphs `g0` and `g1` are isomorphic.
@@ -853,9 +1088,15 @@ where
         return false;
     }
 
-    self::matching::GraphMatcher::new(&g0, &g1, &mut node_match, &mut edge_match, false)
-        .next()
-        .is_some()
+    GraphMatcher::new(
+        &g0,
+        &g1,
+        &mut node_match,
+        &mut edge_match,
+        Mode::Isomorphism,
+    )
+    .next()
+    .is_some()
 }
 
 /// Return `true` if `g0` is isomorphic to a subgraph of `g1`.
@@ -907,9 +1148,15 @@ where
         return false;
     }
 
-    self::matching::GraphMatcher::new(&g0, &g1, &mut NoSemanticMatch, &mut NoSemanticMatch, true)
-        .next()
-        .is_some()
+    GraphMatcher::new(
+        &g0,
+        &g1,
+        &mut NoSemanticMatch,
+        &mut NoSemanticMatch,
+        Mode::InducedSubgraph,
+    )
+    .next()
+    .is_some()
 }
 
 /// Return `true` if `g0` is isomorphic to a subgraph of `g1`.
@@ -946,9 +1193,15 @@ where
         return false;
     }
 
-    self::matching::GraphMatcher::new(&g0, &g1, &mut node_match, &mut edge_match, true)
-        .next()
-        .is_some()
+    GraphMatcher::new(
+        &g0,
+        &g1,
+        &mut node_match,
+        &mut edge_match,
+        Mode::InducedSubgraph,
+    )
+    .next()
+    .is_some()
 }
 
 /// Using the VF2 algorithm, examine both syntactic and semantic graph
@@ -987,7 +1240,314 @@ where
         return None;
     }
 
-    Some(self::matching::GraphMatcher::new(
-        g0, g1, node_match, edge_match, true,
+    Some(GraphMatcher::new(
+        g0,
+        g1,
+        node_match,
+        edge_match,
+        Mode::InducedSubgraph,
     ))
 }
+
+/// How [`subgraph_matches_iter`] maps the nodes of the pattern graph to those of the host graph.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SubgraphMode {
+    /// A node-induced subgraph isomorphism, like [`subgraph_isomorphisms_iter`]: distinct nodes
+    /// are mapped to distinct nodes, and two nodes are adjacent in the pattern if and only if
+    /// their images are adjacent in the host. This is the default.
+    #[default]
+    Induced,
+    /// A monomorphism: distinct nodes are mapped to distinct nodes, and adjacent nodes to
+    /// adjacent nodes, but the host may have more edges between the images.
+    Monomorphism,
+    /// A homomorphism: adjacent nodes are mapped to adjacent nodes, but distinct nodes may be
+    /// mapped to the same node.
+    Homomorphism,
+}
+
+/// The options of [`subgraph_matches_iter`].
+///
+/// The options are:
+/// * [`mode`](Self::mode): the kind of mappings to search for, see [`SubgraphMode`].
+/// * [`limit`](Self::limit): the maximum number of mappings to return, no limit by default.
+/// * [`break_symmetry`](Self::break_symmetry): whether to return only one of the mappings that
+///   differ by an automorphism of the pattern, `false` by default.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SubgraphMatchOptions {
+    mode: SubgraphMode,
+    limit: Option<usize>,
+    break_symmetry: bool,
+}
+
+impl SubgraphMatchOptions {
+    /// Create the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the kind of mappings to search for.
+    pub fn mode(mut self, mode: SubgraphMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Stop after `limit` mappings.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set whether to return only one of the mappings that differ by an automorphism of the
+    /// pattern, so that every embedding of the pattern is returned once.
+    ///
+    /// Only automorphisms that preserve which nodes and edges of the host every node and edge of
+    /// the pattern matches are taken into account, so every embedding is still found. Symmetries
+    /// are not broken for [`SubgraphMode::Homomorphism`], whose mappings are not embeddings.
+    pub fn break_symmetry(mut self, break_symmetry: bool) -> Self {
+        self.break_symmetry = break_symmetry;
+        self
+    }
+}
+
+/// Return the mappings from the nodes of `g0` to the nodes of `g1` of the kind given by
+/// `options`, examining both syntactic and semantic graph structure.
+///
+/// Every mapping is a `Vec` that gives the index of the image of every node of `g0`, like with
+/// [`subgraph_isomorphisms_iter`]. Nodes are mapped to nodes whose weights match according to
+/// `node_match`, and edges to edges whose weights match according to `edge_match`. The mappings
+/// are found with the VF2 algorithm, except for homomorphisms, which are found by backtracking.
+///
+/// With [`break_symmetry`](SubgraphMatchOptions::break_symmetry), the [automorphisms of
+/// `g0`][automorphisms] are computed, and the search only keeps mappings that order the images of
+/// symmetric nodes in a fixed way, following Grochow and Kellis. This calls `node_match` and
+/// `edge_match` on every pair of nodes and every pair of edges of the two graphs, and assumes
+/// that they are consistent.
+///
+/// The graphs should not be [multigraphs].
+///
+/// # Arguments
+/// * `g0`: the pattern graph.
+/// * `g1`: the host graph.
+/// * `node_match`: closure that returns whether a node of `g0` can be mapped to a node of `g1`.
+/// * `edge_match`: closure that returns whether an edge of `g0` can be mapped to an edge of `g1`.
+/// * `options`: see [`SubgraphMatchOptions`].
+///
+/// # Returns
+/// * An iterator over the mappings.
+///
+/// **Reference**
+///
+/// * Joshua A. Grochow, Manolis Kellis; *Network Motif Discovery Using Subgraph Enumeration and
+///   Symmetry-Breaking*
+///
+/// # Example
+/// ```rust
+/// use petgraph::{
+///     algo::isomorphism::{SubgraphMatchOptions, SubgraphMode, subgraph_matches_iter},
+///     prelude::*,
+/// };
+///
+/// // A path a - b - c.
+/// let pattern = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+/// // A triangle.
+/// let host = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+/// let (pattern, host) = (&pattern, &host);
+/// let mut node_match = |_: &(), _: &()| true;
+/// let mut edge_match = |_: &(), _: &()| true;
+///
+/// // The path is not an induced subgraph of the triangle.
+/// let options = SubgraphMatchOptions::new();
+/// let mappings =
+///     subgraph_matches_iter(&pattern, &host, &mut node_match, &mut edge_match, options);
+/// assert_eq!(mappings.count(), 0);
+///
+/// // But it is a subgraph three times, each found once per direction of the path.
+/// let options = SubgraphMatchOptions::new().mode(SubgraphMode::Monomorphism);
+/// let mappings =
+///     subgraph_matches_iter(&pattern, &host, &mut node_match, &mut edge_match, options);
+/// assert_eq!(mappings.count(), 6);
+///
+/// let options = options.break_symmetry(true);
+/// let mappings =
+///     subgraph_matches_iter(&pattern, &host, &mut node_match, &mut edge_match, options);
+/// assert_eq!(mappings.count(), 3);
+///
+/// // The path can be folded onto a single edge.
+/// let edge = UnGraph::<(), ()>::from_edges([(0, 1)]);
+/// let edge = &edge;
+/// let options = SubgraphMatchOptions::new()
+///     .mode(SubgraphMode::Homomorphism)
+///     .limit(1);
+/// let mut mappings =
+///     subgraph_matches_iter(&pattern, &edge, &mut node_match, &mut edge_match, options);
+/// assert_eq!(mappings.next(), Some(vec![0, 1, 0]));
+/// assert_eq!(mappings.next(), None);
+/// ```
+///
+/// [automorphisms]: crate::algo::automorphisms()
+/// [multigraphs]: https://en.wikipedia.org/wiki/Multigraph
+pub fn subgraph_matches_iter<'a, G0, G1, NM, EM>(
+    g0: &'a G0,
+    g1: &'a G1,
+    node_match: &'a mut NM,
+    edge_match: &'a mut EM,
+    options: SubgraphMatchOptions,
+) -> impl Iterator<Item = Vec<usize>> + 'a
+where
+    G0: 'a
+        + NodeCompactIndexable
+        + EdgeCount
+        + DataMap
+        + GetAdjacencyMatrix
+        + GraphProp
+        + IntoEdgesDirected,
+    G1: 'a
+        + NodeCompactIndexable
+        + EdgeCount
+        + DataMap
+        + GetAdjacencyMatrix
+        + GraphProp<EdgeType = G0::EdgeType>
+        + IntoEdgesDirected,
+    NM: 'a + FnMut(&G0::NodeWeight, &G1::NodeWeight) -> bool,
+    EM: 'a + FnMut(&G0::EdgeWeight, &G1::EdgeWeight) -> bool,
+{
+    let matches = match options.mode {
+        SubgraphMode::Homomorphism => {
+            Matches::Homomorphisms(Homomorphisms::new(g0, g1, node_match, edge_match))
+        }
+        _ if g0.node_count() > g1.node_count() || g0.edge_count() > g1.edge_count() => {
+            Matches::None
+        }
+        mode => {
+            let order = if options.break_symmetry {
+                symmetry_order(g0, g1, node_match, edge_match)
+            } else {
+                Vec::new()
+            };
+            let mode = if mode == SubgraphMode::Induced {
+                Mode::InducedSubgraph
+            } else {
+                Mode::Monomorphism
+            };
+            Matches::Injective(
+                GraphMatcher::new(g0, g1, node_match, edge_match, mode).with_order(order),
+            )
+        }
+    };
+    matches.take(options.limit.unwrap_or(usize::MAX))
+}
+
+/// The mappings returned by [`subgraph_matches_iter`].
+enum Matches<I, H> {
+    None,
+    Injective(I),
+    Homomorphisms(H),
+}
+
+impl<I, H> Iterator for Matches<I, H>
+where
+    I: Iterator<Item = Vec<usize>>,
+    H: Iterator<Item = Vec<usize>>,
+{
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Matches::None => None,
+            Matches::Injective(matches) => matches.next(),
+            Matches::Homomorphisms(matches) => matches.next(),
+        }
+    }
+}
+
+/// Return pairs `(a, b)` of nodes of `g0` such that, of the injective mappings that differ by an
+/// automorphism of `g0`, exactly one maps `a` to a smaller index than `b` for every pair.
+///
+/// Only automorphisms that map every node and edge to one that matches the same nodes and edges
+/// of `g1` are taken into account, so that these mappings match or not all together.
+fn symmetry_order<G0, G1, NM, EM>(
+    g0: &G0,
+    g1: &G1,
+    node_match: &mut NM,
+    edge_match: &mut EM,
+) -> Vec<(usize, usize)>
+where
+    G0: NodeCompactIndexable + DataMap + GraphProp + IntoEdgesDirected,
+    G1: NodeCompactIndexable + DataMap + IntoEdgesDirected,
+    NM: FnMut(&G0::NodeWeight, &G1::NodeWeight) -> bool,
+    EM: FnMut(&G0::EdgeWeight, &G1::EdgeWeight) -> bool,
+{
+    let node_count = g0.node_count();
+    let host_nodes = (0..g1.node_count())
+        .map(|i| g1.node_weight(g1.from_index(i)).unwrap())
+        .collect::<Vec<_>>();
+    let node_classes = (0..node_count)
+        .map(|i| {
+            let weight = g0.node_weight(g0.from_index(i)).unwrap();
+            host_nodes
+                .iter()
+                .map(|host| node_match(weight, host))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut colors = ranks(&node_classes);
+
+    // The edges of an undirected graph are listed from both endpoints, so keep one direction.
+    let edges = (0..node_count)
+        .flat_map(|a| {
+            g0.edges_directed(g0.from_index(a), Outgoing)
+                .map(move |e| (a, e))
+        })
+        .filter_map(|(a, e)| {
+            let b = if g0.to_index(e.source()) == a {
+                g0.to_index(e.target())
+            } else {
+                g0.to_index(e.source())
+            };
+            (g0.is_directed() || a <= b).then_some((a, b, e))
+        })
+        .collect::<Vec<_>>();
+    let host_edges = (0..g1.node_count())
+        .flat_map(|i| g1.edges_directed(g1.from_index(i), Outgoing))
+        .collect::<Vec<_>>();
+    let edge_classes = edges
+        .iter()
+        .map(|(_, _, e)| {
+            host_edges
+                .iter()
+                .map(|host| edge_match(e.weight(), host.weight()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let edges = edges
+        .iter()
+        .zip(ranks(&edge_classes))
+        .map(|(&(a, b, _), color)| (a, b, color))
+        .collect::<Vec<_>>();
+
+    let mut order = Vec::new();
+    let mut unique_color = node_count;
+    loop {
+        let mut search = Search::new(g0.is_directed(), edges.clone(), node_count);
+        search.search(colors.clone());
+        let mut orbits = UnionFind::new(node_count);
+        for gamma in &search.generators {
+            for (v, &w) in gamma.iter().enumerate() {
+                orbits.union(v, w);
+            }
+        }
+        let Some(a) =
+            (0..node_count).find(|&a| (0..node_count).any(|b| b != a && orbits.equiv(a, b)))
+        else {
+            return order;
+        };
+        order.extend(
+            (0..node_count)
+                .filter(|&b| b != a && orbits.equiv(a, b))
+                .map(|b| (a, b)),
+        );
+        // Continue with the automorphisms that fix `a`.
+        colors[a] = unique_color;
+        unique_color += 1;
+    }
+}
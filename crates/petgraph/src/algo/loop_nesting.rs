@@ -0,0 +1,377 @@
+//! Find the loops of a control-flow graph and how they nest.
+//!
+//! # Loops
+//!
+//! A *loop* of a directed graph with a root node **R** is a strongly connected set of nodes that
+//! are reachable from **R**. Its *header* is the node that a depth-first search from **R**
+//! visits first, and its *back edges* are the edges from inside the loop to the header. Removing
+//! the back edges of a loop splits it into the smaller loops nested inside of it.
+//!
+//! The *entries* of a loop are its nodes with a predecessor outside the loop. A loop is
+//! *reducible* if its header is its only entry: then the header dominates the loop, and the loop
+//! is the *natural loop* of its back edges, as found in structured programs. A loop with several
+//! entries is *irreducible*; its header depends on the order of the depth-first search.
+
+use alloc::{vec, vec::Vec};
+use core::hash::Hash;
+
+use hashbrown::HashMap;
+
+use crate::visit::{IntoNeighbors, NodeIndexable};
+
+/// A loop of a control-flow graph.
+///
+/// See [`loop_nesting_forest`].
+#[derive(Debug, Clone)]
+pub struct Loop<N> {
+    header: N,
+    entries: Vec<N>,
+    nodes: Vec<N>,
+    back_edges: Vec<(N, N)>,
+    exit_edges: Vec<(N, N)>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+}
+
+impl<N> Loop<N>
+where
+    N: Copy + Eq,
+{
+    /// Get the header of the loop, its first node in depth-first order.
+    pub fn header(&self) -> N {
+        self.header
+    }
+
+    /// Get the entries of the loop, the nodes with a predecessor outside the loop, starting with
+    /// the header.
+    pub fn entries(&self) -> &[N] {
+        &self.entries
+    }
+
+    /// Return `true` if the header is the only entry of the loop.
+    pub fn is_reducible(&self) -> bool {
+        self.entries.len() == 1
+    }
+
+    /// Get the nodes of the loop, including the nodes of the nested loops, starting with the
+    /// header.
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// Return `true` if the node belongs to the loop or to a loop nested inside of it.
+    pub fn contains(&self, node: N) -> bool {
+        self.nodes.contains(&node)
+    }
+
+    /// Get the back edges of the loop, the edges from its nodes to its header.
+    pub fn back_edges(&self) -> &[(N, N)] {
+        &self.back_edges
+    }
+
+    /// Get the exit edges of the loop, the edges from its nodes to nodes outside of it.
+    pub fn exit_edges(&self) -> &[(N, N)] {
+        &self.exit_edges
+    }
+
+    /// Get the index of the innermost loop that contains this loop, if any.
+    pub fn parent(&self) -> Option<usize> {
+        self.parent
+    }
+
+    /// Get the indices of the loops directly nested inside this loop.
+    pub fn children(&self) -> &[usize] {
+        &self.children
+    }
+}
+
+/// The loops of a control-flow graph, and how they nest.
+///
+/// See [`loop_nesting_forest`].
+#[derive(Debug, Clone)]
+pub struct LoopNestingForest<N> {
+    loops: Vec<Loop<N>>,
+    innermost: HashMap<N, usize>,
+}
+
+impl<N> LoopNestingForest<N>
+where
+    N: Copy + Eq + Hash,
+{
+    /// Get all loops. A loop comes before the loops nested inside of it, and its position is the
+    /// index used by [`Loop::parent`], [`Loop::children`] and
+    /// [`innermost_loop`](Self::innermost_loop).
+    pub fn loops(&self) -> &[Loop<N>] {
+        &self.loops
+    }
+
+    /// Iterate over the indices of the outermost loops, which are not nested in other loops.
+    pub fn top_level_loops(&self) -> impl Iterator<Item = usize> + '_ {
+        self.loops
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| l.parent.is_none())
+            .map(|(i, _)| i)
+    }
+
+    /// Get the index of the innermost loop that contains the given node, or `None` if the node
+    /// is not in a loop or not reachable from the root.
+    pub fn innermost_loop(&self, node: N) -> Option<usize> {
+        self.innermost.get(&node).copied()
+    }
+
+    /// Get the number of loops that contain the given node.
+    pub fn loop_depth(&self, node: N) -> usize {
+        let mut depth = 0;
+        let mut current = self.innermost_loop(node);
+        while let Some(i) = current {
+            depth += 1;
+            current = self.loops[i].parent;
+        }
+        depth
+    }
+
+    /// Return `true` if the given node is the header of a loop.
+    pub fn is_loop_header(&self, node: N) -> bool {
+        self.innermost_loop(node)
+            .is_some_and(|i| self.loops[i].header == node)
+    }
+}
+
+/// Find the loops of a control-flow graph and how they nest, including irreducible loops.
+///
+/// Every strongly connected set of nodes that are reachable from `root` is a loop, with the
+/// first of its nodes in depth-first order as header. Without the back edges into the header,
+/// the strongly connected components of the loop are the loops nested inside of it. This is the
+/// loop nesting forest of [Havlak][1], computed with the approach of [Ramalingam][2], and its
+/// reducible loops are the natural loops of the graph.
+///
+/// # Arguments
+/// * `graph`: a control-flow graph.
+/// * `root`: the *root* node of the `graph`.
+///
+/// # Returns
+/// * [`LoopNestingForest`]: the loops and how they nest.
+///
+/// # Complexity
+/// * Time complexity: **O(d · (|V| + |E|))**, where **d** is the largest depth of nested loops.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://doi.org/10.1145/270580.270583
+/// [2]: https://doi.org/10.1145/567097.567100
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::loop_nesting::loop_nesting_forest, prelude::*};
+///
+/// // A loop 1 -> 2 -> 3 -> 1 with an inner loop 2 -> 3 -> 2, and an exit 3 -> 4.
+/// let graph = DiGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 1), (3, 2), (3, 4)]);
+/// let forest = loop_nesting_forest(&graph, 0.into());
+/// assert_eq!(forest.loops().len(), 2);
+///
+/// let outer = &forest.loops()[forest.innermost_loop(1.into()).unwrap()];
+/// assert_eq!(outer.header(), 1.into());
+/// assert!(outer.is_reducible());
+/// assert_eq!(outer.back_edges(), [(3.into(), 1.into())]);
+/// assert_eq!(outer.exit_edges(), [(3.into(), 4.into())]);
+///
+/// let inner = &forest.loops()[outer.children()[0]];
+/// assert_eq!(inner.header(), 2.into());
+/// assert_eq!(forest.loop_depth(3.into()), 2);
+/// assert_eq!(forest.loop_depth(4.into()), 0);
+/// ```
+pub fn loop_nesting_forest<G>(graph: G, root: G::NodeId) -> LoopNestingForest<G::NodeId>
+where
+    G: IntoNeighbors + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    // Number the reachable nodes in depth-first preorder, so that the header of a loop is its
+    // least node.
+    let mut number = vec![usize::MAX; graph.node_bound()];
+    let mut nodes = vec![root];
+    let mut adjacency = vec![Vec::new()];
+    number[graph.to_index(root)] = 0;
+    let mut stack = vec![graph.neighbors(root)];
+    while let Some(neighbors) = stack.last_mut() {
+        let Some(w) = neighbors.next() else {
+            stack.pop();
+            continue;
+        };
+        if number[graph.to_index(w)] == usize::MAX {
+            number[graph.to_index(w)] = nodes.len();
+            nodes.push(w);
+            adjacency.push(Vec::new());
+            stack.push(graph.neighbors(w));
+        }
+    }
+    for (v, successors) in adjacency.iter_mut().enumerate() {
+        successors.extend(graph.neighbors(nodes[v]).map(|w| number[graph.to_index(w)]));
+        successors.sort_unstable();
+        successors.dedup();
+    }
+    let mut reverse = vec![Vec::new(); nodes.len()];
+    for (v, successors) in adjacency.iter().enumerate() {
+        for &w in successors {
+            reverse[w].push(v);
+        }
+    }
+
+    let mut search = LoopSearch {
+        adjacency: &adjacency,
+        reverse: &reverse,
+        is_header: vec![false; nodes.len()],
+        in_set: vec![false; nodes.len()],
+        visited: vec![false; nodes.len()],
+        innermost: vec![usize::MAX; nodes.len()],
+    };
+    let mut loops = Vec::new();
+    let mut work = vec![((0..nodes.len()).collect::<Vec<_>>(), None)];
+    while let Some((set, parent)) = work.pop() {
+        for mut component in search.cyclic_components(&set) {
+            component.sort_unstable();
+            let header = component[0];
+            search.is_header[header] = true;
+            let index = loops.len();
+            for &v in &component {
+                search.innermost[v] = index;
+            }
+            loops.push(search.make_loop(&component, &nodes, parent));
+            if let Some(parent) = parent {
+                loops[parent].children.push(index);
+            }
+            work.push((component, Some(index)));
+        }
+    }
+
+    let innermost = search
+        .innermost
+        .iter()
+        .enumerate()
+        .filter(|&(_, &i)| i != usize::MAX)
+        .map(|(v, &i)| (nodes[v], i))
+        .collect();
+    LoopNestingForest { loops, innermost }
+}
+
+struct LoopSearch<'a> {
+    adjacency: &'a [Vec<usize>],
+    reverse: &'a [Vec<usize>],
+    /// The headers of the loops found so far, whose back edges are ignored.
+    is_header: Vec<bool>,
+    in_set: Vec<bool>,
+    visited: Vec<bool>,
+    innermost: Vec<usize>,
+}
+
+impl LoopSearch<'_> {
+    /// Return the strongly connected components of the subgraph induced by `set` that contain a
+    /// cycle, without the edges into loop headers.
+    fn cyclic_components(&mut self, set: &[usize]) -> Vec<Vec<usize>> {
+        for &v in set {
+            self.in_set[v] = true;
+        }
+        let follow = |in_set: &[bool], is_header: &[bool], w: usize| in_set[w] && !is_header[w];
+
+        // Kosaraju's algorithm: order the nodes by finishing time, then collect the nodes that
+        // reach each root in the reverse order.
+        let mut order = Vec::with_capacity(set.len());
+        let mut stack = Vec::new();
+        for &root in set {
+            if self.visited[root] {
+                continue;
+            }
+            self.visited[root] = true;
+            stack.push((root, 0));
+            while let Some(&mut (v, ref mut next)) = stack.last_mut() {
+                if let Some(&w) = self.adjacency[v].get(*next) {
+                    *next += 1;
+                    if follow(&self.in_set, &self.is_header, w) && !self.visited[w] {
+                        self.visited[w] = true;
+                        stack.push((w, 0));
+                    }
+                } else {
+                    order.push(v);
+                    stack.pop();
+                }
+            }
+        }
+
+        for &v in set {
+            self.visited[v] = false;
+        }
+        let mut components = Vec::new();
+        for &root in order.iter().rev() {
+            if self.visited[root] {
+                continue;
+            }
+            self.visited[root] = true;
+            stack.push((root, 0));
+            let mut component = Vec::new();
+            while let Some((v, _)) = stack.pop() {
+                component.push(v);
+                if self.is_header[v] {
+                    continue;
+                }
+                for &w in &self.reverse[v] {
+                    if self.in_set[w] && !self.visited[w] {
+                        self.visited[w] = true;
+                        stack.push((w, 0));
+                    }
+                }
+            }
+            let cyclic = component.len() > 1
+                || (!self.is_header[root] && self.adjacency[root].contains(&root));
+            if cyclic {
+                components.push(component);
+            }
+        }
+        for &v in set {
+            self.visited[v] = false;
+            self.in_set[v] = false;
+        }
+        components
+    }
+
+    /// Describe the loop with the given nodes, whose header is the first one.
+    fn make_loop<N: Copy>(
+        &mut self,
+        component: &[usize],
+        nodes: &[N],
+        parent: Option<usize>,
+    ) -> Loop<N> {
+        for &v in component {
+            self.in_set[v] = true;
+        }
+        let header = component[0];
+        let mut entries = Vec::new();
+        let mut back_edges = Vec::new();
+        let mut exit_edges = Vec::new();
+        for &v in component {
+            // The root is entered from outside the graph.
+            if v == header || v == 0 || self.reverse[v].iter().any(|&u| !self.in_set[u]) {
+                entries.push(nodes[v]);
+            }
+            for &w in &self.adjacency[v] {
+                if w == header {
+                    back_edges.push((nodes[v], nodes[w]));
+                } else if !self.in_set[w] {
+                    exit_edges.push((nodes[v], nodes[w]));
+                }
+            }
+        }
+        for &v in component {
+            self.in_set[v] = false;
+        }
+        Loop {
+            header: nodes[header],
+            entries,
+            nodes: component.iter().map(|&v| nodes[v]).collect(),
+            back_edges,
+            exit_edges,
+            parent,
+            children: Vec::new(),
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use alloc::{collections::VecDeque, vec, vec::Vec};
-use core::hash::Hash;
-
-use crate::visit::{
-    EdgeRef, GraphBase, IntoEdges, IntoNeighbors, IntoNodeIdentifiers, NodeCount, NodeIndexable,
-    VisitMap, Visitable,
+use core::{hash::Hash, ops::Div};
+
+use crate::{
+    algo::BoundedMeasure,
+    visit::{
+        EdgeRef, GraphBase, IntoEdgeReferences, IntoEdges, IntoNeighbors, IntoNodeIdentifiers,
+        NodeCount, NodeIndexable, VisitMap, Visitable,
+    },
 };
 
 /// Computed
@@ -617,3 +620,822 @@ fn augment_path<G>(
         panic!("Unexpected label when augmenting path");
     }
 }
+
+/// Compute a [*maximum weight matching*][1] using the primal-dual blossom algorithm of Edmonds and
+/// Galil.
+///
+/// The input graph is treated as if undirected. Self-loops are ignored. The returned matching
+/// maximizes the sum of `edge_cost` over its edges; it does not need to be a maximum cardinality
+/// matching. Edges with a negative weight are therefore never matched.
+///
+/// See also [`minimum_weight_perfect_matching`] for the minimum weight perfect matching.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `edge_cost`: closure that returns the weight of a particular edge.
+///
+/// # Returns
+/// * [`struct@Matching`]: computed maximum weight matching.
+///
+/// # Complexity
+/// * Time complexity: **O(|V|³)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Maximum_weight_matching
+///
+/// # Examples
+///
+/// ```
+/// use petgraph::{algo::maximum_weight_matching, prelude::*};
+///
+/// // The example graph:
+/// //
+/// //     5       11      5
+/// //  a ---- b ----- c ---- d
+///
+/// let graph: UnGraph<(), i32> = UnGraph::from_edges(&[(0, 1, 5), (1, 2, 11), (2, 3, 5)]);
+///
+/// let matching = maximum_weight_matching(&graph, |e| *e.weight());
+/// assert_eq!(matching.len(), 1);
+/// assert!(matching.contains_edge(1.into(), 2.into()));
+/// ```
+pub fn maximum_weight_matching<G, F, K>(graph: G, mut edge_cost: F) -> Matching<G>
+where
+    G: NodeIndexable + IntoEdgeReferences,
+    F: FnMut(G::EdgeRef) -> K,
+    K: BoundedMeasure + Copy + Div<K, Output = K>,
+{
+    let edges = weighted_edges(&graph, &mut edge_cost);
+    let mate = max_weight_matching_indices(graph.node_bound(), &edges);
+    weighted_matching(graph, &mate)
+}
+
+/// Compute a [*minimum weight perfect matching*][1] using the primal-dual blossom algorithm of
+/// Edmonds and Galil.
+///
+/// The input graph is treated as if undirected. Self-loops are ignored. Among all
+/// [*perfect*](https://en.wikipedia.org/wiki/Matching_(graph_theory)#Definitions) matchings, the
+/// returned one minimizes the sum of `edge_cost` over its edges.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `edge_cost`: closure that returns the cost of a particular edge.
+///
+/// # Returns
+/// * `Some(Matching)`: a minimum weight perfect matching.
+/// * `None`: if the graph does not have a perfect matching.
+///
+/// # Panics
+/// Panics if **2 · (1 + (⌊|V|/2⌋ + 2) · (c_max − c_min))** overflows `K`, where **c_max** is the
+/// largest edge cost, or zero if that is larger, and **c_min** is the smallest edge cost. The costs
+/// are mirrored around **c_max** and shifted by that much, which keeps the algorithm from going
+/// below zero with unsigned weights.
+///
+/// # Complexity
+/// * Time complexity: **O(|V|³)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Maximum_weight_matching
+///
+/// # Examples
+///
+/// ```
+/// use petgraph::{algo::minimum_weight_perfect_matching, prelude::*};
+///
+/// // The example graph:
+/// //
+/// //     5       1       5
+/// //  a ---- b ----- c ---- d
+/// //  |                     |
+/// //  +---------------------+
+/// //             3
+///
+/// let graph: UnGraph<(), i32> =
+///     UnGraph::from_edges(&[(0, 1, 5), (1, 2, 1), (2, 3, 5), (3, 0, 3)]);
+///
+/// let matching = minimum_weight_perfect_matching(&graph, |e| *e.weight()).unwrap();
+/// assert!(matching.is_perfect());
+/// assert!(matching.contains_edge(1.into(), 2.into()));
+/// assert!(matching.contains_edge(3.into(), 0.into()));
+/// ```
+pub fn minimum_weight_perfect_matching<G, F, K>(graph: G, mut edge_cost: F) -> Option<Matching<G>>
+where
+    G: NodeIndexable + NodeCount + IntoEdgeReferences,
+    F: FnMut(G::EdgeRef) -> K,
+    K: BoundedMeasure + Copy + Div<K, Output = K>,
+{
+    let edges = mirror_weights(graph.node_count(), &weighted_edges(&graph, &mut edge_cost));
+    let mate = max_weight_matching_indices(graph.node_bound(), &edges);
+    let matching = weighted_matching(graph, &mate);
+    matching.is_perfect().then_some(matching)
+}
+
+fn weighted_edges<G, F, K>(graph: &G, edge_cost: &mut F) -> Vec<(usize, usize, K)>
+where
+    G: NodeIndexable + IntoEdgeReferences,
+    F: FnMut(G::EdgeRef) -> K,
+{
+    graph
+        .edge_references()
+        .filter(|edge| edge.source() != edge.target())
+        .map(|edge| {
+            (
+                graph.to_index(edge.source()),
+                graph.to_index(edge.target()),
+                edge_cost(edge),
+            )
+        })
+        .collect()
+}
+
+fn weighted_matching<G>(graph: G, mate: &[Option<usize>]) -> Matching<G>
+where
+    G: NodeIndexable,
+{
+    let n_edges = mate.iter().flatten().count() / 2;
+    let mate = mate
+        .iter()
+        .map(|m| m.map(|i| graph.from_index(i)))
+        .collect();
+    Matching::new(graph, mate, n_edges)
+}
+
+/// Compute a minimum weight perfect matching of the graph with nodes `0..node_count` and the
+/// given weighted edges. Returns the mate of every node, or `None` if there is no perfect
+/// matching.
+pub(crate) fn min_weight_perfect_matching_indices<K>(
+    node_count: usize,
+    edges: &[(usize, usize, K)],
+) -> Option<Vec<usize>>
+where
+    K: BoundedMeasure + Copy + Div<K, Output = K>,
+{
+    max_weight_matching_indices(node_count, &mirror_weights(node_count, edges))
+        .into_iter()
+        .collect()
+}
+
+/// Mirror the weights around the largest weight, and add a bonus to every edge that is larger
+/// than the mirrored weight of any matching of `node_count` nodes.
+///
+/// A maximum weight matching with these weights is a maximum cardinality matching, and it is a
+/// minimum weight perfect matching with the original weights, if there is any. Since all of
+/// them are positive, the duals of [`WeightedBlossom`] never become negative, which would
+/// underflow unsigned weights.
+///
+/// # Panics
+/// Panics if the new weights overflow `K`.
+fn mirror_weights<K>(node_count: usize, edges: &[(usize, usize, K)]) -> Vec<(usize, usize, K)>
+where
+    K: BoundedMeasure + Copy,
+{
+    let max_of = |max: K, w: K| if w > max { w } else { max };
+    let max_weight = edges.iter().map(|&(_, _, w)| w).fold(K::default(), max_of);
+    let spread = edges
+        .iter()
+        .map(|&(_, _, w)| max_weight - w)
+        .fold(K::default(), max_of);
+
+    // A matching has at most `node_count / 2` edges, so one more `spread` and a unit make
+    // any larger matching heavier.
+    let add = |a: K, b: K| {
+        let (sum, overflow) = a.overflowing_add(b);
+        assert!(!overflow, "edge weights are too large to be mirrored");
+        sum
+    };
+    let bonus = (0..=node_count / 2).fold(<K as BoundedMeasure>::from_f32(1.), |bonus, _| {
+        add(bonus, spread)
+    });
+    // The blossom algorithm adds up two mirrored weights, the largest of which is
+    // `bonus + spread`.
+    let largest = add(bonus, spread);
+    assert!(
+        !largest.overflowing_add(largest).1,
+        "edge weights are too large to be mirrored"
+    );
+
+    edges
+        .iter()
+        .map(|&(a, b, w)| (a, b, max_weight - w + bonus))
+        .collect()
+}
+
+/// Sentinel for an absent vertex, endpoint, edge or blossom in [`WeightedBlossom`].
+const NONE: usize = usize::MAX;
+
+/// Label of a top-level blossom that is not part of the alternating forest.
+const FREE: u8 = 0;
+/// Label of an outer (even) top-level blossom.
+const OUTER: u8 = 1;
+/// Label of an inner (odd) top-level blossom.
+const INNER: u8 = 2;
+/// Temporary label of an outer blossom visited by `scan_blossom`.
+const BREADCRUMB: u8 = 4;
+
+/// Compute a maximum weight matching of the graph with nodes `0..node_count` and the given
+/// weighted edges. Returns the mate of every node.
+pub(crate) fn max_weight_matching_indices<K>(
+    node_count: usize,
+    edges: &[(usize, usize, K)],
+) -> Vec<Option<usize>>
+where
+    K: BoundedMeasure + Copy + Div<K, Output = K>,
+{
+    let mut blossom = WeightedBlossom::new(node_count, edges);
+    blossom.run();
+    blossom
+        .mate
+        .iter()
+        .map(|&p| (p != NONE).then(|| blossom.endpoint[p]))
+        .collect()
+}
+
+/// State of the primal-dual blossom algorithm for maximum weight matching.
+///
+/// This follows the implementation notes of Galil's ["Efficient algorithms for finding maximum
+/// matching in graphs"][1]. Vertices are `0..n` and blossoms `n..2n`. Edge `k` has the two
+/// endpoints `2k` and `2k + 1`, so that `p ^ 1` is the opposite endpoint of endpoint `p`. The
+/// vertex duals are stored doubled, so that integer weights keep all duals integral.
+///
+/// [1]: https://dl.acm.org/doi/10.1145/6462.6502
+struct WeightedBlossom<'a, K> {
+    n: usize,
+    edges: &'a [(usize, usize, K)],
+    two: K,
+    /// The vertex of each endpoint.
+    endpoint: Vec<usize>,
+    /// The remote endpoints of the edges incident to each vertex.
+    neighbor_endpoints: Vec<Vec<usize>>,
+    /// The remote endpoint of the matched edge of each vertex.
+    mate: Vec<usize>,
+    label: Vec<u8>,
+    /// The endpoint through which each labeled vertex or blossom got its label.
+    label_end: Vec<usize>,
+    /// The top-level blossom containing each vertex.
+    in_blossom: Vec<usize>,
+    blossom_parent: Vec<usize>,
+    /// The sub-blossoms of each blossom, in cyclic order starting at the base.
+    blossom_childs: Vec<Vec<usize>>,
+    blossom_base: Vec<usize>,
+    /// The endpoints connecting consecutive sub-blossoms of each blossom.
+    blossom_endps: Vec<Vec<usize>>,
+    /// The least-slack edge to a different outer blossom, for each vertex and blossom.
+    best_edge: Vec<usize>,
+    /// The least-slack edges to every other outer blossom, for each outer blossom.
+    blossom_best_edges: Vec<Option<Vec<usize>>>,
+    unused_blossoms: Vec<usize>,
+    dual: Vec<K>,
+    allowed_edge: Vec<bool>,
+    queue: Vec<usize>,
+}
+
+impl<'a, K> WeightedBlossom<'a, K>
+where
+    K: BoundedMeasure + Copy + Div<K, Output = K>,
+{
+    fn new(n: usize, edges: &'a [(usize, usize, K)]) -> Self {
+        let zero = K::default();
+        let max_weight = edges
+            .iter()
+            .map(|&(_, _, w)| w)
+            .fold(zero, |max, w| if w > max { w } else { max });
+
+        let mut endpoint = Vec::with_capacity(2 * edges.len());
+        let mut neighbor_endpoints = vec![Vec::new(); n];
+        for (k, &(i, j, _)) in edges.iter().enumerate() {
+            endpoint.push(i);
+            endpoint.push(j);
+            neighbor_endpoints[i].push(2 * k + 1);
+            neighbor_endpoints[j].push(2 * k);
+        }
+
+        let mut dual = vec![max_weight; n];
+        dual.resize(2 * n, zero);
+
+        WeightedBlossom {
+            n,
+            edges,
+            two: <K as BoundedMeasure>::from_f32(2.),
+            endpoint,
+            neighbor_endpoints,
+            mate: vec![NONE; n],
+            label: vec![FREE; 2 * n],
+            label_end: vec![NONE; 2 * n],
+            in_blossom: (0..n).collect(),
+            blossom_parent: vec![NONE; 2 * n],
+            blossom_childs: vec![Vec::new(); 2 * n],
+            blossom_base: (0..n).chain(core::iter::repeat_n(NONE, n)).collect(),
+            blossom_endps: vec![Vec::new(); 2 * n],
+            best_edge: vec![NONE; 2 * n],
+            blossom_best_edges: vec![None; 2 * n],
+            unused_blossoms: (n..2 * n).rev().collect(),
+            dual,
+            allowed_edge: vec![false; edges.len()],
+            queue: Vec::new(),
+        }
+    }
+
+    fn slack(&self, k: usize) -> K {
+        let (i, j, w) = self.edges[k];
+        self.dual[i] + self.dual[j] - (w + w)
+    }
+
+    fn blossom_leaves(&self, b: usize) -> Vec<usize> {
+        let mut leaves = Vec::new();
+        let mut stack = vec![b];
+        while let Some(t) = stack.pop() {
+            if t < self.n {
+                leaves.push(t);
+            } else {
+                stack.extend_from_slice(&self.blossom_childs[t]);
+            }
+        }
+        leaves
+    }
+
+    fn child_at(&self, b: usize, j: isize) -> usize {
+        let childs = &self.blossom_childs[b];
+        childs[j.rem_euclid(childs.len() as isize) as usize]
+    }
+
+    fn endp_at(&self, b: usize, j: isize) -> usize {
+        let endps = &self.blossom_endps[b];
+        endps[j.rem_euclid(endps.len() as isize) as usize]
+    }
+
+    /// Assign label `t` to the top-level blossom containing vertex `w`, reached through the
+    /// endpoint `p`.
+    fn assign_label(&mut self, w: usize, t: u8, p: usize) {
+        let b = self.in_blossom[w];
+        self.label[w] = t;
+        self.label[b] = t;
+        self.label_end[w] = p;
+        self.label_end[b] = p;
+        self.best_edge[w] = NONE;
+        self.best_edge[b] = NONE;
+        if t == OUTER {
+            let leaves = self.blossom_leaves(b);
+            self.queue.extend(leaves);
+        } else {
+            // The mate of the base of an inner blossom becomes outer.
+            let base_mate = self.mate[self.blossom_base[b]];
+            self.assign_label(self.endpoint[base_mate], OUTER, base_mate ^ 1);
+        }
+    }
+
+    /// Trace back from `v` and `w` to discover either a new blossom, returning its base, or an
+    /// augmenting path, returning `NONE`.
+    fn scan_blossom(&mut self, mut v: usize, mut w: usize) -> usize {
+        let mut path = Vec::new();
+        let mut base = NONE;
+        while v != NONE {
+            let mut b = self.in_blossom[v];
+            if self.label[b] & BREADCRUMB != 0 {
+                base = self.blossom_base[b];
+                break;
+            }
+            path.push(b);
+            self.label[b] = OUTER | BREADCRUMB;
+            if self.label_end[b] == NONE {
+                // The root of an alternating tree.
+                v = NONE;
+            } else {
+                v = self.endpoint[self.label_end[b]];
+                b = self.in_blossom[v];
+                v = self.endpoint[self.label_end[b]];
+            }
+            if w != NONE {
+                core::mem::swap(&mut v, &mut w);
+            }
+        }
+        for b in path {
+            self.label[b] = OUTER;
+        }
+        base
+    }
+
+    /// Construct a new blossom with the given base, closed by edge `k`.
+    fn add_blossom(&mut self, base: usize, k: usize) {
+        let (v, w, _) = self.edges[k];
+        let bb = self.in_blossom[base];
+        let mut bv = self.in_blossom[v];
+        let mut bw = self.in_blossom[w];
+        let b = self
+            .unused_blossoms
+            .pop()
+            .expect("There are at most n / 2 nested blossoms");
+        self.blossom_base[b] = base;
+        self.blossom_parent[b] = NONE;
+        self.blossom_parent[bb] = b;
+
+        let mut path = Vec::new();
+        let mut endps = Vec::new();
+        while bv != bb {
+            self.blossom_parent[bv] = b;
+            path.push(bv);
+            endps.push(self.label_end[bv]);
+            bv = self.in_blossom[self.endpoint[self.label_end[bv]]];
+        }
+        path.push(bb);
+        path.reverse();
+        endps.reverse();
+        endps.push(2 * k);
+        while bw != bb {
+            self.blossom_parent[bw] = b;
+            path.push(bw);
+            endps.push(self.label_end[bw] ^ 1);
+            bw = self.in_blossom[self.endpoint[self.label_end[bw]]];
+        }
+        self.blossom_childs[b] = path.clone();
+        self.blossom_endps[b] = endps;
+
+        self.label[b] = OUTER;
+        self.label_end[b] = self.label_end[bb];
+        self.dual[b] = K::default();
+        for v in self.blossom_leaves(b) {
+            if self.label[self.in_blossom[v]] == INNER {
+                // Inner vertices become outer in the new blossom.
+                self.queue.push(v);
+            }
+            self.in_blossom[v] = b;
+        }
+
+        // Compute the least-slack edges to every other outer blossom.
+        let mut best_edge_to = vec![NONE; 2 * self.n];
+        for bv in path {
+            let neighbor_lists = match self.blossom_best_edges[bv].take() {
+                Some(list) => vec![list],
+                None => self
+                    .blossom_leaves(bv)
+                    .into_iter()
+                    .map(|v| self.neighbor_endpoints[v].iter().map(|p| p / 2).collect())
+                    .collect(),
+            };
+            for k in neighbor_lists.into_iter().flatten() {
+                let (i, mut j, _) = self.edges[k];
+                if self.in_blossom[j] == b {
+                    j = i;
+                }
+                let bj = self.in_blossom[j];
+                if bj != b
+                    && self.label[bj] == OUTER
+                    && (best_edge_to[bj] == NONE || self.slack(k) < self.slack(best_edge_to[bj]))
+                {
+                    best_edge_to[bj] = k;
+                }
+            }
+            self.best_edge[bv] = NONE;
+        }
+        let best_edges = best_edge_to
+            .into_iter()
+            .filter(|&k| k != NONE)
+            .collect::<Vec<_>>();
+        self.best_edge[b] = NONE;
+        for &k in &best_edges {
+            if self.best_edge[b] == NONE || self.slack(k) < self.slack(self.best_edge[b]) {
+                self.best_edge[b] = k;
+            }
+        }
+        self.blossom_best_edges[b] = Some(best_edges);
+    }
+
+    /// Expand the given top-level blossom.
+    fn expand_blossom(&mut self, b: usize, end_stage: bool) {
+        let childs = self.blossom_childs[b].clone();
+        for &s in &childs {
+            self.blossom_parent[s] = NONE;
+            if s < self.n {
+                self.in_blossom[s] = s;
+            } else if end_stage && self.dual[s] == K::default() {
+                // Recursively expand this sub-blossom.
+                self.expand_blossom(s, end_stage);
+            } else {
+                for v in self.blossom_leaves(s) {
+                    self.in_blossom[v] = s;
+                }
+            }
+        }
+
+        // If we expand an inner blossom during a stage, its sub-blossoms must be relabeled.
+        if !end_stage && self.label[b] == INNER {
+            let entry_child = self.in_blossom[self.endpoint[self.label_end[b] ^ 1]];
+            let len = childs.len() as isize;
+            let mut j = childs.iter().position(|&c| c == entry_child).unwrap() as isize;
+            // Walk from the entry child to the base along the even-length side.
+            let (j_step, endp_trick) = if j & 1 != 0 {
+                j -= len;
+                (1, 0)
+            } else {
+                (-1, 1)
+            };
+            let mut p = self.label_end[b];
+            while j != 0 {
+                // Relabel the inner sub-blossom.
+                self.label[self.endpoint[p ^ 1]] = FREE;
+                let q = self.endp_at(b, j - endp_trick as isize);
+                self.label[self.endpoint[q ^ endp_trick ^ 1]] = FREE;
+                self.assign_label(self.endpoint[p ^ 1], INNER, p);
+                // The edges on the path are tight.
+                self.allowed_edge[q / 2] = true;
+                j += j_step;
+                p = self.endp_at(b, j - endp_trick as isize) ^ endp_trick;
+                self.allowed_edge[p / 2] = true;
+                j += j_step;
+            }
+            // Relabel the base sub-blossom without creating a new outer label.
+            let bv = self.child_at(b, j);
+            self.label[self.endpoint[p ^ 1]] = INNER;
+            self.label[bv] = INNER;
+            self.label_end[self.endpoint[p ^ 1]] = p;
+            self.label_end[bv] = p;
+            self.best_edge[bv] = NONE;
+            // Continue along the odd-length side, relabeling sub-blossoms that are reachable.
+            j += j_step;
+            while self.child_at(b, j) != entry_child {
+                let bv = self.child_at(b, j);
+                if self.label[bv] == OUTER {
+                    // Already labeled through an edge to a neighbor outside the blossom.
+                    j += j_step;
+                    continue;
+                }
+                let labeled = self
+                    .blossom_leaves(bv)
+                    .into_iter()
+                    .find(|&v| self.label[v] != FREE);
+                if let Some(v) = labeled {
+                    self.label[v] = FREE;
+                    let base_mate = self.mate[self.blossom_base[bv]];
+                    self.label[self.endpoint[base_mate]] = FREE;
+                    self.assign_label(v, INNER, self.label_end[v]);
+                }
+                j += j_step;
+            }
+        }
+
+        // Recycle the blossom number.
+        self.label[b] = FREE;
+        self.label_end[b] = NONE;
+        self.blossom_childs[b].clear();
+        self.blossom_endps[b].clear();
+        self.blossom_base[b] = NONE;
+        self.blossom_best_edges[b] = None;
+        self.best_edge[b] = NONE;
+        self.unused_blossoms.push(b);
+    }
+
+    /// Swap matched and unmatched edges inside blossom `b` on the path from vertex `v` to the
+    /// base, and make `v` the new base.
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let mut t = v;
+        while self.blossom_parent[t] != b {
+            t = self.blossom_parent[t];
+        }
+        if t >= self.n {
+            self.augment_blossom(t, v);
+        }
+
+        let i = self.blossom_childs[b].iter().position(|&c| c == t).unwrap();
+        let len = self.blossom_childs[b].len() as isize;
+        let mut j = i as isize;
+        let (j_step, endp_trick) = if i & 1 != 0 {
+            j -= len;
+            (1, 0)
+        } else {
+            (-1, 1)
+        };
+        while j != 0 {
+            j += j_step;
+            let t = self.child_at(b, j);
+            let p = self.endp_at(b, j - endp_trick as isize) ^ endp_trick;
+            if t >= self.n {
+                self.augment_blossom(t, self.endpoint[p]);
+            }
+            j += j_step;
+            let t = self.child_at(b, j);
+            if t >= self.n {
+                self.augment_blossom(t, self.endpoint[p ^ 1]);
+            }
+            self.mate[self.endpoint[p]] = p ^ 1;
+            self.mate[self.endpoint[p ^ 1]] = p;
+        }
+
+        self.blossom_childs[b].rotate_left(i);
+        self.blossom_endps[b].rotate_left(i);
+        self.blossom_base[b] = self.blossom_base[self.blossom_childs[b][0]];
+        debug_assert_eq!(self.blossom_base[b], v);
+    }
+
+    /// Augment the matching along the path through edge `k` between two outer vertices.
+    fn augment_matching(&mut self, k: usize) {
+        let (v, w, _) = self.edges[k];
+        for (mut s, mut p) in [(v, 2 * k + 1), (w, 2 * k)] {
+            loop {
+                let bs = self.in_blossom[s];
+                if bs >= self.n {
+                    self.augment_blossom(bs, s);
+                }
+                self.mate[s] = p;
+                if self.label_end[bs] == NONE {
+                    // Reached the root of the alternating tree.
+                    break;
+                }
+                let t = self.endpoint[self.label_end[bs]];
+                let bt = self.in_blossom[t];
+                s = self.endpoint[self.label_end[bt]];
+                let j = self.endpoint[self.label_end[bt] ^ 1];
+                if bt >= self.n {
+                    self.augment_blossom(bt, j);
+                }
+                self.mate[j] = self.label_end[bt];
+                p = self.label_end[bt] ^ 1;
+            }
+        }
+    }
+
+    /// Scan the queued outer vertices, growing the alternating forest. Returns `true` if the
+    /// matching was augmented.
+    fn scan_queue(&mut self) -> bool {
+        while let Some(v) = self.queue.pop() {
+            for idx in 0..self.neighbor_endpoints[v].len() {
+                let p = self.neighbor_endpoints[v][idx];
+                let k = p / 2;
+                let w = self.endpoint[p];
+                if self.in_blossom[v] == self.in_blossom[w] {
+                    // An edge internal to a blossom.
+                    continue;
+                }
+                let mut k_slack = None;
+                if !self.allowed_edge[k] {
+                    let slack = self.slack(k);
+                    if slack <= K::default() {
+                        self.allowed_edge[k] = true;
+                    }
+                    k_slack = Some(slack);
+                }
+                let bw = self.in_blossom[w];
+                if self.allowed_edge[k] {
+                    if self.label[bw] == FREE {
+                        // Grow the alternating tree with an inner blossom.
+                        self.assign_label(w, INNER, p ^ 1);
+                    } else if self.label[bw] == OUTER {
+                        let base = self.scan_blossom(v, w);
+                        if base != NONE {
+                            self.add_blossom(base, k);
+                        } else {
+                            self.augment_matching(k);
+                            return true;
+                        }
+                    } else if self.label[w] == FREE {
+                        // `w` is inside an inner blossom but was not reached yet.
+                        self.label[w] = INNER;
+                        self.label_end[w] = p ^ 1;
+                    }
+                } else if self.label[bw] == OUTER {
+                    let b = self.in_blossom[v];
+                    let slack = k_slack.unwrap();
+                    if self.best_edge[b] == NONE || slack < self.slack(self.best_edge[b]) {
+                        self.best_edge[b] = k;
+                    }
+                } else if self.label[w] == FREE {
+                    let slack = k_slack.unwrap();
+                    if self.best_edge[w] == NONE || slack < self.slack(self.best_edge[w]) {
+                        self.best_edge[w] = k;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    fn run(&mut self) {
+        let n = self.n;
+        let zero = K::default();
+        // Each stage augments the matching by one edge, or stops when no augmentation is
+        // possible anymore.
+        for _ in 0..n {
+            self.label.fill(FREE);
+            self.best_edge.fill(NONE);
+            for best_edges in &mut self.blossom_best_edges[n..] {
+                *best_edges = None;
+            }
+            self.allowed_edge.fill(false);
+            self.queue.clear();
+
+            for v in 0..n {
+                if self.mate[v] == NONE && self.label[self.in_blossom[v]] == FREE {
+                    self.assign_label(v, OUTER, NONE);
+                }
+            }
+
+            let augmented = loop {
+                if self.scan_queue() {
+                    break true;
+                }
+
+                // No augmenting path under the current duals: find the smallest dual update
+                // that creates a new tight edge or unlocks an inner blossom.
+                // Vertex duals reaching zero stop the stage.
+                let mut delta = self.dual[..n]
+                    .iter()
+                    .copied()
+                    .fold(None, |min: Option<K>, d| match min {
+                        Some(m) if m <= d => Some(m),
+                        _ => Some(d),
+                    });
+                let mut delta_type = 1;
+                let mut delta_edge = NONE;
+                let mut delta_blossom = NONE;
+                for v in 0..n {
+                    if self.label[self.in_blossom[v]] == FREE && self.best_edge[v] != NONE {
+                        let d = self.slack(self.best_edge[v]);
+                        if delta.is_none_or(|delta| d < delta) {
+                            delta = Some(d);
+                            delta_type = 2;
+                            delta_edge = self.best_edge[v];
+                        }
+                    }
+                }
+                for b in 0..2 * n {
+                    if self.blossom_parent[b] == NONE
+                        && self.label[b] == OUTER
+                        && self.best_edge[b] != NONE
+                    {
+                        let d = self.slack(self.best_edge[b]) / self.two;
+                        if delta.is_none_or(|delta| d < delta) {
+                            delta = Some(d);
+                            delta_type = 3;
+                            delta_edge = self.best_edge[b];
+                        }
+                    }
+                }
+                for b in n..2 * n {
+                    if self.blossom_base[b] != NONE
+                        && self.blossom_parent[b] == NONE
+                        && self.label[b] == INNER
+                        && delta.is_none_or(|delta| self.dual[b] < delta)
+                    {
+                        delta = Some(self.dual[b]);
+                        delta_type = 4;
+                        delta_blossom = b;
+                    }
+                }
+                let Some(delta) = delta else {
+                    break false;
+                };
+
+                for v in 0..n {
+                    match self.label[self.in_blossom[v]] {
+                        OUTER => self.dual[v] = self.dual[v] - delta,
+                        INNER => self.dual[v] = self.dual[v] + delta,
+                        _ => {}
+                    }
+                }
+                for b in n..2 * n {
+                    if self.blossom_base[b] != NONE && self.blossom_parent[b] == NONE {
+                        match self.label[b] {
+                            OUTER => self.dual[b] = self.dual[b] + delta,
+                            INNER => self.dual[b] = self.dual[b] - delta,
+                            _ => {}
+                        }
+                    }
+                }
+
+                match delta_type {
+                    1 => break false,
+                    2 => {
+                        self.allowed_edge[delta_edge] = true;
+                        let (mut i, j, _) = self.edges[delta_edge];
+                        if self.label[self.in_blossom[i]] == FREE {
+                            i = j;
+                        }
+                        self.queue.push(i);
+                    }
+                    3 => {
+                        self.allowed_edge[delta_edge] = true;
+                        self.queue.push(self.edges[delta_edge].0);
+                    }
+                    _ => self.expand_blossom(delta_blossom, false),
+                }
+            };
+
+            if !augmented {
+                break;
+            }
+
+            // Expand outer blossoms with a zero dual at the end of the stage.
+            for b in n..2 * n {
+                if self.blossom_parent[b] == NONE
+                    && self.blossom_base[b] != NONE
+                    && self.label[b] == OUTER
+                    && self.dual[b] == zero
+                {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+    }
+}
@@ -1,68 +1,110 @@
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 use core::{hash::Hash, iter::FromIterator};
 
 use hashbrown::HashSet;
 
 use crate::visit::{GetAdjacencyMatrix, IntoNeighbors, IntoNodeIdentifiers};
 
-/// Finds maximal cliques containing all the vertices in r, some of the
-/// vertices in p, and none of the vertices in x.
-///
-/// By default, only works on undirected graphs. It can be used on directed graphs
-/// if the graph is symmetric. I.e., if an edge (u, v) exists, then (v, u) also exists.
-///
-/// Uses the [Bron–Kerbosch algorithm][1] with pivoting.
+/// A step of the [Bron–Kerbosch algorithm][1] with pivoting: it finds the maximal cliques
+/// containing all the vertices in r, some of the vertices in p, and none of the vertices in x.
 ///
 /// [1]: https://en.wikipedia.org/wiki/Bron%E2%80%93Kerbosch_algorithm
-fn bron_kerbosch_pivot<G>(
+#[derive(Clone, Debug)]
+struct BronKerboschFrame<N> {
+    r: HashSet<N>,
+    p: HashSet<N>,
+    x: HashSet<N>,
+    /// The vertices of p that are still to be added to r.
+    todo: Vec<N>,
+}
+
+impl<N> BronKerboschFrame<N>
+where
+    N: Copy + Eq + Hash,
+{
+    fn new<G>(g: G, adj_mat: &G::AdjMatrix, r: HashSet<N>, p: HashSet<N>, x: HashSet<N>) -> Self
+    where
+        G: GetAdjacencyMatrix + IntoNeighbors<NodeId = N>,
+    {
+        // pick the pivot u to be the vertex with max degree
+        let todo = match p.iter().max_by_key(|&v| g.neighbors(*v).count()) {
+            Some(u) => p
+                .iter()
+                .filter(
+                    //skip neighbors of pivot
+                    |&v| {
+                        *u == *v
+                            || !g.is_adjacent(adj_mat, *u, *v)
+                            || !g.is_adjacent(adj_mat, *v, *u)
+                    },
+                )
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        BronKerboschFrame { r, p, x, todo }
+    }
+}
+
+/// An iterator over the maximal cliques of a graph.
+///
+/// See [`maximal_cliques_iter`].
+pub struct MaximalCliques<G>
+where
+    G: GetAdjacencyMatrix + IntoNeighbors,
+{
     g: G,
-    adj_mat: &G::AdjMatrix,
-    r: HashSet<G::NodeId>,
-    mut p: HashSet<G::NodeId>,
-    mut x: HashSet<G::NodeId>,
-) -> Vec<HashSet<G::NodeId>>
+    adj_mat: G::AdjMatrix,
+    /// A maximal clique found before the search started, which is the empty clique of an empty
+    /// graph.
+    pending: Option<HashSet<G::NodeId>>,
+    stack: Vec<BronKerboschFrame<G::NodeId>>,
+}
+
+impl<G> Iterator for MaximalCliques<G>
 where
     G: GetAdjacencyMatrix + IntoNeighbors,
     G::NodeId: Eq + Hash,
 {
-    let mut cliques = Vec::with_capacity(1);
-    if p.is_empty() {
-        if x.is_empty() {
-            cliques.push(r);
-        }
-        return cliques;
-    }
-    // pick the pivot u to be the vertex with max degree
-    let u = p.iter().max_by_key(|&v| g.neighbors(*v).count()).unwrap();
-    let mut todo = p
-        .iter()
-        .filter(
-            //skip neighbors of pivot
-            |&v| *u == *v || !g.is_adjacent(adj_mat, *u, *v) || !g.is_adjacent(adj_mat, *v, *u),
-        )
-        .cloned()
-        .collect::<Vec<G::NodeId>>();
-    while let Some(v) = todo.pop() {
-        let neighbors = HashSet::from_iter(g.neighbors(v));
-        p.remove(&v);
-        let mut next_r = r.clone();
-        next_r.insert(v);
+    type Item = HashSet<G::NodeId>;
 
-        let next_p = p
-            .intersection(&neighbors)
-            .cloned()
-            .collect::<HashSet<G::NodeId>>();
-        let next_x = x
-            .intersection(&neighbors)
-            .cloned()
-            .collect::<HashSet<G::NodeId>>();
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(clique) = self.pending.take() {
+            return Some(clique);
+        }
+        loop {
+            let frame = self.stack.last_mut()?;
+            let Some(v) = frame.todo.pop() else {
+                self.stack.pop();
+                continue;
+            };
+            let neighbors = HashSet::from_iter(self.g.neighbors(v));
+            frame.p.remove(&v);
+            let mut next_r = frame.r.clone();
+            next_r.insert(v);
 
-        cliques.extend(bron_kerbosch_pivot(g, adj_mat, next_r, next_p, next_x));
+            let next_p = frame
+                .p
+                .intersection(&neighbors)
+                .cloned()
+                .collect::<HashSet<G::NodeId>>();
+            let next_x = frame
+                .x
+                .intersection(&neighbors)
+                .cloned()
+                .collect::<HashSet<G::NodeId>>();
+            frame.x.insert(v);
 
-        x.insert(v);
+            if next_p.is_empty() {
+                if next_x.is_empty() {
+                    return Some(next_r);
+                }
+                continue;
+            }
+            let next = BronKerboschFrame::new(self.g, &self.adj_mat, next_r, next_p, next_x);
+            self.stack.push(next);
+        }
     }
-
-    cliques
 }
 
 /// Find all maximal cliques in an undirected graph using [Bron–Kerbosch algorithm][1]
@@ -117,13 +159,61 @@ where
 /// // ]
 /// ```
 pub fn maximal_cliques<G>(g: G) -> Vec<HashSet<G::NodeId>>
+where
+    G: GetAdjacencyMatrix + IntoNodeIdentifiers + IntoNeighbors,
+    G::NodeId: Eq + Hash,
+{
+    maximal_cliques_iter(g).collect()
+}
+
+/// Return an iterator over all maximal cliques in an undirected graph, using the
+/// [Bron–Kerbosch algorithm][1] with pivoting. Also works on symmetric directed graphs.
+///
+/// This finds the same cliques as [`maximal_cliques`], but streams them instead of collecting
+/// them, so that the search can be stopped early.
+///
+/// # Arguments
+/// * `g`: The graph to find maximal cliques in.
+///
+/// # Returns
+/// * [`MaximalCliques`]: an iterator over the maximal cliques, each as a
+///   [`struct@hashbrown::HashSet`] of nodes.
+///
+/// # Complexity
+/// * Time complexity: **O(3^(|V|/3))** for all cliques.
+/// * Auxiliary space: **O(|V|²)**.
+///
+/// where **|V|** is the number of nodes.
+///
+/// [1]: https://en.wikipedia.org/wiki/Bron%E2%80%93Kerbosch_algorithm
+///
+/// # Example
+///
+/// ```
+/// use petgraph::{algo::maximal_cliques_iter, graph::UnGraph};
+///
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 2), (2, 3)]);
+/// // Find a maximal clique with at least three nodes, without enumerating the others.
+/// let triangle = maximal_cliques_iter(&g).find(|clique| clique.len() >= 3);
+/// assert_eq!(triangle.map(|clique| clique.len()), Some(3));
+/// ```
+pub fn maximal_cliques_iter<G>(g: G) -> MaximalCliques<G>
 where
     G: GetAdjacencyMatrix + IntoNodeIdentifiers + IntoNeighbors,
     G::NodeId: Eq + Hash,
 {
     let adj_mat = g.adjacency_matrix();
-    let r = HashSet::new();
     let p = g.node_identifiers().collect::<HashSet<G::NodeId>>();
-    let x = HashSet::new();
-    bron_kerbosch_pivot(g, &adj_mat, r, p, x)
+    let (pending, stack) = if p.is_empty() {
+        (Some(HashSet::new()), Vec::new())
+    } else {
+        let frame = BronKerboschFrame::new(g, &adj_mat, HashSet::new(), p, HashSet::new());
+        (None, vec![frame])
+    };
+    MaximalCliques {
+        g,
+        adj_mat,
+        pending,
+        stack,
+    }
 }
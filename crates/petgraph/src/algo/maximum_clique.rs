@@ -0,0 +1,324 @@
+use alloc::{vec, vec::Vec};
+use core::hash::Hash;
+
+use fixedbitset::FixedBitSet;
+use hashbrown::HashSet;
+
+use super::{Measure, simple_adjacency};
+use crate::visit::{IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+/// Find a clique with the largest number of nodes.
+///
+/// A clique is a set of nodes such that every node connects to every other. Unlike
+/// [`maximal_cliques`](super::maximal_cliques()), which lists every clique that cannot be extended,
+/// this only finds one clique of maximum size, with the branch and bound algorithm of
+/// [Tomita and Seki][1]: a greedy coloring of the candidate nodes bounds the size of the cliques
+/// among them, because the nodes of a clique have different colors.
+///
+/// Finding a maximum clique is NP-hard, but this search is fast in practice on sparse graphs and
+/// on graphs with up to a few hundred nodes.
+///
+/// The graph is treated as undirected. Self-loops and parallel edges are ignored.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+///
+/// # Returns
+/// * [`struct@hashbrown::HashSet`]: the nodes of a maximum clique, which is empty only for an empty
+///   graph.
+///
+/// # Complexity
+/// * Time complexity: **O(2^|V|)** in the worst case.
+/// * Auxiliary space: **O(|V|²)**.
+///
+/// where **|V|** is the number of nodes.
+///
+/// [1]: https://doi.org/10.1007/3-540-45066-1_22
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::maximum_clique, graph::UnGraph};
+///
+/// // Triangles that share edges, and a square.
+/// let g = UnGraph::<(), ()>::from_edges([
+///     (0, 1),
+///     (1, 2),
+///     (2, 0),
+///     (2, 3),
+///     (3, 4),
+///     (4, 2),
+///     (1, 3),
+///     (4, 5),
+///     (5, 6),
+///     (6, 7),
+///     (7, 4),
+/// ]);
+/// // The largest cliques are triangles, like {1, 2, 3}.
+/// assert_eq!(maximum_clique(&g).len(), 3);
+/// ```
+pub fn maximum_clique<G>(graph: G) -> HashSet<G::NodeId>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    maximum_weight_clique(graph, |_| 1usize).0
+}
+
+/// Find a clique with the largest total weight of its nodes.
+///
+/// A clique is a set of nodes such that every node connects to every other. This uses the
+/// branch and bound search of [`maximum_clique`], where the bound of a set of candidate nodes is
+/// the sum over the color classes of a greedy coloring of the largest weight in the class.
+///
+/// The graph is treated as undirected. Self-loops and parallel edges are ignored.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `node_weight`: closure that returns the weight of a node, which must not be negative.
+///
+/// # Returns
+/// Returns a tuple of:
+/// * [`struct@hashbrown::HashSet`]: the nodes of a maximum weight clique. Nodes of weight zero may
+///   be left out.
+/// * `K`: the total weight of the clique.
+///
+/// # Complexity
+/// * Time complexity: **O(2^|V|)** in the worst case.
+/// * Auxiliary space: **O(|V|²)**.
+///
+/// where **|V|** is the number of nodes.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::maximum_weight_clique, graph::UnGraph};
+///
+/// // A triangle of light nodes and a heavy edge.
+/// let g = UnGraph::<u32, ()>::from_edges([(0, 1), (1, 2), (2, 0), (2, 3)]);
+/// let weights = [1, 1, 1, 5];
+/// let (clique, weight) = maximum_weight_clique(&g, |v| weights[v.index()]);
+/// assert_eq!(weight, 6);
+/// assert_eq!(clique.len(), 2);
+/// ```
+pub fn maximum_weight_clique<G, F, K>(graph: G, mut node_weight: F) -> (HashSet<G::NodeId>, K)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::NodeId) -> K,
+    K: Measure + Copy,
+{
+    let (nodes, adjacency) = adjacency_bitsets(graph);
+    let weights = nodes.iter().map(|&v| node_weight(v)).collect::<Vec<_>>();
+    let (clique, weight) = CliqueSearch::run(&adjacency, false, &weights);
+    (clique.into_iter().map(|i| nodes[i]).collect(), weight)
+}
+
+/// Find an independent set with the largest number of nodes.
+///
+/// An independent set is a set of nodes such that no two of them are adjacent. This is a
+/// maximum clique in the complement of the graph, found with the search of [`maximum_clique`],
+/// which tests for non-adjacency instead of building the complement.
+///
+/// The graph is treated as undirected. Self-loops and parallel edges are ignored.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+///
+/// # Returns
+/// * [`struct@hashbrown::HashSet`]: the nodes of a maximum independent set.
+///
+/// # Complexity
+/// * Time complexity: **O(2^|V|)** in the worst case.
+/// * Auxiliary space: **O(|V|²)**.
+///
+/// where **|V|** is the number of nodes.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::maximum_independent_set, graph::UnGraph};
+///
+/// // A cycle of seven nodes.
+/// let g = UnGraph::<(), ()>::from_edges((0..7).map(|i| (i, (i + 1) % 7)));
+/// let independent = maximum_independent_set(&g);
+/// assert_eq!(independent.len(), 3);
+/// assert!(g.edge_indices().all(|e| {
+///     let (a, b) = g.edge_endpoints(e).unwrap();
+///     !independent.contains(&a) || !independent.contains(&b)
+/// }));
+/// ```
+pub fn maximum_independent_set<G>(graph: G) -> HashSet<G::NodeId>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let (nodes, adjacency) = adjacency_bitsets(graph);
+    let weights = vec![1usize; nodes.len()];
+    let (independent, _) = CliqueSearch::run(&adjacency, true, &weights);
+    independent.into_iter().map(|i| nodes[i]).collect()
+}
+
+/// Find a vertex cover with the smallest number of nodes.
+///
+/// A vertex cover is a set of nodes such that every edge has an endpoint in the set. Its
+/// complement is an independent set, so this returns the nodes that are not in the
+/// [`maximum_independent_set`].
+///
+/// The graph is treated as undirected. Parallel edges are ignored, and a node with a self-loop
+/// is only in the cover if it is needed for other edges.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+///
+/// # Returns
+/// * [`struct@hashbrown::HashSet`]: the nodes of a minimum vertex cover.
+///
+/// # Complexity
+/// * Time complexity: **O(2^|V|)** in the worst case.
+/// * Auxiliary space: **O(|V|²)**.
+///
+/// where **|V|** is the number of nodes.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{
+///     algo::minimum_vertex_cover,
+///     graph::{NodeIndex, UnGraph},
+/// };
+///
+/// // A star is covered by its center.
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3), (0, 4)]);
+/// let cover = minimum_vertex_cover(&g);
+/// assert_eq!(cover.len(), 1);
+/// assert!(cover.contains(&NodeIndex::new(0)));
+/// ```
+pub fn minimum_vertex_cover<G>(graph: G) -> HashSet<G::NodeId>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let independent = maximum_independent_set(graph);
+    graph
+        .node_identifiers()
+        .filter(|v| !independent.contains(v))
+        .collect()
+}
+
+/// Collect the nodes of `graph` and the neighbors of every node, by position, without
+/// self-loops.
+fn adjacency_bitsets<G>(graph: G) -> (Vec<G::NodeId>, Vec<FixedBitSet>)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+{
+    let (nodes, adjacency) = simple_adjacency(graph);
+    let adjacency = adjacency
+        .into_iter()
+        .map(|neighbors| {
+            let mut set = FixedBitSet::with_capacity(nodes.len());
+            set.extend(neighbors);
+            set
+        })
+        .collect();
+    (nodes, adjacency)
+}
+
+/// The state of the branch and bound search for a maximum weight clique, in the graph or in its
+/// complement.
+struct CliqueSearch<'a, K> {
+    adjacency: &'a [FixedBitSet],
+    complement: bool,
+    weights: &'a [K],
+    clique: Vec<usize>,
+    weight: K,
+    best: Vec<usize>,
+    best_weight: K,
+}
+
+impl<'a, K> CliqueSearch<'a, K>
+where
+    K: Measure + Copy,
+{
+    fn run(adjacency: &'a [FixedBitSet], complement: bool, weights: &'a [K]) -> (Vec<usize>, K) {
+        let mut search = CliqueSearch {
+            adjacency,
+            complement,
+            weights,
+            clique: Vec::new(),
+            weight: K::default(),
+            best: Vec::new(),
+            best_weight: K::default(),
+        };
+        // Coloring the nodes of large degree first gives tighter bounds.
+        let degree = |v: usize| {
+            let degree = adjacency[v].count_ones(..);
+            if complement {
+                adjacency.len() - 1 - degree
+            } else {
+                degree
+            }
+        };
+        let mut candidates = (0..adjacency.len()).collect::<Vec<_>>();
+        candidates.sort_by_key(|&v| core::cmp::Reverse(degree(v)));
+        if !candidates.is_empty() {
+            search.expand(&candidates);
+        }
+        (search.best, search.best_weight)
+    }
+
+    fn adjacent(&self, v: usize, w: usize) -> bool {
+        v != w && self.adjacency[v].contains(w) != self.complement
+    }
+
+    /// Extend the current clique with the `candidates`, which are adjacent to all of its nodes.
+    fn expand(&mut self, candidates: &[usize]) {
+        // Color the candidates greedily. A clique has at most one node of every color, so it
+        // weighs at most as much as the heaviest nodes of the colors.
+        let mut classes: Vec<Vec<usize>> = Vec::new();
+        for &v in candidates {
+            match classes
+                .iter_mut()
+                .find(|class| class.iter().all(|&w| !self.adjacent(v, w)))
+            {
+                Some(class) => class.push(v),
+                None => classes.push(vec![v]),
+            }
+        }
+        let mut order = Vec::with_capacity(candidates.len());
+        let mut bounds = Vec::with_capacity(candidates.len());
+        let mut bound = K::default();
+        for class in classes {
+            let heaviest = class
+                .iter()
+                .map(|&v| self.weights[v])
+                .fold(K::default(), |a, b| if b > a { b } else { a });
+            bound = bound + heaviest;
+            for v in class {
+                order.push(v);
+                bounds.push(bound);
+            }
+        }
+
+        // Branch on the nodes of the last colors first, and drop them from the candidates.
+        while let (Some(v), Some(bound)) = (order.pop(), bounds.pop()) {
+            if self.weight + bound <= self.best_weight && !self.best.is_empty() {
+                return;
+            }
+            let weight = self.weight;
+            self.clique.push(v);
+            self.weight = weight + self.weights[v];
+            let next = order
+                .iter()
+                .copied()
+                .filter(|&w| self.adjacent(v, w))
+                .collect::<Vec<_>>();
+            if next.is_empty() {
+                if self.weight > self.best_weight || self.best.is_empty() {
+                    self.best.clone_from(&self.clique);
+                    self.best_weight = self.weight;
+                }
+            } else {
+                self.expand(&next);
+            }
+            self.clique.pop();
+            self.weight = weight;
+        }
+    }
+}
@@ -0,0 +1,523 @@
+//! Minimum spanning arborescences and branchings of directed graphs.
+//!
+//! An *arborescence* of a directed graph rooted at a node **R** is a set of edges that contains
+//! exactly one path from **R** to every node. A *branching* is a set of edges that enters every
+//! node at most once and contains no cycle: a forest of arborescences.
+
+use alloc::{vec, vec::Vec};
+use core::{cmp::Ordering, ops::Sub};
+
+use super::{Measure, node_positions};
+use crate::{
+    data::Element,
+    unionfind::UnionFind,
+    visit::{
+        Data, EdgeRef, GraphBase, IntoEdgeReferences, IntoNodeReferences, NodeIndexable, NodeRef,
+    },
+};
+
+/// Compute a *minimum spanning arborescence* of a directed graph.
+///
+/// Every node except the root gets exactly one incoming edge, such that all nodes are reachable
+/// from the root, and the total weight of the edges is minimal. With `root` set to `None`, this
+/// finds an arborescence of minimum weight over all roots.
+///
+/// This is [Edmonds' algorithm][1] (also due to Chu and Liu) in the efficient variant of
+/// [Tarjan][2], with the corrections of Camerini et al: every node picks its lightest incoming
+/// edge, cycles of picked edges are contracted, and the weights of the edges into a cycle are
+/// reduced by the weight of the picked edge they would replace. Mergeable heaps keep the edges
+/// into every contracted node.
+///
+/// Self-loops and, with a given root, the edges into the root are ignored. The graph is treated
+/// as directed.
+///
+/// # Arguments
+/// * `g`: a directed graph.
+/// * `root`: the root of the arborescence, or `None` to choose the best root.
+///
+/// # Returns
+/// * `Some(MinSpanningArborescence)`: an iterator producing the nodes of the graph, followed by the
+///   edges of the arborescence. Use `from_elements` to create a graph from it.
+/// * `None`: if some node is not reachable from the root, or from any single node when `root` is
+///   `None`.
+///
+/// # Complexity
+/// * Time complexity: **O(|E| log |V|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Edmonds%27_algorithm
+/// [2]: https://doi.org/10.1002/net.3230070103
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::min_spanning_arborescence, data::FromElements, prelude::*};
+///
+/// let mut g = DiGraph::<&str, u32>::new();
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// let c = g.add_node("c");
+/// g.extend_with_edges([(a, b, 5), (a, c, 1), (c, b, 1), (b, c, 1)]);
+///
+/// let arborescence = min_spanning_arborescence(&g, Some(a)).unwrap();
+/// assert_eq!(arborescence.root(), Some(a));
+/// let tree = DiGraph::<_, _>::from_elements(arborescence);
+/// assert_eq!(tree.edge_weights().sum::<u32>(), 2);
+/// assert!(tree.contains_edge(a, c) && tree.contains_edge(c, b));
+///
+/// // No arborescence is rooted at `b`, because `a` has no incoming edges.
+/// assert!(min_spanning_arborescence(&g, Some(b)).is_none());
+/// // `a` is the only possible root.
+/// assert_eq!(min_spanning_arborescence(&g, None).unwrap().root(), Some(a));
+/// ```
+pub fn min_spanning_arborescence<G>(
+    g: G,
+    root: Option<G::NodeId>,
+) -> Option<MinSpanningArborescence<G>>
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable,
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Measure + Sub<G::EdgeWeight, Output = G::EdgeWeight>,
+{
+    let mode = match root {
+        Some(root) => Mode::Root(root),
+        None => Mode::BestRoot,
+    };
+    arborescence(g, mode)
+}
+
+/// Compute a *minimum branching* of a directed graph.
+///
+/// A branching is a forest of arborescences: every node has at most one incoming edge, and the
+/// edges contain no cycle. Its weight is the total weight of its edges, so only edges of negative
+/// weight make a minimum branching non-empty. Negate the weights to find a maximum branching.
+///
+/// This uses the algorithm of [`min_spanning_arborescence`], with a virtual root that has an edge
+/// of weight zero to every node.
+///
+/// Self-loops are ignored. The graph is treated as directed.
+///
+/// # Arguments
+/// * `g`: a directed graph.
+///
+/// # Returns
+/// * [`MinSpanningArborescence`]: an iterator producing the nodes of the graph, followed by the
+///   edges of the branching. Use `from_elements` to create a graph from it.
+///
+/// # Complexity
+/// * Time complexity: **O(|E| log |V|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::min_branching, data::FromElements, prelude::*};
+///
+/// // Scores of dependencies between words, negated to find the best ones.
+/// let g = DiGraph::<(), i32>::from_edges([(0, 1, -3), (1, 0, -2), (1, 2, -1), (2, 1, -4)]);
+/// let branching = DiGraph::<(), i32>::from_elements(min_branching(&g));
+/// assert_eq!(branching.edge_weights().sum::<i32>(), -6);
+/// assert_eq!(branching.edge_count(), 2);
+/// ```
+pub fn min_branching<G>(g: G) -> MinSpanningArborescence<G>
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable,
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Measure + Sub<G::EdgeWeight, Output = G::EdgeWeight>,
+{
+    arborescence(g, Mode::Branching).unwrap()
+}
+
+/// An iterator producing a minimum spanning arborescence or a minimum branching of a graph.
+/// It will first iterate all Node elements from the original graph,
+/// then iterate the Edge elements of the arborescence.
+///
+/// See [`min_spanning_arborescence`] and [`min_branching`].
+#[derive(Debug, Clone)]
+pub struct MinSpanningArborescence<G>
+where
+    G: Data + GraphBase,
+{
+    root: Option<G::NodeId>,
+    elements: vec::IntoIter<Element<G::NodeWeight, G::EdgeWeight>>,
+}
+
+impl<G> MinSpanningArborescence<G>
+where
+    G: Data + GraphBase,
+{
+    /// Get the root of the arborescence, or `None` for a branching.
+    pub fn root(&self) -> Option<G::NodeId> {
+        self.root
+    }
+}
+
+impl<G> Iterator for MinSpanningArborescence<G>
+where
+    G: Data + GraphBase,
+{
+    type Item = Element<G::NodeWeight, G::EdgeWeight>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.elements.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.elements.size_hint()
+    }
+}
+
+enum Mode<N> {
+    Root(N),
+    BestRoot,
+    Branching,
+}
+
+fn arborescence<G>(g: G, mode: Mode<G::NodeId>) -> Option<MinSpanningArborescence<G>>
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable,
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Measure + Sub<G::EdgeWeight, Output = G::EdgeWeight>,
+{
+    let (nodes, positions) = node_positions(g);
+    let mut weights = vec![None; nodes.len()];
+    for node in g.node_references() {
+        weights[positions[g.to_index(node.id())]] = Some(node.weight().clone());
+    }
+    let mut elements = weights
+        .into_iter()
+        .map(|weight| Element::Node {
+            weight: weight.unwrap(),
+        })
+        .collect::<Vec<_>>();
+    let node_count = nodes.len();
+
+    // Without a given root, a virtual root has an edge to every node. To find a single best root,
+    // these edges cost more than all other edges together, by being in a higher tier.
+    let (root, virtual_tier) = match mode {
+        Mode::Root(root) => (positions[g.to_index(root)], None),
+        Mode::BestRoot => (node_count, Some(1)),
+        Mode::Branching => (node_count, Some(0)),
+    };
+    if node_count == 0 {
+        return match mode {
+            Mode::Branching => Some(MinSpanningArborescence {
+                root: None,
+                elements: elements.into_iter(),
+            }),
+            _ => None,
+        };
+    }
+
+    let mut edges = Vec::new();
+    for edge in g.edge_references() {
+        let source = positions[g.to_index(edge.source())];
+        let target = positions[g.to_index(edge.target())];
+        if source != target && target != root {
+            edges.push(ArborescenceEdge {
+                source,
+                target,
+                cost: Cost::new(0, edge.weight().clone(), G::EdgeWeight::default()),
+                weight: Some(edge.weight().clone()),
+            });
+        }
+    }
+    if let Some(tier) = virtual_tier {
+        for target in 0..node_count {
+            edges.push(ArborescenceEdge {
+                source: root,
+                target,
+                cost: Cost::new(tier, G::EdgeWeight::default(), G::EdgeWeight::default()),
+                weight: None,
+            });
+        }
+    }
+    let vertex_count = node_count + usize::from(virtual_tier.is_some());
+
+    let chosen = contract(vertex_count, root, &edges)?;
+    let mut arborescence_root = None;
+    let mut roots = 0;
+    for e in chosen {
+        let edge = &edges[e];
+        match &edge.weight {
+            Some(weight) => elements.push(Element::Edge {
+                source: edge.source,
+                target: edge.target,
+                weight: weight.clone(),
+            }),
+            None => {
+                roots += 1;
+                arborescence_root = Some(nodes[edge.target]);
+            }
+        }
+    }
+    let root = match mode {
+        Mode::Root(root) => Some(root),
+        Mode::BestRoot if roots == 1 => arborescence_root,
+        Mode::BestRoot => return None,
+        Mode::Branching => None,
+    };
+    Some(MinSpanningArborescence {
+        root,
+        elements: elements.into_iter(),
+    })
+}
+
+/// The cost of an edge: edges in a higher tier cost more than any number of edges in a lower
+/// tier. The weight is the difference `plus - minus`, with one of them zero, so that reduced costs
+/// can be negative even if the weights cannot.
+#[derive(Clone, Debug)]
+struct Cost<K> {
+    tier: isize,
+    plus: K,
+    minus: K,
+}
+
+impl<K> Cost<K>
+where
+    K: Measure + Sub<K, Output = K>,
+{
+    fn new(tier: isize, plus: K, minus: K) -> Self {
+        if plus >= minus {
+            Cost {
+                tier,
+                plus: plus - minus,
+                minus: K::default(),
+            }
+        } else {
+            Cost {
+                tier,
+                plus: K::default(),
+                minus: minus - plus,
+            }
+        }
+    }
+
+    fn zero() -> Self {
+        Cost::new(0, K::default(), K::default())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.tier == 0 && self.plus == self.minus
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Cost::new(
+            self.tier + other.tier,
+            self.plus.clone() + other.plus.clone(),
+            self.minus.clone() + other.minus.clone(),
+        )
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        Cost::new(
+            self.tier - other.tier,
+            self.plus.clone() + other.minus.clone(),
+            self.minus.clone() + other.plus.clone(),
+        )
+    }
+
+    fn less_than(&self, other: &Self) -> bool {
+        match self.tier.cmp(&other.tier) {
+            Ordering::Equal => {
+                self.plus.clone() + other.minus.clone() < other.plus.clone() + self.minus.clone()
+            }
+            ordering => ordering == Ordering::Less,
+        }
+    }
+}
+
+struct ArborescenceEdge<K> {
+    source: usize,
+    target: usize,
+    cost: Cost<K>,
+    /// The weight of the edge in the graph, or `None` for the edges of the virtual root.
+    weight: Option<K>,
+}
+
+const NIL: usize = usize::MAX;
+
+/// A node of a leftist heap of edges, whose pending `reduction` applies to the whole subtree.
+struct HeapNode<K> {
+    edge: usize,
+    cost: Cost<K>,
+    reduction: Cost<K>,
+    left: usize,
+    right: usize,
+    rank: usize,
+}
+
+/// Leftist heaps of edges ordered by cost, which can be merged and have their costs reduced
+/// lazily.
+struct Heaps<K> {
+    nodes: Vec<HeapNode<K>>,
+}
+
+impl<K> Heaps<K>
+where
+    K: Measure + Sub<K, Output = K>,
+{
+    fn rank(&self, h: usize) -> usize {
+        if h == NIL { 0 } else { self.nodes[h].rank }
+    }
+
+    fn push_down(&mut self, h: usize) {
+        let reduction = core::mem::replace(&mut self.nodes[h].reduction, Cost::zero());
+        if reduction.is_zero() {
+            return;
+        }
+        self.nodes[h].cost = self.nodes[h].cost.sub(&reduction);
+        for child in [self.nodes[h].left, self.nodes[h].right] {
+            if child != NIL {
+                self.nodes[child].reduction = self.nodes[child].reduction.add(&reduction);
+            }
+        }
+    }
+
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == NIL {
+            return b;
+        }
+        if b == NIL {
+            return a;
+        }
+        self.push_down(a);
+        self.push_down(b);
+        let (a, b) = if self.nodes[b].cost.less_than(&self.nodes[a].cost) {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        // The right spine of a leftist heap is short, which bounds the depth of the recursion.
+        let right = self.merge(self.nodes[a].right, b);
+        self.nodes[a].right = right;
+        if self.rank(self.nodes[a].left) < self.rank(right) {
+            let node = &mut self.nodes[a];
+            core::mem::swap(&mut node.left, &mut node.right);
+        }
+        self.nodes[a].rank = self.rank(self.nodes[a].right) + 1;
+        a
+    }
+}
+
+/// Run Tarjan's contraction on the vertices `0..vertex_count`, and return the indices of the
+/// edges of a minimum arborescence rooted at `root`, or `None` if some vertex is not reachable.
+fn contract<K>(
+    vertex_count: usize,
+    root: usize,
+    edges: &[ArborescenceEdge<K>],
+) -> Option<Vec<usize>>
+where
+    K: Measure + Sub<K, Output = K>,
+{
+    // The vertices and the contracted cycles are the nodes of a forest, with ids below
+    // `2 * vertex_count`.
+    let capacity = 2 * vertex_count;
+    let mut components = UnionFind::<usize>::new(capacity);
+    let mut name = (0..capacity).collect::<Vec<_>>();
+    let mut heaps = Heaps {
+        nodes: Vec::with_capacity(edges.len()),
+    };
+    let mut heap = vec![NIL; capacity];
+    for (e, edge) in edges.iter().enumerate() {
+        heaps.nodes.push(HeapNode {
+            edge: e,
+            cost: edge.cost.clone(),
+            reduction: Cost::zero(),
+            left: NIL,
+            right: NIL,
+            rank: 1,
+        });
+        heap[edge.target] = heaps.merge(heap[edge.target], e);
+    }
+
+    let mut in_edge = vec![NIL; capacity];
+    let mut parent = vec![NIL; capacity];
+    let mut children = vec![Vec::new(); capacity];
+    let mut next_id = vertex_count;
+    // The vertex whose search reached a node, which is part of the arborescence once that search
+    // is done.
+    let mut seen = vec![NIL; capacity];
+    seen[root] = root;
+    let mut path = Vec::new();
+
+    for start in 0..vertex_count {
+        let mut u = name[components.find_mut(start)];
+        path.clear();
+        while seen[u] == NIL {
+            seen[u] = start;
+            // Pick the lightest edge from outside of `u`.
+            let (e, cost) = loop {
+                let top = heap[u];
+                if top == NIL {
+                    return None;
+                }
+                heaps.push_down(top);
+                let e = heaps.nodes[top].edge;
+                let cost = heaps.nodes[top].cost.clone();
+                heap[u] = heaps.merge(heaps.nodes[top].left, heaps.nodes[top].right);
+                if name[components.find_mut(edges[e].source)] != u {
+                    break (e, cost);
+                }
+            };
+            // The other edges into `u` now cost the difference to `e`.
+            if heap[u] != NIL {
+                let h = heap[u];
+                heaps.nodes[h].reduction = heaps.nodes[h].reduction.add(&cost);
+            }
+            in_edge[u] = e;
+            path.push(u);
+
+            let v = name[components.find_mut(edges[e].source)];
+            if seen[v] == start {
+                // Contract the cycle through `v` into a new node.
+                let cycle = next_id;
+                next_id += 1;
+                let mut merged = NIL;
+                loop {
+                    let w = path.pop().unwrap();
+                    parent[w] = cycle;
+                    children[cycle].push(w);
+                    merged = heaps.merge(merged, heap[w]);
+                    components.union(w, cycle);
+                    if w == v {
+                        break;
+                    }
+                }
+                name[components.find_mut(cycle)] = cycle;
+                heap[cycle] = merged;
+                u = cycle;
+            } else {
+                u = v;
+            }
+        }
+    }
+
+    // Expand the cycles: the edge into a cycle replaces the picked edge into the node of the cycle
+    // that it enters, and the other nodes of the cycle keep their edges.
+    let mut chosen = Vec::with_capacity(vertex_count);
+    let mut deleted = vec![false; next_id];
+    let mut queue = (0..next_id)
+        .filter(|&x| parent[x] == NIL && x != root)
+        .collect::<Vec<_>>();
+    while let Some(top) = queue.pop() {
+        let e = in_edge[top];
+        chosen.push(e);
+        let mut x = edges[e].target;
+        loop {
+            deleted[x] = true;
+            for &child in &children[x] {
+                if !deleted[child] {
+                    queue.push(child);
+                }
+            }
+            if x == top {
+                break;
+            }
+            x = parent[x];
+        }
+    }
+    Some(chosen)
+}
@@ -1,10 +1,11 @@
 //! Minimum Spanning Tree algorithms.
 
-use alloc::collections::BinaryHeap;
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
 
 use hashbrown::{HashMap, HashSet};
 
 use crate::{
+    algo::node_positions,
     data::Element,
     prelude::*,
     scored::MinScored,
@@ -373,3 +374,262 @@ where
         None
     }
 }
+
+/// Compute a *minimum spanning tree* of a graph using Borůvka's algorithm.
+///
+/// The input graph is treated as if undirected. Like [`min_spanning_tree`], this returns a
+/// minimum spanning forest, i.e. a minimum spanning tree for each connected component of the
+/// graph.
+///
+/// [Borůvka's algorithm][1] works in rounds: every tree of the forest picks its lightest edge to
+/// another tree, and the trees are merged along these edges, which at least halves the number of
+/// trees. Edges of equal weight are ordered by their position in the graph, so that the picked
+/// edges never make a cycle. Every round is a scan over the edges, which makes the algorithm easy
+/// to parallelize: see `parallel_min_spanning_tree_boruvka` under the `rayon` feature.
+///
+/// The resulting graph has all the vertices of the input graph (with identical node indices),
+/// and **|V| - c** edges, where **c** is the number of connected components in `g`.
+///
+/// # Arguments
+/// * `g`: an undirected graph.
+///
+/// # Returns
+/// * [`MinSpanningTreeBoruvka`]: an iterator producing a minimum spanning forest of a graph. Use
+///   `from_elements` to create a graph from the resulting iterator.
+///
+/// # Complexity
+/// * Time complexity: **O(|E| log |V|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Bor%C5%AFvka%27s_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::min_spanning_tree_boruvka, data::FromElements, graph::UnGraph};
+///
+/// let g = UnGraph::<(), f64>::from_edges([
+///     (0, 1, 2.0),
+///     (0, 3, 4.0),
+///     (1, 2, 1.0),
+///     (1, 5, 7.0),
+///     (2, 4, 5.0),
+///     (4, 5, 1.0),
+///     (3, 4, 1.0),
+/// ]);
+///
+/// let mst = UnGraph::<_, _>::from_elements(min_spanning_tree_boruvka(&g));
+/// assert_eq!(g.node_count(), mst.node_count());
+/// assert_eq!(mst.node_count() - 1, mst.edge_count());
+/// assert_eq!(mst.edge_weights().sum::<f64>(), 9.0);
+/// ```
+pub fn min_spanning_tree_boruvka<G>(g: G) -> MinSpanningTreeBoruvka<G>
+where
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Clone + PartialOrd,
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable,
+{
+    let (mut elements, mut edges) = boruvka_input(g);
+    let mut subgraphs = UnionFind::<usize>::new(elements.len());
+    let mut cheapest = vec![usize::MAX; elements.len()];
+
+    while !edges.is_empty() {
+        // Find the lightest edge leaving every tree.
+        for (e, &(a, b, ref weight)) in edges.iter().enumerate() {
+            for tree in [subgraphs.find_mut(a), subgraphs.find_mut(b)] {
+                let current = cheapest[tree];
+                if current == usize::MAX || lighter(weight, e, &edges[current].2, current) {
+                    cheapest[tree] = e;
+                }
+            }
+        }
+        for tree in 0..cheapest.len() {
+            let e = core::mem::replace(&mut cheapest[tree], usize::MAX);
+            if e != usize::MAX {
+                let (a, b, ref weight) = edges[e];
+                if subgraphs.union(a, b) {
+                    elements.push(Element::Edge {
+                        source: a,
+                        target: b,
+                        weight: weight.clone(),
+                    });
+                }
+            }
+        }
+        // Drop the edges inside of trees.
+        edges.retain(|&(a, b, _)| !subgraphs.equiv(a, b));
+    }
+
+    MinSpanningTreeBoruvka {
+        elements: elements.into_iter(),
+    }
+}
+
+/// Compute a *minimum spanning tree* of a graph using a parallel version of Borůvka's algorithm.
+///
+/// The rounds of [`min_spanning_tree_boruvka`] scan the edges in parallel with `rayon`, which
+/// returns the same minimum spanning forest.
+///
+/// # Arguments
+/// * `g`: an undirected graph.
+///
+/// # Returns
+/// * [`MinSpanningTreeBoruvka`]: an iterator producing a minimum spanning forest of a graph. Use
+///   `from_elements` to create a graph from the resulting iterator.
+///
+/// # Complexity
+/// * Time complexity: **O(|E| log |V|)** work.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::parallel_min_spanning_tree_boruvka, data::FromElements, graph::UnGraph};
+///
+/// let g = UnGraph::<(), u32>::from_edges([(0, 1, 3), (1, 2, 1), (2, 0, 2), (3, 4, 1)]);
+/// let msf = UnGraph::<_, _>::from_elements(parallel_min_spanning_tree_boruvka(&g));
+/// assert_eq!(msf.edge_count(), 3);
+/// assert_eq!(msf.edge_weights().sum::<u32>(), 4);
+/// ```
+#[cfg(feature = "rayon")]
+pub fn parallel_min_spanning_tree_boruvka<G>(g: G) -> MinSpanningTreeBoruvka<G>
+where
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Clone + PartialOrd + Send + Sync,
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable,
+{
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use rayon::prelude::*;
+
+    let (mut elements, mut edges) = boruvka_input(g);
+    let node_count = elements.len();
+    let mut subgraphs = UnionFind::<usize>::new(node_count);
+    let cheapest = (0..node_count)
+        .map(|_| AtomicUsize::new(usize::MAX))
+        .collect::<Vec<_>>();
+
+    while !edges.is_empty() {
+        let trees = (0..node_count)
+            .into_par_iter()
+            .map(|v| subgraphs.find(v))
+            .collect::<Vec<_>>();
+        // Find the lightest edge leaving every tree.
+        edges
+            .par_iter()
+            .enumerate()
+            .for_each(|(e, (a, b, weight))| {
+                for tree in [trees[*a], trees[*b]] {
+                    let mut current = cheapest[tree].load(Ordering::Relaxed);
+                    while current == usize::MAX || lighter(weight, e, &edges[current].2, current) {
+                        match cheapest[tree].compare_exchange_weak(
+                            current,
+                            e,
+                            Ordering::Relaxed,
+                            Ordering::Relaxed,
+                        ) {
+                            Ok(_) => break,
+                            Err(actual) => current = actual,
+                        }
+                    }
+                }
+            });
+        for tree in &cheapest {
+            let e = tree.swap(usize::MAX, Ordering::Relaxed);
+            if e != usize::MAX {
+                let (a, b, ref weight) = edges[e];
+                if subgraphs.union(a, b) {
+                    elements.push(Element::Edge {
+                        source: a,
+                        target: b,
+                        weight: weight.clone(),
+                    });
+                }
+            }
+        }
+        // Drop the edges inside of trees.
+        edges = edges
+            .into_par_iter()
+            .filter(|&(a, b, _)| !subgraphs.equiv(a, b))
+            .collect();
+    }
+
+    MinSpanningTreeBoruvka {
+        elements: elements.into_iter(),
+    }
+}
+
+/// Collect the Node elements of `g`, and its edges between the positions of their endpoints,
+/// without self-loops.
+#[allow(clippy::type_complexity)]
+fn boruvka_input<G>(
+    g: G,
+) -> (
+    Vec<Element<G::NodeWeight, G::EdgeWeight>>,
+    Vec<(usize, usize, G::EdgeWeight)>,
+)
+where
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Clone,
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable,
+{
+    let (nodes, positions) = node_positions(g);
+    let mut weights = vec![None; nodes.len()];
+    for node in g.node_references() {
+        weights[positions[g.to_index(node.id())]] = Some(node.weight().clone());
+    }
+    let elements = weights
+        .into_iter()
+        .map(|weight| Element::Node {
+            weight: weight.unwrap(),
+        })
+        .collect();
+    let edges = g
+        .edge_references()
+        .map(|edge| {
+            (
+                positions[g.to_index(edge.source())],
+                positions[g.to_index(edge.target())],
+                edge.weight().clone(),
+            )
+        })
+        .filter(|&(a, b, _)| a != b)
+        .collect();
+    (elements, edges)
+}
+
+/// Order edges by weight, then by position, so that no two edges are equally light.
+fn lighter<K: PartialOrd>(weight: &K, e: usize, other_weight: &K, other: usize) -> bool {
+    weight < other_weight || (!(other_weight < weight) && e < other)
+}
+
+/// An iterator producing a minimum spanning forest of a graph, computed with Borůvka's
+/// algorithm.
+/// It will first iterate all Node elements from original graph,
+/// then iterate Edge elements from computed minimum spanning forest.
+///
+/// See [`min_spanning_tree_boruvka`].
+#[derive(Debug, Clone)]
+pub struct MinSpanningTreeBoruvka<G>
+where
+    G: Data,
+{
+    elements: vec::IntoIter<Element<G::NodeWeight, G::EdgeWeight>>,
+}
+
+impl<G> Iterator for MinSpanningTreeBoruvka<G>
+where
+    G: Data,
+{
+    type Item = Element<G::NodeWeight, G::EdgeWeight>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.elements.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.elements.size_hint()
+    }
+}
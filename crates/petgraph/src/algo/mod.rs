@@ -13,61 +13,141 @@ the `Graph` type.
 
 pub mod articulation_points;
 pub mod astar;
+pub mod automorphisms;
 pub mod bellman_ford;
 pub mod bridges;
+pub mod canonical;
+pub mod centrality;
+pub mod chordal;
 pub mod coloring;
+pub mod community;
+pub mod cycles;
 pub mod dijkstra;
 pub mod dominators;
+pub mod eulerian;
 pub mod feedback_arc_set;
+pub mod feedback_vertex_set;
 pub mod floyd_warshall;
 pub mod ford_fulkerson;
 pub mod isomorphism;
 pub mod johnson;
 pub mod k_shortest_path;
+pub mod loop_nesting;
 pub mod matching;
 pub mod maximal_cliques;
+pub mod maximum_clique;
 pub mod maximum_flow;
+pub mod min_spanning_arborescence;
 pub mod min_spanning_tree;
 pub mod page_rank;
+pub mod planarity;
 pub mod scc;
 pub mod simple_paths;
+pub mod spectral_centrality;
 pub mod spfa;
-#[cfg(feature = "stable_graph")]
 pub mod steiner_tree;
+pub mod topological_sort;
 pub mod tred;
+pub mod tree_decomposition;
+pub mod triangles;
+pub mod tsp;
+pub mod weisfeiler_lehman;
 
 use alloc::{vec, vec::Vec};
 
 pub use astar::astar;
+pub use automorphisms::{Automorphisms, automorphisms, automorphisms_matching};
 pub use bellman_ford::{bellman_ford, find_negative_cycle};
 pub use bridges::bridges;
-pub use coloring::dsatur_coloring;
+pub use canonical::{CanonicalForm, canonical_form, canonical_labeling};
+pub use centrality::{
+    betweenness_centrality, closeness_centrality, edge_betweenness_centrality, harmonic_centrality,
+};
+#[cfg(feature = "rayon")]
+pub use centrality::{
+    parallel_betweenness_centrality, parallel_closeness_centrality,
+    parallel_edge_betweenness_centrality, parallel_harmonic_centrality,
+};
+pub use chordal::{is_chordal, minimal_triangulation, perfect_elimination_ordering};
+pub use coloring::{
+    GreedyOrdering, dsatur_coloring, exact_coloring, greedy_coloring, is_proper_coloring,
+    is_proper_edge_coloring, misra_gries_edge_coloring,
+};
+pub use community::{
+    label_propagation_communities, leiden_communities, louvain_communities, modularity,
+};
+pub use cycles::{
+    CycleWitness, ElementaryCircuits, elementary_circuits, find_cycle_directed,
+    find_cycle_undirected, fundamental_cycle_basis, minimum_cycle_basis, toposort_or_cycle,
+};
 pub use dijkstra::{bidirectional_dijkstra, dijkstra};
-pub use feedback_arc_set::greedy_feedback_arc_set;
+pub use eulerian::{
+    chinese_postman, eulerian_circuit, eulerian_path, has_eulerian_circuit, has_eulerian_path,
+};
+pub use feedback_arc_set::{
+    greedy_feedback_arc_set, min_feedback_arc_set, weighted_feedback_arc_set,
+};
+pub use feedback_vertex_set::{greedy_feedback_vertex_set, weighted_feedback_vertex_set};
 pub use floyd_warshall::floyd_warshall;
 pub use isomorphism::{
-    is_isomorphic, is_isomorphic_matching, is_isomorphic_subgraph, is_isomorphic_subgraph_matching,
-    subgraph_isomorphisms_iter,
+    SubgraphMatchOptions, SubgraphMode, is_isomorphic, is_isomorphic_matching,
+    is_isomorphic_subgraph, is_isomorphic_subgraph_matching, subgraph_isomorphisms_iter,
+    subgraph_matches_iter,
 };
 pub use johnson::johnson;
 #[cfg(feature = "rayon")]
 pub use johnson::parallel_johnson;
 pub use k_shortest_path::k_shortest_path;
-pub use matching::{Matching, greedy_matching, maximum_matching};
-pub use maximal_cliques::maximal_cliques;
+pub use matching::{
+    Matching, greedy_matching, maximum_matching, maximum_weight_matching,
+    minimum_weight_perfect_matching,
+};
+pub use maximal_cliques::{MaximalCliques, maximal_cliques, maximal_cliques_iter};
+pub use maximum_clique::{
+    maximum_clique, maximum_independent_set, maximum_weight_clique, minimum_vertex_cover,
+};
 pub use maximum_flow::{dinics, ford_fulkerson};
-pub use min_spanning_tree::{min_spanning_tree, min_spanning_tree_prim};
-pub use page_rank::page_rank;
+pub use min_spanning_arborescence::{
+    MinSpanningArborescence, min_branching, min_spanning_arborescence,
+};
+#[cfg(feature = "rayon")]
+pub use min_spanning_tree::parallel_min_spanning_tree_boruvka;
+pub use min_spanning_tree::{
+    MinSpanningTreeBoruvka, min_spanning_tree, min_spanning_tree_boruvka, min_spanning_tree_prim,
+};
+pub use page_rank::{DanglingNodes, PageRank, PageRankResult, page_rank};
+pub use planarity::{
+    KuratowskiGraph, KuratowskiSubgraph, PlanarEmbedding, is_planar, planar_embedding,
+};
 #[allow(deprecated)]
 pub use scc::scc;
 pub use scc::{
+    condensation::{Condensation, scc_condensation},
     kosaraju_scc::kosaraju_scc,
     tarjan_scc::{TarjanScc, tarjan_scc},
 };
 pub use simple_paths::{all_simple_paths, all_simple_paths_multi};
+pub use spectral_centrality::{eigenvector_centrality, hits, katz_centrality};
 pub use spfa::spfa;
-#[cfg(feature = "stable_graph")]
-pub use steiner_tree::steiner_tree;
+pub use steiner_tree::{steiner_tree, steiner_tree_dreyfus_wagner, steiner_tree_mehlhorn};
+pub use topological_sort::{
+    AllToposorts, all_toposorts, topological_generations, toposort_by, toposort_by_key,
+};
+pub use tree_decomposition::{
+    elimination_tree_decomposition, is_tree_decomposition, treewidth_exact, treewidth_min_degree,
+    treewidth_min_fill_in,
+};
+pub use triangles::{
+    average_clustering, clustering_coefficients, node_triangle_counts, transitivity,
+    triangle_count, triangles,
+};
+#[cfg(feature = "rayon")]
+pub use triangles::{
+    parallel_average_clustering, parallel_clustering_coefficients, parallel_node_triangle_counts,
+    parallel_transitivity, parallel_triangle_count, parallel_triangles,
+};
+pub use tsp::{christofides, held_karp, nearest_neighbor, or_opt, two_opt};
+pub use weisfeiler_lehman::{weisfeiler_lehman_graph_hash, weisfeiler_lehman_subtree_hashes};
 
 use super::{
     EdgeType,
@@ -152,6 +232,8 @@ where
 ///
 /// Always treats the input graph as if undirected.
 ///
+/// Use [`find_cycle_undirected`] to get the nodes and edges of a cycle.
+///
 /// # Arguments:
 /// `g`: an input graph that always treated as undirected.
 ///
@@ -190,6 +272,8 @@ where
 ///
 /// The implementation is iterative.
 ///
+/// Use [`toposort_or_cycle`] to get the nodes and edges of a cycle instead of a single node.
+///
 /// # Arguments
 /// * `g`: an acyclic directed graph.
 /// * `space`: optional [`DfsSpace`]. If `space` is not `None`, it is used instead of creating a new
@@ -266,6 +350,8 @@ where
 ///
 /// This implementation is recursive; use [`toposort`] if an alternative is needed.
 ///
+/// Use [`find_cycle_directed`] to get the nodes and edges of a cycle.
+///
 /// # Arguments:
 /// `g`: a directed graph.
 ///
@@ -608,6 +694,43 @@ where
     true
 }
 
+/// Number the nodes of `graph` by their order in [`IntoNodeIdentifiers::node_identifiers`].
+/// Returns the nodes, and the position of every node indexed by [`NodeIndexable::to_index`].
+pub(crate) fn node_positions<G>(graph: G) -> (Vec<G::NodeId>, Vec<usize>)
+where
+    G: IntoNodeIdentifiers + NodeIndexable,
+{
+    let nodes = graph.node_identifiers().collect::<Vec<_>>();
+    let mut positions = vec![usize::MAX; graph.node_bound()];
+    for (i, &v) in nodes.iter().enumerate() {
+        positions[graph.to_index(v)] = i;
+    }
+    (nodes, positions)
+}
+
+/// Collect the nodes of `graph` with the sorted neighbors of every node, by position, without
+/// self-loops and parallel edges. The graph is treated as undirected.
+pub(crate) fn simple_adjacency<G>(graph: G) -> (Vec<G::NodeId>, Vec<Vec<usize>>)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+{
+    let (nodes, positions) = node_positions(graph);
+    let mut adjacency = vec![Vec::new(); nodes.len()];
+    for edge in graph.edge_references() {
+        let a = positions[graph.to_index(edge.source())];
+        let b = positions[graph.to_index(edge.target())];
+        if a != b {
+            adjacency[a].push(b);
+            adjacency[b].push(a);
+        }
+    }
+    for neighbors in &mut adjacency {
+        neighbors.sort_unstable();
+        neighbors.dedup();
+    }
+    (nodes, adjacency)
+}
+
 use core::{fmt::Debug, ops::Add};
 
 /// Associated data that can be used for measures (such as length).
@@ -4,7 +4,10 @@ use alloc::{vec, vec::Vec};
 use rayon::prelude::*;
 
 use super::UnitMeasure;
-use crate::visit::{EdgeRef, IntoEdges, NodeCount, NodeIndexable};
+use crate::visit::{
+    EdgeRef, GraphProp, IntoEdgeReferences, IntoEdges, IntoNodeIdentifiers, NodeCount,
+    NodeIndexable,
+};
 
 /// Page Rank algorithm.
 ///
@@ -29,6 +32,8 @@ use crate::visit::{EdgeRef, IntoEdges, NodeCount, NodeIndexable};
 /// where **n** is the number of iterations, **|V|** the number of vertices (i.e nodes) and **|E|**
 /// the number of edges.
 ///
+/// See [`PageRank`] for edge weights, personalization and a stopping tolerance.
+///
 /// [pr]: https://en.wikipedia.org/wiki/PageRank
 ///
 /// # Example
@@ -130,7 +135,7 @@ where
 }
 /// Parallel Page Rank algorithm.
 ///
-/// See [`page_rank`].
+/// See [`page_rank`], and [`PageRank::parallel_run`] for the configurable version.
 #[cfg(feature = "rayon")]
 pub fn parallel_page_rank<G, D>(
     graph: G,
@@ -194,3 +199,349 @@ where
     }
     ranks
 }
+
+/// Where the random surfer of [`PageRank`] goes from a dangling node, that is, a node whose
+/// out-edges have a total weight of zero.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DanglingNodes<D> {
+    /// Jump according to the teleport distribution, as from any other node. This is the default.
+    Teleport,
+    /// Jump to any node with the same probability.
+    Uniform,
+    /// Jump according to the given distribution, indexed by [`NodeIndexable::to_index`]. It is
+    /// scaled to sum to one.
+    Distribution(Vec<D>),
+    /// Stay at the dangling node, as if it had an edge to itself.
+    SelfLoop,
+}
+
+/// The ranks computed by [`PageRank`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageRankResult<D> {
+    /// The rank of every node, indexed by [`NodeIndexable::to_index`], which sum to one.
+    pub ranks: Vec<D>,
+    /// The number of iterations that were run.
+    pub iterations: usize,
+    /// Whether the ranks converged within the tolerance before the maximum number of iterations.
+    pub converged: bool,
+}
+
+/// A configurable [Page Rank][pr] computation.
+///
+/// The rank of a node is the probability that a random surfer is at that node. At every step, the
+/// surfer follows an out-edge of its node with probability `damping_factor`, with a probability
+/// proportional to the weight of the edge, and teleports to a random node otherwise. In an
+/// undirected graph, every edge can be followed both ways.
+///
+/// The options are:
+/// * [`damping_factor`](Self::damping_factor): the probability of following an edge, `0.85` by
+///   default.
+/// * [`max_iter`](Self::max_iter): the maximum number of iterations, `100` by default.
+/// * [`tolerance`](Self::tolerance): the iteration stops when the [L1 distance][l1] between two
+///   consecutive rank vectors is at most this, [`UnitMeasure::default_tol`] by default.
+/// * [`personalization`](Self::personalization): the teleport distribution, uniform over all nodes
+///   by default.
+/// * [`dangling_nodes`](Self::dangling_nodes): where to go from nodes without out-edges, see
+///   [`DanglingNodes`].
+///
+/// [`run`](Self::run) computes the ranks, and `parallel_run` does so in parallel with the `rayon`
+/// feature. Both take a closure that returns the weight of an edge, which must be non-negative;
+/// use `|_| 1.` for unweighted graphs.
+///
+/// # Complexity
+/// * Time complexity: **O(n(|V| + |E|))**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **n** is the number of iterations, **|V|** is the number of nodes and **|E|** is the
+/// number of edges.
+///
+/// [pr]: https://en.wikipedia.org/wiki/PageRank
+/// [l1]: https://en.wikipedia.org/wiki/Taxicab_geometry
+///
+/// # Example
+/// ```rust
+/// use petgraph::{
+///     algo::page_rank::{DanglingNodes, PageRank},
+///     prelude::*,
+/// };
+///
+/// let mut g = DiGraph::<(), f64>::new();
+/// let [a, b, c] = [(); 3].map(|_| g.add_node(()));
+/// g.extend_with_edges(&[(a, b, 1.), (a, c, 3.), (b, c, 1.), (c, a, 1.)]);
+///
+/// let result = PageRank::new().tolerance(1e-10).run(&g, |e| *e.weight());
+/// assert!(result.converged);
+/// assert!((result.ranks.iter().sum::<f64>() - 1.).abs() < 1e-9);
+/// assert!(result.ranks[c.index()] > result.ranks[b.index()]);
+///
+/// // Teleport to `b` only, and stay at dangling nodes.
+/// let result = PageRank::new()
+///     .damping_factor(0.5)
+///     .personalization(vec![0., 1., 0.])
+///     .dangling_nodes(DanglingNodes::SelfLoop)
+///     .run(&g, |e| *e.weight());
+/// assert!(result.ranks[b.index()] > result.ranks[a.index()]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PageRank<D> {
+    damping_factor: D,
+    max_iter: usize,
+    tolerance: D,
+    personalization: Option<Vec<D>>,
+    dangling_nodes: DanglingNodes<D>,
+}
+
+impl<D> Default for PageRank<D>
+where
+    D: UnitMeasure + Copy,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D> PageRank<D>
+where
+    D: UnitMeasure + Copy,
+{
+    /// Create a Page Rank computation with the default options.
+    pub fn new() -> Self {
+        PageRank {
+            damping_factor: D::from_f32(0.85),
+            max_iter: 100,
+            tolerance: D::default_tol(),
+            personalization: None,
+            dangling_nodes: DanglingNodes::Teleport,
+        }
+    }
+
+    /// Set the probability of following an edge rather than teleporting.
+    ///
+    /// # Panics
+    /// Panics if `damping_factor` is not between 0 and 1 (both included).
+    #[track_caller]
+    pub fn damping_factor(mut self, damping_factor: D) -> Self {
+        assert!(
+            D::zero() <= damping_factor && damping_factor <= D::one(),
+            "Damping factor should be between 0 and 1."
+        );
+        self.damping_factor = damping_factor;
+        self
+    }
+
+    /// Set the maximum number of iterations.
+    pub fn max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Set the L1 distance between two consecutive rank vectors at which to stop.
+    pub fn tolerance(mut self, tolerance: D) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set the teleport distribution, indexed by [`NodeIndexable::to_index`]. It is scaled to
+    /// sum to one when the ranks are computed.
+    pub fn personalization(mut self, personalization: Vec<D>) -> Self {
+        self.personalization = Some(personalization);
+        self
+    }
+
+    /// Set where to go from dangling nodes.
+    pub fn dangling_nodes(mut self, dangling_nodes: DanglingNodes<D>) -> Self {
+        self.dangling_nodes = dangling_nodes;
+        self
+    }
+
+    /// Compute the ranks of every node of `graph`.
+    ///
+    /// # Panics
+    /// Panics if the personalization or dangling distribution does not have a non-negative
+    /// entry for every index up to [`NodeIndexable::node_bound`], or sums to zero.
+    #[track_caller]
+    pub fn run<G, F>(&self, graph: G, edge_weight: F) -> PageRankResult<D>
+    where
+        G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+        F: FnMut(G::EdgeRef) -> D,
+    {
+        let transitions = self.transitions(graph, edge_weight);
+        self.iterate(graph, &transitions, |ranks, dangling_mass| {
+            (0..ranks.len())
+                .map(|v| transitions.rank(v, ranks, dangling_mass, self.damping_factor))
+                .collect()
+        })
+    }
+
+    /// Compute the ranks of every node of `graph`, with the ranks of different nodes computed
+    /// in parallel.
+    ///
+    /// See [`run`](Self::run).
+    #[cfg(feature = "rayon")]
+    #[track_caller]
+    pub fn parallel_run<G, F>(&self, graph: G, edge_weight: F) -> PageRankResult<D>
+    where
+        G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+        F: FnMut(G::EdgeRef) -> D,
+        D: Send + Sync,
+    {
+        let transitions = self.transitions(graph, edge_weight);
+        self.iterate(graph, &transitions, |ranks, dangling_mass| {
+            (0..ranks.len())
+                .into_par_iter()
+                .map(|v| transitions.rank(v, ranks, dangling_mass, self.damping_factor))
+                .collect()
+        })
+    }
+
+    /// Run the power iteration from uniform ranks, with `step` computing the next ranks from the
+    /// current ones and the total rank of the dangling nodes of `transitions`.
+    fn iterate<G>(
+        &self,
+        graph: G,
+        transitions: &Transitions<D>,
+        mut step: impl FnMut(&[D], D) -> Vec<D>,
+    ) -> PageRankResult<D>
+    where
+        G: IntoNodeIdentifiers + NodeIndexable,
+    {
+        let mut ranks = uniform(graph);
+        let mut iterations = 0;
+        let mut converged = false;
+        while iterations < self.max_iter && !converged {
+            let dangling_mass = transitions.dangling.iter().map(|&v| ranks[v]).sum::<D>();
+            let next = step(&ranks, dangling_mass);
+            let distance = next
+                .iter()
+                .zip(&ranks)
+                .map(|(&x, &y)| if x < y { y - x } else { x - y })
+                .sum::<D>();
+            converged = distance <= self.tolerance;
+            ranks = next;
+            iterations += 1;
+        }
+        PageRankResult {
+            ranks,
+            iterations,
+            converged,
+        }
+    }
+
+    /// Collect the transition probabilities of `graph` and the jump distributions.
+    #[track_caller]
+    fn transitions<G, F>(&self, graph: G, mut edge_weight: F) -> Transitions<D>
+    where
+        G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+        F: FnMut(G::EdgeRef) -> D,
+    {
+        let node_bound = graph.node_bound();
+        let mut edges = Vec::new();
+        let mut out_weights = vec![D::zero(); node_bound];
+        for edge in graph.edge_references() {
+            let (a, b) = (graph.to_index(edge.source()), graph.to_index(edge.target()));
+            let weight = edge_weight(edge);
+            edges.push((a, b, weight));
+            out_weights[a] = out_weights[a] + weight;
+            if !graph.is_directed() && a != b {
+                edges.push((b, a, weight));
+                out_weights[b] = out_weights[b] + weight;
+            }
+        }
+        let mut incoming = vec![Vec::new(); node_bound];
+        for (a, b, weight) in edges {
+            if weight > D::zero() {
+                incoming[b].push((a, weight / out_weights[a]));
+            }
+        }
+        let mut dangling = Vec::new();
+        for node in graph.node_identifiers() {
+            let v = graph.to_index(node);
+            if out_weights[v] <= D::zero() {
+                dangling.push(v);
+            }
+        }
+
+        let teleport = match &self.personalization {
+            Some(personalization) => distribution(personalization, node_bound),
+            None => uniform(graph),
+        };
+        let dangling_jump = match &self.dangling_nodes {
+            DanglingNodes::Teleport => None,
+            DanglingNodes::Uniform => Some(uniform(graph)),
+            DanglingNodes::Distribution(jump) => Some(distribution(jump, node_bound)),
+            DanglingNodes::SelfLoop => {
+                for v in dangling.drain(..) {
+                    incoming[v].push((v, D::one()));
+                }
+                None
+            }
+        };
+        Transitions {
+            incoming,
+            dangling,
+            teleport,
+            dangling_jump,
+        }
+    }
+}
+
+/// The random surfer's moves in a [`PageRank`] computation.
+struct Transitions<D> {
+    /// For every node, the nodes with an edge to it and the probability of following that edge.
+    incoming: Vec<Vec<(usize, D)>>,
+    /// The dangling nodes, whose rank is spread by `dangling_jump`.
+    dangling: Vec<usize>,
+    teleport: Vec<D>,
+    /// The jump distribution from dangling nodes, or `None` for `teleport`.
+    dangling_jump: Option<Vec<D>>,
+}
+
+impl<D> Transitions<D>
+where
+    D: UnitMeasure + Copy,
+{
+    /// The next rank of the node `v`.
+    fn rank(&self, v: usize, ranks: &[D], dangling_mass: D, damping_factor: D) -> D {
+        let followed = self.incoming[v]
+            .iter()
+            .map(|&(u, probability)| ranks[u] * probability)
+            .sum::<D>();
+        let dangling_jump = self.dangling_jump.as_ref().unwrap_or(&self.teleport)[v];
+        damping_factor * (followed + dangling_mass * dangling_jump)
+            + (D::one() - damping_factor) * self.teleport[v]
+    }
+}
+
+/// The same probability for every node of `graph`.
+fn uniform<G, D>(graph: G) -> Vec<D>
+where
+    G: IntoNodeIdentifiers + NodeIndexable,
+    D: UnitMeasure + Copy,
+{
+    let mut distribution = vec![D::zero(); graph.node_bound()];
+    let share = D::one() / D::from_usize(graph.node_identifiers().count());
+    for node in graph.node_identifiers() {
+        distribution[graph.to_index(node)] = share;
+    }
+    distribution
+}
+
+/// `weights` scaled to sum to one.
+#[track_caller]
+fn distribution<D>(weights: &[D], node_bound: usize) -> Vec<D>
+where
+    D: UnitMeasure + Copy,
+{
+    assert_eq!(
+        weights.len(),
+        node_bound,
+        "The distribution should have an entry for every node index."
+    );
+    assert!(
+        weights.iter().all(|&w| w >= D::zero()),
+        "The distribution should be non-negative."
+    );
+    let sum = weights.iter().copied().sum::<D>();
+    assert!(sum > D::zero(), "The distribution should not sum to zero.");
+    weights.iter().map(|&w| w / sum).collect()
+}
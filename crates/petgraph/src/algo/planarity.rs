@@ -0,0 +1,994 @@
+//! Planarity testing and planar embeddings.
+//!
+//! A graph is [planar][1] if it can be drawn in the plane without crossing edges. The
+//! [left-right planarity test][2] decides this in linear time:
+//!
+//! * [`is_planar`] only tests whether a graph is planar.
+//! * [`planar_embedding`] returns a [`PlanarEmbedding`] of a planar graph, the clockwise order of
+//!   the edges around every node in a crossing-free drawing, from which the faces of the drawing
+//!   follow. If the graph is not planar, it returns a [`KuratowskiSubgraph`] instead: a subdivision
+//!   of *K₅* or *K₃,₃* contained in the graph, which proves that it is not planar.
+//!
+//! All functions treat the graph as undirected. Parallel edges and self-loops do not change
+//! whether a graph is planar, but are part of the embedding.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Planar_graph
+//! [2]: https://doi.org/10.48550/arXiv.0911.1865
+
+use alloc::{vec, vec::Vec};
+use core::{hash::Hash, mem};
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::{
+    algo::node_positions,
+    visit::{EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable},
+};
+
+/// A crossing-free drawing of a planar graph, given by the order of the edges around every node.
+///
+/// Such a [rotation system][1] determines the faces of the drawing, the regions that are bounded
+/// by the edges. See [`planar_embedding`].
+///
+/// [1]: https://en.wikipedia.org/wiki/Rotation_system
+#[derive(Clone, Debug)]
+pub struct PlanarEmbedding<N, E> {
+    nodes: Vec<N>,
+    positions: HashMap<N, usize>,
+    /// The other endpoint and the id of the edges around every node, in clockwise order.
+    rotations: Vec<Vec<(N, E)>>,
+    /// The node and slot in its rotation of the other half of every edge in the rotations.
+    twins: Vec<Vec<(usize, usize)>>,
+}
+
+impl<N, E> PlanarEmbedding<N, E>
+where
+    N: Copy + Eq + Hash,
+    E: Copy,
+{
+    /// Return the nodes of the graph.
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// Return the edges around `node` in clockwise order, with their other endpoints, or `None`
+    /// if `node` is not part of the graph.
+    ///
+    /// Parallel edges are next to each other, and a self-loop appears twice in a row.
+    pub fn rotation(&self, node: N) -> Option<&[(N, E)]> {
+        let &position = self.positions.get(&node)?;
+        Some(&self.rotations[position])
+    }
+
+    /// Return the faces of the embedding.
+    ///
+    /// Every face is given by the closed walk around its boundary, as the nodes on the walk with
+    /// the edges that leave them, so that every edge is walked along twice in total: once in each
+    /// direction, or twice in the same face if it is a bridge.
+    ///
+    /// A connected graph with **|V|** nodes and **|E|** edges has **|E| - |V| + 2** faces,
+    /// including the outer one, unless it has no edges at all. Every connected component of a
+    /// graph is embedded on its own, with its own outer face.
+    pub fn faces(&self) -> Vec<Vec<(N, E)>> {
+        let mut visited = self
+            .rotations
+            .iter()
+            .map(|rotation| vec![false; rotation.len()])
+            .collect::<Vec<_>>();
+        let mut faces = Vec::new();
+        for start in 0..self.rotations.len() {
+            for slot in 0..self.rotations[start].len() {
+                if visited[start][slot] {
+                    continue;
+                }
+                let mut face = Vec::new();
+                let (mut node, mut slot) = (start, slot);
+                while !visited[node][slot] {
+                    visited[node][slot] = true;
+                    face.push((self.nodes[node], self.rotations[node][slot].1));
+                    // Continue with the edge before the reverse edge around the next node.
+                    let (next, twin) = self.twins[node][slot];
+                    let degree = self.rotations[next].len();
+                    (node, slot) = (next, (twin + degree - 1) % degree);
+                }
+                faces.push(face);
+            }
+        }
+        faces
+    }
+}
+
+/// The graph whose subdivision a [`KuratowskiSubgraph`] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KuratowskiGraph {
+    /// The complete graph on five nodes.
+    K5,
+    /// The complete bipartite graph on two sets of three nodes.
+    K33,
+}
+
+/// A subdivision of *K₅* or *K₃,₃* in a graph, which proves that the graph is not planar.
+///
+/// The subgraph consists of *branch nodes*, the nodes of *K₅* or *K₃,₃*, and of disjoint paths
+/// between them, one for each edge of *K₅* or *K₃,₃*. See [`planar_embedding`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KuratowskiSubgraph<N, E> {
+    kind: KuratowskiGraph,
+    branch_nodes: Vec<N>,
+    edges: Vec<E>,
+}
+
+impl<N, E> KuratowskiSubgraph<N, E> {
+    /// Return whether this is a subdivision of *K₅* or of *K₃,₃*.
+    pub fn kind(&self) -> KuratowskiGraph {
+        self.kind
+    }
+
+    /// Return the branch nodes: five nodes for *K₅*, or six nodes for *K₃,₃*, where the first
+    /// three and the last three are the two sides.
+    pub fn branch_nodes(&self) -> &[N] {
+        &self.branch_nodes
+    }
+
+    /// Return the edges of the paths between the branch nodes.
+    pub fn edges(&self) -> &[E] {
+        &self.edges
+    }
+}
+
+/// Return `true` if the undirected graph `graph` is [planar][1].
+///
+/// Use [`planar_embedding`] to also get an embedding of a planar graph, or a Kuratowski subgraph
+/// of a graph that is not planar.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+///
+/// # Returns
+/// * `true` if the graph can be drawn in the plane without crossing edges, `false` otherwise.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Planar_graph
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::is_planar, prelude::*};
+///
+/// // K4 is planar, K5 is not.
+/// let k4 = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+/// assert!(is_planar(&k4));
+///
+/// let mut k5 = k4.clone();
+/// k5.extend_with_edges([(4, 0), (4, 1), (4, 2), (4, 3)]);
+/// assert!(!is_planar(&k5));
+/// ```
+pub fn is_planar<G>(graph: G) -> bool
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+{
+    let (nodes, positions) = node_positions(graph);
+    let edges = SimpleEdges::new(graph, &positions);
+    LeftRight::new(nodes.len(), &edges.endpoints).is_planar()
+}
+
+/// Compute a planar embedding of an undirected graph, or find a Kuratowski subgraph.
+///
+/// The embedding is found with the [left-right planarity test][1]. If the graph is not planar,
+/// then by [Kuratowski's theorem][2] it contains a subdivision of *K₅* or *K₃,₃*. It is found by
+/// removing the edges that are not needed to keep the graph non-planar.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+///
+/// # Returns
+/// * `Ok`: a [`PlanarEmbedding`] of all nodes and edges, if the graph is planar.
+/// * `Err`: a [`KuratowskiSubgraph`], if the graph is not planar.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)** for planar graphs. Finding a Kuratowski subgraph takes
+///   **O((|V| + |E|) log |E| + |V|² log |V|)** in the worst case, but is usually much faster
+///   because edges are discarded in blocks.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://doi.org/10.48550/arXiv.0911.1865
+/// [2]: https://en.wikipedia.org/wiki/Kuratowski%27s_theorem
+///
+/// # Example
+/// ```rust
+/// use petgraph::{
+///     algo::{KuratowskiGraph, planar_embedding},
+///     prelude::*,
+/// };
+///
+/// // A cube is planar, with six faces.
+/// let mut g = UnGraph::<(), ()>::from_edges([
+///     (0, 1),
+///     (1, 2),
+///     (2, 3),
+///     (3, 0),
+///     (4, 5),
+///     (5, 6),
+///     (6, 7),
+///     (7, 4),
+///     (0, 4),
+///     (1, 5),
+///     (2, 6),
+///     (3, 7),
+/// ]);
+/// let embedding = planar_embedding(&g).unwrap();
+/// assert_eq!(embedding.faces().len(), 6);
+/// assert!(embedding.faces().iter().all(|face| face.len() == 4));
+///
+/// // Two diagonals through the cube make it contain a subdivision of K3,3.
+/// g.extend_with_edges([(0, 6), (1, 7)]);
+/// let witness = planar_embedding(&g).unwrap_err();
+/// assert_eq!(witness.kind(), KuratowskiGraph::K33);
+/// assert_eq!(witness.branch_nodes().len(), 6);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn planar_embedding<G>(
+    graph: G,
+) -> Result<PlanarEmbedding<G::NodeId, G::EdgeId>, KuratowskiSubgraph<G::NodeId, G::EdgeId>>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let (nodes, positions) = node_positions(graph);
+    let edges = SimpleEdges::new(graph, &positions);
+    let mut left_right = LeftRight::new(nodes.len(), &edges.endpoints);
+    if !left_right.is_planar() {
+        return Err(kuratowski_subgraph(&nodes, &edges));
+    }
+    let simple_rotations = left_right.embed();
+
+    // Expand every simple edge into its parallel edges. They are nested around each other, so
+    // they are in opposite orders around their endpoints.
+    let mut rotations = vec![Vec::new(); nodes.len()];
+    let mut entries = vec![Vec::new(); nodes.len()];
+    for (v, simple_rotation) in simple_rotations.into_iter().enumerate() {
+        for s in simple_rotation {
+            let (a, b) = edges.endpoints[s];
+            let w = if a == v { b } else { a };
+            let parallel = &edges.parallel[s];
+            for i in 0..parallel.len() {
+                let i = if a == v { i } else { parallel.len() - 1 - i };
+                rotations[v].push((nodes[w], parallel[i].1));
+                entries[v].push(parallel[i].0);
+            }
+        }
+    }
+    for &(v, index, id) in &edges.self_loops {
+        for _ in 0..2 {
+            rotations[v].push((nodes[v], id));
+            entries[v].push(index);
+        }
+    }
+
+    // Pair up the two halves of every edge.
+    let mut halves = vec![Vec::with_capacity(2); edges.count];
+    for (v, entries) in entries.iter().enumerate() {
+        for (slot, &index) in entries.iter().enumerate() {
+            halves[index].push((v, slot));
+        }
+    }
+    let mut twins = entries
+        .iter()
+        .map(|entries| vec![(0, 0); entries.len()])
+        .collect::<Vec<_>>();
+    for half in halves {
+        let [a, b] = [half[0], half[1]];
+        twins[a.0][a.1] = b;
+        twins[b.0][b.1] = a;
+    }
+
+    Ok(PlanarEmbedding {
+        positions: nodes.iter().enumerate().map(|(i, &v)| (v, i)).collect(),
+        nodes,
+        rotations,
+        twins,
+    })
+}
+
+/// The edges of a graph, grouped into the edges of its underlying simple graph.
+struct SimpleEdges<E> {
+    /// The endpoints of every simple edge, by position of the nodes.
+    endpoints: Vec<(usize, usize)>,
+    /// The edges that every simple edge stands for, with their index in the order of
+    /// [`IntoEdgeReferences`].
+    parallel: Vec<Vec<(usize, E)>>,
+    /// The node, index and id of every self-loop.
+    self_loops: Vec<(usize, usize, E)>,
+    /// The number of edges, including parallel edges and self-loops.
+    count: usize,
+}
+
+impl<E: Copy> SimpleEdges<E> {
+    fn new<G>(graph: G, positions: &[usize]) -> Self
+    where
+        G: IntoEdgeReferences<EdgeId = E> + NodeIndexable,
+    {
+        let mut simple = HashMap::new();
+        let mut edges = SimpleEdges {
+            endpoints: Vec::new(),
+            parallel: Vec::new(),
+            self_loops: Vec::new(),
+            count: 0,
+        };
+        for (index, edge) in graph.edge_references().enumerate() {
+            let a = positions[graph.to_index(edge.source())];
+            let b = positions[graph.to_index(edge.target())];
+            edges.count += 1;
+            if a == b {
+                edges.self_loops.push((a, index, edge.id()));
+                continue;
+            }
+            let s = *simple.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                edges.endpoints.push((a, b));
+                edges.parallel.push(Vec::new());
+                edges.endpoints.len() - 1
+            });
+            edges.parallel[s].push((index, edge.id()));
+        }
+        edges
+    }
+}
+
+/// Find a subdivision of *K₅* or *K₃,₃* in a graph that is not planar.
+fn kuratowski_subgraph<N, E>(nodes: &[N], edges: &SimpleEdges<E>) -> KuratowskiSubgraph<N, E>
+where
+    N: Copy,
+    E: Copy,
+{
+    let n = nodes.len();
+    let planar = |subgraph: &[(usize, usize)]| LeftRight::new(n, subgraph).is_planar();
+
+    // The shortest non-planar prefix of the edges has at most 3|V| - 5 edges.
+    let (mut low, mut high) = (0, edges.endpoints.len());
+    while high - low > 1 {
+        let middle = low + (high - low) / 2;
+        if planar(&edges.endpoints[..middle]) {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    // Remove the edges that are not needed to keep the graph non-planar. What remains is a
+    // minimal non-planar graph, which is a subdivision of K5 or K3,3. Edges are removed in blocks
+    // that are halved until single paths are tried, and in between, the graph is reduced without
+    // changing whether it is planar.
+    let mut chains = edges.endpoints[..high]
+        .iter()
+        .enumerate()
+        .map(|(s, &ends)| Chain {
+            ends,
+            edges: vec![s],
+        })
+        .collect::<Vec<_>>();
+    let mut labels = vec![NONE; n];
+    let mut endpoints = Vec::new();
+    let mut subgraph = Vec::new();
+    let mut block = usize::MAX;
+    loop {
+        chains = reduce_chains(n, chains);
+        block = block.min(chains.len().div_ceil(2)).max(1);
+
+        // Label the remaining nodes compactly.
+        let mut node_count = 0;
+        endpoints.clear();
+        for chain in &chains {
+            let (a, b) = chain.ends;
+            for v in [a, b] {
+                if labels[v] == NONE {
+                    labels[v] = node_count;
+                    node_count += 1;
+                }
+            }
+            endpoints.push((labels[a], labels[b]));
+        }
+        for chain in &chains {
+            labels[chain.ends.0] = NONE;
+            labels[chain.ends.1] = NONE;
+        }
+
+        let mut kept = vec![true; chains.len()];
+        let mut i = 0;
+        while i < chains.len() {
+            let end = chains.len().min(i + block);
+            subgraph.clear();
+            subgraph.extend(
+                (0..chains.len())
+                    .filter(|&j| kept[j] && (j < i || j >= end))
+                    .map(|j| endpoints[j]),
+            );
+            if !LeftRight::new(node_count, &subgraph).is_planar() {
+                kept[i..end].fill(false);
+            }
+            i = end;
+        }
+        let mut kept = kept.into_iter();
+        chains.retain(|_| kept.next().unwrap());
+        if block == 1 {
+            break;
+        }
+        block = block.div_ceil(2);
+    }
+    let kept = chains
+        .into_iter()
+        .flat_map(|chain| chain.edges)
+        .collect::<Vec<_>>();
+
+    let mut adjacency = vec![Vec::new(); n];
+    for &s in &kept {
+        let (a, b) = edges.endpoints[s];
+        adjacency[a].push(b);
+        adjacency[b].push(a);
+    }
+    let mut branch_nodes = (0..n)
+        .filter(|&v| adjacency[v].len() > 2)
+        .collect::<Vec<_>>();
+    let kind = if branch_nodes.len() == 5 {
+        KuratowskiGraph::K5
+    } else {
+        debug_assert_eq!(branch_nodes.len(), 6);
+        // Follow the paths from the first branch node to the other side.
+        let first = branch_nodes[0];
+        let mut other_side = adjacency[first]
+            .iter()
+            .map(|&next| {
+                let (mut previous, mut current) = (first, next);
+                while adjacency[current].len() == 2 {
+                    let following = if adjacency[current][0] == previous {
+                        adjacency[current][1]
+                    } else {
+                        adjacency[current][0]
+                    };
+                    (previous, current) = (current, following);
+                }
+                current
+            })
+            .collect::<Vec<_>>();
+        other_side.sort_unstable();
+        branch_nodes.retain(|v| !other_side.contains(v));
+        branch_nodes.extend(other_side);
+        KuratowskiGraph::K33
+    };
+
+    KuratowskiSubgraph {
+        kind,
+        branch_nodes: branch_nodes.into_iter().map(|v| nodes[v]).collect(),
+        edges: kept.into_iter().map(|s| edges.parallel[s][0].1).collect(),
+    }
+}
+
+/// A path of simple edges whose inner nodes have no other edges.
+struct Chain {
+    ends: (usize, usize),
+    edges: Vec<usize>,
+}
+
+/// Reduce a graph of chains without changing whether it is planar: remove loops, parallel chains
+/// and chains to nodes without other chains, and join the two chains at nodes that have no others.
+fn reduce_chains(node_count: usize, mut chains: Vec<Chain>) -> Vec<Chain> {
+    let mut incident = vec![Vec::new(); node_count];
+    loop {
+        let mut seen = HashSet::new();
+        let before = chains.len();
+        chains.retain(|chain| {
+            let (a, b) = chain.ends;
+            a != b && seen.insert((a.min(b), a.max(b)))
+        });
+        let mut changed = chains.len() != before;
+
+        for list in &mut incident {
+            list.clear();
+        }
+        for (i, chain) in chains.iter().enumerate() {
+            incident[chain.ends.0].push(i);
+            incident[chain.ends.1].push(i);
+        }
+
+        let mut chains_by_id = chains.into_iter().map(Some).collect::<Vec<_>>();
+        for v in 0..node_count {
+            match incident[v][..] {
+                [c] => {
+                    chains_by_id[c] = None;
+                    changed = true;
+                }
+                [c1, c2]
+                    if c1 != c2 && chains_by_id[c1].is_some() && chains_by_id[c2].is_some() =>
+                {
+                    let second = chains_by_id[c2].take().unwrap();
+                    let first = chains_by_id[c1].as_mut().unwrap();
+                    let other = if second.ends.0 == v {
+                        second.ends.1
+                    } else {
+                        second.ends.0
+                    };
+                    if first.ends.0 == v {
+                        first.ends.0 = other;
+                    } else {
+                        first.ends.1 = other;
+                    }
+                    let mut edges = second.edges;
+                    if edges.len() > first.edges.len() {
+                        mem::swap(&mut edges, &mut first.edges);
+                    }
+                    first.edges.extend(edges);
+                    for c in &mut incident[other] {
+                        if *c == c2 {
+                            *c = c1;
+                        }
+                    }
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+        chains = chains_by_id.into_iter().flatten().collect();
+        if !changed {
+            return chains;
+        }
+    }
+}
+
+/// Marks a missing node or edge.
+const NONE: usize = usize::MAX;
+
+/// An interval of return edges, from `high` down to `low`, linked through `refs`.
+#[derive(Clone, Copy, Debug)]
+struct Interval {
+    low: usize,
+    high: usize,
+}
+
+impl Interval {
+    const EMPTY: Interval = Interval {
+        low: NONE,
+        high: NONE,
+    };
+
+    fn is_empty(&self) -> bool {
+        self.low == NONE && self.high == NONE
+    }
+}
+
+/// Two intervals of return edges that must be on different sides.
+#[derive(Clone, Copy, Debug)]
+struct ConflictPair {
+    left: Interval,
+    right: Interval,
+}
+
+impl ConflictPair {
+    fn swap(&mut self) {
+        mem::swap(&mut self.left, &mut self.right);
+    }
+}
+
+/// The state of the left-right planarity test on a simple graph.
+///
+/// Edges are oriented by a depth-first search, from `source` to `target`. Every oriented edge `e`
+/// has two half-edges, `2 * e` around its source and `2 * e + 1` around its target.
+struct LeftRight<'a> {
+    edges: &'a [(usize, usize)],
+    adjacency: Vec<Vec<(usize, usize)>>,
+    source: Vec<usize>,
+    target: Vec<usize>,
+    height: Vec<usize>,
+    parent_edge: Vec<usize>,
+    roots: Vec<usize>,
+    /// The outgoing edges of every node, in the order in which they were oriented.
+    out_edges: Vec<Vec<usize>>,
+    /// The outgoing edges of every node, ordered by nesting depth.
+    ordered: Vec<Vec<usize>>,
+    lowpt: Vec<usize>,
+    lowpt2: Vec<usize>,
+    nesting_depth: Vec<isize>,
+    refs: Vec<usize>,
+    side: Vec<isize>,
+    lowpt_edge: Vec<usize>,
+    stack_bottom: Vec<usize>,
+    conflicts: Vec<ConflictPair>,
+}
+
+impl<'a> LeftRight<'a> {
+    fn new(node_count: usize, edges: &'a [(usize, usize)]) -> Self {
+        let mut adjacency = vec![Vec::new(); node_count];
+        for (e, &(a, b)) in edges.iter().enumerate() {
+            adjacency[a].push((b, e));
+            adjacency[b].push((a, e));
+        }
+        let m = edges.len();
+        LeftRight {
+            edges,
+            adjacency,
+            source: vec![NONE; m],
+            target: vec![NONE; m],
+            height: vec![NONE; node_count],
+            parent_edge: vec![NONE; node_count],
+            roots: Vec::new(),
+            out_edges: vec![Vec::new(); node_count],
+            ordered: Vec::new(),
+            lowpt: vec![0; m],
+            lowpt2: vec![0; m],
+            nesting_depth: vec![0; m],
+            refs: vec![NONE; m],
+            side: vec![1; m],
+            lowpt_edge: vec![NONE; m],
+            stack_bottom: vec![0; m],
+            conflicts: Vec::new(),
+        }
+    }
+
+    fn is_planar(&mut self) -> bool {
+        let n = self.adjacency.len();
+        if n > 2 && self.edges.len() > 3 * n - 6 {
+            return false;
+        }
+        for v in 0..n {
+            if self.height[v] == NONE {
+                self.height[v] = 0;
+                self.roots.push(v);
+                self.orient(v);
+            }
+        }
+        self.ordered = self.out_edges.clone();
+        for out_edges in &mut self.ordered {
+            out_edges.sort_by_key(|&e| self.nesting_depth[e]);
+        }
+        for i in 0..self.roots.len() {
+            if !self.test(self.roots[i]) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Orient the edges with a depth-first search from `root`, computing lowpoints and nesting
+    /// depths.
+    fn orient(&mut self, root: usize) {
+        let mut next = vec![0; self.adjacency.len()];
+        let mut resumed = vec![false; self.edges.len()];
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            let e = self.parent_edge[v];
+            while next[v] < self.adjacency[v].len() {
+                let (w, vw) = self.adjacency[v][next[v]];
+                if !resumed[vw] {
+                    if self.source[vw] != NONE {
+                        next[v] += 1;
+                        continue;
+                    }
+                    self.source[vw] = v;
+                    self.target[vw] = w;
+                    self.out_edges[v].push(vw);
+                    self.lowpt[vw] = self.height[v];
+                    self.lowpt2[vw] = self.height[v];
+                    if self.height[w] == NONE {
+                        // A tree edge: continue with `v` once `w` is done.
+                        self.parent_edge[w] = vw;
+                        self.height[w] = self.height[v] + 1;
+                        resumed[vw] = true;
+                        stack.push(v);
+                        stack.push(w);
+                        break;
+                    }
+                    // A back edge.
+                    self.lowpt[vw] = self.height[w];
+                }
+
+                self.nesting_depth[vw] = 2 * self.lowpt[vw] as isize;
+                if self.lowpt2[vw] < self.height[v] {
+                    // A chordal edge.
+                    self.nesting_depth[vw] += 1;
+                }
+                if e != NONE {
+                    if self.lowpt[vw] < self.lowpt[e] {
+                        self.lowpt2[e] = self.lowpt[e].min(self.lowpt2[vw]);
+                        self.lowpt[e] = self.lowpt[vw];
+                    } else if self.lowpt[vw] > self.lowpt[e] {
+                        self.lowpt2[e] = self.lowpt2[e].min(self.lowpt[vw]);
+                    } else {
+                        self.lowpt2[e] = self.lowpt2[e].min(self.lowpt2[vw]);
+                    }
+                }
+                next[v] += 1;
+            }
+        }
+    }
+
+    /// Test the constraints on the sides of the back edges in the tree of `root`.
+    fn test(&mut self, root: usize) -> bool {
+        let mut next = vec![0; self.adjacency.len()];
+        let mut resumed = vec![false; self.edges.len()];
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            let e = self.parent_edge[v];
+            let mut descended = false;
+            while next[v] < self.ordered[v].len() {
+                let ei = self.ordered[v][next[v]];
+                if !resumed[ei] {
+                    self.stack_bottom[ei] = self.conflicts.len();
+                    let w = self.target[ei];
+                    if ei == self.parent_edge[w] {
+                        resumed[ei] = true;
+                        stack.push(v);
+                        stack.push(w);
+                        descended = true;
+                        break;
+                    }
+                    self.lowpt_edge[ei] = ei;
+                    self.conflicts.push(ConflictPair {
+                        left: Interval::EMPTY,
+                        right: Interval { low: ei, high: ei },
+                    });
+                }
+
+                // Integrate the new return edges.
+                if self.lowpt[ei] < self.height[v] {
+                    if next[v] == 0 {
+                        self.lowpt_edge[e] = self.lowpt_edge[ei];
+                    } else if !self.add_constraints(ei, e) {
+                        return false;
+                    }
+                }
+                next[v] += 1;
+            }
+            if !descended && e != NONE {
+                self.remove_back_edges(e);
+            }
+        }
+        true
+    }
+
+    fn conflicting(&self, interval: Interval, edge: usize) -> bool {
+        !interval.is_empty() && self.lowpt[interval.high] > self.lowpt[edge]
+    }
+
+    fn lowest(&self, pair: ConflictPair) -> usize {
+        if pair.left.is_empty() {
+            self.lowpt[pair.right.low]
+        } else if pair.right.is_empty() {
+            self.lowpt[pair.left.low]
+        } else {
+            self.lowpt[pair.left.low].min(self.lowpt[pair.right.low])
+        }
+    }
+
+    fn add_constraints(&mut self, ei: usize, e: usize) -> bool {
+        let mut pair = ConflictPair {
+            left: Interval::EMPTY,
+            right: Interval::EMPTY,
+        };
+        // Merge the return edges of `ei` into the right interval.
+        loop {
+            let mut q = self.conflicts.pop().unwrap();
+            if !q.left.is_empty() {
+                q.swap();
+            }
+            if !q.left.is_empty() {
+                return false;
+            }
+            if self.lowpt[q.right.low] > self.lowpt[e] {
+                if pair.right.is_empty() {
+                    pair.right = q.right;
+                } else {
+                    self.refs[pair.right.low] = q.right.high;
+                }
+                pair.right.low = q.right.low;
+            } else {
+                self.refs[q.right.low] = self.lowpt_edge[e];
+            }
+            if self.conflicts.len() == self.stack_bottom[ei] {
+                break;
+            }
+        }
+        // Merge the conflicting return edges of the earlier outgoing edges into the left interval.
+        while let Some(&top) = self.conflicts.last() {
+            if !self.conflicting(top.left, ei) && !self.conflicting(top.right, ei) {
+                break;
+            }
+            let mut q = self.conflicts.pop().unwrap();
+            if self.conflicting(q.right, ei) {
+                q.swap();
+            }
+            if self.conflicting(q.right, ei) {
+                return false;
+            }
+            self.refs[pair.right.low] = q.right.high;
+            if q.right.low != NONE {
+                pair.right.low = q.right.low;
+            }
+            if pair.left.is_empty() {
+                pair.left = q.left;
+            } else {
+                self.refs[pair.left.low] = q.left.high;
+            }
+            pair.left.low = q.left.low;
+        }
+        if !pair.left.is_empty() || !pair.right.is_empty() {
+            self.conflicts.push(pair);
+        }
+        true
+    }
+
+    fn remove_back_edges(&mut self, e: usize) {
+        let u = self.source[e];
+        // Drop the conflict pairs that only contain back edges to `u`.
+        while let Some(&top) = self.conflicts.last() {
+            if self.lowest(top) != self.height[u] {
+                break;
+            }
+            self.conflicts.pop();
+            if top.left.low != NONE {
+                self.side[top.left.low] = -1;
+            }
+        }
+        // Trim the back edges to `u` from the next conflict pair.
+        if let Some(mut pair) = self.conflicts.pop() {
+            while pair.left.high != NONE && self.target[pair.left.high] == u {
+                pair.left.high = self.refs[pair.left.high];
+            }
+            if pair.left.high == NONE && pair.left.low != NONE {
+                self.refs[pair.left.low] = pair.right.low;
+                self.side[pair.left.low] = -1;
+                pair.left.low = NONE;
+            }
+            while pair.right.high != NONE && self.target[pair.right.high] == u {
+                pair.right.high = self.refs[pair.right.high];
+            }
+            if pair.right.high == NONE && pair.right.low != NONE {
+                self.refs[pair.right.low] = pair.left.low;
+                self.side[pair.right.low] = -1;
+                pair.right.low = NONE;
+            }
+            self.conflicts.push(pair);
+        }
+        // The side of `e` is the side of a highest return edge.
+        if self.lowpt[e] < self.height[u] {
+            let top = *self.conflicts.last().unwrap();
+            let (high_left, high_right) = (top.left.high, top.right.high);
+            self.refs[e] = if high_left != NONE
+                && (high_right == NONE || self.lowpt[high_left] > self.lowpt[high_right])
+            {
+                high_left
+            } else {
+                high_right
+            };
+        }
+    }
+
+    /// Resolve the side of `e` relative to the edges it refers to.
+    fn sign(&mut self, e: usize) -> isize {
+        let mut chain = vec![e];
+        while self.refs[*chain.last().unwrap()] != NONE {
+            chain.push(self.refs[*chain.last().unwrap()]);
+        }
+        for i in (0..chain.len() - 1).rev() {
+            self.side[chain[i]] *= self.side[chain[i + 1]];
+            self.refs[chain[i]] = NONE;
+        }
+        self.side[e]
+    }
+
+    /// Compute the clockwise order of the edges around every node, after a successful test.
+    fn embed(mut self) -> Vec<Vec<usize>> {
+        for e in 0..self.edges.len() {
+            self.nesting_depth[e] *= self.sign(e);
+        }
+        self.ordered = mem::take(&mut self.out_edges);
+        for out_edges in &mut self.ordered {
+            out_edges.sort_by_key(|&e| self.nesting_depth[e]);
+        }
+
+        let mut rings = Rings {
+            cw: vec![NONE; 2 * self.edges.len()],
+            ccw: vec![NONE; 2 * self.edges.len()],
+            first: vec![NONE; self.adjacency.len()],
+        };
+        for v in 0..self.adjacency.len() {
+            let mut previous = NONE;
+            for &e in &self.ordered[v] {
+                rings.insert_cw(v, 2 * e, previous);
+                previous = 2 * e;
+            }
+        }
+
+        let mut left_ref = vec![NONE; self.adjacency.len()];
+        let mut right_ref = vec![NONE; self.adjacency.len()];
+        let mut next = vec![0; self.adjacency.len()];
+        for &root in &self.roots {
+            let mut stack = vec![root];
+            while let Some(v) = stack.pop() {
+                while next[v] < self.ordered[v].len() {
+                    let ei = self.ordered[v][next[v]];
+                    next[v] += 1;
+                    let w = self.target[ei];
+                    if ei == self.parent_edge[w] {
+                        rings.insert_first(w, 2 * ei + 1);
+                        left_ref[v] = 2 * ei;
+                        right_ref[v] = 2 * ei;
+                        stack.push(v);
+                        stack.push(w);
+                        break;
+                    }
+                    if self.side[ei] == 1 {
+                        rings.insert_cw(w, 2 * ei + 1, right_ref[w]);
+                    } else {
+                        rings.insert_ccw(w, 2 * ei + 1, left_ref[w]);
+                        left_ref[w] = 2 * ei + 1;
+                    }
+                }
+            }
+        }
+
+        rings
+            .first
+            .iter()
+            .map(|&first| {
+                let mut rotation = Vec::new();
+                if first != NONE {
+                    let mut half = first;
+                    loop {
+                        rotation.push(half / 2);
+                        half = rings.cw[half];
+                        if half == first {
+                            break;
+                        }
+                    }
+                }
+                rotation
+            })
+            .collect()
+    }
+}
+
+/// The half-edges around every node, in circular doubly linked lists.
+struct Rings {
+    cw: Vec<usize>,
+    ccw: Vec<usize>,
+    first: Vec<usize>,
+}
+
+impl Rings {
+    /// Insert `half` around `v` clockwise after `reference`, which is `NONE` if there are no
+    /// half-edges around `v` yet.
+    fn insert_cw(&mut self, v: usize, half: usize, reference: usize) {
+        if reference == NONE {
+            self.cw[half] = half;
+            self.ccw[half] = half;
+            self.first[v] = half;
+        } else {
+            let next = self.cw[reference];
+            self.cw[reference] = half;
+            self.ccw[half] = reference;
+            self.cw[half] = next;
+            self.ccw[next] = half;
+        }
+    }
+
+    /// Insert `half` around `v` counterclockwise before `reference`.
+    fn insert_ccw(&mut self, v: usize, half: usize, reference: usize) {
+        self.insert_cw(v, half, self.ccw[reference]);
+        if self.first[v] == reference {
+            self.first[v] = half;
+        }
+    }
+
+    /// Insert `half` as the first half-edge around `v`.
+    fn insert_first(&mut self, v: usize, half: usize) {
+        if self.first[v] == NONE {
+            self.insert_cw(v, half, NONE);
+        } else {
+            self.insert_ccw(v, half, self.first[v]);
+        }
+    }
+}
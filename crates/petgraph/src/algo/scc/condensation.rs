@@ -0,0 +1,158 @@
+use alloc::{vec, vec::Vec};
+use core::hash::Hash;
+
+use hashbrown::HashMap;
+
+use super::tarjan_scc::TarjanScc;
+use crate::{
+    Direction::Outgoing,
+    graph::{DiGraph, NodeIndex},
+    visit::{IntoNeighborsDirected, IntoNodeIdentifiers, NodeIndexable},
+};
+
+/// The condensation of a graph: its strongly connected components contracted into single nodes.
+///
+/// Created by [`scc_condensation`].
+///
+/// The condensed graph is a DAG whose node weights are the strongly connected components of the
+/// input graph. Its nodes are numbered in topological order, i.e. every edge `a -> b` of the
+/// condensed graph satisfies `a.index() < b.index()`.
+#[derive(Debug, Clone)]
+pub struct Condensation<N> {
+    graph: DiGraph<Vec<N>, ()>,
+    node_map: HashMap<N, NodeIndex>,
+}
+
+impl<N> Condensation<N>
+where
+    N: Copy + Eq + Hash,
+{
+    /// Return the condensed DAG.
+    ///
+    /// Each node holds the node ids of one strongly connected component. There is at most one
+    /// edge between two components and there are no self-loops.
+    pub fn graph(&self) -> &DiGraph<Vec<N>, ()> {
+        &self.graph
+    }
+
+    /// Return the condensed DAG, consuming `self`.
+    pub fn into_graph(self) -> DiGraph<Vec<N>, ()> {
+        self.graph
+    }
+
+    /// Return the node of the condensed DAG that contains `node`.
+    ///
+    /// Returns `None` if `node` is not a node of the input graph.
+    pub fn scc_of(&self, node: N) -> Option<NodeIndex> {
+        self.node_map.get(&node).copied()
+    }
+
+    /// Return the map from every node of the input graph to the node of the condensed DAG that
+    /// contains it.
+    pub fn node_map(&self) -> &HashMap<N, NodeIndex> {
+        &self.node_map
+    }
+
+    /// Iterate over the strongly connected components in topological order.
+    ///
+    /// The order of node ids within each component is arbitrary.
+    pub fn sccs(&self) -> impl Iterator<Item = &[N]> {
+        self.graph.node_weights().map(Vec::as_slice)
+    }
+}
+
+/// Condense every strongly connected component into a single node without consuming the graph.
+///
+/// This is the counterpart of [`condensation`](crate::algo::condensation) for any graph that
+/// implements the visitor traits, such as [`GraphMap`](crate::graphmap::GraphMap),
+/// [`StableGraph`](crate::stable_graph::StableGraph), [`Csr`](crate::csr::Csr) or filtered
+/// views. Since the graph is only borrowed, the components hold node ids instead of node weights.
+/// The condensed graph is always acyclic: self-loops and parallel edges between components are
+/// not kept.
+///
+/// # Arguments
+/// * `g`: a directed graph.
+///
+/// # Returns
+/// * [`Condensation`]: the condensed DAG, with its nodes in topological order, together with the
+///   map from each node of `g` to its strongly connected component.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::scc_condensation, graphmap::DiGraphMap};
+///
+/// let g = DiGraphMap::<&str, ()>::from_edges([
+///     ("a", "b"),
+///     ("b", "a"),
+///     ("b", "c"),
+///     ("c", "d"),
+///     ("d", "c"),
+///     ("a", "e"),
+/// ]);
+///
+/// // a <---> b ----> c <---> d
+/// // |
+/// // v
+/// // e
+///
+/// let condensation = scc_condensation(&g);
+/// let dag = condensation.graph();
+/// assert_eq!(dag.node_count(), 3);
+/// assert_eq!(dag.edge_count(), 2);
+///
+/// // The first component in topological order is {a, b}.
+/// let first = condensation.scc_of("a").unwrap();
+/// assert_eq!(first.index(), 0);
+/// assert_eq!(condensation.scc_of("b"), Some(first));
+/// assert!(dag.contains_edge(first, condensation.scc_of("c").unwrap()));
+/// assert!(dag.contains_edge(first, condensation.scc_of("e").unwrap()));
+/// ```
+pub fn scc_condensation<G>(g: G) -> Condensation<G::NodeId>
+where
+    G: IntoNeighborsDirected + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    // Tarjan's algorithm yields the components in reverse topological order.
+    let mut sccs = Vec::new();
+    TarjanScc::new().run(g, |scc| sccs.push(scc.to_vec()));
+    sccs.reverse();
+
+    let mut node_map = HashMap::with_capacity(g.node_bound());
+    for (i, scc) in sccs.iter().enumerate() {
+        for &node in scc {
+            node_map.insert(node, NodeIndex::new(i));
+        }
+    }
+
+    // `last_source[b]` is the last component that got an edge to `b`, to skip parallel edges.
+    let mut last_source = vec![NodeIndex::end(); sccs.len()];
+    let mut edges = Vec::new();
+    for (i, scc) in sccs.iter().enumerate() {
+        let scc_ix = NodeIndex::new(i);
+        for &node in scc {
+            for succ in g.neighbors_directed(node, Outgoing) {
+                let succ_ix = node_map[&succ];
+                if succ_ix != scc_ix && last_source[succ_ix.index()] != scc_ix {
+                    last_source[succ_ix.index()] = scc_ix;
+                    edges.push((scc_ix, succ_ix));
+                }
+            }
+        }
+    }
+
+    let mut graph = DiGraph::with_capacity(sccs.len(), edges.len());
+    for scc in sccs {
+        graph.add_node(scc);
+    }
+    for (source, target) in edges {
+        graph.add_edge(source, target, ());
+    }
+
+    Condensation { graph, node_map }
+}
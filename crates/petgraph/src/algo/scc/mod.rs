@@ -1,6 +1,8 @@
+pub mod condensation;
 pub mod kosaraju_scc;
 pub mod tarjan_scc;
 
+pub use condensation::{Condensation, scc_condensation};
 #[allow(deprecated)]
 pub use kosaraju_scc::{kosaraju_scc, scc};
 pub use tarjan_scc::{TarjanScc, tarjan_scc};
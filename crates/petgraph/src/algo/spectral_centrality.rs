@@ -0,0 +1,299 @@
+//! Spectral centrality measures, which rank a node by the scores of the nodes that point to it.
+//!
+//! * [`eigenvector_centrality`] scores a node by the sum of the scores of its in-neighbors.
+//! * [`katz_centrality`] also gives every node a base score, so that nodes with no in-neighbors
+//!   count too.
+//! * [`hits`] computes Kleinberg's hub and authority scores.
+//!
+//! All of them use power iteration: starting from a uniform vector, they apply the weighted
+//! adjacency matrix until the [L1 distance][l1] between two consecutive iterates is at most
+//! `tol`, or for at most `nb_iter` iterations. In an undirected graph, every edge points both
+//! ways. Scores are returned as a `Vec` indexed by [`NodeIndexable::to_index`], as with
+//! [`page_rank`](crate::algo::page_rank()), with a score of zero for indices that are not in use.
+//!
+//! [l1]: https://en.wikipedia.org/wiki/Taxicab_geometry
+
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    algo::UnitMeasure,
+    visit::{EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable},
+};
+
+/// Compute the [eigenvector centrality][1] of every node.
+///
+/// The eigenvector centrality of a node is proportional to the sum of the centralities of the
+/// nodes that have an edge to it, weighted by `edge_weight`. The scores are the principal
+/// eigenvector of the transposed adjacency matrix, scaled to sum to one.
+///
+/// Every iteration adds the previous scores to the new ones, which leaves the eigenvector
+/// unchanged but makes the iteration converge on bipartite graphs as well. The scores are only
+/// meaningful for strongly connected graphs: in other graphs, the nodes that can not be reached
+/// from a cycle get a score that tends to zero.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+/// * `nb_iter`: the maximum number of iterations.
+/// * `tol`: the L1 distance between two iterates to stop at, or `None` for
+///   [`UnitMeasure::default_tol`].
+/// * `edge_weight`: closure that returns the weight of an edge, which must be non-negative.
+///
+/// # Returns
+/// * `Vec<D>`: the centrality of every node, indexed by [`NodeIndexable::to_index`].
+///
+/// # Complexity
+/// * Time complexity: **O(n(|V| + |E|))**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **n** is the number of iterations, **|V|** is the number of nodes and **|E|** is the
+/// number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Eigenvector_centrality
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::eigenvector_centrality, prelude::*};
+///
+/// // A star with the center `a`.
+/// let mut g = UnGraph::<(), f64>::new_undirected();
+/// let [a, b, c, d] = [(); 4].map(|_| g.add_node(()));
+/// g.extend_with_edges(&[(a, b, 1.), (a, c, 1.), (a, d, 1.)]);
+///
+/// let centrality = eigenvector_centrality(&g, 1000, Some(1e-12), |e| *e.weight());
+/// // The center has √3 times the centrality of every leaf.
+/// let center = 3f64.sqrt() / (3f64.sqrt() + 3.);
+/// assert!((centrality[a.index()] - center).abs() < 1e-9);
+/// assert!((centrality[b.index()] - (1. - center) / 3.).abs() < 1e-9);
+/// ```
+pub fn eigenvector_centrality<G, F, D>(
+    graph: G,
+    nb_iter: usize,
+    tol: Option<D>,
+    edge_weight: F,
+) -> Vec<D>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> D,
+    D: UnitMeasure + Copy,
+{
+    let tol = tol.unwrap_or_else(D::default_tol);
+    let edges = weighted_edges(graph, edge_weight);
+    let mut scores = uniform_scores(graph);
+    for _ in 0..nb_iter {
+        let mut next = scores.clone();
+        for &(a, b, weight) in &edges {
+            next[b] = next[b] + weight * scores[a];
+        }
+        normalize(&mut next);
+        let converged = l1_distance(&scores, &next) <= tol;
+        scores = next;
+        if converged {
+            break;
+        }
+    }
+    scores
+}
+
+/// Compute the [Katz centrality][1] of every node.
+///
+/// The Katz centrality **x** is the solution of **x = α Aᵀx + β**, where **A** is the adjacency
+/// matrix weighted by `edge_weight`. It counts the walks that end at every node, where a walk of
+/// length **k** counts **αᵏ β**, so every node scores at least `beta`.
+///
+/// The iteration only converges if `alpha` is less than the inverse of the largest eigenvalue of
+/// **A**. In particular, it converges on directed acyclic graphs for any `alpha`.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+/// * `alpha`: the attenuation factor of every edge.
+/// * `beta`: the base score of every node.
+/// * `nb_iter`: the maximum number of iterations.
+/// * `tol`: the L1 distance between two iterates to stop at, or `None` for
+///   [`UnitMeasure::default_tol`].
+/// * `edge_weight`: closure that returns the weight of an edge, which must be non-negative.
+///
+/// # Returns
+/// * `Vec<D>`: the centrality of every node, indexed by [`NodeIndexable::to_index`].
+///
+/// # Complexity
+/// * Time complexity: **O(n(|V| + |E|))**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **n** is the number of iterations, **|V|** is the number of nodes and **|E|** is the
+/// number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Katz_centrality
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::katz_centrality, prelude::*};
+///
+/// // A path a → b → c.
+/// let mut g = DiGraph::<(), f64>::new();
+/// let [a, b, c] = [(); 3].map(|_| g.add_node(()));
+/// g.extend_with_edges(&[(a, b, 1.), (b, c, 2.)]);
+///
+/// let centrality = katz_centrality(&g, 0.5, 1., 100, None, |e| *e.weight());
+/// assert_eq!(centrality, [1., 1.5, 2.5]);
+/// ```
+pub fn katz_centrality<G, F, D>(
+    graph: G,
+    alpha: D,
+    beta: D,
+    nb_iter: usize,
+    tol: Option<D>,
+    edge_weight: F,
+) -> Vec<D>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> D,
+    D: UnitMeasure + Copy,
+{
+    let tol = tol.unwrap_or_else(D::default_tol);
+    let edges = weighted_edges(graph, edge_weight);
+    let mut base = vec![D::zero(); graph.node_bound()];
+    for node in graph.node_identifiers() {
+        base[graph.to_index(node)] = beta;
+    }
+    let mut scores = vec![D::zero(); graph.node_bound()];
+    for _ in 0..nb_iter {
+        let mut next = base.clone();
+        for &(a, b, weight) in &edges {
+            next[b] = next[b] + alpha * weight * scores[a];
+        }
+        let converged = l1_distance(&scores, &next) <= tol;
+        scores = next;
+        if converged {
+            break;
+        }
+    }
+    scores
+}
+
+/// Compute the hub and authority scores of every node with [Kleinberg's HITS algorithm][1].
+///
+/// A good authority is a node that many good hubs point to, and a good hub is a node that points
+/// to many good authorities. The authority scores are the principal eigenvector of **AᵀA** and
+/// the hub scores that of **AAᵀ**, where **A** is the adjacency matrix weighted by `edge_weight`.
+/// Both are scaled to sum to one, and are all zero in a graph without edges.
+///
+/// The iteration stops when both the hub and the authority scores move by at most `tol`.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+/// * `nb_iter`: the maximum number of iterations.
+/// * `tol`: the L1 distance between two iterates to stop at, or `None` for
+///   [`UnitMeasure::default_tol`].
+/// * `edge_weight`: closure that returns the weight of an edge, which must be non-negative.
+///
+/// # Returns
+/// * `(Vec<D>, Vec<D>)`: the hub and the authority score of every node, indexed by
+///   [`NodeIndexable::to_index`].
+///
+/// # Complexity
+/// * Time complexity: **O(n(|V| + |E|))**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **n** is the number of iterations, **|V|** is the number of nodes and **|E|** is the
+/// number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/HITS_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::hits, prelude::*};
+///
+/// // `a` points to both `b` and `c`, and `d` points to `c` only.
+/// let mut g = DiGraph::<(), f64>::new();
+/// let [a, b, c, d] = [(); 4].map(|_| g.add_node(()));
+/// g.extend_with_edges(&[(a, b, 1.), (a, c, 1.), (d, c, 1.)]);
+///
+/// let (hubs, authorities) = hits(&g, 1000, Some(1e-12), |e| *e.weight());
+/// // The scores are split in the golden ratio.
+/// let phi = (1. + 5f64.sqrt()) / 2.;
+/// assert!((hubs[a.index()] - 1. / phi).abs() < 1e-9);
+/// assert!((hubs[d.index()] - 1. / (phi * phi)).abs() < 1e-9);
+/// assert!((authorities[c.index()] - 1. / phi).abs() < 1e-9);
+/// assert!((authorities[b.index()] - 1. / (phi * phi)).abs() < 1e-9);
+/// assert_eq!(authorities[a.index()], 0.);
+/// ```
+pub fn hits<G, F, D>(graph: G, nb_iter: usize, tol: Option<D>, edge_weight: F) -> (Vec<D>, Vec<D>)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> D,
+    D: UnitMeasure + Copy,
+{
+    let tol = tol.unwrap_or_else(D::default_tol);
+    let edges = weighted_edges(graph, edge_weight);
+    let mut hubs = uniform_scores(graph);
+    let mut authorities = vec![D::zero(); graph.node_bound()];
+    for _ in 0..nb_iter {
+        let mut next_authorities = vec![D::zero(); graph.node_bound()];
+        for &(a, b, weight) in &edges {
+            next_authorities[b] = next_authorities[b] + weight * hubs[a];
+        }
+        normalize(&mut next_authorities);
+        let mut next_hubs = vec![D::zero(); graph.node_bound()];
+        for &(a, b, weight) in &edges {
+            next_hubs[a] = next_hubs[a] + weight * next_authorities[b];
+        }
+        normalize(&mut next_hubs);
+        let converged = l1_distance(&hubs, &next_hubs) <= tol
+            && l1_distance(&authorities, &next_authorities) <= tol;
+        hubs = next_hubs;
+        authorities = next_authorities;
+        if converged {
+            break;
+        }
+    }
+    (hubs, authorities)
+}
+
+/// The edges of `graph` as pairs of node indices with their weight, in both directions for
+/// undirected graphs.
+fn weighted_edges<G, F, D>(graph: G, mut edge_weight: F) -> Vec<(usize, usize, D)>
+where
+    G: IntoEdgeReferences + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> D,
+    D: Copy,
+{
+    let mut edges = Vec::new();
+    for edge in graph.edge_references() {
+        let (a, b) = (graph.to_index(edge.source()), graph.to_index(edge.target()));
+        let weight = edge_weight(edge);
+        edges.push((a, b, weight));
+        if !graph.is_directed() && a != b {
+            edges.push((b, a, weight));
+        }
+    }
+    edges
+}
+
+/// A score of one over the number of nodes for every node.
+fn uniform_scores<G, D>(graph: G) -> Vec<D>
+where
+    G: IntoNodeIdentifiers + NodeIndexable,
+    D: UnitMeasure + Copy,
+{
+    let mut scores = vec![D::zero(); graph.node_bound()];
+    let share = D::one() / D::from_usize(graph.node_identifiers().count());
+    for node in graph.node_identifiers() {
+        scores[graph.to_index(node)] = share;
+    }
+    scores
+}
+
+/// Scale `scores` to sum to one, unless they are all zero.
+fn normalize<D: UnitMeasure + Copy>(scores: &mut [D]) {
+    let sum = scores.iter().copied().sum::<D>();
+    if sum > D::zero() {
+        scores.iter_mut().for_each(|score| *score = *score / sum);
+    }
+}
+
+fn l1_distance<D: UnitMeasure + Copy>(a: &[D], b: &[D]) -> D {
+    a.iter()
+        .zip(b)
+        .map(|(&x, &y)| if x < y { y - x } else { x - y })
+        .sum()
+}
@@ -1,160 +1,55 @@
-use alloc::vec::Vec;
-use core::{fmt::Debug, hash::Hash};
+//! Steiner trees: trees of minimum cost that connect a set of terminal nodes.
+//!
+//! Finding a minimum Steiner tree is NP-hard. [`steiner_tree`] and [`steiner_tree_mehlhorn`] are
+//! 2-approximations, which return a tree that costs at most twice as much as the minimum, while
+//! [`steiner_tree_dreyfus_wagner`] finds the minimum, in time exponential in the number of
+//! terminals.
+
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+use core::{cmp::Ordering, hash::Hash};
 
 use hashbrown::{HashMap, HashSet};
 
-#[cfg(feature = "stable_graph")]
-use crate::stable_graph::StableGraph;
 use crate::{
-    Undirected,
-    algo::{
-        BoundedMeasure, Measure, dijkstra, floyd_warshall::floyd_warshall_path, min_spanning_tree,
-    },
-    data::FromElements,
-    graph::{IndexType, NodeIndex, UnGraph},
-    visit::{
-        Data, EdgeRef, GraphBase, GraphProp, IntoEdgeReferences, IntoEdges, IntoNeighbors,
-        IntoNodeIdentifiers, IntoNodeReferences, NodeCompactIndexable, NodeIndexable, Visitable,
-    },
+    algo::Measure,
+    scored::MinScored,
+    unionfind::UnionFind,
+    visit::{EdgeRef, IntoEdges, NodeIndexable},
 };
 
-type Edge<G> = (<G as GraphBase>::NodeId, <G as GraphBase>::NodeId);
-type Subgraph<G> = HashSet<<G as GraphBase>::NodeId>;
-
-fn compute_shortest_path_length<G>(graph: G, source: G::NodeId, target: G::NodeId) -> G::EdgeWeight
-where
-    G: Visitable + IntoEdges,
-    G::NodeId: Eq + Hash,
-    G::EdgeWeight: Measure + Copy,
-{
-    let output = dijkstra(graph, source, Some(target), |e| *e.weight());
-    output[&target]
-}
-
-fn compute_metric_closure<G>(
-    graph: G,
-    terminals: &[G::NodeId],
-) -> HashMap<(usize, usize), G::EdgeWeight>
-where
-    G: Data + IntoNodeReferences + NodeIndexable + Visitable + IntoEdges,
-    G::EdgeWeight: Copy + Measure,
-    G::NodeId: PartialOrd + Eq + Hash,
-{
-    let mut closure = HashMap::new();
-    for (i, node_id_1) in terminals.iter().enumerate() {
-        for node_id_2 in terminals.iter().skip(i + 1) {
-            closure.insert(
-                (graph.to_index(*node_id_1), graph.to_index(*node_id_2)),
-                compute_shortest_path_length(graph, *node_id_1, *node_id_2),
-            );
-        }
-    }
-    closure
-}
-
-fn subgraph_edges_from_metric_closure<G>(
-    graph: G,
-    minimum_spanning_closure: G,
-) -> (Vec<Edge<G>>, Subgraph<G>)
-where
-    G: GraphBase
-        + NodeCompactIndexable
-        + IntoEdgeReferences
-        + IntoNodeIdentifiers
-        + GraphProp
-        + IntoNodeReferences,
-    G::EdgeWeight: BoundedMeasure + Copy,
-    G::NodeId: Eq + Hash + Ord + Debug,
-{
-    let mut retained_nodes = HashSet::new();
-    let mut retained_edges = Vec::new();
-    let (_, prev) = floyd_warshall_path(graph, |e| *e.weight()).unwrap();
-
-    for edge in minimum_spanning_closure.edge_references() {
-        let target = graph.to_index(edge.target());
-        let source = graph.to_index(edge.source());
-
-        let mut current = target;
-        while current != source {
-            if let Some(prev_node) = prev[source][current] {
-                retained_nodes.insert(graph.from_index(prev_node));
-                retained_nodes.insert(graph.from_index(current));
-                retained_edges.push((graph.from_index(prev_node), graph.from_index(current)));
-                current = prev_node;
-            }
-        }
-    }
-
-    (retained_edges, retained_nodes)
-}
-
-fn non_terminal_leaves<G>(graph: G, terminals: &[G::NodeId]) -> HashSet<G::NodeId>
-where
-    G: GraphBase + IntoNodeReferences + IntoNodeIdentifiers + IntoNeighbors,
-    G::NodeId: Hash + Eq + Debug,
-    G::NodeRef: Eq + Hash,
-{
-    let mut removed_leaves = HashSet::new();
-
-    let mut remaining_leaves = graph
-        .node_identifiers()
-        .filter(|node_id| {
-            graph.neighbors(*node_id).collect::<HashSet<_>>().len() == 1
-                && !terminals.contains(node_id)
-        })
-        .collect::<HashSet<_>>();
-
-    while !remaining_leaves.is_empty() {
-        remaining_leaves = graph
-            .node_identifiers()
-            .filter(|node_id| {
-                !terminals.contains(node_id)
-                    && !removed_leaves.contains(node_id)
-                    && (graph
-                        .neighbors(*node_id)
-                        .collect::<HashSet<_>>()
-                        .difference(&removed_leaves))
-                    .collect::<Vec<_>>()
-                    .len()
-                        == 1
-            })
-            .collect::<HashSet<_>>();
-
-        removed_leaves = removed_leaves
-            .union(&remaining_leaves)
-            .cloned()
-            .collect::<HashSet<_>>();
-    }
-
-    removed_leaves
-}
-
 /// [Steiner Tree][1] algorithm.
 ///
-/// Computes the Steiner tree of an undirected connected graph given a set of terminal nodes via
-/// [Kou's algorithm][2]. Implementation details are the same as in the [NetworkX
-/// implementation][3].
+/// Computes a Steiner tree of an undirected graph given a set of terminal nodes via
+/// [Kou's algorithm][2]: a minimum spanning tree of the shortest paths between the terminals is
+/// turned back into paths of the graph, of which the minimum spanning tree without non-terminal
+/// leaves is the Steiner tree. It costs at most `2 - 2 / |S|` times as much as a minimum Steiner
+/// tree.
+///
+/// See also [`steiner_tree_mehlhorn`], which finds a tree with the same guarantee faster.
 ///
-/// ## Arguments
-/// * `graph`: The undirected graph in which to find the Steiner tree.
-/// * `terminals`: A slice of node indices representing the terminals for which the Steiner tree is
-///   computed.
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `terminals`: the nodes to connect.
+/// * `edge_cost`: closure that returns the cost of an edge, which must not be negative.
 ///
-/// ## Returns
-/// A `StableGraph` containing the nodes and edges of the Steiner tree.
+/// # Returns
+/// * `Some((edges, cost))`: the edges of the Steiner tree and their total cost.
+/// * `None`: if the terminals are not connected.
 ///
-/// ## Complexity
-/// Time complexity: **O(|S| |V|²)**.
-/// where **|V|** the number of vertices (i.e nodes) and **|S|** the number of provided terminals.
+/// # Complexity
+/// * Time complexity: **O(|S| (|E| + |V| log |V|))**.
+/// * Auxiliary space: **O(|S| |V| + |E|)**.
+///
+/// where **|V|** is the number of nodes, **|E|** is the number of edges and **|S|** is the number
+/// of terminals.
 ///
 /// [1]: https://en.wikipedia.org/wiki/Steiner_tree_problem
 /// [2]: https://doi.org/10.1007/BF00288961
-/// [3]: https://networkx.org/documentation/stable/_modules/networkx/algorithms/approximation/steinertree.html#steiner_tree
 ///
 /// # Example
+/// ```rust
+/// use petgraph::{algo::steiner_tree, graph::UnGraph, visit::EdgeRef};
 ///
-/// ```
-/// use petgraph::{Graph, algo::steiner_tree::steiner_tree, graph::UnGraph};
 /// let mut graph = UnGraph::<(), i32>::default();
 /// let a = graph.add_node(());
 /// let b = graph.add_node(());
@@ -173,195 +68,461 @@ where
 ///     (d, f, 4),
 ///     (e, f, 10),
 /// ]);
-/// let terminals = vec![a, c, e, f];
-/// let tree = steiner_tree(&graph, &terminals);
-/// assert_eq!(tree.edge_weights().sum::<i32>(), 12);
+/// let terminals = [a, c, e, f];
+/// let (tree, cost) = steiner_tree(&graph, &terminals, |e| *e.weight()).unwrap();
+/// assert_eq!(cost, 12);
+/// assert_eq!(tree.len(), 4);
 /// ```
-#[cfg(feature = "stable_graph")]
-pub fn steiner_tree<N, E, Ix>(
-    graph: &UnGraph<N, E, Ix>,
-    terminals: &[NodeIndex<Ix>],
-) -> StableGraph<N, E, Undirected, Ix>
+pub fn steiner_tree<G, F, K>(
+    graph: G,
+    terminals: &[G::NodeId],
+    mut edge_cost: F,
+) -> Option<(HashSet<G::EdgeId>, K)>
 where
-    N: Default + Clone + Eq + Hash + Debug,
-    E: Copy + Eq + Ord + Measure + BoundedMeasure,
-    Ix: IndexType,
+    G: IntoEdges + NodeIndexable,
+    G::EdgeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
 {
-    let metric_closure = compute_metric_closure(&graph, terminals);
-    let metric_closure_graph: UnGraph<N, E, _> = UnGraph::from_edges(
-        metric_closure
-            .iter()
-            .map(|((node1, node2), &weight)| (*node1, *node2, weight)),
-    );
-
-    let minimum_spanning = UnGraph::from_elements(min_spanning_tree(&metric_closure_graph));
-
-    let (subgraph_edges, subgraph_nodes) =
-        subgraph_edges_from_metric_closure(graph, &minimum_spanning);
+    let terminals = terminal_indices(graph, terminals);
+    if terminals.len() <= 1 {
+        return Some((HashSet::new(), K::default()));
+    }
 
-    let mut graph = StableGraph::from(graph.clone());
-    graph.retain_edges(|graph, e| {
-        let edge = graph.edge_endpoints(e).unwrap();
-        subgraph_edges.contains(&(edge.0, edge.1)) || subgraph_edges.contains(&(edge.1, edge.0))
-    });
-    graph.retain_nodes(|_, n| subgraph_nodes.contains(&n));
+    // The shortest paths from every terminal.
+    let trees = terminals
+        .iter()
+        .map(|&t| shortest_path_tree(graph, &[t], &mut edge_cost))
+        .collect::<Vec<_>>();
+    if terminals.iter().any(|&t| trees[0].distance[t].is_none()) {
+        return None;
+    }
 
-    let non_terminal_nodes = non_terminal_leaves(&graph, terminals);
-    graph.retain_nodes(|_, n| !non_terminal_nodes.contains(&n));
+    // Prim's algorithm on the complete graph of the distances between terminals.
+    let distance = |i: usize, j: usize| trees[i].distance[terminals[j]].unwrap();
+    let mut in_tree = vec![false; terminals.len()];
+    let mut closest = (0..terminals.len())
+        .map(|j| (distance(0, j), 0))
+        .collect::<Vec<_>>();
+    in_tree[0] = true;
+    let mut edges = HashMap::new();
+    for _ in 1..terminals.len() {
+        let j = (0..terminals.len())
+            .filter(|&j| !in_tree[j])
+            .min_by(|&a, &b| compare(&closest[a].0, &closest[b].0))
+            .unwrap();
+        in_tree[j] = true;
+        trees[closest[j].1].add_path(terminals[j], &mut edges);
+        for k in 0..terminals.len() {
+            if !in_tree[k] && distance(j, k) < closest[k].0 {
+                closest[k] = (distance(j, k), j);
+            }
+        }
+    }
 
-    graph
+    Some(prune_tree(graph.node_bound(), &terminals, edges))
 }
 
-#[cfg(test)]
-mod test {
-    use alloc::vec;
-
-    use hashbrown::{HashMap, HashSet};
-
-    use super::{compute_metric_closure, non_terminal_leaves, subgraph_edges_from_metric_closure};
-    use crate::{
-        Graph, Undirected,
-        algo::{EdgeRef, UnGraph, min_spanning_tree},
-        data::FromElements,
-        graph::NodeIndex,
-    };
-
-    #[test]
-    fn test_compute_metric_closure() {
-        let mut graph = Graph::<(), i32, Undirected>::new_undirected();
-
-        let a = graph.add_node(());
-        let b = graph.add_node(());
-        let c = graph.add_node(());
-        let d = graph.add_node(());
-        let e = graph.add_node(());
-        let f = graph.add_node(());
-        graph.extend_with_edges([
-            (a, b, 7),
-            (a, f, 6),
-            (b, c, 1),
-            (b, f, 5),
-            (c, d, 1),
-            (c, e, 3),
-            (d, e, 1),
-            (d, f, 4),
-            (e, f, 10),
-        ]);
-
-        let terminals = vec![a, c, e, f];
-        let metric_closure = compute_metric_closure(&graph, &terminals);
-
-        let metric_closure_graph: UnGraph<&str, _, _> = UnGraph::from_edges(
-            metric_closure
-                .iter()
-                .map(|((node1, node2), &weight)| (*node1, *node2, weight)),
-        );
-
-        let ref_weights = HashMap::<_, _>::from([
-            ((0, 2), 8),
-            ((0, 4), 10),
-            ((0, 5), 6),
-            ((2, 4), 2),
-            ((2, 5), 5),
-            ((4, 5), 5),
-        ]);
-        for ((node1, node2), ref_weight) in ref_weights {
-            assert_eq!(metric_closure[&(node1, node2)], ref_weight);
-            assert_eq!(
-                *metric_closure_graph
-                    .edge_weight(
-                        metric_closure_graph
-                            .find_edge(NodeIndex::new(node1), NodeIndex::new(node2))
-                            .unwrap()
-                    )
-                    .unwrap(),
-                ref_weight
-            );
+/// Steiner tree algorithm of [Mehlhorn][1].
+///
+/// Computes a Steiner tree of an undirected graph given a set of terminal nodes. Like
+/// [`steiner_tree`], this is a 2-approximation, but it finds the shortest paths between
+/// terminals from the Voronoi regions of the terminals: every node belongs to its closest
+/// terminal, and every edge between two regions gives a path between their terminals. A single
+/// run of Dijkstra's algorithm from all terminals at once computes the regions.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `terminals`: the nodes to connect.
+/// * `edge_cost`: closure that returns the cost of an edge, which must not be negative.
+///
+/// # Returns
+/// * `Some((edges, cost))`: the edges of the Steiner tree and their total cost.
+/// * `None`: if the terminals are not connected.
+///
+/// # Complexity
+/// * Time complexity: **O(|E| log |V|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://doi.org/10.1016/0020-0190(88)90066-X
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::steiner_tree_mehlhorn, graph::UnGraph, visit::EdgeRef};
+///
+/// // A star whose center is not a terminal, around a cycle of expensive edges.
+/// let graph = UnGraph::<(), u32>::from_edges([
+///     (0, 1, 3),
+///     (0, 2, 3),
+///     (0, 3, 3),
+///     (1, 2, 5),
+///     (2, 3, 5),
+///     (3, 1, 5),
+/// ]);
+/// let terminals = [1.into(), 2.into(), 3.into()];
+/// let (tree, cost) = steiner_tree_mehlhorn(&graph, &terminals, |e| *e.weight()).unwrap();
+/// // Within twice the cost of the star.
+/// assert_eq!(tree.len(), 2);
+/// assert_eq!(cost, 10);
+/// ```
+pub fn steiner_tree_mehlhorn<G, F, K>(
+    graph: G,
+    terminals: &[G::NodeId],
+    mut edge_cost: F,
+) -> Option<(HashSet<G::EdgeId>, K)>
+where
+    G: IntoEdges + NodeIndexable,
+    G::EdgeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let terminals = terminal_indices(graph, terminals);
+    if terminals.len() <= 1 {
+        return Some((HashSet::new(), K::default()));
+    }
+    let voronoi = shortest_path_tree(graph, &terminals, &mut edge_cost);
+
+    // Every edge between two regions is a path between their terminals.
+    let mut bridges = Vec::new();
+    for edge in graph.edge_references() {
+        let (a, b) = (graph.to_index(edge.source()), graph.to_index(edge.target()));
+        if let (Some(da), Some(db)) = (voronoi.distance[a], voronoi.distance[b])
+            && voronoi.origin[a] != voronoi.origin[b]
+        {
+            let cost = edge_cost(edge);
+            bridges.push((da + cost + db, edge.id(), a, b, cost));
         }
     }
+    bridges.sort_by(|x, y| compare(&x.0, &y.0));
+
+    // Kruskal's algorithm on the terminals, whose paths make a tree in the graph.
+    let mut regions = UnionFind::<usize>::new(terminals.len());
+    let mut edges = HashMap::new();
+    let mut joined = 1;
+    for (_, id, a, b, cost) in bridges {
+        if regions.union(voronoi.origin[a], voronoi.origin[b]) {
+            edges.insert(id, (a, b, cost));
+            voronoi.add_path_to_tree(a, &mut edges);
+            voronoi.add_path_to_tree(b, &mut edges);
+            joined += 1;
+        }
+    }
+    if joined < terminals.len() {
+        return None;
+    }
 
-    #[test]
-    fn test_subgraph_from_metric_closure() {
-        let mut graph = Graph::<(), i32, _>::new_undirected();
-
-        let a = graph.add_node(());
-        let b = graph.add_node(());
-        let c = graph.add_node(());
-        let d = graph.add_node(());
-        let e = graph.add_node(());
-        let f = graph.add_node(());
-        graph.extend_with_edges([
-            (a, b, 7),
-            (a, f, 6),
-            (b, c, 1),
-            (b, f, 5),
-            (c, d, 1),
-            (c, e, 3),
-            (d, e, 1),
-            (d, f, 4),
-            (e, f, 10),
-        ]);
+    Some(prune_tree(graph.node_bound(), &terminals, edges))
+}
 
-        let terminals = vec![a, c, e, f];
-        let metric_closure = compute_metric_closure(&graph, &terminals);
+/// Exact Steiner tree algorithm of [Dreyfus and Wagner][1].
+///
+/// Computes a minimum Steiner tree of an undirected graph given a set of terminal nodes, by
+/// dynamic programming over the subsets of the terminals: the cheapest tree that connects a
+/// subset of the terminals and a node either splits at that node into trees of two smaller
+/// subsets, or follows a shortest path to another node.
+///
+/// The running time grows exponentially with the number of terminals, so at most 16 terminals are
+/// supported.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `terminals`: the nodes to connect.
+/// * `edge_cost`: closure that returns the cost of an edge, which must not be negative.
+///
+/// # Returns
+/// * `Some((edges, cost))`: the edges of a minimum Steiner tree and their total cost.
+/// * `None`: if the terminals are not connected.
+///
+/// # Panics
+/// * If there are more than 16 distinct terminals.
+///
+/// # Complexity
+/// * Time complexity: **O(3^|S| |V| + 2^|S| (|E| + |V| log |V|))**.
+/// * Auxiliary space: **O(2^|S| |V|)**.
+///
+/// where **|V|** is the number of nodes, **|E|** is the number of edges and **|S|** is the number
+/// of terminals.
+///
+/// [1]: https://doi.org/10.1002/net.3230010302
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::steiner_tree_dreyfus_wagner, graph::UnGraph, visit::EdgeRef};
+///
+/// // A star whose center is not a terminal, around a cycle of expensive edges.
+/// let graph = UnGraph::<(), u32>::from_edges([
+///     (0, 1, 3),
+///     (0, 2, 3),
+///     (0, 3, 3),
+///     (1, 2, 5),
+///     (2, 3, 5),
+///     (3, 1, 5),
+/// ]);
+/// let terminals = [1.into(), 2.into(), 3.into()];
+/// let (tree, cost) = steiner_tree_dreyfus_wagner(&graph, &terminals, |e| *e.weight()).unwrap();
+/// assert_eq!(cost, 9);
+/// assert!(tree.iter().all(|e| graph[*e] == 3));
+/// ```
+pub fn steiner_tree_dreyfus_wagner<G, F, K>(
+    graph: G,
+    terminals: &[G::NodeId],
+    mut edge_cost: F,
+) -> Option<(HashSet<G::EdgeId>, K)>
+where
+    G: IntoEdges + NodeIndexable,
+    G::EdgeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let terminals = terminal_indices(graph, terminals);
+    assert!(
+        terminals.len() <= 16,
+        "steiner_tree_dreyfus_wagner supports at most 16 terminals"
+    );
+    if terminals.len() <= 1 {
+        return Some((HashSet::new(), K::default()));
+    }
+    let node_bound = graph.node_bound();
+    // The last terminal is the root where the trees of all other terminals meet.
+    let (&root, terminals) = terminals.split_last().unwrap();
+    let full = (1usize << terminals.len()) - 1;
+
+    // `cost[mask][v]` is the cost of the cheapest tree that connects the terminals of `mask` and
+    // `v`, which is built as `step[mask][v]`.
+    let mut cost = vec![vec![None; node_bound]; full + 1];
+    let mut step = vec![vec![Step::Leaf; node_bound]; full + 1];
+    for mask in 1..=full {
+        if mask.is_power_of_two() {
+            cost[mask][terminals[mask.trailing_zeros() as usize]] = Some(K::default());
+        } else {
+            // Split into a subset with the lowest terminal, and its complement.
+            let low = mask & mask.wrapping_neg();
+            let mut sub = (mask - 1) & mask;
+            while sub > 0 {
+                if sub & low != 0 {
+                    for v in 0..node_bound {
+                        if let (Some(a), Some(b)) = (cost[sub][v], cost[mask ^ sub][v]) {
+                            let split = a + b;
+                            if cost[mask][v].is_none_or(|c| split < c) {
+                                cost[mask][v] = Some(split);
+                                step[mask][v] = Step::Split(sub);
+                            }
+                        }
+                    }
+                }
+                sub = (sub - 1) & mask;
+            }
+        }
 
-        let metric_closure_graph: UnGraph<(), _, _> = UnGraph::from_edges(
-            metric_closure
-                .iter()
-                .map(|((node1, node2), &weight)| (*node1 as u32, *node2 as u32, weight)),
-        );
+        // Extend the trees along shortest paths.
+        let mut heap = BinaryHeap::new();
+        for (v, c) in cost[mask].iter().enumerate() {
+            if let Some(c) = *c {
+                heap.push(MinScored(c, v));
+            }
+        }
+        while let Some(MinScored(c, v)) = heap.pop() {
+            if cost[mask][v].is_some_and(|best| best < c) {
+                continue;
+            }
+            for edge in graph.edges(graph.from_index(v)) {
+                let w = graph.to_index(edge.target());
+                let edge_cost = edge_cost(edge);
+                let next = c + edge_cost;
+                if cost[mask][w].is_none_or(|best| next < best) {
+                    cost[mask][w] = Some(next);
+                    step[mask][w] = Step::Edge(edge.id(), v, edge_cost);
+                    heap.push(MinScored(next, w));
+                }
+            }
+        }
+    }
+    cost[full][root]?;
+
+    // The trees of subsets may share edges of cost zero, so the tree is pruned like an
+    // approximation.
+    let mut edges = HashMap::new();
+    let mut stack = vec![(full, root)];
+    while let Some((mask, v)) = stack.pop() {
+        match step[mask][v] {
+            Step::Leaf => {}
+            Step::Split(sub) => {
+                stack.push((sub, v));
+                stack.push((mask ^ sub, v));
+            }
+            Step::Edge(id, u, c) => {
+                edges.insert(id, (u, v, c));
+                stack.push((mask, u));
+            }
+        }
+    }
+    let mut all_terminals = terminals.to_vec();
+    all_terminals.push(root);
+    Some(prune_tree(node_bound, &all_terminals, edges))
+}
 
-        let minimum_spanning = UnGraph::from_elements(min_spanning_tree(&metric_closure_graph));
+/// How the cheapest tree for a subset of terminals and a node is built.
+#[derive(Clone, Copy)]
+enum Step<E, K> {
+    /// The node is the only terminal of the subset.
+    Leaf,
+    /// The trees of a subset and of its complement meet at the node.
+    Split(usize),
+    /// The tree reaches the node along an edge from another node, with the cost of the edge.
+    Edge(E, usize, K),
+}
 
-        let (subgraph_edges, _subgraph_nodes) =
-            subgraph_edges_from_metric_closure(&graph, &minimum_spanning);
+/// Deduplicate the terminals and return their indices.
+fn terminal_indices<G>(graph: G, terminals: &[G::NodeId]) -> Vec<usize>
+where
+    G: NodeIndexable,
+{
+    let mut seen = HashSet::new();
+    terminals
+        .iter()
+        .map(|&t| graph.to_index(t))
+        .filter(|&t| seen.insert(t))
+        .collect()
+}
 
-        graph.retain_edges(|graph, e| {
-            let edge = graph.edge_endpoints(e).unwrap();
-            subgraph_edges.contains(&(edge.0, edge.1))
-        });
+fn compare<K: PartialOrd>(a: &K, b: &K) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
 
-        let mut ref_graph = UnGraph::<(), _>::new_undirected();
-        let ref_a = ref_graph.add_node(());
-        let _ = ref_graph.add_node(());
-        let ref_c = ref_graph.add_node(());
-        let ref_d = ref_graph.add_node(());
-        let ref_e = ref_graph.add_node(());
-        let ref_f = ref_graph.add_node(());
+/// The shortest paths from the closest of some source nodes to every node.
+struct ShortestPathTree<E, K> {
+    distance: Vec<Option<K>>,
+    /// The position among the sources of the closest source.
+    origin: Vec<usize>,
+    /// The last edge on the shortest path to a node, with the previous node and the edge cost.
+    predecessor: Vec<Option<(E, usize, K)>>,
+}
 
-        ref_graph.extend_with_edges([
-            (ref_c, ref_d, 1),
-            (ref_d, ref_e, 1),
-            (ref_d, ref_f, 4),
-            (ref_a, ref_f, 6),
-        ]);
+impl<E, K> ShortestPathTree<E, K>
+where
+    E: Copy + Eq + Hash,
+    K: Copy,
+{
+    /// Add the edges of the path from the source to `v` to `edges`.
+    fn add_path(&self, mut v: usize, edges: &mut HashMap<E, (usize, usize, K)>) {
+        while let Some((id, u, cost)) = self.predecessor[v] {
+            edges.insert(id, (u, v, cost));
+            v = u;
+        }
+    }
 
-        for ref_edge in ref_graph.edge_references() {
-            let (edge_index, _) = graph
-                .find_edge_undirected(ref_edge.source(), ref_edge.target())
-                .unwrap();
-            let edge_endpoints = graph.edge_endpoints(edge_index).unwrap();
-            assert_eq!(graph.edge_weight(edge_index).unwrap(), ref_edge.weight());
-            assert_eq!(edge_endpoints.0, ref_edge.source());
-            assert_eq!(edge_endpoints.1, ref_edge.target());
+    /// Add the edges of the path from the source to `v` to `edges`, which only contains paths of
+    /// this tree, so that the rest of the path is known once an edge is.
+    fn add_path_to_tree(&self, mut v: usize, edges: &mut HashMap<E, (usize, usize, K)>) {
+        while let Some((id, u, cost)) = self.predecessor[v] {
+            if edges.insert(id, (u, v, cost)).is_some() {
+                break;
+            }
+            v = u;
         }
     }
+}
 
-    #[test]
-    fn test_remove_non_terminal_nodes() {
-        let mut graph = Graph::<(), i32, _>::new_undirected();
+/// Run Dijkstra's algorithm from all of the `sources` at once.
+fn shortest_path_tree<G, F, K>(
+    graph: G,
+    sources: &[usize],
+    edge_cost: &mut F,
+) -> ShortestPathTree<G::EdgeId, K>
+where
+    G: IntoEdges + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let node_bound = graph.node_bound();
+    let mut tree = ShortestPathTree {
+        distance: vec![None; node_bound],
+        origin: vec![usize::MAX; node_bound],
+        predecessor: vec![None; node_bound],
+    };
+    let mut visited = vec![false; node_bound];
+    let mut heap = BinaryHeap::new();
+    for (i, &s) in sources.iter().enumerate() {
+        tree.distance[s] = Some(K::default());
+        tree.origin[s] = i;
+        heap.push(MinScored(K::default(), s));
+    }
+    while let Some(MinScored(d, v)) = heap.pop() {
+        if visited[v] {
+            continue;
+        }
+        visited[v] = true;
+        for edge in graph.edges(graph.from_index(v)) {
+            let w = graph.to_index(edge.target());
+            if visited[w] {
+                continue;
+            }
+            let cost = edge_cost(edge);
+            let next = d + cost;
+            if tree.distance[w].is_none_or(|best| next < best) {
+                tree.distance[w] = Some(next);
+                tree.origin[w] = tree.origin[v];
+                tree.predecessor[w] = Some((edge.id(), v, cost));
+                heap.push(MinScored(next, w));
+            }
+        }
+    }
+    tree
+}
 
-        let a = graph.add_node(());
-        let b = graph.add_node(());
-        let c = graph.add_node(());
-        let d = graph.add_node(());
-        let e = graph.add_node(());
-        let f = graph.add_node(());
-        graph.extend_with_edges([(a, b, 7), (b, c, 6), (c, d, 1), (d, e, 5), (e, f, 1)]);
+/// Turn a connected set of edges into a Steiner tree: take a minimum spanning tree of the edges,
+/// and remove its non-terminal leaves.
+fn prune_tree<E, K>(
+    node_bound: usize,
+    terminals: &[usize],
+    edges: HashMap<E, (usize, usize, K)>,
+) -> (HashSet<E>, K)
+where
+    E: Copy + Eq + Hash,
+    K: Measure + Copy,
+{
+    let mut edges = edges.into_iter().collect::<Vec<_>>();
+    edges.sort_by(|a, b| compare(&a.1.2, &b.1.2));
+    let mut subgraphs = UnionFind::<usize>::new(node_bound);
+    edges.retain(|&(_, (a, b, _))| subgraphs.union(a, b));
+
+    let mut is_terminal = vec![false; node_bound];
+    for &t in terminals {
+        is_terminal[t] = true;
+    }
+    let mut incident = vec![Vec::new(); node_bound];
+    for (i, &(_, (a, b, _))) in edges.iter().enumerate() {
+        incident[a].push(i);
+        incident[b].push(i);
+    }
+    let mut removed = vec![false; edges.len()];
+    let mut degree = incident.iter().map(Vec::len).collect::<Vec<_>>();
+    let mut leaves = (0..node_bound)
+        .filter(|&v| degree[v] == 1 && !is_terminal[v])
+        .collect::<Vec<_>>();
+    while let Some(v) = leaves.pop() {
+        let Some(&i) = incident[v].iter().find(|&&i| !removed[i]) else {
+            continue;
+        };
+        removed[i] = true;
+        let (_, (a, b, _)) = edges[i];
+        let other = if a == v { b } else { a };
+        degree[v] -= 1;
+        degree[other] -= 1;
+        if degree[other] == 1 && !is_terminal[other] {
+            leaves.push(other);
+        }
+    }
 
-        let terminals = vec![a, c];
-        let non_terminal_nodes = non_terminal_leaves(&graph, &terminals);
-        let non_terminal_refs = HashSet::from([d, e, f]);
-        assert_eq!(non_terminal_refs, non_terminal_nodes);
+    let mut tree = HashSet::new();
+    let mut total = K::default();
+    for (i, (id, (_, _, cost))) in edges.into_iter().enumerate() {
+        if !removed[i] {
+            tree.insert(id);
+            total = total + cost;
+        }
     }
+    (tree, total)
 }
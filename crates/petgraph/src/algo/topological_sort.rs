@@ -0,0 +1,467 @@
+//! Deterministic topological orders of directed acyclic graphs.
+//!
+//! * [`toposort_by`] and [`toposort_by_key`] use Kahn's algorithm with a priority queue, to return
+//!   the lexicographically smallest topological order under a comparator or key.
+//! * [`all_toposorts`] lazily enumerates every topological order.
+//! * [`topological_generations`] groups the nodes by the length of the longest path that ends at
+//!   them, so that the nodes of every group can be processed in parallel.
+//!
+//! Unlike [`toposort`][crate::algo::toposort], which returns whatever order its depth-first search
+//! happens to produce, these only depend on the order of [`IntoNodeIdentifiers`] and of the given
+//! comparator or key.
+
+use alloc::{vec, vec::Vec};
+use core::cmp::Ordering;
+
+use crate::{
+    Direction::Incoming,
+    algo::{Cycle, node_positions},
+    visit::{GraphBase, IntoNeighborsDirected, IntoNodeIdentifiers, NodeIndexable},
+};
+
+/// Return the lexicographically smallest topological order of a directed graph, comparing nodes
+/// with `compare`.
+///
+/// This is Kahn's algorithm, which repeatedly removes a node without incoming edges, with the
+/// least such node removed first. Nodes that `compare` considers equal are ordered as in
+/// [`IntoNodeIdentifiers`], so the result only depends on `compare` and the node order.
+///
+/// # Arguments
+/// * `g`: a directed graph.
+/// * `compare`: a function that compares two nodes.
+///
+/// # Returns
+/// * `Ok`: a vector of nodes in topological order: each node is ordered before its successors.
+/// * `Err`: [`Cycle`] with a node on a cycle, if the graph is not acyclic. Self-loops are also
+///   cycles.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| log |V| + |E|)**, with as many calls to `compare`.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::toposort_by, prelude::*};
+///
+/// let mut g = DiGraph::<&str, ()>::new();
+/// let socks = g.add_node("socks");
+/// let shoes = g.add_node("shoes");
+/// let pants = g.add_node("pants");
+/// let belt = g.add_node("belt");
+/// g.extend_with_edges(&[(socks, shoes), (pants, shoes), (pants, belt)]);
+///
+/// // Get dressed in alphabetical order, as far as possible.
+/// let order = toposort_by(&g, |a, b| g[a].cmp(g[b])).unwrap();
+/// assert_eq!(order, [pants, belt, socks, shoes]);
+///
+/// g.add_edge(shoes, socks, ());
+/// assert!(toposort_by(&g, |a, b| g[a].cmp(g[b])).is_err());
+/// ```
+pub fn toposort_by<G, F>(g: G, mut compare: F) -> Result<Vec<G::NodeId>, Cycle<G::NodeId>>
+where
+    G: IntoNeighborsDirected + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::NodeId, G::NodeId) -> Ordering,
+{
+    let dag = Dag::new(g);
+    let nodes = &dag.nodes;
+    dag.kahn(|a, b| compare(nodes[a], nodes[b]).then(a.cmp(&b)))
+        .map(|order| order.into_iter().map(|v| nodes[v]).collect())
+        .map_err(|in_degree| Cycle(dag.node_on_cycle(g, &in_degree)))
+}
+
+/// Return the lexicographically smallest topological order of a directed graph, comparing nodes
+/// by `key`.
+///
+/// This is like [`toposort_by`], but `key` is called only once for every node. Nodes with the
+/// same key are ordered as in [`IntoNodeIdentifiers`].
+///
+/// # Arguments
+/// * `g`: a directed graph.
+/// * `key`: a function that returns the key of a node.
+///
+/// # Returns
+/// * `Ok`: a vector of nodes in topological order: each node is ordered before its successors.
+/// * `Err`: [`Cycle`] with a node on a cycle, if the graph is not acyclic. Self-loops are also
+///   cycles.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| log |V| + |E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::toposort_by_key, prelude::*};
+///
+/// // Build steps, with the step that must come first as the source of each edge.
+/// let mut g = DiGraph::<&str, ()>::new();
+/// let fetch = g.add_node("fetch");
+/// let lint = g.add_node("lint");
+/// let build = g.add_node("build");
+/// let test = g.add_node("test");
+/// g.extend_with_edges(&[(fetch, build), (build, test), (fetch, lint)]);
+///
+/// assert_eq!(
+///     toposort_by_key(&g, |v| g[v]).unwrap(),
+///     [fetch, build, lint, test]
+/// );
+/// // Prefer the steps that were added last.
+/// assert_eq!(
+///     toposort_by_key(&g, |v| core::cmp::Reverse(v)).unwrap(),
+///     [fetch, build, test, lint]
+/// );
+/// ```
+pub fn toposort_by_key<G, F, K>(g: G, mut key: F) -> Result<Vec<G::NodeId>, Cycle<G::NodeId>>
+where
+    G: IntoNeighborsDirected + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::NodeId) -> K,
+    K: Ord,
+{
+    let dag = Dag::new(g);
+    let nodes = &dag.nodes;
+    let keys = nodes.iter().map(|&v| key(v)).collect::<Vec<_>>();
+    dag.kahn(|a, b| keys[a].cmp(&keys[b]).then(a.cmp(&b)))
+        .map(|order| order.into_iter().map(|v| nodes[v]).collect())
+        .map_err(|in_degree| Cycle(dag.node_on_cycle(g, &in_degree)))
+}
+
+/// Group the nodes of a directed graph into generations: the nodes in generation **k** are those
+/// where the longest path that ends at them has **k** edges.
+///
+/// Every edge goes from an earlier generation to a later one, so the nodes within a generation
+/// do not depend on each other, and all nodes of a generation can be scheduled at the same time
+/// once the earlier generations are done. Concatenating the generations gives a topological
+/// order.
+///
+/// The nodes of every generation are ordered as in [`IntoNodeIdentifiers`].
+///
+/// # Arguments
+/// * `g`: a directed graph.
+///
+/// # Returns
+/// * `Ok`: the generations, starting with the nodes without incoming edges.
+/// * `Err`: [`Cycle`] with a node on a cycle, if the graph is not acyclic. Self-loops are also
+///   cycles.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| log |V| + |E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::topological_generations, prelude::*};
+///
+/// // Compile four crates, where `app` depends on all others and `net` depends on `core`.
+/// let mut g = DiGraph::<&str, ()>::new();
+/// let core = g.add_node("core");
+/// let log = g.add_node("log");
+/// let net = g.add_node("net");
+/// let app = g.add_node("app");
+/// g.extend_with_edges(&[(core, net), (core, app), (log, app), (net, app)]);
+///
+/// assert_eq!(
+///     topological_generations(&g).unwrap(),
+///     [vec![core, log], vec![net], vec![app]]
+/// );
+/// ```
+pub fn topological_generations<G>(g: G) -> Result<Vec<Vec<G::NodeId>>, Cycle<G::NodeId>>
+where
+    G: IntoNeighborsDirected + IntoNodeIdentifiers + NodeIndexable,
+{
+    let mut dag = Dag::new(g);
+    let mut generation = (0..dag.nodes.len())
+        .filter(|&v| dag.in_degree[v] == 0)
+        .collect::<Vec<_>>();
+    let mut generations = Vec::new();
+    let mut done = 0;
+    while !generation.is_empty() {
+        done += generation.len();
+        let mut next = Vec::new();
+        for &v in &generation {
+            dag.remove(v, |w| next.push(w));
+        }
+        next.sort_unstable();
+        generations.push(generation.iter().map(|&v| dag.nodes[v]).collect());
+        generation = next;
+    }
+    if done < dag.nodes.len() {
+        return Err(Cycle(dag.node_on_cycle(g, &dag.in_degree)));
+    }
+    Ok(generations)
+}
+
+/// Enumerate all topological orders of a directed graph.
+///
+/// The orders are generated in lexicographic order, comparing nodes by their position in
+/// [`IntoNodeIdentifiers`]. The number of orders can grow exponentially with the number of nodes,
+/// up to **|V|!** for a graph without edges; the iterator is lazy, so [`Iterator::take`] can be
+/// used to look at the first few only.
+///
+/// A graph with a cycle has no topological orders, and an empty graph has one, which is empty.
+///
+/// # Arguments
+/// * `g`: a directed graph.
+///
+/// # Returns
+/// An iterator over the topological orders of the graph, each a `Vec<G::NodeId>`.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|)** per order, and **O(|V| + |E|)** to find that there are none.
+/// * Auxiliary space: **O(|V|² + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::all_toposorts, prelude::*};
+///
+/// let mut g = DiGraph::<&str, ()>::new();
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// let c = g.add_node("c");
+/// let d = g.add_node("d");
+/// g.extend_with_edges(&[(a, b), (a, c), (b, d), (c, d)]);
+///
+/// let orders = all_toposorts(&g).collect::<Vec<_>>();
+/// assert_eq!(orders, [[a, b, c, d], [a, c, b, d]]);
+///
+/// g.add_edge(d, a, ());
+/// assert_eq!(all_toposorts(&g).count(), 0);
+/// ```
+pub fn all_toposorts<G>(g: G) -> AllToposorts<G>
+where
+    G: IntoNeighborsDirected + IntoNodeIdentifiers + NodeIndexable,
+{
+    let dag = Dag::new(g);
+    let ready = (0..dag.nodes.len())
+        .filter(|&v| dag.in_degree[v] == 0)
+        .collect::<Vec<_>>();
+    // Without a complete order to start from, the search would only find dead ends.
+    let acyclic = dag.kahn(|a, b| a.cmp(&b)).is_ok();
+    AllToposorts {
+        dag,
+        order: Vec::new(),
+        stack: if acyclic {
+            vec![(ready.clone(), 0)]
+        } else {
+            Vec::new()
+        },
+        ready,
+    }
+}
+
+/// An iterator over the topological orders of a directed graph.
+///
+/// See [`all_toposorts`].
+#[derive(Clone)]
+pub struct AllToposorts<G>
+where
+    G: GraphBase,
+{
+    dag: Dag<G::NodeId>,
+    /// The positions of the nodes of the current prefix of an order.
+    order: Vec<usize>,
+    /// For every node in `order` and the next one: the nodes that were ready to be chosen, and
+    /// how many of them have been tried so far.
+    stack: Vec<(Vec<usize>, usize)>,
+    /// The nodes that are not in `order` but whose predecessors all are.
+    ready: Vec<usize>,
+}
+
+impl<G> Iterator for AllToposorts<G>
+where
+    G: GraphBase,
+{
+    type Item = Vec<G::NodeId>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (candidates, tried) = self.stack.last_mut()?;
+            if *tried > 0 {
+                // Put back the node that was tried last.
+                let v = self.order.pop().unwrap();
+                let ready = &mut self.ready;
+                self.dag.restore(v, |w| {
+                    let i = ready.iter().position(|&u| u == w).unwrap();
+                    ready.swap_remove(i);
+                });
+                self.ready.push(v);
+            }
+            if *tried == candidates.len() {
+                self.stack.pop();
+                // The order of the empty graph is complete before choosing any node.
+                if self.dag.nodes.is_empty() {
+                    return Some(Vec::new());
+                }
+                continue;
+            }
+
+            let v = candidates[*tried];
+            *tried += 1;
+            let i = self.ready.iter().position(|&u| u == v).unwrap();
+            self.ready.swap_remove(i);
+            let ready = &mut self.ready;
+            self.dag.remove(v, |w| ready.push(w));
+            self.order.push(v);
+            if self.order.len() == self.dag.nodes.len() {
+                return Some(self.order.iter().map(|&v| self.dag.nodes[v]).collect());
+            }
+            let mut candidates = self.ready.clone();
+            candidates.sort_unstable();
+            self.stack.push((candidates, 0));
+        }
+    }
+}
+
+/// The successors and the number of remaining predecessors of the nodes of a graph, by their
+/// position in [`IntoNodeIdentifiers`].
+#[derive(Clone)]
+struct Dag<N> {
+    nodes: Vec<N>,
+    /// The position of every node by its index.
+    positions: Vec<usize>,
+    successors: Vec<Vec<usize>>,
+    in_degree: Vec<usize>,
+}
+
+impl<N: Copy> Dag<N> {
+    fn new<G>(g: G) -> Self
+    where
+        G: IntoNeighborsDirected + IntoNodeIdentifiers + NodeIndexable<NodeId = N>,
+    {
+        let (nodes, positions) = node_positions(g);
+        let mut in_degree = vec![0; nodes.len()];
+        let successors = nodes
+            .iter()
+            .map(|&v| {
+                g.neighbors(v)
+                    .map(|w| {
+                        let w = positions[g.to_index(w)];
+                        in_degree[w] += 1;
+                        w
+                    })
+                    .collect()
+            })
+            .collect();
+        Dag {
+            nodes,
+            positions,
+            successors,
+            in_degree,
+        }
+    }
+
+    /// Remove the edges from `v`, and call `ready` on the successors that are left without
+    /// incoming edges.
+    fn remove(&mut self, v: usize, mut ready: impl FnMut(usize)) {
+        for &w in &self.successors[v] {
+            self.in_degree[w] -= 1;
+            if self.in_degree[w] == 0 {
+                ready(w);
+            }
+        }
+    }
+
+    /// Undo [`Dag::remove`], calling `unready` on the successors that get incoming edges again.
+    fn restore(&mut self, v: usize, mut unready: impl FnMut(usize)) {
+        for &w in &self.successors[v] {
+            if self.in_degree[w] == 0 {
+                unready(w);
+            }
+            self.in_degree[w] += 1;
+        }
+    }
+
+    /// Kahn's algorithm, which always removes the least ready node under `compare`. Return the
+    /// order, or the number of incoming edges that are left for every node if there is a cycle.
+    fn kahn(
+        &self,
+        mut compare: impl FnMut(usize, usize) -> Ordering,
+    ) -> Result<Vec<usize>, Vec<usize>> {
+        let mut in_degree = self.in_degree.clone();
+        let mut heap = Vec::new();
+        for v in (0..self.nodes.len()).filter(|&v| in_degree[v] == 0) {
+            heap_push(&mut heap, v, &mut compare);
+        }
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(v) = heap_pop(&mut heap, &mut compare) {
+            order.push(v);
+            for &w in &self.successors[v] {
+                in_degree[w] -= 1;
+                if in_degree[w] == 0 {
+                    heap_push(&mut heap, w, &mut compare);
+                }
+            }
+        }
+        if order.len() < self.nodes.len() {
+            return Err(in_degree);
+        }
+        Ok(order)
+    }
+
+    /// Return a node on a cycle, given the number of incoming edges that are left for every node
+    /// after Kahn's algorithm got stuck.
+    fn node_on_cycle<G>(&self, g: G, in_degree: &[usize]) -> N
+    where
+        G: IntoNeighborsDirected + NodeIndexable<NodeId = N>,
+    {
+        // Every node that is left has a predecessor that is left, so walking back from any of
+        // them must run into a cycle.
+        let mut seen = vec![false; self.nodes.len()];
+        let mut v = (0..self.nodes.len()).find(|&v| in_degree[v] > 0).unwrap();
+        while !seen[v] {
+            seen[v] = true;
+            v = g
+                .neighbors_directed(self.nodes[v], Incoming)
+                .map(|u| self.positions[g.to_index(u)])
+                .find(|&u| in_degree[u] > 0)
+                .unwrap();
+        }
+        self.nodes[v]
+    }
+}
+
+/// Push `v` onto the binary min-heap `heap` ordered by `compare`.
+fn heap_push(heap: &mut Vec<usize>, v: usize, compare: &mut impl FnMut(usize, usize) -> Ordering) {
+    heap.push(v);
+    let mut i = heap.len() - 1;
+    while i > 0 {
+        let parent = (i - 1) / 2;
+        if compare(heap[i], heap[parent]).is_ge() {
+            break;
+        }
+        heap.swap(i, parent);
+        i = parent;
+    }
+}
+
+/// Pop the least element of the binary min-heap `heap` ordered by `compare`.
+fn heap_pop(
+    heap: &mut Vec<usize>,
+    compare: &mut impl FnMut(usize, usize) -> Ordering,
+) -> Option<usize> {
+    let last = heap.pop()?;
+    let Some(&top) = heap.first() else {
+        return Some(last);
+    };
+    heap[0] = last;
+    let mut i = 0;
+    loop {
+        let mut least = i;
+        for child in [2 * i + 1, 2 * i + 2] {
+            if child < heap.len() && compare(heap[child], heap[least]).is_lt() {
+                least = child;
+            }
+        }
+        if least == i {
+            return Some(top);
+        }
+        heap.swap(i, least);
+        i = least;
+    }
+}
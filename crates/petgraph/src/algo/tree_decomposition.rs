@@ -0,0 +1,532 @@
+//! Tree decompositions and treewidth.
+//!
+//! A [tree decomposition][1] of a graph is a tree whose nodes are *bags* of graph nodes, such that
+//! every graph node and every edge is contained in a bag, and the bags that contain any given
+//! graph node form a subtree. Its *width* is the size of its largest bag minus one, and the
+//! *treewidth* of a graph is the smallest width of any of its tree decompositions.
+//!
+//! Many problems that are hard on general graphs can be solved by dynamic programming over a tree
+//! decomposition, in time that is exponential only in its width.
+//!
+//! * [`treewidth_min_degree`] and [`treewidth_min_fill_in`] compute tree decompositions with
+//!   elimination heuristics, which are fast but not necessarily of minimum width.
+//! * [`treewidth_exact`] computes a tree decomposition of minimum width of a small graph.
+//! * [`elimination_tree_decomposition`] turns any elimination ordering into a tree decomposition,
+//!   for example a [`perfect_elimination_ordering`](crate::algo::perfect_elimination_ordering).
+//! * [`is_tree_decomposition`] checks that a tree of bags is a tree decomposition of a graph.
+//!
+//! All functions treat the graph as undirected and ignore self-loops and parallel edges.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Tree_decomposition
+
+use alloc::{
+    collections::{BTreeSet, BinaryHeap},
+    vec,
+    vec::Vec,
+};
+use core::{cmp::Reverse, hash::Hash};
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::{
+    algo::simple_adjacency,
+    graph::{NodeIndex, UnGraph},
+    unionfind::UnionFind,
+    visit::{EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable},
+};
+
+/// Compute a tree decomposition with the *minimum degree* heuristic.
+///
+/// The nodes are eliminated one by one, always a node of minimum degree, where eliminating a node
+/// connects all its neighbors to each other. See [`elimination_tree_decomposition`] for how the
+/// elimination ordering becomes a tree decomposition.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+///
+/// # Returns
+/// * A tree decomposition, with one bag for every node of the graph.
+/// * Its width, which is an upper bound of the treewidth of the graph, or zero if the graph has no
+///   edges.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| · w² log |V|)** for the elimination and **O(|V| · w²)** for the
+///   decomposition.
+/// * Auxiliary space: **O(|V| · w)**.
+///
+/// where **|V|** is the number of nodes and **w** is the width of the decomposition.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{
+///     algo::{is_tree_decomposition, treewidth_min_degree},
+///     prelude::*,
+/// };
+///
+/// // A cycle has treewidth two.
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+/// let (decomposition, width) = treewidth_min_degree(&g);
+/// assert_eq!(width, 2);
+/// assert_eq!(decomposition.node_count(), 5);
+/// assert!(is_tree_decomposition(&g, &decomposition));
+/// ```
+pub fn treewidth_min_degree<G>(graph: G) -> (UnGraph<HashSet<G::NodeId>, ()>, usize)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let (nodes, adjacency) = simple_adjacency(graph);
+    let order = greedy_elimination(&adjacency, |adjacency, v| adjacency[v].len());
+    decompose(&nodes, &adjacency, &order)
+}
+
+/// Compute a tree decomposition with the *minimum fill-in* heuristic.
+///
+/// The nodes are eliminated one by one, always a node whose elimination adds the fewest edges
+/// between its neighbors, where eliminating a node connects all its neighbors to each other. This
+/// is slower than [`treewidth_min_degree`], but often finds decompositions of smaller width. See
+/// [`elimination_tree_decomposition`] for how the elimination ordering becomes a tree
+/// decomposition.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+///
+/// # Returns
+/// * A tree decomposition, with one bag for every node of the graph.
+/// * Its width, which is an upper bound of the treewidth of the graph, or zero if the graph has no
+///   edges.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| · w⁴ log |V|)**.
+/// * Auxiliary space: **O(|V| · w)**.
+///
+/// where **|V|** is the number of nodes and **w** is the width of the decomposition.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{
+///     algo::{is_tree_decomposition, treewidth_min_fill_in},
+///     prelude::*,
+/// };
+///
+/// // A 3 × 3 grid has treewidth three.
+/// let mut g = UnGraph::<(), ()>::default();
+/// for i in 0..3 {
+///     for j in 0..3 {
+///         if i < 2 {
+///             g.extend_with_edges([(3 * i + j, 3 * i + j + 3)]);
+///         }
+///         if j < 2 {
+///             g.extend_with_edges([(3 * i + j, 3 * i + j + 1)]);
+///         }
+///     }
+/// }
+/// let (decomposition, width) = treewidth_min_fill_in(&g);
+/// assert_eq!(width, 3);
+/// assert!(is_tree_decomposition(&g, &decomposition));
+/// ```
+pub fn treewidth_min_fill_in<G>(graph: G) -> (UnGraph<HashSet<G::NodeId>, ()>, usize)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let (nodes, adjacency) = simple_adjacency(graph);
+    let order = greedy_elimination(&adjacency, fill_in);
+    decompose(&nodes, &adjacency, &order)
+}
+
+/// Compute a tree decomposition of minimum width.
+///
+/// The treewidth is found by dynamic programming over the sets of nodes that are eliminated first,
+/// following [Bodlaender, Fomin, Koster, Kratsch and Thilikos][1]. This takes exponential time
+/// and memory, so it is only practical for graphs with up to about twenty nodes.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+///
+/// # Returns
+/// * A tree decomposition of minimum width, with one bag for every node of the graph.
+/// * The treewidth of the graph, or zero if the graph has no edges.
+///
+/// # Panics
+/// * If the graph has more than 20 nodes.
+///
+/// # Complexity
+/// * Time complexity: **O(2^|V| · |V|³)**.
+/// * Auxiliary space: **O(2^|V|)**.
+///
+/// where **|V|** is the number of nodes.
+///
+/// [1]: https://doi.org/10.1145/2390176.2390188
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::treewidth_exact, prelude::*};
+///
+/// // The Petersen graph has treewidth four.
+/// let mut g = UnGraph::<(), ()>::from_edges((0..5).map(|i| (i, (i + 1) % 5)));
+/// g.extend_with_edges((0..5).map(|i| (i, i + 5)));
+/// g.extend_with_edges((0..5).map(|i| (i + 5, (i + 2) % 5 + 5)));
+/// let (decomposition, width) = treewidth_exact(&g);
+/// assert_eq!(width, 4);
+/// assert!(decomposition.node_weights().all(|bag| bag.len() <= 5));
+/// ```
+pub fn treewidth_exact<G>(graph: G) -> (UnGraph<HashSet<G::NodeId>, ()>, usize)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let (nodes, adjacency) = simple_adjacency(graph);
+    let n = nodes.len();
+    assert!(n <= 20, "treewidth_exact supports at most 20 nodes");
+    let masks = adjacency
+        .iter()
+        .map(|neighbors| neighbors.iter().fold(0u64, |mask, &w| mask | 1 << w))
+        .collect::<Vec<_>>();
+
+    // The number of nodes that are adjacent to `v` once the nodes in `eliminated` are eliminated,
+    // that is, that can be reached from `v` through `eliminated`.
+    let later_degree = |eliminated: u64, v: usize| {
+        let mut reached = 1u64 << v;
+        let mut frontier = reached;
+        while frontier != 0 {
+            let w = frontier.trailing_zeros() as usize;
+            frontier &= frontier - 1;
+            let next = masks[w] & !reached;
+            reached |= next;
+            frontier |= next & eliminated;
+        }
+        (reached & !eliminated & !(1 << v)).count_ones() as u8
+    };
+
+    // The smallest width of eliminating the nodes of every set first.
+    let mut width = vec![0u8; 1 << n];
+    for set in 1..1usize << n {
+        let set = set as u64;
+        let mut best = u8::MAX;
+        let mut remaining = set;
+        while remaining != 0 {
+            let v = remaining.trailing_zeros() as usize;
+            remaining &= remaining - 1;
+            let before = set & !(1 << v);
+            let w = width[before as usize];
+            if w < best {
+                best = best.min(w.max(later_degree(before, v)));
+            }
+        }
+        width[set as usize] = best;
+    }
+
+    // Take the nodes in reverse elimination order.
+    let mut order = Vec::with_capacity(n);
+    let mut set = (1u64 << n) - 1;
+    while set != 0 {
+        let v = (0..n)
+            .filter(|&v| set & (1 << v) != 0)
+            .find(|&v| {
+                let before = set & !(1 << v);
+                width[before as usize].max(later_degree(before, v)) == width[set as usize]
+            })
+            .unwrap();
+        order.push(v);
+        set &= !(1 << v);
+    }
+    order.reverse();
+    decompose(&nodes, &adjacency, &order)
+}
+
+/// Compute the tree decomposition of an elimination ordering.
+///
+/// Eliminating the nodes in the given order, where eliminating a node connects all its neighbors
+/// to each other, every node gets a bag with its neighbors at the time of its elimination. The
+/// parent of the bag is the bag of the neighbor that is eliminated next. The bags of nodes without
+/// such neighbors, one in every connected component, are joined into a path.
+///
+/// The width of the decomposition is the treewidth of the graph for some elimination orderings,
+/// for example a perfect elimination ordering of a chordal graph.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `order`: every node of the graph exactly once.
+///
+/// # Returns
+/// * A tree decomposition, with one bag for every node of the graph.
+/// * Its width, or zero if the graph has no edges.
+///
+/// # Panics
+/// * If `order` does not contain every node of the graph exactly once.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| · w²)**.
+/// * Auxiliary space: **O(|V| · w)**.
+///
+/// where **|V|** is the number of nodes and **w** is the width of the decomposition.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{
+///     algo::{elimination_tree_decomposition, is_tree_decomposition},
+///     prelude::*,
+/// };
+///
+/// // A star is a tree, so its treewidth is one, unless the center is eliminated first.
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3)]);
+/// let leaves_first = [1, 2, 3, 0].map(NodeIndex::new);
+/// let (decomposition, width) = elimination_tree_decomposition(&g, &leaves_first);
+/// assert_eq!(width, 1);
+/// assert!(is_tree_decomposition(&g, &decomposition));
+///
+/// let center_first = [0, 1, 2, 3].map(NodeIndex::new);
+/// assert_eq!(elimination_tree_decomposition(&g, &center_first).1, 3);
+/// ```
+pub fn elimination_tree_decomposition<G>(
+    graph: G,
+    order: &[G::NodeId],
+) -> (UnGraph<HashSet<G::NodeId>, ()>, usize)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let (nodes, adjacency) = simple_adjacency(graph);
+    let positions = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (v, i))
+        .collect::<HashMap<_, _>>();
+    let mut seen = vec![false; nodes.len()];
+    let order = order
+        .iter()
+        .map(|v| {
+            let &i = positions
+                .get(v)
+                .expect("the elimination ordering contains a node that is not in the graph");
+            assert!(!seen[i], "the elimination ordering contains a node twice");
+            seen[i] = true;
+            i
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        order.len(),
+        nodes.len(),
+        "the elimination ordering does not contain every node"
+    );
+    decompose(&nodes, &adjacency, &order)
+}
+
+/// Return `true` if `decomposition` is a tree decomposition of the undirected graph `graph`.
+///
+/// That is, `decomposition` is a tree, every node of the graph is contained in a bag, the
+/// endpoints of every edge are contained in a common bag, and the bags that contain any given
+/// node form a connected subtree. Bags must not contain nodes that are not in the graph. A graph
+/// without nodes only has the empty decomposition.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `decomposition`: a tree of bags of nodes of `graph`.
+///
+/// # Returns
+/// * `true` if `decomposition` is a tree decomposition of `graph`, `false` otherwise.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E| · b + B · b)**.
+/// * Auxiliary space: **O(|V| + B · b)**.
+///
+/// where **|V|** is the number of nodes, **|E|** is the number of edges, **B** is the number of
+/// bags and **b** is the number of bags that contain a node, at most.
+///
+/// # Example
+/// ```rust
+/// use hashbrown::HashSet;
+/// use petgraph::{algo::is_tree_decomposition, prelude::*};
+///
+/// // A path a - b - c.
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+/// let (a, b, c) = (NodeIndex::new(0), NodeIndex::new(1), NodeIndex::new(2));
+///
+/// let mut decomposition = UnGraph::<HashSet<NodeIndex>, ()>::default();
+/// let ab = decomposition.add_node(HashSet::from_iter([a, b]));
+/// let bc = decomposition.add_node(HashSet::from_iter([b, c]));
+/// decomposition.add_edge(ab, bc, ());
+/// assert!(is_tree_decomposition(&g, &decomposition));
+///
+/// // The bags that contain b must be connected.
+/// let ca = decomposition.add_node(HashSet::from_iter([c, a]));
+/// decomposition.add_edge(ca, ab, ());
+/// decomposition[ab].remove(&b);
+/// decomposition[ab].insert(a);
+/// assert!(!is_tree_decomposition(&g, &decomposition));
+/// ```
+pub fn is_tree_decomposition<G>(graph: G, decomposition: &UnGraph<HashSet<G::NodeId>, ()>) -> bool
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    // The decomposition must be a tree.
+    let bag_count = decomposition.node_count();
+    if bag_count == 0 {
+        return graph.node_identifiers().next().is_none();
+    }
+    if decomposition.edge_count() != bag_count - 1 {
+        return false;
+    }
+    let mut components = UnionFind::new(bag_count);
+    for edge in decomposition.edge_references() {
+        if !components.union(edge.source().index(), edge.target().index()) {
+            return false;
+        }
+    }
+
+    // The bags that contain every node.
+    let mut bags_of = graph
+        .node_identifiers()
+        .map(|v| (v, Vec::new()))
+        .collect::<HashMap<_, _>>();
+    for bag in decomposition.node_indices() {
+        for v in &decomposition[bag] {
+            match bags_of.get_mut(v) {
+                Some(bags) => bags.push(bag),
+                None => return false,
+            }
+        }
+    }
+    if bags_of.values().any(|bags| bags.is_empty()) {
+        return false;
+    }
+
+    for edge in graph.edge_references() {
+        let (a, b) = (edge.source(), edge.target());
+        if !bags_of[&a]
+            .iter()
+            .any(|&bag| decomposition[bag].contains(&b))
+        {
+            return false;
+        }
+    }
+
+    // The bags that contain a node are connected if they are joined by one edge less than their
+    // number.
+    let mut shared_edges = bags_of.keys().map(|&v| (v, 0)).collect::<HashMap<_, _>>();
+    for edge in decomposition.edge_references() {
+        let (p, q) = (&decomposition[edge.source()], &decomposition[edge.target()]);
+        let (smaller, larger) = if p.len() <= q.len() { (p, q) } else { (q, p) };
+        for v in smaller.intersection(larger) {
+            *shared_edges.get_mut(v).unwrap() += 1;
+        }
+    }
+    bags_of
+        .iter()
+        .all(|(v, bags)| shared_edges[v] + 1 == bags.len())
+}
+
+/// Collect the neighbors of every node into sets, which elimination updates.
+fn neighbor_sets(adjacency: &[Vec<usize>]) -> Vec<BTreeSet<usize>> {
+    adjacency
+        .iter()
+        .map(|neighbors| neighbors.iter().copied().collect())
+        .collect()
+}
+
+/// Return the number of edges that eliminating `v` adds between its neighbors.
+fn fill_in(adjacency: &[BTreeSet<usize>], v: usize) -> usize {
+    let neighbors = &adjacency[v];
+    neighbors
+        .iter()
+        .map(|&a| {
+            neighbors
+                .range(a + 1..)
+                .filter(|b| !adjacency[a].contains(b))
+                .count()
+        })
+        .sum()
+}
+
+/// Remove `v` from the graph and connect its neighbors to each other. Return its neighbors.
+fn eliminate(adjacency: &mut [BTreeSet<usize>], v: usize) -> BTreeSet<usize> {
+    let neighbors = core::mem::take(&mut adjacency[v]);
+    for &a in &neighbors {
+        adjacency[a].remove(&v);
+        adjacency[a].extend(neighbors.iter().filter(|&&b| b != a));
+    }
+    neighbors
+}
+
+/// Eliminate the nodes greedily, always one with the smallest `score`, which only depends on the
+/// neighbors of the node and the edges between them.
+fn greedy_elimination<F>(adjacency: &[Vec<usize>], score: F) -> Vec<usize>
+where
+    F: Fn(&[BTreeSet<usize>], usize) -> usize,
+{
+    let mut adjacency = neighbor_sets(adjacency);
+    let n = adjacency.len();
+    let mut scores = (0..n).map(|v| score(&adjacency, v)).collect::<Vec<_>>();
+    let mut queue = (0..n)
+        .map(|v| Reverse((scores[v], v)))
+        .collect::<BinaryHeap<_>>();
+    let mut eliminated = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    while let Some(Reverse((s, v))) = queue.pop() {
+        if eliminated[v] || s != scores[v] {
+            continue;
+        }
+        eliminated[v] = true;
+        order.push(v);
+
+        // The scores that may change are those of the neighbors and their neighbors.
+        let neighbors = eliminate(&mut adjacency, v);
+        let mut affected = neighbors.clone();
+        for &a in &neighbors {
+            affected.extend(&adjacency[a]);
+        }
+        for w in affected {
+            let new_score = score(&adjacency, w);
+            if new_score != scores[w] {
+                scores[w] = new_score;
+                queue.push(Reverse((new_score, w)));
+            }
+        }
+    }
+    order
+}
+
+/// Build the tree decomposition of an elimination ordering of node positions.
+fn decompose<N>(
+    nodes: &[N],
+    adjacency: &[Vec<usize>],
+    order: &[usize],
+) -> (UnGraph<HashSet<N>, ()>, usize)
+where
+    N: Copy + Eq + Hash,
+{
+    let mut adjacency = neighbor_sets(adjacency);
+    let n = nodes.len();
+    let mut position = vec![0; n];
+    for (i, &v) in order.iter().enumerate() {
+        position[v] = i;
+    }
+
+    let mut decomposition = UnGraph::with_capacity(n, n.saturating_sub(1));
+    let mut parents = Vec::with_capacity(n);
+    let mut width = 0;
+    for &v in order {
+        let neighbors = eliminate(&mut adjacency, v);
+        width = width.max(neighbors.len());
+        parents.push(neighbors.iter().map(|&w| position[w]).min());
+        let mut bag = HashSet::with_capacity(neighbors.len() + 1);
+        bag.insert(nodes[v]);
+        bag.extend(neighbors.iter().map(|&w| nodes[w]));
+        decomposition.add_node(bag);
+    }
+
+    // Join the roots of the connected components into a path.
+    let mut previous_root = None;
+    for (i, parent) in parents.into_iter().enumerate() {
+        let parent = match parent {
+            Some(parent) => Some(parent),
+            None => previous_root.replace(i),
+        };
+        if let Some(parent) = parent {
+            decomposition.add_edge(NodeIndex::new(i), NodeIndex::new(parent), ());
+        }
+    }
+    (decomposition, width)
+}
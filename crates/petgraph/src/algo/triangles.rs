@@ -0,0 +1,476 @@
+//! Triangles and the clustering statistics that are computed from them.
+//!
+//! * [`triangles`] lists every triangle, and [`triangle_count`] and [`node_triangle_counts`] count
+//!   them in the whole graph and at every node.
+//! * [`clustering_coefficients`] and [`average_clustering`] measure how many of the pairs of
+//!   neighbors of every node are adjacent, and [`transitivity`] how many of all paths of length two
+//!   are closed.
+//!
+//! All of them treat the graph as a simple undirected graph: they ignore edge directions,
+//! self-loops and parallel edges. They orient every edge towards its endpoint of higher degree,
+//! so that every node has **O(√|E|)** out-neighbors, and find every triangle once from its node
+//! of lowest degree in **O(|E|√|E|)** time. With the `rayon` feature, every function has a
+//! `parallel_` variant that divides the nodes among threads.
+//!
+//! Results per node are returned as a `Vec` indexed by [`NodeIndexable::to_index`], with zero for
+//! indices that are not in use.
+
+use alloc::{vec, vec::Vec};
+
+use crate::visit::{IntoNeighbors, IntoNodeIdentifiers, NodeIndexable};
+
+/// List the triangles of `graph`.
+///
+/// # Arguments
+/// * `graph`: an undirected graph, whose edge directions are ignored otherwise.
+///
+/// # Returns
+/// * `Vec<[G::NodeId; 3]>`: the nodes of every triangle, with every triangle listed once, in no
+///   particular order.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|√|E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::triangles, prelude::*};
+///
+/// // Two triangles that share the edge `bc`.
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (1, 3), (3, 2)]);
+///
+/// let mut found = triangles(&g)
+///     .into_iter()
+///     .map(|mut triangle| {
+///         triangle.sort();
+///         triangle.map(|v| v.index())
+///     })
+///     .collect::<Vec<_>>();
+/// found.sort();
+/// assert_eq!(found, [[0, 1, 2], [1, 2, 3]]);
+/// ```
+pub fn triangles<G>(graph: G) -> Vec<[G::NodeId; 3]>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let oriented = Oriented::new(graph);
+    let mut triangles = Vec::new();
+    for u in 0..oriented.len() {
+        oriented.triangles_from(u, |v, w| {
+            triangles.push([u, v, w].map(|i| graph.from_index(i)));
+        });
+    }
+    triangles
+}
+
+/// Count the triangles of `graph`.
+///
+/// # Arguments
+/// * `graph`: an undirected graph, whose edge directions are ignored otherwise.
+///
+/// # Returns
+/// * `usize`: the number of triangles.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|√|E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::triangle_count, prelude::*};
+///
+/// // The complete graph on four nodes.
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+/// assert_eq!(triangle_count(&g), 4);
+/// ```
+pub fn triangle_count<G>(graph: G) -> usize
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let oriented = Oriented::new(graph);
+    (0..oriented.len()).map(|u| oriented.count_from(u)).sum()
+}
+
+/// Count the triangles that every node of `graph` is in.
+///
+/// # Arguments
+/// * `graph`: an undirected graph, whose edge directions are ignored otherwise.
+///
+/// # Returns
+/// * `Vec<usize>`: the number of triangles of every node, indexed by [`NodeIndexable::to_index`].
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|√|E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::node_triangle_counts, prelude::*};
+///
+/// // Two triangles that share the edge `bc`.
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (1, 3), (3, 2)]);
+/// assert_eq!(node_triangle_counts(&g), [1, 2, 2, 1]);
+/// ```
+pub fn node_triangle_counts<G>(graph: G) -> Vec<usize>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let oriented = Oriented::new(graph);
+    let mut counts = vec![0; oriented.len()];
+    for u in 0..oriented.len() {
+        oriented.count_nodes_from(u, &mut counts);
+    }
+    counts
+}
+
+/// Compute the [local clustering coefficient][1] of every node of `graph`.
+///
+/// The clustering coefficient of a node is the fraction of the pairs of its neighbors that are
+/// adjacent, or zero if it has fewer than two neighbors.
+///
+/// # Arguments
+/// * `graph`: an undirected graph, whose edge directions are ignored otherwise.
+///
+/// # Returns
+/// * `Vec<f64>`: the clustering coefficient of every node, indexed by [`NodeIndexable::to_index`].
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|√|E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Clustering_coefficient#Local_clustering_coefficient
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::clustering_coefficients, prelude::*};
+///
+/// // Two triangles that share the edge `bc`.
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (1, 3), (3, 2)]);
+/// // Two of the three pairs of neighbors of `b` are adjacent.
+/// assert_eq!(clustering_coefficients(&g), [1., 2. / 3., 2. / 3., 1.]);
+/// ```
+pub fn clustering_coefficients<G>(graph: G) -> Vec<f64>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let oriented = Oriented::new(graph);
+    let mut counts = vec![0; oriented.len()];
+    for u in 0..oriented.len() {
+        oriented.count_nodes_from(u, &mut counts);
+    }
+    oriented.clustering(&counts)
+}
+
+/// Compute the average of the [local clustering coefficients][1] of the nodes of `graph`.
+///
+/// # Arguments
+/// * `graph`: an undirected graph, whose edge directions are ignored otherwise.
+///
+/// # Returns
+/// * `f64`: the average clustering coefficient of the nodes, or zero if there are none.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|√|E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Clustering_coefficient#Network_average_clustering_coefficient
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::average_clustering, prelude::*};
+///
+/// // Two triangles that share the edge `bc`.
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (1, 3), (3, 2)]);
+/// assert_eq!(average_clustering(&g), (1. + 2. / 3. + 2. / 3. + 1.) / 4.);
+/// ```
+pub fn average_clustering<G>(graph: G) -> f64
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    average(graph, &clustering_coefficients(graph))
+}
+
+/// Compute the [transitivity][1] of `graph`.
+///
+/// The transitivity is the fraction of the paths of length two that are closed into a
+/// triangle, which is three times the number of triangles over the number of such paths.
+///
+/// # Arguments
+/// * `graph`: an undirected graph, whose edge directions are ignored otherwise.
+///
+/// # Returns
+/// * `f64`: the transitivity, or zero if there are no paths of length two.
+///
+/// # Complexity
+/// * Time complexity: **O(|V| + |E|√|E|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Clustering_coefficient#Global_clustering_coefficient
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::transitivity, prelude::*};
+///
+/// // Two triangles that share the edge `bc`.
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (1, 3), (3, 2)]);
+/// // Six of the eight paths of length two are closed.
+/// assert_eq!(transitivity(&g), 6. / 8.);
+/// ```
+pub fn transitivity<G>(graph: G) -> f64
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let oriented = Oriented::new(graph);
+    let count = (0..oriented.len()).map(|u| oriented.count_from(u)).sum();
+    oriented.transitivity(count)
+}
+
+/// List the triangles of `graph` in parallel.
+///
+/// See [`triangles`].
+#[cfg(feature = "rayon")]
+pub fn parallel_triangles<G>(graph: G) -> Vec<[G::NodeId; 3]>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + Sync,
+    G::NodeId: Send,
+{
+    use rayon::prelude::*;
+
+    let oriented = Oriented::new(graph);
+    (0..oriented.len())
+        .into_par_iter()
+        .flat_map_iter(|u| {
+            let mut triangles = Vec::new();
+            oriented.triangles_from(u, |v, w| {
+                triangles.push([u, v, w].map(|i| graph.from_index(i)));
+            });
+            triangles
+        })
+        .collect()
+}
+
+/// Count the triangles of `graph` in parallel.
+///
+/// See [`triangle_count`].
+#[cfg(feature = "rayon")]
+pub fn parallel_triangle_count<G>(graph: G) -> usize
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    use rayon::prelude::*;
+
+    let oriented = Oriented::new(graph);
+    (0..oriented.len())
+        .into_par_iter()
+        .map(|u| oriented.count_from(u))
+        .sum()
+}
+
+/// Count the triangles that every node of `graph` is in, in parallel.
+///
+/// See [`node_triangle_counts`].
+#[cfg(feature = "rayon")]
+pub fn parallel_node_triangle_counts<G>(graph: G) -> Vec<usize>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    Oriented::new(graph).parallel_node_counts()
+}
+
+/// Compute the local clustering coefficient of every node of `graph` in parallel.
+///
+/// See [`clustering_coefficients`].
+#[cfg(feature = "rayon")]
+pub fn parallel_clustering_coefficients<G>(graph: G) -> Vec<f64>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let oriented = Oriented::new(graph);
+    oriented.clustering(&oriented.parallel_node_counts())
+}
+
+/// Compute the average local clustering coefficient of the nodes of `graph` in parallel.
+///
+/// See [`average_clustering`].
+#[cfg(feature = "rayon")]
+pub fn parallel_average_clustering<G>(graph: G) -> f64
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    average(graph, &parallel_clustering_coefficients(graph))
+}
+
+/// Compute the transitivity of `graph` in parallel.
+///
+/// See [`transitivity`].
+#[cfg(feature = "rayon")]
+pub fn parallel_transitivity<G>(graph: G) -> f64
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    use rayon::prelude::*;
+
+    let oriented = Oriented::new(graph);
+    let count = (0..oriented.len())
+        .into_par_iter()
+        .map(|u| oriented.count_from(u))
+        .sum();
+    oriented.transitivity(count)
+}
+
+/// The average of `coefficients` over the nodes of `graph`.
+fn average<G>(graph: G, coefficients: &[f64]) -> f64
+where
+    G: IntoNodeIdentifiers,
+{
+    let node_count = graph.node_identifiers().count();
+    if node_count == 0 {
+        return 0.;
+    }
+    coefficients.iter().sum::<f64>() / node_count as f64
+}
+
+/// A simple undirected graph with every edge oriented from the endpoint of lower degree to the
+/// one of higher degree, with ties broken by index.
+struct Oriented {
+    /// The out-neighbors of every node, in increasing order.
+    out: Vec<Vec<usize>>,
+    /// The number of distinct neighbors of every node, other than itself.
+    degree: Vec<usize>,
+}
+
+impl Oriented {
+    fn new<G>(graph: G) -> Self
+    where
+        G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+    {
+        let mut adjacency = vec![Vec::new(); graph.node_bound()];
+        for node in graph.node_identifiers() {
+            let a = graph.to_index(node);
+            for neighbor in graph.neighbors(node) {
+                let b = graph.to_index(neighbor);
+                if a != b {
+                    adjacency[a].push(b);
+                    adjacency[b].push(a);
+                }
+            }
+        }
+        for neighbors in &mut adjacency {
+            neighbors.sort_unstable();
+            neighbors.dedup();
+        }
+        let degree = adjacency.iter().map(Vec::len).collect::<Vec<_>>();
+        let out = adjacency
+            .into_iter()
+            .enumerate()
+            .map(|(a, neighbors)| {
+                neighbors
+                    .into_iter()
+                    .filter(|&b| (degree[a], a) < (degree[b], b))
+                    .collect()
+            })
+            .collect();
+        Oriented { out, degree }
+    }
+
+    fn len(&self) -> usize {
+        self.degree.len()
+    }
+
+    /// Call `f` with the two other nodes of every triangle whose lowest node is `u`.
+    fn triangles_from(&self, u: usize, mut f: impl FnMut(usize, usize)) {
+        for &v in &self.out[u] {
+            let (mut a, mut b) = (self.out[u].iter().peekable(), self.out[v].iter().peekable());
+            while let (Some(&&x), Some(&&y)) = (a.peek(), b.peek()) {
+                if x < y {
+                    a.next();
+                } else if y < x {
+                    b.next();
+                } else {
+                    f(v, x);
+                    a.next();
+                    b.next();
+                }
+            }
+        }
+    }
+
+    /// The number of triangles whose lowest node is `u`.
+    fn count_from(&self, u: usize) -> usize {
+        let mut count = 0;
+        self.triangles_from(u, |_, _| count += 1);
+        count
+    }
+
+    /// Add one to `counts` for every node of every triangle whose lowest node is `u`.
+    fn count_nodes_from(&self, u: usize, counts: &mut [usize]) {
+        self.triangles_from(u, |v, w| {
+            counts[u] += 1;
+            counts[v] += 1;
+            counts[w] += 1;
+        });
+    }
+
+    #[cfg(feature = "rayon")]
+    fn parallel_node_counts(&self) -> Vec<usize> {
+        use rayon::prelude::*;
+
+        (0..self.len())
+            .into_par_iter()
+            .fold(
+                || vec![0; self.len()],
+                |mut counts, u| {
+                    self.count_nodes_from(u, &mut counts);
+                    counts
+                },
+            )
+            .reduce(
+                || vec![0; self.len()],
+                |mut a, b| {
+                    a.iter_mut().zip(b).for_each(|(a, b)| *a += b);
+                    a
+                },
+            )
+    }
+
+    /// The clustering coefficient of every node, from its number of triangles.
+    fn clustering(&self, counts: &[usize]) -> Vec<f64> {
+        counts
+            .iter()
+            .zip(&self.degree)
+            .map(|(&count, &degree)| {
+                if degree < 2 {
+                    0.
+                } else {
+                    2. * count as f64 / (degree * (degree - 1)) as f64
+                }
+            })
+            .collect()
+    }
+
+    /// The transitivity, from the number of triangles.
+    fn transitivity(&self, count: usize) -> f64 {
+        let paths = self
+            .degree
+            .iter()
+            .map(|&degree| degree * degree.saturating_sub(1) / 2)
+            .sum::<usize>();
+        if paths == 0 {
+            0.
+        } else {
+            3. * count as f64 / paths as f64
+        }
+    }
+}
@@ -0,0 +1,594 @@
+//! Algorithms for the [*traveling salesman problem*][1].
+//!
+//! A *tour* visits every node of a graph exactly once and returns to the node it started from.
+//! The algorithms in this module look for a tour of minimum total edge cost. Tours are returned
+//! as the list of nodes in the order in which they are visited; the closing edge from the last
+//! node back to the first one is implied.
+//!
+//! * [`held_karp`] finds an optimal tour, but its running time is exponential in the number of
+//!   nodes, so it is only suitable for small graphs.
+//! * [`christofides`] finds a tour at most 3/2 times as long as an optimal one, provided the edge
+//!   costs satisfy the triangle inequality.
+//! * [`nearest_neighbor`] quickly builds a tour, which can then be improved with the local searches
+//!   [`two_opt`] and [`or_opt`].
+//!
+//! Parallel edges are allowed; the cheapest one is used. Self-loops are ignored.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Travelling_salesman_problem
+
+use alloc::{vec, vec::Vec};
+use core::ops::Div;
+
+use crate::{
+    algo::{
+        BoundedMeasure, Measure, eulerian_circuit, matching::min_weight_perfect_matching_indices,
+        min_spanning_tree, node_positions,
+    },
+    data::Element,
+    graph::{UnGraph, node_index},
+    visit::{EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable},
+};
+
+/// Find an optimal tour using the [Held-Karp algorithm][1].
+///
+/// Directed graphs are supported: the tour follows the direction of the edges.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+/// * `edge_cost`: closure that returns the cost of an edge.
+///
+/// # Returns
+/// * `Some((K, Vec<G::NodeId>))`: the cost of an optimal tour and its nodes, in visiting order,
+///   starting from the first node of the graph.
+/// * `None`: if the graph has no tour.
+///
+/// # Panics
+/// * If the graph has more than 20 nodes, since the table of partial tours grows exponentially.
+///
+/// # Complexity
+/// * Time complexity: **O(|V|² 2^|V| + |E|)**.
+/// * Auxiliary space: **O(|V| 2^|V|)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Held%E2%80%93Karp_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::tsp::held_karp, prelude::*};
+///
+/// // A one-way street 0 -> 1 -> 2 -> 3 -> 0 and expensive detours.
+/// let g = DiGraph::<(), u32>::from_edges(&[
+///     (0, 1, 1),
+///     (1, 2, 1),
+///     (2, 3, 1),
+///     (3, 0, 1),
+///     (0, 2, 5),
+///     (2, 1, 5),
+///     (1, 3, 5),
+/// ]);
+///
+/// let (cost, tour) = held_karp(&g, |e| *e.weight()).unwrap();
+/// assert_eq!(cost, 4);
+/// assert_eq!(tour, vec![0.into(), 1.into(), 2.into(), 3.into()]);
+/// ```
+pub fn held_karp<G, F, K>(graph: G, edge_cost: F) -> Option<(K, Vec<G::NodeId>)>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let costs = CostMatrix::new(graph, edge_cost, !graph.is_directed());
+    let n = costs.nodes.len();
+    if n <= 1 {
+        return Some((K::default(), costs.nodes));
+    }
+    assert!(n <= 20, "held_karp supports at most 20 nodes");
+
+    // `best[set * others + j]` is the cost of the cheapest path that starts at node 0, visits
+    // exactly the nodes in `set` and ends at node `j + 1`, where node `i + 1` is in `set` iff
+    // bit `i` is set.
+    let others = n - 1;
+    let sets = 1usize << others;
+    let size = sets
+        .checked_mul(others)
+        .expect("the Held-Karp table does not fit in memory");
+    let mut best = vec![None; size];
+    let mut previous = vec![usize::MAX; size];
+    for j in 0..others {
+        best[(1 << j) * others + j] = costs.get(0, j + 1);
+    }
+    for set in 1..sets {
+        for j in (0..others).filter(|j| set & (1 << j) != 0) {
+            let Some(cost) = best[set * others + j] else {
+                continue;
+            };
+            for k in (0..others).filter(|k| set & (1 << k) == 0) {
+                let Some(step) = costs.get(j + 1, k + 1) else {
+                    continue;
+                };
+                let next = (set | (1 << k)) * others + k;
+                let cost = cost + step;
+                if best[next].is_none_or(|old| cost < old) {
+                    best[next] = Some(cost);
+                    previous[next] = j;
+                }
+            }
+        }
+    }
+
+    let full = sets - 1;
+    let (cost, mut last) = (0..others)
+        .filter_map(|j| Some((best[full * others + j]? + costs.get(j + 1, 0)?, j)))
+        .reduce(|a, b| if b.0 < a.0 { b } else { a })?;
+    let mut tour = Vec::with_capacity(n);
+    let mut set = full;
+    while set != 0 {
+        tour.push(costs.nodes[last + 1]);
+        let before = previous[set * others + last];
+        set &= !(1 << last);
+        last = before;
+    }
+    tour.push(costs.nodes[0]);
+    tour.reverse();
+    Some((cost, tour))
+}
+
+/// Find a tour using [Christofides' algorithm][1].
+///
+/// The algorithm joins a minimum spanning tree (see [`min_spanning_tree()`]) with a minimum weight
+/// perfect matching of its odd-degree nodes, takes an Eulerian circuit of the result and skips
+/// the nodes that were already visited. If the edge costs satisfy the triangle inequality, the
+/// tour costs at most 3/2 times as much as an optimal tour.
+///
+/// The graph is treated as if undirected. It should be complete: shortcuts that are not present
+/// in the graph make the algorithm fail.
+///
+/// Unlike the other algorithms in this module, the costs must be a [`BoundedMeasure`] that can be
+/// divided: the matching is computed like [`minimum_weight_perfect_matching`], whose blossom
+/// algorithm halves its dual variables and checks the mirrored costs for overflow.
+///
+/// # Arguments
+/// * `graph`: an undirected, complete graph.
+/// * `edge_cost`: closure that returns the cost of an edge.
+///
+/// # Returns
+/// * `Some((K, Vec<G::NodeId>))`: the cost of the tour and its nodes, in visiting order, starting
+///   from the first node of the graph.
+/// * `None`: if a required edge is missing from the graph.
+///
+/// # Panics
+/// * If the costs are too large for the matching, see [`minimum_weight_perfect_matching`].
+///
+/// # Complexity
+/// * Time complexity: **O(|V|³ + |E| log |E|)**.
+/// * Auxiliary space: **O(|V|²)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Christofides_algorithm
+/// [`minimum_weight_perfect_matching`]: crate::algo::minimum_weight_perfect_matching
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::tsp::christofides, prelude::*};
+///
+/// // Five points on a line, at positions 0, 1, 2, 3 and 4, with the distance as cost.
+/// let mut g = UnGraph::<i32, i32>::new_undirected();
+/// let nodes = (0..5).map(|x| g.add_node(x)).collect::<Vec<_>>();
+/// for (i, &a) in nodes.iter().enumerate() {
+///     for &b in &nodes[i + 1..] {
+///         g.add_edge(a, b, (g[a] - g[b]).abs());
+///     }
+/// }
+///
+/// let (cost, tour) = christofides(&g, |e| *e.weight()).unwrap();
+/// assert_eq!(cost, 8);
+/// assert_eq!(tour.len(), 5);
+/// ```
+pub fn christofides<G, F, K>(graph: G, edge_cost: F) -> Option<(K, Vec<G::NodeId>)>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: BoundedMeasure + Copy + Div<K, Output = K>,
+{
+    let costs = CostMatrix::new(graph, edge_cost, true);
+    let n = costs.nodes.len();
+    if n <= 1 {
+        return Some((K::default(), costs.nodes));
+    }
+
+    let mut complete = UnGraph::<(), K>::with_capacity(n, n * (n - 1) / 2);
+    for _ in 0..n {
+        complete.add_node(());
+    }
+    for a in 0..n {
+        for b in a + 1..n {
+            if let Some(cost) = costs.get(a, b) {
+                complete.add_edge(node_index(a), node_index(b), cost);
+            }
+        }
+    }
+
+    // Node elements come first, in index order, so the edge endpoints are node indices.
+    let mut multigraph = UnGraph::<(), ()>::with_capacity(n, n);
+    let mut degree = vec![0; n];
+    for element in min_spanning_tree(&complete) {
+        match element {
+            Element::Node { .. } => {
+                multigraph.add_node(());
+            }
+            Element::Edge { source, target, .. } => {
+                multigraph.add_edge(node_index(source), node_index(target), ());
+                degree[source] += 1;
+                degree[target] += 1;
+            }
+        }
+    }
+
+    let odd = (0..n).filter(|&v| degree[v] % 2 == 1).collect::<Vec<_>>();
+    let mut odd_edges = Vec::new();
+    for (i, &a) in odd.iter().enumerate() {
+        for (j, &b) in odd.iter().enumerate().skip(i + 1) {
+            if let Some(cost) = costs.get(a, b) {
+                odd_edges.push((i, j, cost));
+            }
+        }
+    }
+    let mate = min_weight_perfect_matching_indices(odd.len(), &odd_edges)?;
+    for (i, &j) in mate.iter().enumerate() {
+        if i < j {
+            multigraph.add_edge(node_index(odd[i]), node_index(odd[j]), ());
+        }
+    }
+
+    // A spanning tree that does not reach every node leaves the circuit short of a tour.
+    let circuit = eulerian_circuit(&multigraph)?;
+    let mut visited = vec![false; n];
+    let mut tour = Vec::with_capacity(n);
+    let walk = circuit
+        .first()
+        .map(|e| e.source().index())
+        .into_iter()
+        .chain(circuit.iter().map(|e| e.target().index()));
+    for v in walk {
+        if !visited[v] {
+            visited[v] = true;
+            tour.push(v);
+        }
+    }
+    if tour.len() < n {
+        return None;
+    }
+    costs.into_tour(&tour)
+}
+
+/// Build a tour with the [nearest neighbor heuristic][1].
+///
+/// Starting from the first node of the graph, the tour repeatedly moves on to the closest node
+/// that has not been visited yet. The result can be improved with [`two_opt`] and [`or_opt`].
+///
+/// The graph is treated as if undirected.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `edge_cost`: closure that returns the cost of an edge.
+///
+/// # Returns
+/// * `Some((K, Vec<G::NodeId>))`: the cost of the tour and its nodes, in visiting order.
+/// * `None`: if the tour gets stuck at a node without edges to unvisited nodes, or cannot return to
+///   the first node.
+///
+/// # Complexity
+/// * Time complexity: **O(|V|² + |E|)**.
+/// * Auxiliary space: **O(|V|²)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Nearest_neighbour_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::{
+///     algo::tsp::{nearest_neighbor, or_opt, two_opt},
+///     prelude::*,
+/// };
+///
+/// // Points on a circle, at angles 0, 3, 1, 4 and 2 times 72 degrees, with the number of steps
+/// // along the circle between two points as cost.
+/// let positions = [0, 3, 1, 4, 2];
+/// let mut g = UnGraph::<u32, u32>::new_undirected();
+/// let nodes = positions.map(|p| g.add_node(p));
+/// for (i, &a) in nodes.iter().enumerate() {
+///     for &b in &nodes[i + 1..] {
+///         let steps = g[a].abs_diff(g[b]);
+///         g.add_edge(a, b, steps.min(5 - steps));
+///     }
+/// }
+///
+/// let (_, tour) = nearest_neighbor(&g, |e| *e.weight()).unwrap();
+/// let (_, tour) = two_opt(&g, &tour, |e| *e.weight()).unwrap();
+/// let (cost, _) = or_opt(&g, &tour, |e| *e.weight()).unwrap();
+/// assert_eq!(cost, 5);
+/// ```
+pub fn nearest_neighbor<G, F, K>(graph: G, edge_cost: F) -> Option<(K, Vec<G::NodeId>)>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let costs = CostMatrix::new(graph, edge_cost, true);
+    let n = costs.nodes.len();
+    if n == 0 {
+        return Some((K::default(), Vec::new()));
+    }
+
+    let mut visited = vec![false; n];
+    visited[0] = true;
+    let mut tour = Vec::with_capacity(n);
+    tour.push(0);
+    let mut current = 0;
+    for _ in 1..n {
+        let (_, next) = (0..n)
+            .filter(|&v| !visited[v])
+            .filter_map(|v| Some((costs.get(current, v)?, v)))
+            .reduce(|a, b| if b.0 < a.0 { b } else { a })?;
+        visited[next] = true;
+        tour.push(next);
+        current = next;
+    }
+    costs.into_tour(&tour)
+}
+
+/// Improve a tour with the [2-opt][1] local search.
+///
+/// A 2-opt move removes two edges of the tour and reconnects the two resulting paths the other
+/// way around, which reverses one of them. Moves that lower the cost are applied until there are
+/// none left.
+///
+/// The graph is treated as if undirected.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `tour`: the nodes of a tour, in visiting order.
+/// * `edge_cost`: closure that returns the cost of an edge.
+///
+/// # Returns
+/// * `Some((K, Vec<G::NodeId>))`: the cost of the improved tour and its nodes, in visiting order,
+///   starting from the first node of `tour`.
+/// * `None`: if `tour` uses an edge that is not in the graph.
+///
+/// # Panics
+/// If `tour` contains a node that is not in the graph.
+///
+/// # Complexity
+/// * Time complexity: **O(k |V|² + |E|)**, where **k** is the number of improving passes.
+/// * Auxiliary space: **O(|V|²)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/2-opt
+pub fn two_opt<G, F, K>(graph: G, tour: &[G::NodeId], edge_cost: F) -> Option<(K, Vec<G::NodeId>)>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let costs = CostMatrix::new(graph, edge_cost, true);
+    let mut tour = costs.tour_indices(graph, tour);
+    costs.tour_cost(&tour)?;
+
+    let n = tour.len();
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for i in 0..n.saturating_sub(2) {
+            // The edge (i, i + 1) is exchanged with the edge (j, j + 1), which must not be
+            // adjacent to it.
+            for j in i + 2..n - usize::from(i == 0) {
+                let (a, b, c, d) = (tour[i], tour[i + 1], tour[j], tour[(j + 1) % n]);
+                let (Some(ab), Some(cd), Some(ac), Some(bd)) = (
+                    costs.get(a, b),
+                    costs.get(c, d),
+                    costs.get(a, c),
+                    costs.get(b, d),
+                ) else {
+                    continue;
+                };
+                if ac + bd < ab + cd {
+                    tour[i + 1..=j].reverse();
+                    improved = true;
+                }
+            }
+        }
+    }
+    costs.into_tour(&tour)
+}
+
+/// Improve a tour with the [Or-opt][1] local search.
+///
+/// An Or-opt move takes a segment of up to three consecutive nodes out of the tour and inserts
+/// it, possibly reversed, between two other consecutive nodes. Moves that lower the cost are
+/// applied until there are none left.
+///
+/// The graph is treated as if undirected.
+///
+/// # Arguments
+/// * `graph`: an undirected graph.
+/// * `tour`: the nodes of a tour, in visiting order.
+/// * `edge_cost`: closure that returns the cost of an edge.
+///
+/// # Returns
+/// * `Some((K, Vec<G::NodeId>))`: the cost of the improved tour and its nodes, in visiting order.
+/// * `None`: if `tour` uses an edge that is not in the graph.
+///
+/// # Panics
+/// If `tour` contains a node that is not in the graph.
+///
+/// # Complexity
+/// * Time complexity: **O(k |V|² + |E|)**, where **k** is the number of improving moves.
+/// * Auxiliary space: **O(|V|²)**.
+///
+/// where **|V|** is the number of nodes and **|E|** is the number of edges.
+///
+/// [1]: https://en.wikipedia.org/wiki/Travelling_salesman_problem#Heuristic_and_approximation_algorithms
+pub fn or_opt<G, F, K>(graph: G, tour: &[G::NodeId], edge_cost: F) -> Option<(K, Vec<G::NodeId>)>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let costs = CostMatrix::new(graph, edge_cost, true);
+    let mut tour = costs.tour_indices(graph, tour);
+    costs.tour_cost(&tour)?;
+
+    let n = tour.len();
+    while let Some(moved) = or_opt_move(&costs, &tour) {
+        tour = moved;
+    }
+    debug_assert_eq!(tour.len(), n);
+    costs.into_tour(&tour)
+}
+
+/// Find an improving Or-opt move and return the resulting tour.
+fn or_opt_move<N, K>(costs: &CostMatrix<N, K>, tour: &[usize]) -> Option<Vec<usize>>
+where
+    N: Copy,
+    K: Measure + Copy,
+{
+    let n = tour.len();
+    // At least three nodes must remain outside of the segment to make a move.
+    for len in (1..=3).take_while(|len| len + 3 <= n) {
+        for start in 0..n {
+            let segment = (0..len).map(|k| tour[(start + k) % n]).collect::<Vec<_>>();
+            // The rest of the tour, from the node after the segment to the node before it.
+            let rest = (0..n - len)
+                .map(|k| tour[(start + len + k) % n])
+                .collect::<Vec<_>>();
+            let (first, last) = (segment[0], segment[len - 1]);
+            let (before, after) = (rest[n - len - 1], rest[0]);
+            let (Some(in_cost), Some(out_cost), Some(bridge)) = (
+                costs.get(before, first),
+                costs.get(last, after),
+                costs.get(before, after),
+            ) else {
+                continue;
+            };
+            let removed = in_cost + out_cost;
+            for k in 0..n - len - 1 {
+                let (a, b) = (rest[k], rest[k + 1]);
+                let Some(ab) = costs.get(a, b) else {
+                    continue;
+                };
+                for reversed in [false, true] {
+                    let (x, y) = if reversed {
+                        (last, first)
+                    } else {
+                        (first, last)
+                    };
+                    let (Some(ax), Some(yb)) = (costs.get(a, x), costs.get(y, b)) else {
+                        continue;
+                    };
+                    if bridge + ax + yb < removed + ab {
+                        let mut moved = Vec::with_capacity(n);
+                        moved.extend_from_slice(&rest[..=k]);
+                        if reversed {
+                            moved.extend(segment.iter().rev());
+                        } else {
+                            moved.extend_from_slice(&segment);
+                        }
+                        moved.extend_from_slice(&rest[k + 1..]);
+                        return Some(moved);
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The cheapest edge cost between every ordered pair of nodes, indexed by the position of the
+/// nodes in `nodes`.
+struct CostMatrix<N, K> {
+    nodes: Vec<N>,
+    /// Position of every node in `nodes`, indexed by `NodeIndexable::to_index`.
+    positions: Vec<usize>,
+    costs: Vec<Option<K>>,
+}
+
+impl<N, K> CostMatrix<N, K>
+where
+    N: Copy,
+    K: Measure + Copy,
+{
+    /// Collect the edge costs of `graph`. Each edge is used in both directions if `symmetric`.
+    fn new<G, F>(graph: G, mut edge_cost: F, symmetric: bool) -> Self
+    where
+        G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable<NodeId = N>,
+        F: FnMut(G::EdgeRef) -> K,
+    {
+        let (nodes, positions) = node_positions(graph);
+        let n = nodes.len();
+        let mut costs = vec![None; n * n];
+        for edge in graph.edge_references() {
+            let a = positions[graph.to_index(edge.source())];
+            let b = positions[graph.to_index(edge.target())];
+            if a == b {
+                continue;
+            }
+            let cost = edge_cost(edge);
+            let pairs = if symmetric {
+                [(a, b), (b, a)]
+            } else {
+                [(a, b); 2]
+            };
+            for (a, b) in pairs {
+                let slot: &mut Option<K> = &mut costs[a * n + b];
+                if slot.is_none_or(|old| cost < old) {
+                    *slot = Some(cost);
+                }
+            }
+        }
+        CostMatrix {
+            nodes,
+            positions,
+            costs,
+        }
+    }
+
+    fn get(&self, a: usize, b: usize) -> Option<K> {
+        self.costs[a * self.nodes.len() + b]
+    }
+
+    /// Translate the nodes of a tour into positions.
+    fn tour_indices<G>(&self, graph: G, tour: &[N]) -> Vec<usize>
+    where
+        G: NodeIndexable<NodeId = N>,
+    {
+        tour.iter()
+            .map(|&v| {
+                let position = self.positions.get(graph.to_index(v)).copied();
+                position
+                    .filter(|&p| p != usize::MAX)
+                    .expect("tour contains a node that is not in the graph")
+            })
+            .collect()
+    }
+
+    /// Return the cost of a tour, including the edge back to its first node.
+    fn tour_cost(&self, tour: &[usize]) -> Option<K> {
+        if tour.len() <= 1 {
+            return Some(K::default());
+        }
+        let closing = self.get(tour[tour.len() - 1], tour[0])?;
+        tour.windows(2).try_fold(closing, |cost, pair| {
+            Some(cost + self.get(pair[0], pair[1])?)
+        })
+    }
+
+    /// Return the cost of a tour and its nodes.
+    fn into_tour(self, tour: &[usize]) -> Option<(K, Vec<N>)> {
+        let cost = self.tour_cost(tour)?;
+        Some((cost, tour.iter().map(|&i| self.nodes[i]).collect()))
+    }
+}
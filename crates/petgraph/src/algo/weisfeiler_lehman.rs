@@ -0,0 +1,270 @@
+//! [Weisfeiler-Lehman][1] hashing, which summarizes a graph or the neighborhood of a node in a
+//! hash that isomorphic graphs share.
+//!
+//! Every node starts with the hash of its label. In every iteration, the hash of a node is
+//! replaced by the hash of itself and the sorted hashes of its neighbors with the labels of the
+//! edges to them, so after **i** iterations, it summarizes the depth-**i** neighborhood of the
+//! node. In a directed graph, out-neighbors and in-neighbors are told apart.
+//!
+//! Isomorphic graphs always get the same hash, but graphs that are not isomorphic may too, for
+//! example any two regular graphs with the same number of nodes and degree. Use
+//! [`canonical_form`](crate::algo::canonical_form) to tell graphs apart exactly, or to confirm
+//! a match between hashes.
+//!
+//! The hashes do not depend on the run, the platform or the order of the nodes and edges, so
+//! they can be stored and compared later, as long as the labels hash the same way.
+//!
+//! [1]: https://www.jmlr.org/papers/v12/shervashidze11a.html
+
+use alloc::{vec, vec::Vec};
+use core::hash::{Hash, Hasher};
+
+use crate::{
+    algo::node_positions,
+    visit::{EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable},
+};
+
+/// Compute the Weisfeiler-Lehman hash of `graph`.
+///
+/// The hash combines the multisets of the node hashes of every iteration, see the
+/// [module documentation](self).
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+/// * `iterations`: the number of iterations, usually three or so.
+/// * `node_label`: closure that returns the label of a node; use `|_| ()` for no labels.
+/// * `edge_label`: closure that returns the label of an edge; use `|_| ()` for no labels.
+///
+/// # Returns
+/// * `u64`: the hash of the graph.
+///
+/// # Complexity
+/// * Time complexity: **O(k(|V| + |E|) log |V|)**.
+/// * Auxiliary space: **O(|V| + |E|)**.
+///
+/// where **k** is the number of iterations, **|V|** is the number of nodes and **|E|** is the
+/// number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::weisfeiler_lehman_graph_hash, prelude::*};
+///
+/// // A path a - b - c with a label on every node.
+/// let g = UnGraph::<&str, ()>::from_edges([(0, 1), (1, 2)])
+///     .map(|v, _| ["C", "O", "C"][v.index()], |_, _| ());
+/// // The same path with its nodes in another order.
+/// let h = UnGraph::<&str, ()>::from_edges([(1, 0), (0, 2)])
+///     .map(|v, _| ["O", "C", "C"][v.index()], |_, _| ());
+///
+/// let hash = |g: &UnGraph<&str, ()>| weisfeiler_lehman_graph_hash(g, 3, |v| g[v], |_| ());
+/// assert_eq!(hash(&g), hash(&h));
+/// // Moving the label changes the hash.
+/// let k = UnGraph::<&str, ()>::from_edges([(0, 1), (1, 2)])
+///     .map(|v, _| ["O", "C", "C"][v.index()], |_, _| ());
+/// assert_ne!(hash(&g), hash(&k));
+/// ```
+pub fn weisfeiler_lehman_graph_hash<G, NL, EL, N, E>(
+    graph: G,
+    iterations: usize,
+    node_label: NL,
+    edge_label: EL,
+) -> u64
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    NL: FnMut(G::NodeId) -> N,
+    EL: FnMut(G::EdgeRef) -> E,
+    N: Hash,
+    E: Hash,
+{
+    let (_, rounds) = refine(graph, iterations, node_label, edge_label);
+    let mut hasher = StableHasher::default();
+    graph.is_directed().hash(&mut hasher);
+    for mut hashes in rounds {
+        hashes.sort_unstable();
+        // Hashing the slice at once would hash the bytes of the integers, in native order.
+        hasher.write_usize(hashes.len());
+        hashes.iter().for_each(|&hash| hasher.write_u64(hash));
+    }
+    hasher.finish()
+}
+
+/// Compute the Weisfeiler-Lehman hashes of the neighborhoods of every node of `graph`.
+///
+/// The hash of a node after **i** iterations summarizes its neighborhood up to depth **i**, see
+/// the [module documentation](self). Nodes whose neighborhoods are isomorphic up to that depth
+/// have the same hash.
+///
+/// # Arguments
+/// * `graph`: a directed or undirected graph.
+/// * `iterations`: the number of iterations.
+/// * `node_label`: closure that returns the label of a node; use `|_| ()` for no labels.
+/// * `edge_label`: closure that returns the label of an edge; use `|_| ()` for no labels.
+///
+/// # Returns
+/// * `Vec<Vec<u64>>`: the hashes of every node after zero to `iterations` iterations, indexed by
+///   [`NodeIndexable::to_index`], and empty for indices that are not in use.
+///
+/// # Complexity
+/// * Time complexity: **O(k(|V| + |E|) log |V|)**.
+/// * Auxiliary space: **O(k|V| + |E|)**.
+///
+/// where **k** is the number of iterations, **|V|** is the number of nodes and **|E|** is the
+/// number of edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::{algo::weisfeiler_lehman_subtree_hashes, prelude::*};
+///
+/// // A path a - b - c - d - e.
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 4)]);
+///
+/// let hashes = weisfeiler_lehman_subtree_hashes(&g, 2, |_| (), |_| ());
+/// assert_eq!(hashes[0].len(), 3);
+/// // All nodes look the same before the first iteration.
+/// assert!(hashes.iter().all(|h| h[0] == hashes[0][0]));
+/// // Then the ends stand out, and `b` and `d` look the same.
+/// assert_eq!(hashes[0][1], hashes[4][1]);
+/// assert_ne!(hashes[0][1], hashes[1][1]);
+/// assert_eq!(hashes[1][1], hashes[2][1]);
+/// // After two iterations, `c` stands out too.
+/// assert_eq!(hashes[1][2], hashes[3][2]);
+/// assert_ne!(hashes[1][2], hashes[2][2]);
+/// ```
+pub fn weisfeiler_lehman_subtree_hashes<G, NL, EL, N, E>(
+    graph: G,
+    iterations: usize,
+    node_label: NL,
+    edge_label: EL,
+) -> Vec<Vec<u64>>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    NL: FnMut(G::NodeId) -> N,
+    EL: FnMut(G::EdgeRef) -> E,
+    N: Hash,
+    E: Hash,
+{
+    let (nodes, rounds) = refine(graph, iterations, node_label, edge_label);
+    let mut hashes = vec![Vec::new(); graph.node_bound()];
+    for (i, &node) in nodes.iter().enumerate() {
+        hashes[graph.to_index(node)] = rounds.iter().map(|round| round[i]).collect();
+    }
+    hashes
+}
+
+/// Return the nodes of `graph`, and the hash of every node after every iteration, in the order
+/// of the nodes.
+fn refine<G, NL, EL, N, E>(
+    graph: G,
+    iterations: usize,
+    mut node_label: NL,
+    mut edge_label: EL,
+) -> (Vec<G::NodeId>, Vec<Vec<u64>>)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    NL: FnMut(G::NodeId) -> N,
+    EL: FnMut(G::EdgeRef) -> E,
+    N: Hash,
+    E: Hash,
+{
+    let (nodes, index) = node_positions(graph);
+    // The neighbors of every node, with the hash of the label and the direction of the edge.
+    let mut adjacency = vec![Vec::new(); nodes.len()];
+    for edge in graph.edge_references() {
+        let a = index[graph.to_index(edge.source())];
+        let b = index[graph.to_index(edge.target())];
+        let label = hash_one(edge_label(edge));
+        if graph.is_directed() {
+            adjacency[a].push((hash_one((label, true)), b));
+            adjacency[b].push((hash_one((label, false)), a));
+        } else {
+            adjacency[a].push((label, b));
+            adjacency[b].push((label, a));
+        }
+    }
+
+    let mut rounds = Vec::with_capacity(iterations + 1);
+    rounds.push(
+        nodes
+            .iter()
+            .map(|&node| hash_one(node_label(node)))
+            .collect::<Vec<_>>(),
+    );
+    let mut neighbors = Vec::new();
+    for _ in 0..iterations {
+        let hashes = rounds.last().unwrap();
+        let next = (0..nodes.len())
+            .map(|v| {
+                neighbors.extend(adjacency[v].iter().map(|&(label, u)| (label, hashes[u])));
+                neighbors.sort_unstable();
+                let hash = hash_one((hashes[v], &neighbors));
+                neighbors.clear();
+                hash
+            })
+            .collect();
+        rounds.push(next);
+    }
+    (nodes, rounds)
+}
+
+fn hash_one<T: Hash>(value: T) -> u64 {
+    let mut hasher = StableHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A hasher that mixes every integer it is given into its state, with the finalizer of
+/// [SplitMix64][1], and reads integers as little-endian.
+///
+/// Unlike the hashers of `std` and `hashbrown`, it is not seeded at random, so that hashes can
+/// be compared between runs and platforms.
+///
+/// [1]: https://doi.org/10.1145/2714064.2660195
+#[derive(Default)]
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn mix(mut z: u64) -> u64 {
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        Self::mix(self.0)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.write_u64(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u8(&mut self, n: u8) {
+        self.write_u64(n.into());
+    }
+
+    fn write_u16(&mut self, n: u16) {
+        self.write_u64(n.into());
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.write_u64(n.into());
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.0 = Self::mix(self.0.wrapping_add(0x9E37_79B9_7F4A_7C15) ^ n);
+    }
+
+    fn write_u128(&mut self, n: u128) {
+        self.write_u64(n as u64);
+        self.write_u64((n >> 64) as u64);
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64);
+    }
+}
@@ -15,6 +15,9 @@
 //! `.next()` call on the walker. They can be converted to iterators
 //! through the [`Walker`][w] trait.
 //!
+//! [`LexBfs`] and [`MaxCardinalitySearch`] visit every node of an undirected
+//! graph in orders that are used to recognize chordal graphs.
+//!
 //! There is also the callback based traversal [`depth_first_search`][dfs].
 //!
 //! [bfs]: struct.Bfs.html
@@ -1,8 +1,10 @@
-use alloc::{collections::VecDeque, vec::Vec};
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+use fixedbitset::FixedBitSet;
 
 use super::{
-    GraphRef, IntoNeighbors, IntoNeighborsDirected, IntoNodeIdentifiers, Reversed, VisitMap,
-    Visitable,
+    GraphRef, IntoNeighbors, IntoNeighborsDirected, IntoNodeIdentifiers, NodeIndexable, Reversed,
+    VisitMap, Visitable,
 };
 use crate::Incoming;
 
@@ -428,6 +430,339 @@ where
     }
 }
 
+/// Marks a missing cell in [`LexBfs`].
+const NO_CELL: usize = usize::MAX;
+
+/// A part of the partition of the unvisited nodes in [`LexBfs`]: the nodes with the same label.
+#[derive(Clone, Debug)]
+struct LexBfsCell {
+    nodes: Vec<usize>,
+    prev: usize,
+    next: usize,
+    /// The last step that split this cell, and the cell that was split off in that step.
+    split_step: usize,
+    split_cell: usize,
+}
+
+/// Visit the nodes of an undirected graph in [lexicographic breadth-first search][1] (Lex-BFS)
+/// order.
+///
+/// Every node is labeled with the list of steps at which its visited neighbors were visited, and
+/// the next node to visit is an unvisited node with the lexicographically largest label. The
+/// traversal visits every node of the graph, one connected component after another.
+///
+/// In the reverse of a Lex-BFS order, every node forms a clique with its neighbors that come after
+/// it, if and only if the graph is [chordal][2]. The implementation uses partition refinement.
+///
+/// `LexBfs` does not itself borrow the graph. For directed graphs, only the outgoing edges are
+/// followed.
+///
+/// ```
+/// use petgraph::{graph::UnGraph, visit::LexBfs};
+///
+/// // a - b - c
+/// //  \  |  /
+/// //     d
+/// let mut graph = UnGraph::<(), ()>::new_undirected();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// let d = graph.add_node(());
+/// graph.extend_with_edges(&[(a, b), (b, c), (a, d), (b, d), (c, d)]);
+///
+/// let mut lex_bfs = LexBfs::new(&graph);
+/// let mut order = Vec::new();
+/// while let Some(nx) = lex_bfs.next(&graph) {
+///     order.push(nx);
+/// }
+/// assert_eq!(order[0], a);
+/// // b and d, which are both adjacent to a, come before c.
+/// assert_eq!(order[3], c);
+/// ```
+///
+/// **Note:** The algorithm may not behave correctly if nodes are added or removed during
+/// iteration.
+///
+/// [1]: https://en.wikipedia.org/wiki/Lexicographic_breadth-first_search
+/// [2]: https://en.wikipedia.org/wiki/Chordal_graph
+#[derive(Clone, Debug)]
+pub struct LexBfs {
+    cells: Vec<LexBfsCell>,
+    /// The first cell, which holds the unvisited nodes with the largest label.
+    head: usize,
+    cell_of: Vec<usize>,
+    position: Vec<usize>,
+    visited: FixedBitSet,
+    step: usize,
+}
+
+impl LexBfs {
+    /// Create a new `LexBfs` that starts at the first node of the graph.
+    pub fn new<G>(graph: G) -> Self
+    where
+        G: IntoNodeIdentifiers + NodeIndexable,
+    {
+        let mut nodes = graph
+            .node_identifiers()
+            .map(|n| graph.to_index(n))
+            .collect::<Vec<_>>();
+        // Nodes are taken from the end of their cell.
+        nodes.reverse();
+        Self::from_nodes(graph.node_bound(), nodes)
+    }
+
+    /// Create a new `LexBfs` that starts at `start`.
+    pub fn with_start<G>(graph: G, start: G::NodeId) -> Self
+    where
+        G: IntoNodeIdentifiers + NodeIndexable,
+    {
+        let start = graph.to_index(start);
+        let mut nodes = graph
+            .node_identifiers()
+            .map(|n| graph.to_index(n))
+            .filter(|&n| n != start)
+            .collect::<Vec<_>>();
+        nodes.reverse();
+        nodes.push(start);
+        Self::from_nodes(graph.node_bound(), nodes)
+    }
+
+    fn from_nodes(node_bound: usize, nodes: Vec<usize>) -> Self {
+        let mut cell_of = vec![NO_CELL; node_bound];
+        let mut position = vec![0; node_bound];
+        for (i, &n) in nodes.iter().enumerate() {
+            cell_of[n] = 0;
+            position[n] = i;
+        }
+        let (cells, head) = if nodes.is_empty() {
+            (Vec::new(), NO_CELL)
+        } else {
+            let cell = LexBfsCell {
+                nodes,
+                prev: NO_CELL,
+                next: NO_CELL,
+                split_step: 0,
+                split_cell: NO_CELL,
+            };
+            (vec![cell], 0)
+        };
+        LexBfs {
+            cells,
+            head,
+            cell_of,
+            position,
+            visited: FixedBitSet::with_capacity(node_bound),
+            step: 0,
+        }
+    }
+
+    /// Remove an empty cell from the list of cells.
+    fn unlink(&mut self, cell: usize) {
+        let (prev, next) = (self.cells[cell].prev, self.cells[cell].next);
+        if prev == NO_CELL {
+            self.head = next;
+        } else {
+            self.cells[prev].next = next;
+        }
+        if next != NO_CELL {
+            self.cells[next].prev = prev;
+        }
+    }
+
+    /// Move `node` from its cell to the cell in front of it, which holds the nodes whose label was
+    /// extended in this step.
+    fn promote(&mut self, node: usize) {
+        let cell = self.cell_of[node];
+        let target = if self.cells[cell].split_step == self.step {
+            self.cells[cell].split_cell
+        } else {
+            let target = self.cells.len();
+            let prev = self.cells[cell].prev;
+            self.cells.push(LexBfsCell {
+                nodes: Vec::new(),
+                prev,
+                next: cell,
+                split_step: self.step,
+                split_cell: target,
+            });
+            if prev == NO_CELL {
+                self.head = target;
+            } else {
+                self.cells[prev].next = target;
+            }
+            self.cells[cell].prev = target;
+            self.cells[cell].split_step = self.step;
+            self.cells[cell].split_cell = target;
+            target
+        };
+        if target == cell {
+            return;
+        }
+
+        let nodes = &mut self.cells[cell].nodes;
+        let i = self.position[node];
+        nodes.swap_remove(i);
+        if let Some(&moved) = nodes.get(i) {
+            self.position[moved] = i;
+        }
+        if nodes.is_empty() {
+            self.unlink(cell);
+        }
+        self.position[node] = self.cells[target].nodes.len();
+        self.cells[target].nodes.push(node);
+        self.cell_of[node] = target;
+    }
+
+    /// Return the next node in the traversal, or `None` if every node was visited.
+    pub fn next<G>(&mut self, graph: G) -> Option<G::NodeId>
+    where
+        G: IntoNeighbors + NodeIndexable,
+    {
+        if self.head == NO_CELL {
+            return None;
+        }
+        let head = self.head;
+        let node = self.cells[head].nodes.pop()?;
+        if self.cells[head].nodes.is_empty() {
+            self.unlink(head);
+        }
+        self.visited.insert(node);
+        self.step += 1;
+
+        let id = graph.from_index(node);
+        for neighbor in graph.neighbors(id) {
+            let neighbor = graph.to_index(neighbor);
+            if !self.visited[neighbor] {
+                self.promote(neighbor);
+            }
+        }
+        Some(id)
+    }
+}
+
+/// Visit the nodes of an undirected graph in [maximum cardinality search][1] (MCS) order.
+///
+/// The next node to visit is an unvisited node with the largest number of visited neighbors. The
+/// traversal visits every node of the graph, one connected component after another.
+///
+/// In the reverse of an MCS order, every node forms a clique with its neighbors that come after
+/// it, if and only if the graph is [chordal][2].
+///
+/// `MaxCardinalitySearch` does not itself borrow the graph. For directed graphs, only the
+/// outgoing edges are followed.
+///
+/// ```
+/// use petgraph::{
+///     graph::UnGraph,
+///     visit::{MaxCardinalitySearch, Walker},
+/// };
+///
+/// // A path a - b - c - d with the chord a - c.
+/// let mut graph = UnGraph::<(), ()>::new_undirected();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// let d = graph.add_node(());
+/// graph.extend_with_edges(&[(a, b), (b, c), (c, d), (a, c)]);
+///
+/// let order = MaxCardinalitySearch::with_start(&graph, d)
+///     .iter(&graph)
+///     .collect::<Vec<_>>();
+/// // After d and c, b has two visited neighbors and a has one.
+/// assert_eq!(order, vec![d, c, b, a]);
+/// ```
+///
+/// **Note:** The algorithm may not behave correctly if nodes are added or removed during
+/// iteration.
+///
+/// [1]: https://doi.org/10.1137/0213035
+/// [2]: https://en.wikipedia.org/wiki/Chordal_graph
+#[derive(Clone, Debug)]
+pub struct MaxCardinalitySearch {
+    /// The number of visited neighbors of every node.
+    weight: Vec<usize>,
+    /// The unvisited nodes by weight. Entries whose weight changed since are skipped.
+    buckets: Vec<Vec<usize>>,
+    max_weight: usize,
+    visited: FixedBitSet,
+    /// The last node whose neighbors were counted, to count parallel edges once.
+    counted_for: Vec<usize>,
+}
+
+impl MaxCardinalitySearch {
+    /// Create a new `MaxCardinalitySearch` that starts at the first node of the graph.
+    pub fn new<G>(graph: G) -> Self
+    where
+        G: IntoNodeIdentifiers + NodeIndexable,
+    {
+        let mut nodes = graph
+            .node_identifiers()
+            .map(|n| graph.to_index(n))
+            .collect::<Vec<_>>();
+        nodes.reverse();
+        Self::from_nodes(graph.node_bound(), nodes)
+    }
+
+    /// Create a new `MaxCardinalitySearch` that starts at `start`.
+    pub fn with_start<G>(graph: G, start: G::NodeId) -> Self
+    where
+        G: IntoNodeIdentifiers + NodeIndexable,
+    {
+        let start = graph.to_index(start);
+        let mut nodes = graph
+            .node_identifiers()
+            .map(|n| graph.to_index(n))
+            .filter(|&n| n != start)
+            .collect::<Vec<_>>();
+        nodes.reverse();
+        nodes.push(start);
+        Self::from_nodes(graph.node_bound(), nodes)
+    }
+
+    fn from_nodes(node_bound: usize, nodes: Vec<usize>) -> Self {
+        MaxCardinalitySearch {
+            weight: vec![0; node_bound],
+            buckets: vec![nodes],
+            max_weight: 0,
+            visited: FixedBitSet::with_capacity(node_bound),
+            counted_for: vec![usize::MAX; node_bound],
+        }
+    }
+
+    /// Return the next node in the traversal, or `None` if every node was visited.
+    pub fn next<G>(&mut self, graph: G) -> Option<G::NodeId>
+    where
+        G: IntoNeighbors + NodeIndexable,
+    {
+        let node = loop {
+            match self.buckets[self.max_weight].pop() {
+                Some(n) if self.visited[n] || self.weight[n] != self.max_weight => {}
+                Some(n) => break n,
+                None if self.max_weight == 0 => return None,
+                None => self.max_weight -= 1,
+            }
+        };
+        self.visited.insert(node);
+
+        let id = graph.from_index(node);
+        for neighbor in graph.neighbors(id) {
+            let neighbor = graph.to_index(neighbor);
+            if self.visited[neighbor] || self.counted_for[neighbor] == node {
+                continue;
+            }
+            self.counted_for[neighbor] = node;
+            self.weight[neighbor] += 1;
+            let weight = self.weight[neighbor];
+            if weight == self.buckets.len() {
+                self.buckets.push(Vec::new());
+            }
+            self.buckets[weight].push(neighbor);
+            self.max_weight = self.max_weight.max(weight);
+        }
+        Some(id)
+    }
+}
+
 /// A walker is a traversal state, but where part of the traversal
 /// information is supplied manually to each next call.
 ///
@@ -542,3 +877,25 @@ where
         self.next(context)
     }
 }
+
+impl<G> Walker<G> for LexBfs
+where
+    G: IntoNeighbors + NodeIndexable,
+{
+    type Item = G::NodeId;
+
+    fn walk_next(&mut self, context: G) -> Option<Self::Item> {
+        self.next(context)
+    }
+}
+
+impl<G> Walker<G> for MaxCardinalitySearch
+where
+    G: IntoNeighbors + NodeIndexable,
+{
+    type Item = G::NodeId;
+
+    fn walk_next(&mut self, context: G) -> Option<Self::Item> {
+        self.next(context)
+    }
+}
@@ -0,0 +1,210 @@
+#[path = "utils/random.rs"]
+mod random;
+
+use hashbrown::HashSet;
+use petgraph::{
+    EdgeType,
+    algo::{Automorphisms, automorphisms, automorphisms_matching},
+    graph::node_index as n,
+    prelude::*,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::random_graph;
+
+/// The sorted edges of `g` after renaming node `v` to `permutation[v]`.
+fn relabeled_edges<Ty: EdgeType>(
+    g: &Graph<u8, u8, Ty>,
+    permutation: &[usize],
+    with_weights: bool,
+) -> Vec<(usize, usize, u8)> {
+    let mut edges = g
+        .edge_references()
+        .map(|e| {
+            let (a, b) = (
+                permutation[e.source().index()],
+                permutation[e.target().index()],
+            );
+            let (a, b) = if g.is_directed() {
+                (a, b)
+            } else {
+                (a.min(b), a.max(b))
+            };
+            (a, b, if with_weights { *e.weight() } else { 0 })
+        })
+        .collect::<Vec<_>>();
+    edges.sort();
+    edges
+}
+
+fn is_automorphism<Ty: EdgeType>(
+    g: &Graph<u8, u8, Ty>,
+    permutation: &[usize],
+    labels: bool,
+) -> bool {
+    let identity = (0..g.node_count()).collect::<Vec<_>>();
+    (!labels
+        || g.node_indices()
+            .all(|v| g[v] == g[n(permutation[v.index()])]))
+        && relabeled_edges(g, permutation, labels) == relabeled_edges(g, &identity, labels)
+}
+
+/// All automorphisms of `g`, by trying every permutation.
+fn brute_force<Ty: EdgeType>(g: &Graph<u8, u8, Ty>, labels: bool) -> HashSet<Vec<usize>> {
+    fn permutations(rest: &mut Vec<usize>, prefix: &mut Vec<usize>, out: &mut Vec<Vec<usize>>) {
+        if rest.is_empty() {
+            out.push(prefix.clone());
+        }
+        for i in 0..rest.len() {
+            let v = rest.remove(i);
+            prefix.push(v);
+            permutations(rest, prefix, out);
+            prefix.pop();
+            rest.insert(i, v);
+        }
+    }
+    let mut all = Vec::new();
+    permutations(
+        &mut (0..g.node_count()).collect(),
+        &mut Vec::new(),
+        &mut all,
+    );
+    all.into_iter()
+        .filter(|p| is_automorphism(g, p, labels))
+        .collect()
+}
+
+/// The group generated by `generators`.
+fn closure(node_count: usize, generators: &[Vec<usize>]) -> HashSet<Vec<usize>> {
+    let identity = (0..node_count).collect::<Vec<_>>();
+    let mut group = HashSet::from_iter([identity.clone()]);
+    let mut stack = vec![identity];
+    while let Some(element) = stack.pop() {
+        for generator in generators {
+            let product = element.iter().map(|&v| generator[v]).collect::<Vec<_>>();
+            if group.insert(product.clone()) {
+                stack.push(product);
+            }
+        }
+    }
+    group
+}
+
+fn check<Ty: EdgeType>(g: &Graph<u8, u8, Ty>, group: &Automorphisms<NodeIndex>, labels: bool) {
+    let generators = group
+        .generators
+        .iter()
+        .map(|generator| generator.iter().map(|v| v.index()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let identity = (0..g.node_count()).collect::<Vec<_>>();
+    for generator in &generators {
+        assert_ne!(generator, &identity);
+        assert!(is_automorphism(g, generator, labels));
+    }
+    let expected = brute_force(g, labels);
+    assert_eq!(closure(g.node_count(), &generators), expected);
+
+    assert_eq!(group.orbits.len(), g.node_count());
+    for a in g.node_indices() {
+        for b in g.node_indices() {
+            let same = expected.iter().any(|p| p[a.index()] == b.index());
+            assert_eq!(group.orbits[&a] == group.orbits[&b], same);
+        }
+    }
+    let count = group.orbits.values().collect::<HashSet<_>>().len();
+    assert_eq!(group.orbit_count, count);
+}
+
+fn check_random<Ty: EdgeType>(seed: u8) {
+    let mut rng = ChaChaRng::from_seed([seed; 32]);
+    for _ in 0..300 {
+        let node_count = rng.gen_range(0, 8);
+        let labels = rng.gen_range(1, 3);
+        // Sparse and dense graphs have more symmetries than those in between.
+        let density = if rng.gen_bool(0.5) {
+            rng.gen_range(0.05, 0.3)
+        } else {
+            rng.gen_range(0.7, 1.)
+        };
+        let g: Graph<u8, u8, Ty> = random_graph(
+            &mut rng,
+            node_count,
+            density,
+            |rng| rng.gen_range(0, labels),
+            |rng| rng.gen_range(0, 2),
+        );
+        check(&g, &automorphisms(&g), false);
+        check(
+            &g,
+            &automorphisms_matching(&g, |a, b| a == b, |a, b| a == b),
+            true,
+        );
+    }
+}
+
+#[test]
+fn automorphisms_random_undirected() {
+    check_random::<Undirected>(1);
+}
+
+#[test]
+fn automorphisms_random_directed() {
+    check_random::<Directed>(2);
+}
+
+#[test]
+fn automorphisms_known_groups() {
+    // The Petersen graph has 120 automorphisms, and a single orbit.
+    let outer = (0..5).map(|i| (i, (i + 1) % 5));
+    let spokes = (0..5).map(|i| (i, i + 5));
+    let inner = (0..5).map(|i| (i + 5, (i + 2) % 5 + 5));
+    let petersen = UnGraph::<u8, u8>::from_edges(outer.chain(spokes).chain(inner));
+    let group = automorphisms(&petersen);
+    let generators = group
+        .generators
+        .iter()
+        .map(|generator| generator.iter().map(|v| v.index()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(closure(10, &generators).len(), 120);
+    assert_eq!(group.orbit_count, 1);
+
+    // A directed cycle can only be rotated.
+    let cycle = DiGraph::<u8, u8>::from_edges((0..6).map(|i| (i, (i + 1) % 6)));
+    let group = automorphisms(&cycle);
+    check(&cycle, &group, false);
+    assert_eq!(group.orbit_count, 1);
+
+    // An empty graph has every permutation as an automorphism.
+    let mut empty = UnGraph::<u8, u8>::new_undirected();
+    let group = automorphisms(&empty);
+    assert!(group.generators.is_empty());
+    assert_eq!(group.orbit_count, 0);
+    for _ in 0..6 {
+        empty.add_node(0);
+    }
+    let group = automorphisms(&empty);
+    check(&empty, &group, false);
+    assert_eq!(group.orbit_count, 1);
+}
+
+#[test]
+fn automorphisms_parallel_edges() {
+    // A path a - b - c, where a - b is doubled, has no symmetries.
+    let g = UnGraph::<u8, u8>::from_edges([(0, 1), (0, 1), (1, 2)]);
+    let group = automorphisms(&g);
+    assert!(group.generators.is_empty());
+    assert_eq!(group.orbit_count, 3);
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn automorphisms_stable_graph() {
+    let mut g = StableUnGraph::<u8, u8>::from_edges([(0, 1), (1, 2), (2, 3), (3, 1)]);
+    g.remove_node(n(0));
+    let group = automorphisms(&g);
+    assert_eq!(group.orbit_count, 1);
+    assert_eq!(group.orbits.len(), 3);
+    for generator in &group.generators {
+        assert_eq!(generator.len(), 4);
+        assert_eq!(generator[0], n(0));
+    }
+}
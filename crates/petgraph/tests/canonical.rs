@@ -0,0 +1,163 @@
+#[path = "utils/random.rs"]
+mod random;
+
+use petgraph::{
+    EdgeType,
+    algo::{CanonicalForm, canonical_form, canonical_labeling, is_isomorphic_matching},
+    graph::node_index as n,
+    prelude::*,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::{permute, random_graph};
+
+fn form<Ty: EdgeType>(g: &Graph<u8, u8, Ty>) -> CanonicalForm<u8, u8> {
+    canonical_form(g, |v| g[v], |e| *e.weight())
+}
+
+/// Check that `form` describes `g` with its nodes in the order of `labeling`.
+fn assert_describes<Ty: EdgeType>(g: &Graph<u8, u8, Ty>, form: &CanonicalForm<u8, u8>) {
+    let labeling = canonical_labeling(g, |v| g[v], |e| *e.weight());
+    let mut position = vec![0; g.node_count()];
+    for (i, v) in labeling.iter().enumerate() {
+        position[v.index()] = i;
+    }
+    assert_eq!(form.directed, g.is_directed());
+    assert_eq!(
+        form.node_labels,
+        labeling.iter().map(|&v| g[v]).collect::<Vec<_>>()
+    );
+    let mut edges = g
+        .edge_references()
+        .map(|e| {
+            let (a, b) = (position[e.source().index()], position[e.target().index()]);
+            if g.is_directed() {
+                (a, b, *e.weight())
+            } else {
+                (a.min(b), a.max(b), *e.weight())
+            }
+        })
+        .collect::<Vec<_>>();
+    edges.sort();
+    assert_eq!(form.edges, edges);
+}
+
+fn check_permuted<Ty: EdgeType>(seed: u8) {
+    let mut rng = ChaChaRng::from_seed([seed; 32]);
+    for _ in 0..200 {
+        let node_count = rng.gen_range(0, 16);
+        let labels = rng.gen_range(1, 3);
+        let density = rng.gen_range(0.05, 0.6);
+        let g: Graph<u8, u8, Ty> = random_graph(
+            &mut rng,
+            node_count,
+            density,
+            |rng| rng.gen_range(0, labels),
+            |rng| rng.gen_range(0, 2),
+        );
+        let (h, _) = permute(&g, &mut rng);
+        let g_form = form(&g);
+        assert_describes(&g, &g_form);
+        assert_eq!(g_form, form(&h));
+    }
+}
+
+#[test]
+fn canonical_form_permuted_undirected() {
+    check_permuted::<Undirected>(1);
+}
+
+#[test]
+fn canonical_form_permuted_directed() {
+    check_permuted::<Directed>(2);
+}
+
+fn check_against_isomorphism<Ty: EdgeType>(seed: u8) {
+    let mut rng = ChaChaRng::from_seed([seed; 32]);
+    let labeled_graph = |rng: &mut ChaChaRng| -> Graph<u8, u8, Ty> {
+        let node_count = rng.gen_range(0, 6);
+        let labels = rng.gen_range(1, 3);
+        let density = rng.gen_range(0.05, 0.6);
+        random_graph(
+            rng,
+            node_count,
+            density,
+            |rng| rng.gen_range(0, labels),
+            |rng| rng.gen_range(0, 2),
+        )
+    };
+    for _ in 0..300 {
+        let g = labeled_graph(&mut rng);
+        let h = labeled_graph(&mut rng);
+        let isomorphic = is_isomorphic_matching(&g, &h, |a, b| a == b, |a, b| a == b);
+        assert_eq!(form(&g) == form(&h), isomorphic);
+    }
+}
+
+#[test]
+fn canonical_form_against_isomorphism_undirected() {
+    check_against_isomorphism::<Undirected>(3);
+}
+
+#[test]
+fn canonical_form_against_isomorphism_directed() {
+    check_against_isomorphism::<Directed>(4);
+}
+
+#[test]
+fn canonical_form_symmetric_graphs() {
+    // Graphs that refinement alone cannot tell apart.
+    let triangles = UnGraph::<u8, u8>::from_edges([(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)]);
+    let hexagon = UnGraph::<u8, u8>::from_edges([(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)]);
+    assert_ne!(form(&triangles), form(&hexagon));
+
+    // The Petersen graph, in two drawings.
+    let outer = (0..5).map(|i| (i, (i + 1) % 5));
+    let spokes = (0..5).map(|i| (i, i + 5));
+    let inner = (0..5).map(|i| (i + 5, (i + 2) % 5 + 5));
+    let petersen = UnGraph::<u8, u8>::from_edges(outer.chain(spokes).chain(inner));
+    let mut rng = ChaChaRng::from_seed([5; 32]);
+    for _ in 0..10 {
+        assert_eq!(form(&petersen), form(&permute(&petersen, &mut rng).0));
+    }
+
+    // Empty and complete graphs.
+    let mut empty = UnGraph::<u8, u8>::new_undirected();
+    assert_eq!(form(&empty).edges, []);
+    for _ in 0..12 {
+        empty.add_node(0);
+    }
+    assert_eq!(form(&empty).node_labels, [0; 12]);
+    let mut complete = empty.clone();
+    for a in 0..12 {
+        for b in a + 1..12 {
+            complete.add_edge(n(a), n(b), 0);
+        }
+    }
+    assert_eq!(form(&complete), form(&permute(&complete, &mut rng).0));
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn canonical_form_stable_graph() {
+    let mut g = StableUnGraph::<u8, u8>::from_edges([(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)]);
+    g.remove_node(n(0));
+    let h = UnGraph::<u8, u8>::from_edges([(0, 1), (1, 2), (2, 0), (0, 3)]);
+    assert_eq!(
+        canonical_form(&g, |_| (), |_| ()),
+        canonical_form(&h, |_| (), |_| ())
+    );
+    let labeling = canonical_labeling(&g, |_| (), |_| ());
+    assert_eq!(labeling.len(), 4);
+    assert!(!labeling.contains(&n(0)));
+}
+
+#[cfg(feature = "graphmap")]
+#[test]
+fn canonical_form_graphmap() {
+    let g = DiGraphMap::<u32, char>::from_edges([(10, 20, 'a'), (20, 30, 'b'), (30, 10, 'a')]);
+    let h = DiGraphMap::<u32, char>::from_edges([(1, 2, 'a'), (2, 3, 'a'), (3, 1, 'b')]);
+    assert_eq!(
+        canonical_form(&g, |_| (), |(_, _, w)| *w),
+        canonical_form(&h, |_| (), |(_, _, w)| *w)
+    );
+}
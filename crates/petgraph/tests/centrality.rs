@@ -0,0 +1,313 @@
+#[path = "utils/random.rs"]
+mod random;
+
+use petgraph::{
+    EdgeType,
+    algo::{
+        betweenness_centrality, closeness_centrality, edge_betweenness_centrality,
+        harmonic_centrality,
+    },
+    graph::node_index as n,
+    prelude::*,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::{assert_close, random_graph};
+
+/// Distances and numbers of shortest paths between all pairs of nodes.
+struct AllPairs {
+    distance: Vec<Vec<Option<u32>>>,
+    paths: Vec<Vec<f64>>,
+    /// The edges in both directions for undirected graphs, with their index.
+    edges: Vec<(usize, usize, u32, usize)>,
+}
+
+fn all_pairs<Ty: EdgeType>(g: &Graph<(), u32, Ty>) -> AllPairs {
+    let node_count = g.node_count();
+    let mut edges = Vec::new();
+    for e in g.edge_references() {
+        let (a, b) = (e.source().index(), e.target().index());
+        edges.push((a, b, *e.weight(), e.id().index()));
+        if !g.is_directed() && a != b {
+            edges.push((b, a, *e.weight(), e.id().index()));
+        }
+    }
+    let mut distance = vec![vec![None; node_count]; node_count];
+    for (v, row) in distance.iter_mut().enumerate() {
+        row[v] = Some(0);
+    }
+    for &(a, b, w, _) in &edges {
+        if distance[a][b].is_none_or(|d| w < d) {
+            distance[a][b] = Some(w);
+        }
+    }
+    for k in 0..node_count {
+        for a in 0..node_count {
+            for b in 0..node_count {
+                if let (Some(x), Some(y)) = (distance[a][k], distance[k][b])
+                    && distance[a][b].is_none_or(|d| x + y < d)
+                {
+                    distance[a][b] = Some(x + y);
+                }
+            }
+        }
+    }
+    // Count the paths to nodes in order of distance, by their last edge.
+    let mut paths = vec![vec![0.; node_count]; node_count];
+    for s in 0..node_count {
+        let mut order = (0..node_count)
+            .filter(|&v| distance[s][v].is_some())
+            .collect::<Vec<_>>();
+        order.sort_by_key(|&v| distance[s][v]);
+        paths[s][s] = 1.;
+        for &t in &order[1..] {
+            paths[s][t] = edges
+                .iter()
+                .filter(|&&(a, b, w, _)| {
+                    b == t && distance[s][a].is_some_and(|d| Some(d + w) == distance[s][t])
+                })
+                .map(|&(a, ..)| paths[s][a])
+                .sum();
+        }
+    }
+    AllPairs {
+        distance,
+        paths,
+        edges,
+    }
+}
+
+/// Betweenness of nodes and edges, from the pairs with a source in `sources`, with every
+/// ordered pair counted.
+fn brute_force_betweenness(
+    g: &AllPairs,
+    edge_bound: usize,
+    sources: &[usize],
+) -> (Vec<f64>, Vec<f64>) {
+    let node_count = g.distance.len();
+    let mut nodes = vec![0.; node_count];
+    let mut edges = vec![0.; edge_bound];
+    for &s in sources {
+        for t in 0..node_count {
+            let Some(d) = g.distance[s][t] else {
+                continue;
+            };
+            if s == t {
+                continue;
+            }
+            for (v, score) in nodes.iter_mut().enumerate() {
+                if v != s
+                    && v != t
+                    && let (Some(x), Some(y)) = (g.distance[s][v], g.distance[v][t])
+                    && x + y == d
+                {
+                    *score += g.paths[s][v] * g.paths[v][t] / g.paths[s][t];
+                }
+            }
+            for &(a, b, w, e) in &g.edges {
+                if let (Some(x), Some(y)) = (g.distance[s][a], g.distance[b][t])
+                    && x + w + y == d
+                {
+                    edges[e] += g.paths[s][a] * g.paths[b][t] / g.paths[s][t];
+                }
+            }
+        }
+    }
+    (nodes, edges)
+}
+
+fn check_random<Ty: EdgeType>(seed: u8) {
+    let mut rng = ChaChaRng::from_seed([seed; 32]);
+    for _ in 0..200 {
+        let node_count = rng.gen_range(0, 8);
+        let density = rng.gen_range(0.1, 0.6);
+        let g = random_graph::<_, _, Ty>(
+            &mut rng,
+            node_count,
+            density,
+            |_| (),
+            |rng| rng.gen_range(1, 4u32),
+        );
+        let node_count = g.node_count();
+        let pairs = all_pairs(&g);
+        let half = if g.is_directed() { 1. } else { 0.5 };
+
+        let all = (0..node_count).collect::<Vec<_>>();
+        let (nodes, edges) = brute_force_betweenness(&pairs, g.edge_count(), &all);
+        let scaled =
+            |scores: &[f64], factor: f64| scores.iter().map(|x| x * factor).collect::<Vec<_>>();
+        assert_close(
+            &betweenness_centrality(&g, false, None, |e| *e.weight()),
+            &scaled(&nodes, half),
+        );
+        assert_close(
+            &edge_betweenness_centrality(&g, false, None, |e| *e.weight()),
+            &scaled(&edges, half),
+        );
+        if node_count > 2 {
+            let pairs_count = ((node_count - 1) * (node_count - 2)) as f64;
+            assert_close(
+                &betweenness_centrality(&g, true, None, |e| *e.weight()),
+                &scaled(&nodes, 1. / pairs_count),
+            );
+            let pairs_count = (node_count * (node_count - 1)) as f64;
+            assert_close(
+                &edge_betweenness_centrality(&g, true, None, |e| *e.weight()),
+                &scaled(&edges, 1. / pairs_count),
+            );
+        }
+
+        // Sampled sources are extrapolated to all nodes.
+        let sample = all
+            .iter()
+            .copied()
+            .filter(|_| rng.gen_bool(0.5))
+            .collect::<Vec<_>>();
+        if !sample.is_empty() {
+            let (nodes, edges) = brute_force_betweenness(&pairs, g.edge_count(), &sample);
+            let factor = half * node_count as f64 / sample.len() as f64;
+            let sources = sample.iter().map(|&v| n(v)).collect::<Vec<_>>();
+            assert_close(
+                &betweenness_centrality(&g, false, Some(&sources), |e| *e.weight()),
+                &scaled(&nodes, factor),
+            );
+            assert_close(
+                &edge_betweenness_centrality(&g, false, Some(&sources), |e| *e.weight()),
+                &scaled(&edges, factor),
+            );
+        }
+
+        let mut closeness = vec![0.; node_count];
+        let mut harmonic = vec![0.; node_count];
+        for u in 0..node_count {
+            let reached = pairs.distance[u].iter().flatten().collect::<Vec<_>>();
+            let total = reached.iter().map(|&&d| d as f64).sum::<f64>();
+            let r = (reached.len() - 1) as f64;
+            if total > 0. {
+                closeness[u] = r / total * r / (node_count - 1) as f64;
+            }
+            harmonic[u] = reached
+                .iter()
+                .filter(|&&&d| d > 0)
+                .map(|&&d| 1. / d as f64)
+                .sum();
+        }
+        assert_close(&closeness_centrality(&g, |e| *e.weight()), &closeness);
+        assert_close(&harmonic_centrality(&g, |e| *e.weight()), &harmonic);
+    }
+}
+
+#[test]
+fn centrality_random_directed() {
+    check_random::<Directed>(37);
+}
+
+#[test]
+fn centrality_random_undirected() {
+    check_random::<Undirected>(38);
+}
+
+#[test]
+fn centrality_known_graphs() {
+    let empty = DiGraph::<(), u32>::new();
+    assert!(betweenness_centrality(&empty, true, None, |_| 1).is_empty());
+    assert!(closeness_centrality(&empty, |_| 1).is_empty());
+
+    // In a star, all paths between leaves pass through the center.
+    let star = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3), (0, 4)]);
+    assert_eq!(
+        betweenness_centrality(&star, false, None, |_| 1),
+        [6., 0., 0., 0., 0.]
+    );
+    assert_eq!(
+        betweenness_centrality(&star, true, None, |_| 1),
+        [1., 0., 0., 0., 0.]
+    );
+    assert_eq!(
+        edge_betweenness_centrality(&star, false, None, |_| 1),
+        [4.; 4]
+    );
+    assert_eq!(
+        closeness_centrality(&star, |_| 1),
+        [1., 4. / 7., 4. / 7., 4. / 7., 4. / 7.]
+    );
+    assert_eq!(harmonic_centrality(&star, |_| 1), [4., 2.5, 2.5, 2.5, 2.5]);
+
+    // Costs decide which of two routes is shortest.
+    let g = DiGraph::<(), f64>::from_edges([(0, 1, 1.), (1, 3, 1.), (0, 2, 0.5), (2, 3, 1.)]);
+    assert_eq!(
+        betweenness_centrality(&g, false, None, |e| *e.weight()),
+        [0., 0., 1., 0.]
+    );
+    assert_eq!(
+        betweenness_centrality(&g, false, None, |_| 1),
+        [0., 0.5, 0.5, 0.]
+    );
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn centrality_parallel() {
+    use petgraph::{
+        algo::{
+            parallel_betweenness_centrality, parallel_closeness_centrality,
+            parallel_edge_betweenness_centrality, parallel_harmonic_centrality,
+        },
+        graph::EdgeReference,
+    };
+
+    let mut rng = ChaChaRng::from_seed([39; 32]);
+    for _ in 0..10 {
+        let node_count = rng.gen_range(0, 101);
+        let density = rng.gen_range(0.1, 0.6);
+        let g = random_graph::<_, _, Directed>(
+            &mut rng,
+            node_count,
+            density,
+            |_| (),
+            |rng| rng.gen_range(1, 4u32),
+        );
+        let cost = |e: EdgeReference<u32>| *e.weight();
+        assert_close(
+            &parallel_betweenness_centrality(&g, true, None, cost),
+            &betweenness_centrality(&g, true, None, cost),
+        );
+        assert_close(
+            &parallel_edge_betweenness_centrality(&g, true, None, cost),
+            &edge_betweenness_centrality(&g, true, None, cost),
+        );
+        let sources = g.node_indices().step_by(3).collect::<Vec<_>>();
+        assert_close(
+            &parallel_betweenness_centrality(&g, false, Some(&sources), cost),
+            &betweenness_centrality(&g, false, Some(&sources), cost),
+        );
+        assert_eq!(
+            parallel_closeness_centrality(&g, cost),
+            closeness_centrality(&g, cost)
+        );
+        assert_eq!(
+            parallel_harmonic_centrality(&g, cost),
+            harmonic_centrality(&g, cost)
+        );
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn centrality_stable_graph() {
+    let mut g = StableUnGraph::<(), u32>::from_edges([(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 0, 1)]);
+    g.remove_node(n(0));
+    let edge = g.find_edge(n(1), n(2)).unwrap();
+    assert_eq!(
+        betweenness_centrality(&g, false, None, |e| *e.weight()),
+        [0., 0., 1., 0.]
+    );
+    assert_eq!(
+        edge_betweenness_centrality(&g, false, None, |e| *e.weight())[edge.index()],
+        2.
+    );
+    assert_eq!(harmonic_centrality(&g, |e| *e.weight())[0], 0.);
+    assert_eq!(
+        closeness_centrality(&g, |e| *e.weight()),
+        [0., 2. / 3., 1., 2. / 3.]
+    );
+}
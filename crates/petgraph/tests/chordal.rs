@@ -0,0 +1,268 @@
+#[path = "utils/random.rs"]
+mod random;
+
+use petgraph::{
+    algo::{CycleWitness, is_chordal, minimal_triangulation, perfect_elimination_ordering},
+    graph::{EdgeIndex, node_index as n},
+    prelude::*,
+    visit::{LexBfs, MaxCardinalitySearch, Walker},
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::random_graph;
+
+fn adjacency_matrix(g: &UnGraph<(), ()>) -> Vec<Vec<bool>> {
+    let mut adjacent = vec![vec![false; g.node_count()]; g.node_count()];
+    for e in g.edge_references() {
+        let (a, b) = (e.source().index(), e.target().index());
+        if a != b {
+            adjacent[a][b] = true;
+            adjacent[b][a] = true;
+        }
+    }
+    adjacent
+}
+
+/// Check chordality by repeatedly removing a node whose neighbors form a clique.
+fn brute_force_chordal(adjacent: &[Vec<bool>]) -> bool {
+    let mut remaining = (0..adjacent.len()).collect::<Vec<_>>();
+    'outer: while !remaining.is_empty() {
+        for (i, &v) in remaining.iter().enumerate() {
+            let neighbors = remaining
+                .iter()
+                .copied()
+                .filter(|&w| adjacent[v][w])
+                .collect::<Vec<_>>();
+            let clique = neighbors
+                .iter()
+                .all(|&a| neighbors.iter().all(|&b| a == b || adjacent[a][b]));
+            if clique {
+                remaining.remove(i);
+                continue 'outer;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+fn is_perfect_elimination_ordering(adjacent: &[Vec<bool>], order: &[NodeIndex]) -> bool {
+    let mut sorted = order.to_vec();
+    sorted.sort();
+    if sorted != (0..adjacent.len()).map(n).collect::<Vec<_>>() {
+        return false;
+    }
+    (0..order.len()).all(|i| {
+        let later = order[i + 1..]
+            .iter()
+            .map(|v| v.index())
+            .filter(|&w| adjacent[order[i].index()][w])
+            .collect::<Vec<_>>();
+        later
+            .iter()
+            .all(|&a| later.iter().all(|&b| a == b || adjacent[a][b]))
+    })
+}
+
+fn assert_chordless_cycle(
+    g: &UnGraph<(), ()>,
+    adjacent: &[Vec<bool>],
+    cycle: &CycleWitness<NodeIndex, EdgeIndex>,
+) {
+    let nodes = cycle.nodes();
+    let len = nodes.len();
+    assert!(len >= 4, "cycle {nodes:?} is too short");
+    assert_eq!(cycle.edges().len(), len);
+    for (i, &e) in cycle.edges().iter().enumerate() {
+        let (a, b) = g.edge_endpoints(e).unwrap();
+        let (u, v) = (nodes[i], nodes[(i + 1) % len]);
+        assert!((a, b) == (u, v) || (a, b) == (v, u));
+    }
+    for i in 0..len {
+        for j in i + 1..len {
+            assert_ne!(nodes[i], nodes[j]);
+            let consecutive = j == i + 1 || (i == 0 && j == len - 1);
+            assert_eq!(
+                adjacent[nodes[i].index()][nodes[j].index()],
+                consecutive,
+                "{:?} and {:?} in {nodes:?}",
+                nodes[i],
+                nodes[j]
+            );
+        }
+    }
+}
+
+#[test]
+fn chordal_random() {
+    let mut rng = ChaChaRng::from_seed([8; 32]);
+    for _ in 0..2000 {
+        let node_count = rng.gen_range(0, 10);
+        let density = rng.gen_range(0.1, 0.8);
+        let g: UnGraph<(), ()> = random_graph(&mut rng, node_count, density, |_| (), |_| ());
+        let adjacent = adjacency_matrix(&g);
+        let chordal = brute_force_chordal(&adjacent);
+        assert_eq!(is_chordal(&g), chordal);
+        match perfect_elimination_ordering(&g) {
+            Ok(order) => {
+                assert!(chordal);
+                assert!(is_perfect_elimination_ordering(&adjacent, &order));
+            }
+            Err(cycle) => {
+                assert!(!chordal);
+                assert_chordless_cycle(&g, &adjacent, &cycle);
+            }
+        }
+    }
+}
+
+#[test]
+fn chordal_long_cycle() {
+    let mut g = UnGraph::<(), ()>::from_edges((0..10).map(|i| (i, (i + 1) % 10)));
+    let adjacent = adjacency_matrix(&g);
+    let cycle = perfect_elimination_ordering(&g).unwrap_err();
+    assert_eq!(cycle.len(), 10);
+    assert_chordless_cycle(&g, &adjacent, &cycle);
+
+    // A chord splits it into a triangle and a chordless cycle of length nine.
+    g.add_edge(n(0), n(2), ());
+    let adjacent = adjacency_matrix(&g);
+    let cycle = perfect_elimination_ordering(&g).unwrap_err();
+    assert_eq!(cycle.len(), 9);
+    assert_chordless_cycle(&g, &adjacent, &cycle);
+}
+
+#[test]
+fn minimal_triangulation_random() {
+    let mut rng = ChaChaRng::from_seed([9; 32]);
+    for _ in 0..1000 {
+        let node_count = rng.gen_range(0, 10);
+        let density = rng.gen_range(0.1, 0.8);
+        let g: UnGraph<(), ()> = random_graph(&mut rng, node_count, density, |_| (), |_| ());
+        let adjacent = adjacency_matrix(&g);
+        let (order, fill) = minimal_triangulation(&g);
+        if brute_force_chordal(&adjacent) {
+            assert!(fill.is_empty());
+        }
+
+        let mut triangulated = adjacent.clone();
+        for &(a, b) in &fill {
+            let (a, b) = (a.index(), b.index());
+            assert_ne!(a, b);
+            assert!(!triangulated[a][b], "duplicate or existing fill edge");
+            triangulated[a][b] = true;
+            triangulated[b][a] = true;
+        }
+        assert!(is_perfect_elimination_ordering(&triangulated, &order));
+
+        // The triangulation is minimal if and only if no single fill edge can be removed.
+        for &(a, b) in &fill {
+            let (a, b) = (a.index(), b.index());
+            triangulated[a][b] = false;
+            triangulated[b][a] = false;
+            assert!(!brute_force_chordal(&triangulated));
+            triangulated[a][b] = true;
+            triangulated[b][a] = true;
+        }
+    }
+}
+
+#[test]
+fn lex_bfs_random() {
+    let mut rng = ChaChaRng::from_seed([10; 32]);
+    for _ in 0..500 {
+        let node_count = rng.gen_range(0, 9);
+        let density = rng.gen_range(0.1, 0.8);
+        let g: UnGraph<(), ()> = random_graph(&mut rng, node_count, density, |_| (), |_| ());
+        let adjacent = adjacency_matrix(&g);
+        let start = if g.node_count() > 0 {
+            n(rng.gen_range(0, g.node_count()))
+        } else {
+            n(0)
+        };
+        let order = if g.node_count() > 0 && rng.gen_bool(0.5) {
+            LexBfs::with_start(&g, start).iter(&g).collect::<Vec<_>>()
+        } else {
+            LexBfs::new(&g).iter(&g).collect::<Vec<_>>()
+        };
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(sorted, g.node_indices().collect::<Vec<_>>());
+
+        // The label of a node lists its visited neighbors, earliest first, and each visited node
+        // has the lexicographically largest label.
+        let mut visited = Vec::new();
+        for &v in &order {
+            let label = visited
+                .iter()
+                .enumerate()
+                .filter(|&(_, &w): &(usize, &NodeIndex)| adjacent[v.index()][w.index()])
+                .map(|(i, _)| order.len() - i)
+                .collect::<Vec<_>>();
+            // Every remaining node has a label no larger than the one of `v`.
+            for w in g.node_indices().filter(|w| !visited.contains(w)) {
+                let other = visited
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &u): &(usize, &NodeIndex)| adjacent[w.index()][u.index()])
+                    .map(|(i, _)| order.len() - i)
+                    .collect::<Vec<_>>();
+                assert!(other <= label, "{w:?} should be visited before {v:?}");
+            }
+            visited.push(v);
+        }
+
+        // Its reverse is a perfect elimination ordering of chordal graphs.
+        if brute_force_chordal(&adjacent) {
+            let mut reversed = order.clone();
+            reversed.reverse();
+            assert!(is_perfect_elimination_ordering(&adjacent, &reversed));
+        }
+    }
+}
+
+#[test]
+fn max_cardinality_search_random() {
+    let mut rng = ChaChaRng::from_seed([12; 32]);
+    for _ in 0..500 {
+        let node_count = rng.gen_range(0, 9);
+        let density = rng.gen_range(0.1, 0.8);
+        let g: UnGraph<(), ()> = random_graph(&mut rng, node_count, density, |_| (), |_| ());
+        let adjacent = adjacency_matrix(&g);
+        let order = MaxCardinalitySearch::new(&g).iter(&g).collect::<Vec<_>>();
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(sorted, g.node_indices().collect::<Vec<_>>());
+
+        let mut visited = vec![false; g.node_count()];
+        for &v in &order {
+            let count = |w: usize| {
+                (0..g.node_count())
+                    .filter(|&u| visited[u] && adjacent[w][u])
+                    .count()
+            };
+            let best = (0..g.node_count())
+                .filter(|&w| !visited[w])
+                .map(count)
+                .max()
+                .unwrap();
+            assert_eq!(count(v.index()), best);
+            visited[v.index()] = true;
+        }
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn chordal_stable_graph() {
+    let mut g = StableUnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+    assert!(!is_chordal(&g));
+    assert_eq!(minimal_triangulation(&g).1.len(), 2);
+    g.remove_node(n(3));
+    assert!(is_chordal(&g));
+    assert_eq!(LexBfs::new(&g).iter(&g).count(), 4);
+    let order = perfect_elimination_ordering(&g).unwrap();
+    assert_eq!(order.len(), 4);
+    assert!(!order.contains(&n(3)));
+    assert_eq!(MaxCardinalitySearch::new(&g).iter(&g).count(), 4);
+    assert!(minimal_triangulation(&g).1.is_empty());
+}
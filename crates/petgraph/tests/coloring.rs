@@ -1,4 +1,18 @@
-use petgraph::{Graph, Undirected, algo::dsatur_coloring};
+#[path = "utils/random.rs"]
+mod random;
+
+use hashbrown::HashMap;
+use petgraph::{
+    Graph, Undirected,
+    algo::{
+        GreedyOrdering, dsatur_coloring, exact_coloring, greedy_coloring, is_proper_coloring,
+        is_proper_edge_coloring, misra_gries_edge_coloring,
+    },
+    graph::node_index as n,
+    prelude::*,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::random_graph;
 
 #[test]
 fn dsatur_coloring_cycle6() {
@@ -56,3 +70,249 @@ fn dsatur_coloring_bipartite() {
     let (_, nb_colors) = dsatur_coloring(&graph);
     assert_eq!(nb_colors, 2);
 }
+
+fn color_count(coloring: &HashMap<NodeIndex, usize>) -> usize {
+    coloring.values().map(|&c| c + 1).max().unwrap_or(0)
+}
+
+/// Find the chromatic number by trying every number of colors.
+fn brute_force_chromatic_number(g: &UnGraph<(), ()>) -> usize {
+    fn colorable(g: &UnGraph<(), ()>, colors: &mut Vec<usize>, k: usize) -> bool {
+        let v = colors.len();
+        if v == g.node_count() {
+            return true;
+        }
+        for c in 0..k {
+            if g.neighbors(n(v))
+                .all(|w| w.index() >= v || colors[w.index()] != c)
+            {
+                colors.push(c);
+                if colorable(g, colors, k) {
+                    return true;
+                }
+                colors.pop();
+            }
+        }
+        false
+    }
+    (0..).find(|&k| colorable(g, &mut Vec::new(), k)).unwrap()
+}
+
+#[test]
+fn exact_coloring_known_graphs() {
+    let empty = UnGraph::<(), ()>::default();
+    assert_eq!(exact_coloring(&empty, None), (HashMap::new(), 0));
+
+    let mut isolated = UnGraph::<(), ()>::default();
+    isolated.add_node(());
+    isolated.add_node(());
+    let odd_cycle = UnGraph::<(), ()>::from_edges((0..7).map(|i| (i, (i + 1) % 7)));
+    // The Mycielski graph of a 5-cycle has no triangle, but needs four colors.
+    let mut grotzsch = UnGraph::<(), ()>::from_edges((0..5).map(|i| (i, (i + 1) % 5)));
+    grotzsch.extend_with_edges((0..5).flat_map(|i| [(i + 5, (i + 1) % 5), (i + 5, (i + 4) % 5)]));
+    grotzsch.extend_with_edges((5..10).map(|i| (i, 10)));
+    let mut complete = UnGraph::<(), ()>::default();
+    for a in 0..6 {
+        for b in a + 1..6 {
+            complete.extend_with_edges([(a, b)]);
+        }
+    }
+    for (g, expected) in [
+        (&isolated, 1),
+        (&odd_cycle, 3),
+        (&grotzsch, 4),
+        (&complete, 6),
+    ] {
+        let (coloring, colors) = exact_coloring(g, None);
+        assert_eq!(colors, expected);
+        assert_eq!(color_count(&coloring), colors);
+        assert!(is_proper_coloring(g, &coloring));
+    }
+}
+
+#[test]
+fn exact_coloring_random() {
+    let mut rng = ChaChaRng::from_seed([15; 32]);
+    for _ in 0..500 {
+        let node_count = rng.gen_range(0, 10);
+        let density = rng.gen_range(0.05, 0.9);
+        let g: UnGraph<(), ()> = random_graph(&mut rng, node_count, density, |_| (), |_| ());
+        let (coloring, colors) = exact_coloring(&g, None);
+        assert_eq!(colors, brute_force_chromatic_number(&g));
+        assert_eq!(coloring.len(), g.node_count());
+        assert_eq!(color_count(&coloring), colors);
+        assert!(is_proper_coloring(&g, &coloring));
+        assert!(colors <= dsatur_coloring(&g).1);
+    }
+}
+
+#[test]
+fn exact_coloring_budget() {
+    let mut rng = ChaChaRng::from_seed([16; 32]);
+    for _ in 0..50 {
+        let node_count = rng.gen_range(0, 41);
+        let density = rng.gen_range(0.05, 0.9);
+        let g: UnGraph<(), ()> = random_graph(&mut rng, node_count, density, |_| (), |_| ());
+        let (optimal, colors) = exact_coloring(&g, None);
+        assert!(is_proper_coloring(&g, &optimal));
+        for budget in [0, 10, 1000] {
+            // A budget still gives a proper coloring, but maybe not a minimal one.
+            let (coloring, budget_colors) = exact_coloring(&g, Some(budget));
+            assert!(is_proper_coloring(&g, &coloring));
+            assert_eq!(color_count(&coloring), budget_colors);
+            assert!(budget_colors >= colors);
+        }
+    }
+}
+
+#[test]
+fn greedy_coloring_orderings() {
+    // A crown graph with the nodes of every matched pair next to each other needs half as many
+    // colors as nodes when colored in index order, but is bipartite.
+    let mut crown = UnGraph::<(), ()>::default();
+    for a in 0..6 {
+        for b in 0..6 {
+            if a < b {
+                crown.extend_with_edges([(2 * a, 2 * b + 1), (2 * a + 1, 2 * b)]);
+            }
+        }
+    }
+    for ordering in [
+        GreedyOrdering::LargestFirst,
+        GreedyOrdering::SmallestLast,
+        GreedyOrdering::Degeneracy,
+    ] {
+        let (coloring, colors) = greedy_coloring(&crown, ordering);
+        assert!(is_proper_coloring(&crown, &coloring));
+        assert_eq!(color_count(&coloring), colors);
+        assert!(colors >= 2);
+    }
+
+    // Smallest-last colors planar graphs with at most six colors, and trees with two.
+    let tree = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3), (3, 4), (4, 5), (0, 6)]);
+    assert_eq!(greedy_coloring(&tree, GreedyOrdering::SmallestLast).1, 2);
+    assert_eq!(greedy_coloring(&tree, GreedyOrdering::Degeneracy).1, 2);
+}
+
+#[test]
+fn greedy_coloring_random() {
+    let mut rng = ChaChaRng::from_seed([17; 32]);
+    for _ in 0..300 {
+        let node_count = rng.gen_range(0, 31);
+        let density = rng.gen_range(0.05, 0.9);
+        let g: UnGraph<(), ()> = random_graph(&mut rng, node_count, density, |_| (), |_| ());
+        let max_degree = g
+            .node_indices()
+            .map(|v| g.neighbors(v).filter(|&w| w != v).count())
+            .max()
+            .unwrap_or(0);
+        // The degeneracy is the largest minimum degree of a subgraph.
+        let mut degeneracy = 0;
+        let mut h = g.clone();
+        while h.node_count() > 0 {
+            let (v, degree) = h
+                .node_indices()
+                .map(|v| (v, h.neighbors(v).filter(|&w| w != v).collect::<Vec<_>>()))
+                .map(|(v, mut neighbors)| {
+                    neighbors.sort();
+                    neighbors.dedup();
+                    (v, neighbors.len())
+                })
+                .min_by_key(|&(_, degree)| degree)
+                .unwrap();
+            degeneracy = degeneracy.max(degree);
+            h.remove_node(v);
+        }
+
+        for ordering in [
+            GreedyOrdering::LargestFirst,
+            GreedyOrdering::SmallestLast,
+            GreedyOrdering::Degeneracy,
+        ] {
+            let (coloring, colors) = greedy_coloring(&g, ordering);
+            assert!(is_proper_coloring(&g, &coloring));
+            assert_eq!(coloring.len(), g.node_count());
+            assert_eq!(color_count(&coloring), colors);
+            assert!(colors <= max_degree + 1);
+            if ordering == GreedyOrdering::SmallestLast {
+                assert!(colors <= degeneracy + 1);
+            }
+        }
+    }
+}
+
+#[test]
+fn edge_coloring_random() {
+    let mut rng = ChaChaRng::from_seed([18; 32]);
+    for _ in 0..500 {
+        let node_count = rng.gen_range(0, 26);
+        let density = rng.gen_range(0.05, 0.9);
+        let g: UnGraph<(), ()> = random_graph(&mut rng, node_count, density, |_| (), |_| ());
+        let (coloring, colors) = misra_gries_edge_coloring(&g);
+        assert!(is_proper_edge_coloring(&g, &coloring));
+        assert_eq!(coloring.values().map(|&c| c + 1).max().unwrap_or(0), colors);
+        assert!(
+            g.edge_references()
+                .all(|e| { (e.source() == e.target()) != coloring.contains_key(&e.id()) })
+        );
+
+        let simple = g.edge_references().all(|e| {
+            e.source() != e.target() && g.edges_connecting(e.source(), e.target()).count() == 1
+        });
+        let max_degree = g
+            .node_indices()
+            .map(|v| g.edges(v).filter(|e| e.source() != e.target()).count())
+            .max()
+            .unwrap_or(0);
+        if simple {
+            assert!(colors <= max_degree + 1);
+        }
+        assert!(colors >= max_degree);
+    }
+}
+
+#[test]
+fn edge_coloring_multigraph() {
+    // Three parallel edges and a self-loop.
+    let g = UnGraph::<(), ()>::from_edges([(0, 1), (0, 1), (1, 0), (1, 2), (2, 2)]);
+    let (coloring, colors) = misra_gries_edge_coloring(&g);
+    assert!(is_proper_edge_coloring(&g, &coloring));
+    assert_eq!(colors, 4);
+    assert_eq!(coloring.len(), 4);
+}
+
+#[test]
+fn is_proper_coloring_invalid() {
+    let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 2)]);
+    let mut coloring = HashMap::from_iter([(n(0), 0), (n(1), 1)]);
+    // A node without a color.
+    assert!(!is_proper_coloring(&g, &coloring));
+    coloring.insert(n(2), 0);
+    assert!(is_proper_coloring(&g, &coloring));
+
+    let edges = g.edge_indices().collect::<Vec<_>>();
+    let mut edge_coloring = HashMap::from_iter([(edges[0], 0)]);
+    assert!(!is_proper_edge_coloring(&g, &edge_coloring));
+    edge_coloring.insert(edges[1], 0);
+    assert!(!is_proper_edge_coloring(&g, &edge_coloring));
+    edge_coloring.insert(edges[1], 1);
+    assert!(is_proper_edge_coloring(&g, &edge_coloring));
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn coloring_stable_graph() {
+    let mut g = StableUnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (2, 3), (3, 4)]);
+    g.remove_node(n(1));
+    let (coloring, colors) = exact_coloring(&g, None);
+    assert_eq!(colors, 2);
+    assert!(!coloring.contains_key(&n(1)));
+    assert!(is_proper_coloring(&g, &coloring));
+    let (coloring, colors) = greedy_coloring(&g, GreedyOrdering::SmallestLast);
+    assert_eq!(colors, 2);
+    assert!(is_proper_coloring(&g, &coloring));
+    let (coloring, colors) = misra_gries_edge_coloring(&g);
+    assert!(colors <= 3);
+    assert_eq!(coloring.len(), 3);
+    assert!(is_proper_edge_coloring(&g, &coloring));
+}
@@ -0,0 +1,296 @@
+#[path = "utils/random.rs"]
+mod random;
+
+use hashbrown::HashMap;
+use petgraph::{
+    EdgeType,
+    algo::{label_propagation_communities, leiden_communities, louvain_communities, modularity},
+    graph::node_index as n,
+    prelude::*,
+    unionfind::UnionFind,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::random_graph;
+
+/// `count` cliques of `size` nodes in a ring, joined by single edges.
+fn ring_of_cliques(count: usize, size: usize) -> UnGraph<(), f64> {
+    let mut g = UnGraph::with_capacity(count * size, 0);
+    for _ in 0..count * size {
+        g.add_node(());
+    }
+    for clique in 0..count {
+        for a in 0..size {
+            for b in a + 1..size {
+                g.add_edge(n(clique * size + a), n(clique * size + b), 1.);
+            }
+        }
+        g.add_edge(
+            n(clique * size + size - 1),
+            n((clique + 1) % count * size),
+            1.,
+        );
+    }
+    g
+}
+
+/// Modularity from its definition, as a sum over pairs of nodes.
+fn brute_force_modularity<Ty: EdgeType>(
+    g: &Graph<(), f64, Ty>,
+    communities: &HashMap<NodeIndex, usize>,
+    resolution: f64,
+) -> f64 {
+    let node_count = g.node_count();
+    let mut adjacency = vec![vec![0.; node_count]; node_count];
+    for e in g.edge_references() {
+        let (a, b) = (e.source().index(), e.target().index());
+        adjacency[a][b] += e.weight();
+        adjacency[b][a] += e.weight();
+    }
+    let degree = adjacency
+        .iter()
+        .map(|row| row.iter().sum::<f64>())
+        .collect::<Vec<_>>();
+    let total = degree.iter().sum::<f64>();
+    if total == 0. {
+        return 0.;
+    }
+    let mut sum = 0.;
+    for a in 0..node_count {
+        for b in 0..node_count {
+            if communities[&n(a)] == communities[&n(b)] {
+                sum += adjacency[a][b] - resolution * degree[a] * degree[b] / total;
+            }
+        }
+    }
+    sum / total
+}
+
+fn assert_partition<Ty: EdgeType>(
+    g: &Graph<(), f64, Ty>,
+    (communities, count): &(HashMap<NodeIndex, usize>, usize),
+) {
+    assert_eq!(communities.len(), g.node_count());
+    assert!(communities.values().all(|&c| c < *count));
+    let mut used = vec![false; *count];
+    communities.values().for_each(|&c| used[c] = true);
+    assert!(used.into_iter().all(|used| used));
+}
+
+fn assert_connected<Ty: EdgeType>(g: &Graph<(), f64, Ty>, communities: &HashMap<NodeIndex, usize>) {
+    let mut components = UnionFind::new(g.node_count());
+    for e in g.edge_references() {
+        if communities[&e.source()] == communities[&e.target()] {
+            components.union(e.source().index(), e.target().index());
+        }
+    }
+    let mut representative = HashMap::new();
+    for v in g.node_indices() {
+        let root = components.find(v.index());
+        assert_eq!(*representative.entry(communities[&v]).or_insert(root), root);
+    }
+}
+
+/// The modularity of `communities` is at least that of putting every node in its own
+/// community or all of them in the same one.
+fn assert_better_than_trivial<Ty: EdgeType>(
+    g: &Graph<(), f64, Ty>,
+    communities: &HashMap<NodeIndex, usize>,
+) {
+    let score = modularity(g, communities, 1., |e| *e.weight());
+    let singletons = g.node_indices().map(|v| (v, v.index())).collect();
+    let together = g.node_indices().map(|v| (v, 0)).collect();
+    assert!(score >= modularity(g, &singletons, 1., |e| *e.weight()) - 1e-12);
+    assert!(score >= modularity(g, &together, 1., |e| *e.weight()) - 1e-12);
+}
+
+#[test]
+fn modularity_random() {
+    let mut rng = ChaChaRng::from_seed([3; 32]);
+    for _ in 0..100 {
+        let node_count = rng.gen_range(0, 30);
+        let density = rng.gen_range(0.05, 0.4);
+        let g = random_graph::<_, _, Undirected>(
+            &mut rng,
+            node_count,
+            density,
+            |_| (),
+            |rng| rng.gen_range(0.5, 2.),
+        );
+        let count = rng.gen_range(1, 5);
+        let communities = g
+            .node_indices()
+            .map(|v| (v, rng.gen_range(0, count)))
+            .collect();
+        let resolution = rng.gen_range(0.5, 2.);
+        let score = modularity(&g, &communities, resolution, |e| *e.weight());
+        let expected = brute_force_modularity(&g, &communities, resolution);
+        assert!((score - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn louvain_and_leiden_ring_of_cliques() {
+    let g = ring_of_cliques(6, 5);
+    for seed in 0..10 {
+        for (communities, count) in [
+            louvain_communities(&g, 1., seed, |e| *e.weight()),
+            leiden_communities(&g, 1., seed, |e| *e.weight()),
+        ] {
+            assert_eq!(count, 6);
+            for v in g.node_indices() {
+                assert_eq!(communities[&v], communities[&n(v.index() / 5 * 5)]);
+            }
+        }
+    }
+
+    // At a low resolution, pairs of cliques merge.
+    let g = ring_of_cliques(8, 3);
+    let (_, count) = leiden_communities(&g, 0.2, 0, |e| *e.weight());
+    assert!(count < 8);
+}
+
+#[test]
+fn louvain_and_leiden_random() {
+    let mut rng = ChaChaRng::from_seed([17; 32]);
+    for _ in 0..100 {
+        let node_count = rng.gen_range(0, 30);
+        let density = rng.gen_range(0.05, 0.4);
+        let g = random_graph::<_, _, Undirected>(
+            &mut rng,
+            node_count,
+            density,
+            |_| (),
+            |rng| rng.gen_range(0.5, 2.),
+        );
+        let seed = rng.gen_range(0, u64::MAX);
+
+        let louvain = louvain_communities(&g, 1., seed, |e| *e.weight());
+        assert_partition(&g, &louvain);
+        assert_better_than_trivial(&g, &louvain.0);
+        assert_eq!(louvain, louvain_communities(&g, 1., seed, |e| *e.weight()));
+
+        let leiden = leiden_communities(&g, 1., seed, |e| *e.weight());
+        assert_partition(&g, &leiden);
+        assert_better_than_trivial(&g, &leiden.0);
+        assert_connected(&g, &leiden.0);
+        assert_eq!(leiden, leiden_communities(&g, 1., seed, |e| *e.weight()));
+    }
+}
+
+#[test]
+fn label_propagation_ring_of_cliques() {
+    // Labels may take over a neighboring clique, so only check that no clique is split.
+    let g = ring_of_cliques(6, 8);
+    for seed in 0..10 {
+        let (communities, count) = label_propagation_communities(&g, seed, |e| *e.weight());
+        assert!((3..=6).contains(&count));
+        for v in g.node_indices() {
+            assert_eq!(communities[&v], communities[&n(v.index() / 8 * 8)]);
+        }
+    }
+}
+
+#[test]
+fn label_propagation_random() {
+    let mut rng = ChaChaRng::from_seed([23; 32]);
+    for _ in 0..100 {
+        let node_count = rng.gen_range(0, 30);
+        let density = rng.gen_range(0.05, 0.4);
+        let g = random_graph::<_, _, Directed>(
+            &mut rng,
+            node_count,
+            density,
+            |_| (),
+            |rng| rng.gen_range(0.5, 2.),
+        );
+        let seed = rng.gen_range(0, u64::MAX);
+        let result = label_propagation_communities(&g, seed, |e| *e.weight());
+        assert_partition(&g, &result);
+        assert_eq!(
+            result,
+            label_propagation_communities(&g, seed, |e| *e.weight())
+        );
+
+        // Every node is in one of the heaviest communities among its neighbors.
+        let communities = result.0;
+        for v in g.node_indices() {
+            let mut weights = HashMap::new();
+            for e in g
+                .edges_directed(v, Outgoing)
+                .chain(g.edges_directed(v, Incoming))
+            {
+                let u = if e.source() == v {
+                    e.target()
+                } else {
+                    e.source()
+                };
+                if u != v {
+                    *weights.entry(communities[&u]).or_insert(0.) += e.weight();
+                }
+            }
+            let max = weights.values().copied().fold(0., f64::max);
+            if max > 0. {
+                assert!(weights[&communities[&v]] >= max - 1e-9);
+            }
+        }
+    }
+}
+
+#[test]
+fn communities_without_edges() {
+    let mut g = UnGraph::<(), f64>::new_undirected();
+    for (communities, count) in [
+        louvain_communities(&g, 1., 0, |e| *e.weight()),
+        leiden_communities(&g, 1., 0, |e| *e.weight()),
+        label_propagation_communities(&g, 0, |e| *e.weight()),
+    ] {
+        assert!(communities.is_empty());
+        assert_eq!(count, 0);
+    }
+
+    g.add_node(());
+    g.add_node(());
+    g.add_node(());
+    for (communities, count) in [
+        louvain_communities(&g, 1., 0, |e| *e.weight()),
+        leiden_communities(&g, 1., 0, |e| *e.weight()),
+        label_propagation_communities(&g, 0, |e| *e.weight()),
+    ] {
+        assert_eq!(count, 3);
+        assert_eq!(communities[&n(0)], 0);
+        assert_eq!(communities[&n(2)], 2);
+    }
+    assert_eq!(
+        modularity(&g, &HashMap::from_iter([(n(0), 0)]), 1., |e| *e.weight()),
+        0.
+    );
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn communities_stable_graph() {
+    let mut g = StableUnGraph::<(), f64>::from_edges([
+        (0, 1, 1.),
+        (1, 2, 1.),
+        (2, 3, 1.),
+        (3, 1, 1.),
+        (4, 5, 1.),
+        (5, 6, 1.),
+        (6, 4, 1.),
+        (3, 4, 1.),
+    ]);
+    g.remove_node(n(0));
+    for (communities, count) in [
+        louvain_communities(&g, 1., 0, |e| *e.weight()),
+        leiden_communities(&g, 1., 0, |e| *e.weight()),
+        label_propagation_communities(&g, 0, |e| *e.weight()),
+    ] {
+        assert_eq!(count, 2);
+        assert_eq!(communities.len(), 6);
+        assert_eq!(communities[&n(1)], communities[&n(3)]);
+        assert_eq!(communities[&n(4)], communities[&n(6)]);
+        assert_ne!(communities[&n(1)], communities[&n(4)]);
+        let score = modularity(&g, &communities, 1., |e| *e.weight());
+        assert!(score > 0.3);
+    }
+}
@@ -0,0 +1,415 @@
+extern crate alloc;
+
+#[path = "utils/random.rs"]
+mod random;
+
+use alloc::collections::BTreeSet;
+
+use hashbrown::HashSet;
+use petgraph::{
+    EdgeType,
+    algo::{
+        Cycle, CycleWitness, connected_components, elementary_circuits, find_cycle_directed,
+        find_cycle_undirected, fundamental_cycle_basis, is_cyclic_directed, is_cyclic_undirected,
+        minimum_cycle_basis, toposort_or_cycle,
+    },
+    graph::{EdgeReference, node_index as n},
+    prelude::*,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::random_multigraph;
+
+/// Assert that `circuit` is a closed walk that does not visit any node twice.
+fn assert_circuit<E>(circuit: &[EdgeReference<E>]) {
+    assert!(!circuit.is_empty());
+    for pair in circuit.windows(2) {
+        assert_eq!(pair[0].target(), pair[1].source());
+    }
+    assert_eq!(circuit.last().unwrap().target(), circuit[0].source());
+    let nodes = circuit.iter().map(|e| e.source()).collect::<BTreeSet<_>>();
+    assert_eq!(nodes.len(), circuit.len());
+}
+
+/// Count the elementary circuits through distinct edges by extending simple paths.
+fn count_circuits(g: &DiGraph<(), ()>, min_length: usize, max_length: usize) -> usize {
+    fn extend(
+        g: &DiGraph<(), ()>,
+        path: &mut Vec<NodeIndex>,
+        bounds: (usize, usize),
+        count: &mut usize,
+    ) {
+        let (start, last) = (path[0], *path.last().unwrap());
+        for e in g.edges(last) {
+            let length = path.len();
+            if e.target() == start {
+                if (bounds.0..=bounds.1).contains(&length) {
+                    *count += 1;
+                }
+            } else if e.target() > start && !path.contains(&e.target()) && length < bounds.1 {
+                path.push(e.target());
+                extend(g, path, bounds, count);
+                path.pop();
+            }
+        }
+    }
+    let mut count = 0;
+    for v in g.node_indices() {
+        extend(g, &mut vec![v], (min_length, max_length), &mut count);
+    }
+    count
+}
+
+#[test]
+fn elementary_circuits_complete_digraph() {
+    // The complete digraph on n nodes has sum over k of C(n, k) (k - 1)! circuits.
+    let mut g = DiGraph::<(), ()>::new();
+    let nodes = (0..5).map(|_| g.add_node(())).collect::<Vec<_>>();
+    for &a in &nodes {
+        for &b in &nodes {
+            if a != b {
+                g.add_edge(a, b, ());
+            }
+        }
+    }
+    let circuits = elementary_circuits(&g, 0, None).collect::<Vec<_>>();
+    assert_eq!(circuits.len(), 10 + 20 + 30 + 24);
+    for circuit in &circuits {
+        assert_circuit(circuit);
+    }
+    let distinct = circuits
+        .iter()
+        .map(|c| c.iter().map(|e| e.id()).collect::<BTreeSet<_>>())
+        .collect::<BTreeSet<_>>();
+    assert_eq!(distinct.len(), circuits.len());
+
+    assert_eq!(elementary_circuits(&g, 3, Some(4)).count(), 20 + 30);
+    assert_eq!(elementary_circuits(&g, 5, None).count(), 24);
+    assert_eq!(elementary_circuits(&g, 0, Some(0)).count(), 0);
+}
+
+#[test]
+fn elementary_circuits_self_loops_and_parallel_edges() {
+    let mut g = DiGraph::<(), ()>::from_edges([(0, 1), (1, 0), (1, 0), (2, 2), (1, 2)]);
+    let circuits = elementary_circuits(&g, 0, None).collect::<Vec<_>>();
+    assert_eq!(circuits.len(), 3);
+    for circuit in &circuits {
+        assert_circuit(circuit);
+    }
+    assert_eq!(elementary_circuits(&g, 2, None).count(), 2);
+    assert_eq!(elementary_circuits(&g, 0, Some(1)).count(), 1);
+
+    // A DAG has no circuits.
+    g.clear_edges();
+    g.extend_with_edges([(0, 1), (1, 2), (0, 2)]);
+    assert_eq!(elementary_circuits(&g, 0, None).count(), 0);
+}
+
+#[test]
+fn elementary_circuits_random() {
+    let mut rng = ChaChaRng::from_seed([9; 32]);
+    for _ in 0..200 {
+        let node_count = rng.gen_range(1, 8);
+        let mut g = DiGraph::<(), ()>::with_capacity(node_count, 0);
+        for _ in 0..node_count {
+            g.add_node(());
+        }
+        for _ in 0..rng.gen_range(0, 3 * node_count) {
+            let (a, b) = (rng.gen_range(0, node_count), rng.gen_range(0, node_count));
+            g.add_edge(n(a), n(b), ());
+        }
+
+        let circuits = elementary_circuits(&g, 0, None).collect::<Vec<_>>();
+        for circuit in &circuits {
+            assert_circuit(circuit);
+        }
+        assert_eq!(circuits.len(), count_circuits(&g, 0, node_count));
+
+        let (min, max) = (rng.gen_range(0, 4), rng.gen_range(0, 5));
+        let bounded = elementary_circuits(&g, min, Some(max)).collect::<Vec<_>>();
+        assert!(bounded.iter().all(|c| (min..=max).contains(&c.len())));
+        assert_eq!(bounded.len(), count_circuits(&g, min, max));
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn elementary_circuits_stable_graph() {
+    let mut g = StableDiGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (2, 3), (3, 2)]);
+    assert_eq!(elementary_circuits(&g, 0, None).count(), 2);
+    g.remove_node(n(0));
+    let circuits = elementary_circuits(&g, 0, None).collect::<Vec<_>>();
+    assert_eq!(circuits.len(), 1);
+    assert_eq!(circuits[0].len(), 2);
+}
+
+/// Return the edges of a cycle as a bit mask.
+fn edge_mask<E>(cycle: &[EdgeReference<E>]) -> u64 {
+    cycle.iter().fold(0, |mask, e| mask ^ (1 << e.id().index()))
+}
+
+/// Return the rank of a set of bit masks over GF(2).
+fn rank(vectors: impl IntoIterator<Item = u64>) -> usize {
+    let mut pivots = Vec::<u64>::new();
+    for mut v in vectors {
+        for &p in &pivots {
+            v = v.min(v ^ p);
+        }
+        if v != 0 {
+            pivots.push(v);
+            pivots.sort_unstable_by(|a, b| b.cmp(a));
+        }
+    }
+    pivots.len()
+}
+
+/// Assert that `cycle` is a closed walk in an undirected graph that visits every node once.
+fn assert_undirected_cycle<E>(cycle: &[EdgeReference<E>]) {
+    assert!(!cycle.is_empty());
+    let ends = |e: &EdgeReference<E>| [e.source(), e.target()];
+    if let [e] = cycle {
+        assert_eq!(e.source(), e.target());
+        return;
+    }
+    // Find the node at which the walk starts, then follow it around.
+    let start = ends(&cycle[0])
+        .into_iter()
+        .find(|v| ends(cycle.last().unwrap()).contains(v))
+        .unwrap();
+    let mut at = start;
+    let mut visited = HashSet::new();
+    for e in cycle {
+        assert!(ends(e).contains(&at));
+        assert!(visited.insert(at), "the walk is not a simple cycle");
+        at = if e.source() == at {
+            e.target()
+        } else {
+            e.source()
+        };
+    }
+    assert_eq!(at, start);
+    assert_eq!(edge_mask(cycle).count_ones() as usize, cycle.len());
+}
+
+#[test]
+fn fundamental_cycle_basis_random() {
+    let mut rng = ChaChaRng::from_seed([13; 32]);
+    for _ in 0..200 {
+        let node_count = rng.gen_range(1, 10);
+        let edge_count = rng.gen_range(0, 20);
+        let g: UnGraph<(), u32> = random_multigraph(
+            &mut rng,
+            node_count,
+            edge_count,
+            |_| (),
+            |rng| rng.gen_range(1, 10),
+        );
+        let basis = fundamental_cycle_basis(&g);
+        let dimension = g.edge_count() + connected_components(&g) - node_count;
+        assert_eq!(basis.len(), dimension);
+        for cycle in &basis {
+            assert_undirected_cycle(cycle);
+        }
+        assert_eq!(rank(basis.iter().map(|c| edge_mask(c))), dimension);
+    }
+}
+
+/// Compute the weight of a minimum cycle basis by greedily picking independent elements of the
+/// cycle space, spanned by `basis`, in order of weight.
+fn brute_force_minimum_weight(g: &UnGraph<(), u32>, basis: &[u64]) -> u32 {
+    let weight = |mask: u64| {
+        g.edge_references()
+            .filter(|e| mask & (1 << e.id().index()) != 0)
+            .map(|e| *e.weight())
+            .sum::<u32>()
+    };
+    let mut elements = (1..1u64 << basis.len())
+        .map(|subset| {
+            (0..basis.len())
+                .filter(|i| subset & (1 << i) != 0)
+                .fold(0, |mask, i| mask ^ basis[i])
+        })
+        .collect::<Vec<_>>();
+    elements.sort_by_key(|&mask| weight(mask));
+    let mut chosen = Vec::new();
+    for mask in elements {
+        chosen.push(mask);
+        if rank(chosen.iter().copied()) < chosen.len() {
+            chosen.pop();
+        }
+    }
+    chosen.into_iter().map(weight).sum()
+}
+
+#[test]
+fn minimum_cycle_basis_random() {
+    let mut rng = ChaChaRng::from_seed([17; 32]);
+    for _ in 0..200 {
+        let node_count = rng.gen_range(1, 8);
+        let edge_count = rng.gen_range(0, node_count + 7);
+        let g: UnGraph<(), u32> = random_multigraph(
+            &mut rng,
+            node_count,
+            edge_count,
+            |_| (),
+            |rng| rng.gen_range(1, 10),
+        );
+        let dimension = g.edge_count() + connected_components(&g) - node_count;
+        let basis = minimum_cycle_basis(&g, |e| *e.weight());
+        assert_eq!(basis.len(), dimension);
+        for cycle in &basis {
+            assert_undirected_cycle(cycle);
+        }
+        let masks = basis.iter().map(|c| edge_mask(c)).collect::<Vec<_>>();
+        assert_eq!(rank(masks.iter().copied()), dimension);
+
+        let weight = basis.iter().flatten().map(|e| *e.weight()).sum::<u32>();
+        let fundamental = fundamental_cycle_basis(&g)
+            .iter()
+            .map(|c| edge_mask(c))
+            .collect::<Vec<_>>();
+        assert_eq!(weight, brute_force_minimum_weight(&g, &fundamental));
+    }
+}
+
+#[test]
+fn minimum_cycle_basis_zero_costs() {
+    // A cheapest odd walk can join the cycle 1 - 3 - 2 - 5 with the triangle 2 - 4 - 6 at node 2.
+    let g = UnGraph::<(), u32>::from_edges([
+        (6, 4, 2),
+        (2, 5, 1),
+        (1, 0, 0),
+        (1, 3, 0),
+        (4, 4, 0),
+        (6, 4, 0),
+        (5, 2, 0),
+        (6, 2, 2),
+        (3, 5, 1),
+        (3, 2, 0),
+        (1, 5, 0),
+        (0, 4, 1),
+        (4, 2, 0),
+    ]);
+    let basis = minimum_cycle_basis(&g, |e| *e.weight());
+    assert_eq!(basis.len(), 7);
+    for cycle in &basis {
+        assert_undirected_cycle(cycle);
+    }
+
+    let mut rng = ChaChaRng::from_seed([19; 32]);
+    for _ in 0..200 {
+        let node_count = rng.gen_range(1, 8);
+        let edge_count = rng.gen_range(0, node_count + 7);
+        let g: UnGraph<(), u32> = random_multigraph(
+            &mut rng,
+            node_count,
+            edge_count,
+            |_| (),
+            |rng| rng.gen_range(0, 3),
+        );
+        let dimension = g.edge_count() + connected_components(&g) - node_count;
+        let basis = minimum_cycle_basis(&g, |e| *e.weight());
+        assert_eq!(basis.len(), dimension);
+        for cycle in &basis {
+            assert_undirected_cycle(cycle);
+        }
+        let masks = basis.iter().map(|c| edge_mask(c)).collect::<Vec<_>>();
+        assert_eq!(rank(masks.iter().copied()), dimension);
+
+        let weight = basis.iter().flatten().map(|e| *e.weight()).sum::<u32>();
+        let fundamental = fundamental_cycle_basis(&g)
+            .iter()
+            .map(|c| edge_mask(c))
+            .collect::<Vec<_>>();
+        assert_eq!(weight, brute_force_minimum_weight(&g, &fundamental));
+    }
+}
+
+#[test]
+fn minimum_cycle_basis_grid() {
+    // A 3 x 3 grid of nodes has four square faces, and the outer cycle is not in the basis.
+    let mut g = UnGraph::<(), u32>::new_undirected();
+    let nodes = (0..9).map(|_| g.add_node(())).collect::<Vec<_>>();
+    for i in 0..9 {
+        if i % 3 < 2 {
+            g.add_edge(nodes[i], nodes[i + 1], 1);
+        }
+        if i < 6 {
+            g.add_edge(nodes[i], nodes[i + 3], 1);
+        }
+    }
+    let basis = minimum_cycle_basis(&g, |e| *e.weight());
+    assert_eq!(basis.len(), 4);
+    assert!(basis.iter().all(|cycle| cycle.len() == 4));
+}
+
+/// Assert that `cycle` is a closed walk in `g` along the given nodes.
+fn assert_witness<Ty: EdgeType>(g: &Graph<(), (), Ty>, cycle: &CycleWitness<NodeIndex, EdgeIndex>) {
+    assert!(!cycle.is_empty());
+    assert_eq!(cycle.nodes().len(), cycle.len());
+    for (i, &edge) in cycle.edges().iter().enumerate() {
+        let (from, to) = (cycle.nodes()[i], cycle.nodes()[(i + 1) % cycle.len()]);
+        let (source, target) = g.edge_endpoints(edge).unwrap();
+        assert!(
+            (source, target) == (from, to) || (!g.is_directed() && (target, source) == (from, to))
+        );
+    }
+    let distinct = cycle.nodes().iter().collect::<BTreeSet<_>>();
+    assert_eq!(distinct.len(), cycle.len());
+}
+
+#[test]
+fn cycle_witness_random() {
+    let mut rng = ChaChaRng::from_seed([21; 32]);
+    for _ in 0..500 {
+        let node_count = rng.gen_range(1, 10);
+        let edges = (0..rng.gen_range(0, 2 * node_count))
+            .map(|_| {
+                let (a, b) = (rng.gen_range(0, node_count), rng.gen_range(0, node_count));
+                // Mostly forward edges, so that some of the graphs are acyclic.
+                if rng.gen_bool(0.8) {
+                    (a.min(b) as u32, a.max(b) as u32)
+                } else {
+                    (a as u32, b as u32)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let g = DiGraph::<(), ()>::from_edges(&edges);
+        match toposort_or_cycle(&g) {
+            Ok(order) => {
+                assert!(!is_cyclic_directed(&g));
+                assert_eq!(order.len(), g.node_count());
+                let position = |v: NodeIndex| order.iter().position(|&w| w == v).unwrap();
+                for e in g.edge_references() {
+                    assert!(position(e.source()) < position(e.target()));
+                }
+            }
+            Err(cycle) => {
+                assert!(is_cyclic_directed(&g));
+                assert_witness(&g, &cycle);
+                assert_eq!(find_cycle_directed(&g), Some(cycle));
+            }
+        }
+
+        let g = UnGraph::<(), ()>::from_edges(&edges);
+        let cycle = find_cycle_undirected(&g);
+        assert_eq!(cycle.is_some(), is_cyclic_undirected(&g));
+        if let Some(cycle) = cycle {
+            assert_witness(&g, &cycle);
+        }
+    }
+}
+
+#[test]
+fn cycle_witness_self_loop() {
+    let g = DiGraph::<(), ()>::from_edges([(0, 1), (1, 1)]);
+    let cycle = toposort_or_cycle(&g).unwrap_err();
+    assert_eq!(cycle.nodes(), [n(1)]);
+    assert_eq!(cycle.edges(), [EdgeIndex::new(1)]);
+    assert_eq!(Cycle::from(cycle).node_id(), n(1));
+
+    let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 0)]);
+    let (nodes, edges) = find_cycle_undirected(&g).unwrap().into_parts();
+    assert_eq!(nodes, [n(1), n(0)]);
+    assert_eq!(edges, [EdgeIndex::new(1), EdgeIndex::new(0)]);
+}
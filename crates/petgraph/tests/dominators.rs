@@ -0,0 +1,279 @@
+#[path = "utils/random.rs"]
+mod random;
+
+use hashbrown::{HashMap, HashSet};
+use petgraph::{
+    algo::dominators::{self, Dominators},
+    graph::node_index as n,
+    prelude::*,
+    visit::{Dfs, NodeFiltered, Walker},
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::random_multigraph;
+
+fn reachable(
+    g: &DiGraph<(), ()>,
+    start: NodeIndex,
+    removed: Option<NodeIndex>,
+) -> HashSet<NodeIndex> {
+    if Some(start) == removed {
+        return HashSet::new();
+    }
+    let filtered = NodeFiltered::from_fn(g, |v| Some(v) != removed);
+    Dfs::new(&filtered, start).iter(&filtered).collect()
+}
+
+/// Compute the strict dominators of every reachable node by removing every node in turn.
+fn brute_force_dominators(
+    g: &DiGraph<(), ()>,
+    root: NodeIndex,
+) -> HashMap<NodeIndex, HashSet<NodeIndex>> {
+    let all = reachable(g, root, None);
+    let mut dominators = all
+        .iter()
+        .map(|&v| (v, HashSet::new()))
+        .collect::<HashMap<_, _>>();
+    for &a in &all {
+        let without = reachable(g, root, Some(a));
+        for &b in &all {
+            if a != b && !without.contains(&b) {
+                dominators.get_mut(&b).unwrap().insert(a);
+            }
+        }
+    }
+    dominators
+}
+
+/// Compute the strict post-dominators of every node that can reach an exit.
+fn brute_force_post_dominators(
+    g: &DiGraph<(), ()>,
+    exits: &[NodeIndex],
+) -> HashMap<NodeIndex, HashSet<NodeIndex>> {
+    let reaches_exit = |v: NodeIndex, removed: Option<NodeIndex>| {
+        reachable(g, v, removed).iter().any(|w| exits.contains(w))
+    };
+    let nodes = g
+        .node_indices()
+        .filter(|&v| reaches_exit(v, None))
+        .collect::<Vec<_>>();
+    nodes
+        .iter()
+        .map(|&b| {
+            let post = nodes
+                .iter()
+                .copied()
+                .filter(|&a| a != b && !reaches_exit(b, Some(a)))
+                .collect();
+            (b, post)
+        })
+        .collect()
+}
+
+fn strict_dominators(doms: &Dominators<NodeIndex>, v: NodeIndex) -> Option<HashSet<NodeIndex>> {
+    doms.strict_dominators(v).map(|iter| iter.collect())
+}
+
+fn assert_dominators(
+    g: &DiGraph<(), ()>,
+    doms: &Dominators<NodeIndex>,
+    expected: &HashMap<NodeIndex, HashSet<NodeIndex>>,
+) {
+    for v in g.node_indices() {
+        assert_eq!(
+            strict_dominators(doms, v).as_ref(),
+            expected.get(&v),
+            "{v:?}"
+        );
+    }
+}
+
+#[test]
+fn lengauer_tarjan_random() {
+    let mut rng = ChaChaRng::from_seed([21; 32]);
+    for _ in 0..500 {
+        let node_count = rng.gen_range(1, 13);
+        let edge_count = rng.gen_range(0, 3 * node_count);
+        let g: DiGraph<(), ()> =
+            random_multigraph(&mut rng, node_count, edge_count, |_| (), |_| ());
+        let root = n(rng.gen_range(0, g.node_count()));
+        let expected = brute_force_dominators(&g, root);
+        let doms = dominators::lengauer_tarjan(&g, root);
+        assert_eq!(doms.root(), root);
+        assert_eq!(doms.immediate_dominator(root), None);
+        assert_dominators(&g, &doms, &expected);
+        assert_dominators(&g, &dominators::simple_fast(&g, root), &expected);
+    }
+}
+
+#[test]
+fn lengauer_tarjan_long_path() {
+    // A deep dominator tree, with back edges to the root.
+    let node_count = 20_000;
+    let mut g = DiGraph::<(), ()>::from_edges((0..node_count - 1).map(|i| (i, i + 1)));
+    g.extend_with_edges((0..node_count).step_by(100).map(|i| (i, 0)));
+    let doms = dominators::lengauer_tarjan(&g, n(0));
+    assert_eq!(
+        doms.immediate_dominator(n(node_count as usize - 1)),
+        Some(n(node_count as usize - 2))
+    );
+    assert_eq!(
+        doms.dominators(n(node_count as usize - 1)).unwrap().count(),
+        node_count as usize
+    );
+}
+
+#[test]
+fn post_dominators_random() {
+    let mut rng = ChaChaRng::from_seed([22; 32]);
+    for _ in 0..500 {
+        let node_count = rng.gen_range(1, 13);
+        let edge_count = rng.gen_range(0, 3 * node_count);
+        let g: DiGraph<(), ()> =
+            random_multigraph(&mut rng, node_count, edge_count, |_| (), |_| ());
+        let mut exits = g.externals(Outgoing).collect::<Vec<_>>();
+        if exits.is_empty() || rng.gen_bool(0.3) {
+            exits = vec![n(rng.gen_range(0, g.node_count()))];
+        }
+        let post_doms = dominators::post_dominators(&g, exits.iter().copied());
+        assert_eq!(post_doms.root(), exits[0]);
+        let expected = brute_force_post_dominators(&g, &exits);
+        assert_dominators(&g, &post_doms, &expected);
+        for &exit in &exits {
+            assert_eq!(post_doms.immediate_dominator(exit), None);
+        }
+    }
+}
+
+#[test]
+fn dominance_frontiers_random() {
+    let mut rng = ChaChaRng::from_seed([23; 32]);
+    for _ in 0..500 {
+        let node_count = rng.gen_range(1, 11);
+        let edge_count = rng.gen_range(0, 3 * node_count);
+        let g: DiGraph<(), ()> =
+            random_multigraph(&mut rng, node_count, edge_count, |_| (), |_| ());
+        let root = n(0);
+        let doms = dominators::simple_fast(&g, root);
+        let dominates = |a: NodeIndex, b: NodeIndex| doms.dominators(b).unwrap().any(|d| d == a);
+        let frontiers = doms.dominance_frontiers(&g);
+        let all = reachable(&g, root, None);
+        for v in g.node_indices() {
+            let Some(frontier) = frontiers.frontier(v) else {
+                assert!(!all.contains(&v));
+                continue;
+            };
+            let frontier = frontier.collect::<HashSet<_>>();
+            let expected = all
+                .iter()
+                .copied()
+                .filter(|&b| {
+                    g.neighbors_directed(b, Incoming)
+                        .any(|p| all.contains(&p) && dominates(v, p))
+                        && !(v != b && dominates(v, b))
+                })
+                .collect::<HashSet<_>>();
+            assert_eq!(frontier, expected, "{v:?}");
+        }
+
+        // The iterated frontier is the least fixed point.
+        let nodes = all
+            .iter()
+            .copied()
+            .filter(|_| rng.gen_bool(0.3))
+            .collect::<Vec<_>>();
+        let iterated = frontiers.iterated_frontier(nodes.iter().copied());
+        let mut expected = HashSet::new();
+        loop {
+            let next = nodes
+                .iter()
+                .chain(&expected)
+                .flat_map(|&v| frontiers.frontier(v).unwrap())
+                .collect::<HashSet<_>>();
+            if next == expected {
+                break;
+            }
+            expected = next;
+        }
+        assert_eq!(iterated, expected);
+    }
+}
+
+#[test]
+fn control_dependences_random() {
+    let mut rng = ChaChaRng::from_seed([24; 32]);
+    for _ in 0..500 {
+        let node_count = rng.gen_range(1, 11);
+        let edge_count = rng.gen_range(0, 3 * node_count);
+        let g: DiGraph<(), ()> =
+            random_multigraph(&mut rng, node_count, edge_count, |_| (), |_| ());
+        let exits = g.externals(Outgoing).collect::<Vec<_>>();
+        if exits.is_empty() {
+            continue;
+        }
+        let post_doms = dominators::post_dominators(&g, exits.iter().copied());
+        let strict = brute_force_post_dominators(&g, &exits);
+        let dependences = post_doms.control_dependences(&g);
+        assert_eq!(dependences.len(), strict.len());
+        for (&a, dependent) in &dependences {
+            let expected = strict
+                .keys()
+                .copied()
+                .filter(|&b| {
+                    g.neighbors(a)
+                        .any(|s| s == b || strict.get(&s).is_some_and(|post| post.contains(&b)))
+                        && !strict[&a].contains(&b)
+                })
+                .collect::<HashSet<_>>();
+            assert_eq!(dependent, &expected, "{a:?}");
+        }
+    }
+}
+
+#[test]
+fn control_dependences_if_then_else() {
+    //   entry
+    //   /   \
+    // then  else --> early return
+    //   \   /
+    //   join
+    let mut g = DiGraph::<(), ()>::new();
+    let entry = g.add_node(());
+    let then = g.add_node(());
+    let other = g.add_node(());
+    let early = g.add_node(());
+    let join = g.add_node(());
+    g.extend_with_edges([
+        (entry, then),
+        (entry, other),
+        (then, join),
+        (other, join),
+        (other, early),
+    ]);
+    let post_doms = dominators::post_dominators(&g, [join, early]);
+    assert_eq!(post_doms.immediate_dominator(then), Some(join));
+    assert_eq!(post_doms.immediate_dominator(entry), None);
+    let dependences = post_doms.control_dependences(&g);
+    assert_eq!(dependences[&entry], HashSet::from_iter([then, other, join]));
+    assert_eq!(dependences[&other], HashSet::from_iter([join, early]));
+    assert!(dependences[&then].is_empty());
+
+    let frontiers = dominators::lengauer_tarjan(&g, entry).dominance_frontiers(&g);
+    assert_eq!(
+        frontiers.iterated_frontier([then]),
+        HashSet::from_iter([join])
+    );
+    assert!(frontiers.iterated_frontier([entry]).is_empty());
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn dominators_stable_graph() {
+    let mut g = StableDiGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 3), (2, 3), (3, 4)]);
+    g.remove_node(n(2));
+    let doms = dominators::lengauer_tarjan(&g, n(0));
+    assert_eq!(doms.immediate_dominator(n(3)), Some(n(1)));
+    assert_eq!(doms.immediate_dominator(n(2)), None);
+    let post_doms = dominators::post_dominators(&g, [n(4)]);
+    assert_eq!(post_doms.immediate_dominator(n(0)), Some(n(1)));
+    assert_eq!(post_doms.dominators(n(0)).unwrap().count(), 4);
+}
@@ -0,0 +1,209 @@
+use core::fmt::Debug;
+
+use petgraph::{
+    algo::{
+        chinese_postman, eulerian_circuit, eulerian_path, floyd_warshall, has_eulerian_circuit,
+        has_eulerian_path,
+    },
+    graph::node_index as n,
+    prelude::*,
+    visit::{EdgeRef, IntoEdgeReferences},
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+/// Assert that `walk` is a walk through every edge of `g` exactly once.
+fn assert_eulerian<G>(g: G, walk: &[G::EdgeRef], closed: bool)
+where
+    G: IntoEdgeReferences,
+    G::EdgeId: Ord + Debug,
+{
+    for pair in walk.windows(2) {
+        assert!(
+            pair[0].target() == pair[1].source(),
+            "walk is not connected"
+        );
+    }
+    if closed && !walk.is_empty() {
+        assert!(walk[walk.len() - 1].target() == walk[0].source());
+    }
+    let mut walked = walk.iter().map(|e| e.id()).collect::<Vec<_>>();
+    let mut edges = g.edge_references().map(|e| e.id()).collect::<Vec<_>>();
+    walked.sort();
+    edges.sort();
+    assert_eq!(walked, edges);
+}
+
+#[test]
+fn eulerian_empty() {
+    let g = DiGraph::<(), ()>::new();
+    assert_eq!(eulerian_circuit(&g), Some(vec![]));
+    assert_eq!(eulerian_path(&g), Some(vec![]));
+
+    let mut g = UnGraph::<(), ()>::new_undirected();
+    g.add_node(());
+    assert!(has_eulerian_circuit(&g));
+    assert!(has_eulerian_path(&g));
+}
+
+#[test]
+fn eulerian_directed() {
+    // 0 -> 1 -> 2 -> 0 -> 3 -> 4 -> 0, with a self-loop at 2
+    let mut g = DiGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (0, 3), (3, 4), (4, 0)]);
+    g.add_edge(2.into(), 2.into(), ());
+    let circuit = eulerian_circuit(&g).unwrap();
+    assert_eulerian(&g, &circuit, true);
+
+    // Remove 4 -> 0, so that the only Eulerian path goes from 0 to 4.
+    g.remove_edge(g.find_edge(4.into(), 0.into()).unwrap());
+    assert!(!has_eulerian_circuit(&g));
+    let path = eulerian_path(&g).unwrap();
+    assert_eulerian(&g, &path, false);
+    assert_eq!(path[0].source(), 0.into());
+    assert_eq!(path.last().unwrap().target(), 4.into());
+
+    // Two sources.
+    g.add_edge(3.into(), 1.into(), ());
+    assert!(!has_eulerian_path(&g));
+}
+
+#[test]
+fn eulerian_directed_disconnected() {
+    // Balanced, but split in two cycles.
+    let mut g = DiGraph::<(), ()>::from_edges([(0, 1), (1, 0), (2, 3), (3, 2)]);
+    assert!(!has_eulerian_circuit(&g));
+    assert!(!has_eulerian_path(&g));
+
+    g.add_edge(1.into(), 2.into(), ());
+    assert!(!has_eulerian_circuit(&g));
+    assert_eulerian(&g, &eulerian_path(&g).unwrap(), false);
+}
+
+#[test]
+fn eulerian_undirected_multigraph() {
+    // Königsberg: four land masses and seven bridges.
+    let mut g =
+        UnGraph::<(), ()>::from_edges([(0, 1), (0, 1), (0, 2), (0, 2), (0, 3), (1, 3), (2, 3)]);
+    assert!(!has_eulerian_circuit(&g));
+    assert!(!has_eulerian_path(&g));
+
+    // A second bridge between 0 and 3 and one between 1 and 2 make it Eulerian.
+    g.add_edge(0.into(), 3.into(), ());
+    assert!(!has_eulerian_circuit(&g));
+    let path = eulerian_path(&g).unwrap();
+    assert_eulerian(&g, &path, false);
+    let ends = [path[0].source(), path.last().unwrap().target()];
+    assert!(ends == [1.into(), 2.into()] || ends == [2.into(), 1.into()]);
+
+    g.add_edge(1.into(), 2.into(), ());
+    g.add_edge(3.into(), 3.into(), ());
+    assert_eulerian(&g, &eulerian_circuit(&g).unwrap(), true);
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn eulerian_stable_graph() {
+    let mut g = StableUnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 0), (1, 4)]);
+    assert!(!has_eulerian_circuit(&g));
+    g.remove_node(4.into());
+    g.remove_node(0.into());
+    assert!(!has_eulerian_circuit(&g));
+    let path = eulerian_path(&g).unwrap();
+    assert_eulerian(&g, &path, false);
+    assert_eq!(path.len(), 2);
+}
+
+#[test]
+fn chinese_postman_eulerian() {
+    let g = UnGraph::<(), u32>::from_edges([(0, 1, 2), (1, 2, 3), (2, 0, 4)]);
+    let (cost, walk) = chinese_postman(&g, |e| *e.weight()).unwrap();
+    assert_eq!(cost, 9);
+    assert_eulerian(&g, &walk, true);
+}
+
+#[test]
+fn chinese_postman_disconnected() {
+    let mut g = UnGraph::<(), u32>::from_edges([(0, 1, 1), (2, 3, 1)]);
+    assert!(chinese_postman(&g, |e| *e.weight()).is_none());
+
+    // Isolated nodes do not matter.
+    g.remove_edge(g.find_edge(2.into(), 3.into()).unwrap());
+    let (cost, walk) = chinese_postman(&g, |e| *e.weight()).unwrap();
+    assert_eq!(cost, 2);
+    assert_eq!(walk.len(), 2);
+}
+
+#[test]
+#[should_panic]
+fn chinese_postman_directed() {
+    let g = DiGraph::<(), u32>::from_edges([(0, 1, 1), (1, 2, 1), (2, 0, 1)]);
+    chinese_postman(&g, |e| *e.weight());
+}
+
+/// Compute the minimum cost of pairing up the nodes in `odd`.
+fn min_pairing(odd: &[usize], dist: &[Vec<Option<u64>>]) -> Option<u64> {
+    let Some((&first, rest)) = odd.split_first() else {
+        return Some(0);
+    };
+    (0..rest.len())
+        .filter_map(|i| {
+            let d = dist[first][rest[i]]?;
+            let mut others = rest.to_vec();
+            others.remove(i);
+            Some(d + min_pairing(&others, dist)?)
+        })
+        .min()
+}
+
+#[test]
+fn chinese_postman_random() {
+    let mut rng = ChaChaRng::from_seed([3; 32]);
+    for _ in 0..200 {
+        let node_count = rng.gen_range(1, 9);
+        let mut g = UnGraph::<(), u64>::with_capacity(node_count, 0);
+        for _ in 0..node_count {
+            g.add_node(());
+        }
+        // A spanning path keeps the graph connected, the remaining edges may be parallel.
+        for i in 1..node_count {
+            g.add_edge(n(i - 1), n(i), rng.gen_range(0, 10));
+        }
+        for _ in 0..rng.gen_range(0, 2 * node_count) {
+            let (a, b) = (rng.gen_range(0, node_count), rng.gen_range(0, node_count));
+            g.add_edge(n(a), n(b), rng.gen_range(0, 10));
+        }
+
+        let (cost, walk) = chinese_postman(&g, |e| *e.weight()).unwrap();
+        assert_eq!(walk.iter().map(|e| *e.weight()).sum::<u64>(), cost);
+        for pair in walk.windows(2) {
+            assert_eq!(pair[0].target(), pair[1].source());
+        }
+        if let (Some(first), Some(last)) = (walk.first(), walk.last()) {
+            assert_eq!(last.target(), first.source());
+        }
+        for e in g.edge_references() {
+            assert!(walk.iter().any(|w| w.id() == e.id()));
+        }
+
+        let all_pairs = floyd_warshall(&g, |e| *e.weight()).unwrap();
+        let dist = (0..node_count)
+            .map(|a| {
+                (0..node_count)
+                    .map(|b| all_pairs.get(&(n(a), n(b))).copied())
+                    .collect()
+            })
+            .collect::<Vec<Vec<_>>>();
+        let odd = g
+            .node_indices()
+            .filter(|&v| {
+                g.edges(v)
+                    .map(|e| if e.source() == e.target() { 2 } else { 1 })
+                    .sum::<usize>()
+                    % 2
+                    == 1
+            })
+            .map(|v| v.index())
+            .collect::<Vec<_>>();
+        let total = g.edge_weights().sum::<u64>();
+        assert_eq!(Some(cost), min_pairing(&odd, &dist).map(|p| p + total));
+    }
+}
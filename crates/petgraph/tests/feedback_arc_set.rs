@@ -0,0 +1,172 @@
+#[path = "utils/random.rs"]
+mod random;
+
+use hashbrown::HashSet;
+use petgraph::{
+    algo::{
+        greedy_feedback_arc_set, is_cyclic_directed, min_feedback_arc_set,
+        weighted_feedback_arc_set,
+    },
+    graph::{EdgeReference, node_index as n},
+    prelude::*,
+    visit::EdgeFiltered,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::random_multigraph;
+
+/// Check that removing `fas` makes `g` acyclic, and return its weight.
+fn check_feedback_arc_set(g: &DiGraph<(), u32>, fas: &[EdgeReference<u32>]) -> u32 {
+    let removed = fas.iter().map(|e| e.id()).collect::<HashSet<_>>();
+    assert_eq!(removed.len(), fas.len());
+    assert!(!is_cyclic_directed(&EdgeFiltered::from_fn(g, |e| {
+        !removed.contains(&e.id())
+    })));
+    fas.iter().map(|e| *e.weight()).sum()
+}
+
+/// Find the weight of a minimum feedback arc set by trying all sets of edges.
+fn brute_force(g: &DiGraph<(), u32>) -> u32 {
+    let mut best = u32::MAX;
+    for subset in 0..1usize << g.edge_count() {
+        let removed = |e: EdgeIndex| subset >> e.index() & 1 == 1;
+        let weight = g.edge_indices().filter(|&e| removed(e)).map(|e| g[e]).sum();
+        if weight < best && !is_cyclic_directed(&EdgeFiltered::from_fn(g, |e| !removed(e.id()))) {
+            best = weight;
+        }
+    }
+    best
+}
+
+#[test]
+fn feedback_arc_set_known_graphs() {
+    let empty = DiGraph::<(), u32>::new();
+    assert!(weighted_feedback_arc_set(&empty, |e| *e.weight()).is_empty());
+    assert_eq!(
+        min_feedback_arc_set(&empty, |e| *e.weight(), 0),
+        Some(Vec::new())
+    );
+
+    // A DAG with a loop.
+    let dag = DiGraph::<(), u32>::from_edges([(0, 1, 1), (1, 2, 1), (0, 2, 1), (2, 2, 7)]);
+    for fas in [
+        weighted_feedback_arc_set(&dag, |e| *e.weight()),
+        min_feedback_arc_set(&dag, |e| *e.weight(), 1).unwrap(),
+    ] {
+        assert_eq!(fas.len(), 1);
+        assert_eq!(fas[0].source(), n(2));
+    }
+
+    // Two cycles that share a heavy edge, each with a light edge of its own.
+    let g =
+        DiGraph::<(), u32>::from_edges([(0, 1, 10), (1, 2, 2), (2, 0, 10), (1, 3, 3), (3, 0, 10)]);
+    let fas = min_feedback_arc_set(&g, |e| *e.weight(), 4).unwrap();
+    assert_eq!(check_feedback_arc_set(&g, &fas), 5);
+    let fas = weighted_feedback_arc_set(&g, |e| *e.weight());
+    assert!(check_feedback_arc_set(&g, &fas) >= 5);
+    // Without weights, cutting the shared edge is best.
+    let fas = min_feedback_arc_set(&g, |_| 1, 4).unwrap();
+    assert_eq!(fas.len(), 1);
+    assert_eq!(*fas[0].weight(), 10);
+    assert!(min_feedback_arc_set(&g, |_| 1, 3).is_none());
+}
+
+#[test]
+fn feedback_arc_set_random() {
+    let mut rng = ChaChaRng::from_seed([31; 32]);
+    for _ in 0..300 {
+        let node_count = rng.gen_range(0, 7);
+        let edge_count = rng.gen_range(0, 12);
+        // Include edges of weight zero.
+        let g: DiGraph<(), u32> = random_multigraph(
+            &mut rng,
+            node_count,
+            edge_count,
+            |_| (),
+            |rng| rng.gen_range(0, 10),
+        );
+        let optimum = brute_force(&g);
+        let fas = min_feedback_arc_set(&g, |e| *e.weight(), 6).unwrap();
+        assert_eq!(check_feedback_arc_set(&g, &fas), optimum);
+        let fas = weighted_feedback_arc_set(&g, |e| *e.weight());
+        assert!(check_feedback_arc_set(&g, &fas) >= optimum);
+    }
+}
+
+#[test]
+fn feedback_arc_set_larger_graphs() {
+    let mut rng = ChaChaRng::from_seed([32; 32]);
+    for _ in 0..20 {
+        let node_count = rng.gen_range(0, 301);
+        let edge_count = rng.gen_range(0, 901);
+        let g: DiGraph<(), u32> = random_multigraph(
+            &mut rng,
+            node_count,
+            edge_count,
+            |_| (),
+            |rng| rng.gen_range(0, 10),
+        );
+        let weighted = weighted_feedback_arc_set(&g, |e| *e.weight());
+        check_feedback_arc_set(&g, &weighted);
+        let unweighted = weighted_feedback_arc_set(&g, |_| 1);
+        check_feedback_arc_set(&g, &unweighted);
+        assert!(unweighted.len() <= g.edge_count() / 2 + g.edge_count() % 2 + loops(&g));
+        let greedy = greedy_feedback_arc_set(&g).collect::<Vec<_>>();
+        check_feedback_arc_set(&g, &greedy);
+    }
+
+    // Many small components, solved exactly.
+    let mut g = DiGraph::<(), u32>::new();
+    for _ in 0..50 {
+        let node_count = rng.gen_range(0, 6);
+        let edge_count = rng.gen_range(0, 10);
+        let small: DiGraph<(), u32> = random_multigraph(
+            &mut rng,
+            node_count,
+            edge_count,
+            |_| (),
+            |rng| rng.gen_range(0, 10),
+        );
+        let offset = g.node_count();
+        for _ in small.node_indices() {
+            g.add_node(());
+        }
+        for e in small.edge_references() {
+            g.add_edge(
+                n(offset + e.source().index()),
+                n(offset + e.target().index()),
+                *e.weight(),
+            );
+        }
+        if offset > 0 && small.node_count() > 0 {
+            g.add_edge(n(rng.gen_range(0, offset)), n(offset), 1);
+        }
+    }
+    let fas = min_feedback_arc_set(&g, |e| *e.weight(), 5).unwrap();
+    let weighted = weighted_feedback_arc_set(&g, |e| *e.weight());
+    assert!(check_feedback_arc_set(&g, &fas) <= check_feedback_arc_set(&g, &weighted));
+}
+
+fn loops(g: &DiGraph<(), u32>) -> usize {
+    g.edge_references()
+        .filter(|e| e.source() == e.target())
+        .count()
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn feedback_arc_set_stable_graph() {
+    let mut g = StableDiGraph::<(), u32>::from_edges([
+        (0, 1, 1),
+        (1, 2, 4),
+        (2, 0, 3),
+        (2, 3, 2),
+        (3, 2, 5),
+    ]);
+    g.remove_node(n(0));
+    let fas = min_feedback_arc_set(&g, |e| *e.weight(), 2).unwrap();
+    assert_eq!(fas.len(), 1);
+    assert_eq!(fas[0].id(), g.find_edge(n(2), n(3)).unwrap());
+    let fas = weighted_feedback_arc_set(&g, |e| *e.weight());
+    assert_eq!(fas.len(), 1);
+    assert_eq!(*fas[0].weight(), 2);
+}
@@ -0,0 +1,139 @@
+#[path = "utils/random.rs"]
+mod random;
+
+use hashbrown::HashSet;
+use petgraph::{
+    algo::{greedy_feedback_vertex_set, is_cyclic_directed, weighted_feedback_vertex_set},
+    graph::node_index as n,
+    prelude::*,
+    visit::NodeFiltered,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::random_multigraph;
+
+fn is_acyclic_without(g: &DiGraph<u32, ()>, removed: &HashSet<NodeIndex>) -> bool {
+    !is_cyclic_directed(&NodeFiltered::from_fn(g, |v| !removed.contains(&v)))
+}
+
+/// Check that removing `fvs` makes `g` acyclic, that no node of `fvs` can be left out, and that
+/// every node with a loop is in `fvs`. Return its weight.
+fn check_feedback_vertex_set(g: &DiGraph<u32, ()>, fvs: &[NodeIndex]) -> u32 {
+    let mut removed = fvs.iter().copied().collect::<HashSet<_>>();
+    assert_eq!(removed.len(), fvs.len());
+    assert!(is_acyclic_without(g, &removed));
+    for &v in fvs {
+        removed.remove(&v);
+        assert!(!is_acyclic_without(g, &removed));
+        removed.insert(v);
+    }
+    for v in g.node_indices() {
+        if g.contains_edge(v, v) {
+            assert!(removed.contains(&v));
+        }
+    }
+    fvs.iter().map(|&v| g[v]).sum()
+}
+
+/// Find the weight of a minimum weight feedback vertex set by trying all sets of nodes.
+fn brute_force(g: &DiGraph<u32, ()>, weight: impl Fn(NodeIndex) -> u32) -> u32 {
+    let mut best = u32::MAX;
+    for subset in 0..1usize << g.node_count() {
+        let removed = g
+            .node_indices()
+            .filter(|v| subset >> v.index() & 1 == 1)
+            .collect::<HashSet<_>>();
+        let total = removed.iter().map(|&v| weight(v)).sum();
+        if total < best && is_acyclic_without(g, &removed) {
+            best = total;
+        }
+    }
+    best
+}
+
+#[test]
+fn feedback_vertex_set_known_graphs() {
+    let empty = DiGraph::<u32, ()>::new();
+    assert!(greedy_feedback_vertex_set(&empty).is_empty());
+    assert!(weighted_feedback_vertex_set(&empty, |v| empty[v]).is_empty());
+
+    let dag = DiGraph::<u32, ()>::from_edges([(0, 1), (1, 2), (0, 2), (2, 3)]);
+    assert!(greedy_feedback_vertex_set(&dag).is_empty());
+    assert!(weighted_feedback_vertex_set(&dag, |_| 1).is_empty());
+
+    // Three cycles through a hub, and a loop elsewhere.
+    let mut g = DiGraph::<u32, ()>::from_edges([
+        (0, 1),
+        (1, 0),
+        (0, 2),
+        (2, 0),
+        (0, 3),
+        (3, 4),
+        (4, 0),
+        (5, 5),
+        (5, 1),
+    ]);
+    for (i, weight) in g.node_weights_mut().enumerate() {
+        *weight = if i == 0 { 10 } else { 1 };
+    }
+    let mut fvs = greedy_feedback_vertex_set(&g);
+    fvs.sort();
+    assert_eq!(fvs, [n(0), n(5)]);
+    check_feedback_vertex_set(&g, &fvs);
+    // The hub is too heavy to be worth removing.
+    let mut fvs = weighted_feedback_vertex_set(&g, |v| g[v]);
+    fvs.sort();
+    assert_eq!(check_feedback_vertex_set(&g, &fvs), 4);
+    assert!(!fvs.contains(&n(0)));
+}
+
+#[test]
+fn feedback_vertex_set_random() {
+    let mut rng = ChaChaRng::from_seed([33; 32]);
+    for _ in 0..300 {
+        let node_count = rng.gen_range(0, 9);
+        let edge_count = rng.gen_range(0, 17);
+        let g: DiGraph<u32, ()> = random_multigraph(
+            &mut rng,
+            node_count,
+            edge_count,
+            |rng| rng.gen_range(0, 10),
+            |_| (),
+        );
+        let fvs = greedy_feedback_vertex_set(&g);
+        check_feedback_vertex_set(&g, &fvs);
+        assert!(fvs.len() as u32 >= brute_force(&g, |_| 1));
+        let fvs = weighted_feedback_vertex_set(&g, |v| g[v]);
+        assert!(check_feedback_vertex_set(&g, &fvs) >= brute_force(&g, |v| g[v]));
+    }
+}
+
+#[test]
+fn feedback_vertex_set_larger_graphs() {
+    let mut rng = ChaChaRng::from_seed([34; 32]);
+    for _ in 0..10 {
+        let node_count = rng.gen_range(0, 201);
+        let edge_count = rng.gen_range(0, 601);
+        let g: DiGraph<u32, ()> = random_multigraph(
+            &mut rng,
+            node_count,
+            edge_count,
+            |rng| rng.gen_range(0, 10),
+            |_| (),
+        );
+        let greedy = greedy_feedback_vertex_set(&g);
+        check_feedback_vertex_set(&g, &greedy);
+        let weighted = weighted_feedback_vertex_set(&g, |v| g[v]);
+        check_feedback_vertex_set(&g, &weighted);
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn feedback_vertex_set_stable_graph() {
+    let mut g = StableDiGraph::<u32, ()>::from_edges([(0, 1), (1, 2), (2, 1), (2, 3), (3, 2)]);
+    g.remove_node(n(0));
+    assert_eq!(greedy_feedback_vertex_set(&g), [n(2)]);
+    assert_eq!(weighted_feedback_vertex_set(&g, |_| 1), [n(2)]);
+    g.remove_node(n(2));
+    assert!(greedy_feedback_vertex_set(&g).is_empty());
+}
@@ -1026,6 +1026,61 @@ fn condensation() {
     assert!(cond.edge_count() == gr.edge_count());
 }
 
+#[test]
+fn scc_condensation() {
+    let gr: Graph<(), ()> = Graph::from_edges([
+        (6, 0),
+        (0, 3),
+        (3, 6),
+        (8, 6),
+        (8, 2),
+        (2, 3),
+        (2, 5),
+        (5, 8),
+        (7, 5),
+        (1, 7),
+        (7, 4),
+        (4, 1),
+    ]);
+
+    let cond = petgraph::algo::scc_condensation(&gr);
+    let dag = cond.graph();
+    assert_eq!(dag.node_count(), 3);
+    assert_eq!(dag.edge_count(), 2);
+    assert!(!petgraph::algo::is_cyclic_directed(dag));
+
+    // Nodes of the condensed graph are numbered in topological order.
+    for edge in dag.edge_references() {
+        assert!(edge.source() < edge.target());
+    }
+    let sccs = cond
+        .sccs()
+        .map(|scc| set(scc.iter().copied()))
+        .collect::<Vec<_>>();
+    assert_eq!(sccs[0], set([n(1), n(4), n(7)]));
+    assert_eq!(sccs[1], set([n(2), n(5), n(8)]));
+    assert_eq!(sccs[2], set([n(0), n(3), n(6)]));
+
+    for node in gr.node_indices() {
+        let scc = cond.scc_of(node).unwrap();
+        assert!(dag[scc].contains(&node));
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn scc_condensation_stable_graph() {
+    let mut gr: StableGraph<(), ()> = StableGraph::from_edges([(0, 1), (1, 2), (2, 0), (2, 3)]);
+    gr.remove_node(n(1));
+
+    let cond = petgraph::algo::scc_condensation(&gr);
+    assert_eq!(cond.graph().node_count(), 3);
+    assert_eq!(cond.graph().edge_count(), 2);
+    assert_eq!(cond.scc_of(n(1)), None);
+    assert!(cond.scc_of(n(2)) < cond.scc_of(n(0)));
+    assert!(cond.scc_of(n(2)) < cond.scc_of(n(3)));
+}
+
 #[test]
 fn connected_comp() {
     let n = NodeIndex::new;
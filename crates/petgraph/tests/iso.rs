@@ -1,15 +1,25 @@
 extern crate petgraph;
 
-use std::{collections::HashSet, fs::File, io::prelude::*};
+#[path = "utils/random.rs"]
+mod random;
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::prelude::*,
+};
 
 use petgraph::{
     EdgeType,
     algo::{
-        is_isomorphic, is_isomorphic_matching, is_isomorphic_subgraph, subgraph_isomorphisms_iter,
+        SubgraphMatchOptions, SubgraphMode, is_isomorphic, is_isomorphic_matching,
+        is_isomorphic_subgraph, subgraph_isomorphisms_iter, subgraph_matches_iter,
     },
     graph::{edge_index, node_index},
     prelude::*,
 };
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::{random_graph, remove_parallel_edges};
 
 /// Petersen A and B are isomorphic
 ///
@@ -584,6 +594,189 @@ fn iter_subgraph() {
     );
 }
 
+/// The edges of `g` by their endpoints, with the endpoints of undirected edges sorted.
+fn edge_map<Ty: EdgeType>(g: &Graph<u8, u8, Ty>) -> HashMap<(usize, usize), u8> {
+    g.edge_references()
+        .map(|e| {
+            (
+                endpoints(g.is_directed(), e.source().index(), e.target().index()),
+                *e.weight(),
+            )
+        })
+        .collect()
+}
+
+fn endpoints(directed: bool, a: usize, b: usize) -> (usize, usize) {
+    if directed || a <= b { (a, b) } else { (b, a) }
+}
+
+/// All mappings of the kind given by `mode`, by trying every map.
+fn brute_force_matches<Ty: EdgeType>(
+    g0: &Graph<u8, u8, Ty>,
+    g1: &Graph<u8, u8, Ty>,
+    mode: SubgraphMode,
+) -> Vec<Vec<usize>> {
+    let directed = g0.is_directed();
+    let (edges0, edges1) = (edge_map(g0), edge_map(g1));
+    let (n0, n1) = (g0.node_count(), g1.node_count());
+    let mut matches = Vec::new();
+    if n1 == 0 && n0 > 0 {
+        return matches;
+    }
+    let mut mapping = vec![0; n0];
+    loop {
+        let injective = mapping.iter().collect::<HashSet<_>>().len() == n0;
+        let nodes_match = (0..n0).all(|v| g0[node_index(v)] == g1[node_index(mapping[v])]);
+        let edges_match = edges0.iter().all(|(&(a, b), w)| {
+            edges1.get(&endpoints(directed, mapping[a], mapping[b])) == Some(w)
+        });
+        let induced = (0..n0).all(|a| {
+            (0..n0).all(|b| {
+                let image = endpoints(directed, mapping[a], mapping[b]);
+                edges1.contains_key(&image) <= edges0.contains_key(&endpoints(directed, a, b))
+            })
+        });
+        let found = match mode {
+            SubgraphMode::Induced => injective && induced,
+            SubgraphMode::Monomorphism => injective,
+            SubgraphMode::Homomorphism => true,
+        };
+        if found && nodes_match && edges_match {
+            matches.push(mapping.clone());
+        }
+        // Go to the next map, in lexicographic order.
+        let Some(i) = (0..n0).rev().find(|&i| mapping[i] + 1 < n1) else {
+            break;
+        };
+        mapping[i] += 1;
+        mapping[i + 1..].iter_mut().for_each(|x| *x = 0);
+    }
+    matches.sort();
+    matches
+}
+
+fn matches<Ty: EdgeType>(
+    g0: &Graph<u8, u8, Ty>,
+    g1: &Graph<u8, u8, Ty>,
+    options: SubgraphMatchOptions,
+) -> Vec<Vec<usize>> {
+    let mut node_match = |a: &u8, b: &u8| a == b;
+    let mut edge_match = |a: &u8, b: &u8| a == b;
+    subgraph_matches_iter(&g0, &g1, &mut node_match, &mut edge_match, options).collect()
+}
+
+/// The nodes and edges of `g1` that `mapping` maps `g0` to.
+fn embedding<Ty: EdgeType>(
+    g0: &Graph<u8, u8, Ty>,
+    mapping: &[usize],
+) -> (Vec<usize>, Vec<(usize, usize)>) {
+    let mut nodes = mapping.to_vec();
+    nodes.sort();
+    let mut edges = g0
+        .edge_references()
+        .map(|e| {
+            endpoints(
+                g0.is_directed(),
+                mapping[e.source().index()],
+                mapping[e.target().index()],
+            )
+        })
+        .collect::<Vec<_>>();
+    edges.sort();
+    (nodes, edges)
+}
+
+fn check_subgraph_matches<Ty: EdgeType>(seed: u8) {
+    let mut rng = ChaChaRng::from_seed([seed; 32]);
+    let labeled_graph = |rng: &mut ChaChaRng, max_nodes: usize| -> Graph<u8, u8, Ty> {
+        let node_count = rng.gen_range(0, max_nodes + 1);
+        let density = rng.gen_range(0.2, 0.8);
+        let mut g = random_graph(
+            rng,
+            node_count,
+            density,
+            |rng| rng.gen_range(0, 2),
+            |rng| rng.gen_range(0, 2),
+        );
+        // `edge_map` keeps one edge between every pair of nodes.
+        remove_parallel_edges(&mut g);
+        g
+    };
+    for _ in 0..150 {
+        let g0 = labeled_graph(&mut rng, 4);
+        let g1 = labeled_graph(&mut rng, 6);
+        for mode in [
+            SubgraphMode::Induced,
+            SubgraphMode::Monomorphism,
+            SubgraphMode::Homomorphism,
+        ] {
+            let options = SubgraphMatchOptions::new().mode(mode);
+            let mut found = matches(&g0, &g1, options);
+            found.sort();
+            assert_eq!(found, brute_force_matches(&g0, &g1, mode), "{mode:?}");
+
+            if mode == SubgraphMode::Homomorphism {
+                continue;
+            }
+            // Every embedding is found once, by one of its mappings.
+            let unique = matches(&g0, &g1, options.break_symmetry(true));
+            let embeddings = unique
+                .iter()
+                .map(|mapping| embedding(&g0, mapping))
+                .collect::<HashSet<_>>();
+            assert_eq!(embeddings.len(), unique.len());
+            let expected = found
+                .iter()
+                .map(|mapping| embedding(&g0, mapping))
+                .collect::<HashSet<_>>();
+            assert_eq!(embeddings, expected);
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore = "Takes too long to run in Miri")]
+fn subgraph_matches_undirected() {
+    check_subgraph_matches::<Undirected>(1);
+}
+
+#[test]
+#[cfg_attr(miri, ignore = "Takes too long to run in Miri")]
+fn subgraph_matches_directed() {
+    check_subgraph_matches::<Directed>(2);
+}
+
+#[test]
+fn subgraph_matches_triangle_in_k4() {
+    let triangle = Graph::<u8, u8, Undirected>::from_edges([(0, 1), (1, 2), (2, 0)]);
+    let k4 =
+        Graph::<u8, u8, Undirected>::from_edges([(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+    let options = SubgraphMatchOptions::new();
+    assert_eq!(matches(&triangle, &k4, options).len(), 24);
+    assert_eq!(
+        matches(&triangle, &k4, options.break_symmetry(true)).len(),
+        4
+    );
+    assert_eq!(matches(&triangle, &k4, options.limit(5)).len(), 5);
+    assert!(matches(&triangle, &k4, options.limit(0)).is_empty());
+
+    // The pattern is larger than the host.
+    assert!(matches(&k4, &triangle, options).is_empty());
+    let options = options.mode(SubgraphMode::Homomorphism);
+    assert!(matches(&k4, &triangle, options).is_empty());
+    assert_eq!(matches(&triangle, &triangle, options).len(), 6);
+
+    // Labels break the symmetry of the pattern.
+    let mut labeled = triangle.clone();
+    labeled[node_index(0)] = 1;
+    let mut host = k4.clone();
+    host[node_index(3)] = 1;
+    let options = SubgraphMatchOptions::new().break_symmetry(true);
+    let found = matches(&labeled, &host, options);
+    assert_eq!(found.len(), 3);
+    assert!(found.iter().all(|mapping| mapping[0] == 3));
+}
+
 /// Isomorphic pair
 const COXETER_A: &str = "
  0 1 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 0 0 0 0 0 0 0 1
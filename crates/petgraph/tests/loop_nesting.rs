@@ -0,0 +1,263 @@
+#[path = "utils/random.rs"]
+mod random;
+
+use hashbrown::HashSet;
+use petgraph::{
+    algo::{
+        dominators,
+        loop_nesting::{LoopNestingForest, loop_nesting_forest},
+    },
+    graph::node_index as n,
+    prelude::*,
+    visit::{Dfs, EdgeFiltered, Reversed, Walker},
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::random_multigraph;
+
+/// Find the strongly connected components with a cycle of the subgraph induced by `nodes`,
+/// without the edges into `headers`.
+fn cyclic_components(
+    g: &DiGraph<(), ()>,
+    nodes: &HashSet<NodeIndex>,
+    headers: &HashSet<NodeIndex>,
+) -> Vec<HashSet<NodeIndex>> {
+    let filtered = EdgeFiltered::from_fn(g, |e| {
+        nodes.contains(&e.source()) && nodes.contains(&e.target()) && !headers.contains(&e.target())
+    });
+    let reach =
+        |v: NodeIndex| -> HashSet<NodeIndex> { Dfs::new(&filtered, v).iter(&filtered).collect() };
+    let mut components = Vec::new();
+    let mut seen = HashSet::new();
+    for &v in nodes {
+        if seen.contains(&v) {
+            continue;
+        }
+        let forward = reach(v);
+        let component = forward
+            .iter()
+            .copied()
+            .filter(|&w| reach(w).contains(&v))
+            .collect::<HashSet<_>>();
+        seen.extend(component.iter().copied());
+        let cyclic = component.len() > 1 || g.contains_edge(v, v) && !headers.contains(&v);
+        if cyclic {
+            components.push(component);
+        }
+    }
+    components
+}
+
+fn assert_loop_nesting_forest(
+    g: &DiGraph<(), ()>,
+    root: NodeIndex,
+    forest: &LoopNestingForest<NodeIndex>,
+) {
+    let reachable = Dfs::new(g, root).iter(g).collect::<HashSet<_>>();
+    let doms = dominators::simple_fast(g, root);
+    let loops = forest.loops();
+    let set = |nodes: &[NodeIndex]| nodes.iter().copied().collect::<HashSet<_>>();
+    let sorted = |mut components: Vec<HashSet<NodeIndex>>| {
+        let mut components = components
+            .drain(..)
+            .map(|c| {
+                let mut c = c.into_iter().collect::<Vec<_>>();
+                c.sort();
+                c
+            })
+            .collect::<Vec<_>>();
+        components.sort();
+        components
+    };
+
+    // The top-level loops are the cyclic components of the reachable graph.
+    let top = forest
+        .top_level_loops()
+        .map(|i| set(loops[i].nodes()))
+        .collect();
+    assert_eq!(
+        sorted(top),
+        sorted(cyclic_components(g, &reachable, &HashSet::new()))
+    );
+
+    for (i, l) in loops.iter().enumerate() {
+        let nodes = set(l.nodes());
+        assert_eq!(nodes.len(), l.nodes().len());
+        assert_eq!(l.nodes()[0], l.header());
+        assert_eq!(l.entries()[0], l.header());
+        if let Some(parent) = l.parent() {
+            assert!(parent < i);
+            assert!(loops[parent].children().contains(&i));
+            assert!(nodes.is_subset(&set(loops[parent].nodes())));
+            assert!(!nodes.contains(&loops[parent].header()));
+        }
+
+        // The nested loops are the cyclic components without the back edges.
+        let mut headers = HashSet::new();
+        headers.insert(l.header());
+        let children = l
+            .children()
+            .iter()
+            .map(|&c| set(loops[c].nodes()))
+            .collect();
+        assert_eq!(
+            sorted(children),
+            sorted(cyclic_components(g, &nodes, &headers))
+        );
+
+        let entries = nodes
+            .iter()
+            .copied()
+            .filter(|&v| {
+                v == root
+                    || g.neighbors_directed(v, Incoming)
+                        .any(|u| reachable.contains(&u) && !nodes.contains(&u))
+            })
+            .collect::<HashSet<_>>();
+        assert_eq!(set(l.entries()), entries);
+        let dominated = nodes
+            .iter()
+            .all(|&v| doms.dominators(v).unwrap().any(|d| d == l.header()));
+        assert_eq!(l.is_reducible(), dominated);
+
+        let mut back_edges = g
+            .edge_references()
+            .filter(|e| nodes.contains(&e.source()) && e.target() == l.header())
+            .map(|e| (e.source(), e.target()))
+            .collect::<Vec<_>>();
+        back_edges.sort();
+        back_edges.dedup();
+        let mut found = l.back_edges().to_vec();
+        found.sort();
+        assert_eq!(found, back_edges);
+        assert!(!back_edges.is_empty());
+
+        let mut exit_edges = g
+            .edge_references()
+            .filter(|e| nodes.contains(&e.source()) && !nodes.contains(&e.target()))
+            .map(|e| (e.source(), e.target()))
+            .collect::<Vec<_>>();
+        exit_edges.sort();
+        exit_edges.dedup();
+        let mut found = l.exit_edges().to_vec();
+        found.sort();
+        assert_eq!(found, exit_edges);
+
+        // A reducible loop is the natural loop of its back edges: the nodes that reach a back edge
+        // without passing through the header.
+        if l.is_reducible() {
+            let header = l.header();
+            let reversed = EdgeFiltered::from_fn(g, |e| {
+                e.target() != header && reachable.contains(&e.source())
+            });
+            let mut natural = HashSet::new();
+            natural.insert(header);
+            for &(source, _) in l.back_edges() {
+                let mut dfs = Dfs::new(Reversed(&reversed), source);
+                while let Some(v) = dfs.next(Reversed(&reversed)) {
+                    natural.insert(v);
+                }
+            }
+            assert_eq!(natural, nodes);
+        }
+    }
+
+    for v in g.node_indices() {
+        let containing = loops.iter().filter(|l| l.contains(v)).count();
+        assert_eq!(forest.loop_depth(v), containing);
+        match forest.innermost_loop(v) {
+            Some(i) => {
+                assert!(loops[i].contains(v));
+                assert!(loops[i].children().iter().all(|&c| !loops[c].contains(v)));
+            }
+            None => assert_eq!(containing, 0),
+        }
+        assert_eq!(
+            forest.is_loop_header(v),
+            loops.iter().any(|l| l.header() == v)
+        );
+    }
+}
+
+#[test]
+fn loop_nesting_random() {
+    let mut rng = ChaChaRng::from_seed([25; 32]);
+    let mut irreducible = 0;
+    for _ in 0..1000 {
+        let node_count = rng.gen_range(1, 11);
+        let edge_count = rng.gen_range(0, 3 * node_count);
+        let g: DiGraph<(), ()> =
+            random_multigraph(&mut rng, node_count, edge_count, |_| (), |_| ());
+        let root = n(rng.gen_range(0, g.node_count()));
+        let forest = loop_nesting_forest(&g, root);
+        assert_loop_nesting_forest(&g, root, &forest);
+        irreducible += forest.loops().iter().filter(|l| !l.is_reducible()).count();
+    }
+    assert!(irreducible > 50, "{irreducible} irreducible loops");
+}
+
+#[test]
+fn loop_nesting_irreducible() {
+    // Two entries into the loop between 1 and 2.
+    let g = DiGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 2), (2, 1), (2, 3)]);
+    let forest = loop_nesting_forest(&g, n(0));
+    assert_eq!(forest.loops().len(), 1);
+    let l = &forest.loops()[0];
+    assert!(!l.is_reducible());
+    // The header depends on the order of the depth-first search.
+    let other = if l.header() == n(1) { n(2) } else { n(1) };
+    assert_eq!(l.entries(), [l.header(), other]);
+    assert_eq!(l.back_edges(), [(other, l.header())]);
+    assert_eq!(l.exit_edges(), [(n(2), n(3))]);
+    assert_loop_nesting_forest(&g, n(0), &forest);
+}
+
+#[test]
+fn loop_nesting_self_loops_and_siblings() {
+    // Two sibling loops in a loop, and a self-loop.
+    let g = DiGraph::<(), ()>::from_edges([
+        (0, 1),
+        (1, 2),
+        (2, 2),
+        (2, 3),
+        (3, 4),
+        (4, 3),
+        (4, 5),
+        (5, 1),
+        (5, 6),
+        (6, 6),
+    ]);
+    let forest = loop_nesting_forest(&g, n(0));
+    assert_loop_nesting_forest(&g, n(0), &forest);
+    assert_eq!(forest.loops().len(), 4);
+    let outer = &forest.loops()[forest.innermost_loop(n(1)).unwrap()];
+    assert_eq!(outer.children().len(), 2);
+    assert_eq!(forest.loop_depth(n(2)), 2);
+    assert_eq!(forest.loop_depth(n(4)), 2);
+    assert_eq!(forest.loop_depth(n(6)), 1);
+    assert_eq!(forest.loop_depth(n(0)), 0);
+    assert_eq!(forest.top_level_loops().count(), 2);
+}
+
+#[test]
+fn loop_nesting_deep() {
+    // Loops nested a hundred levels deep.
+    let depth = 100;
+    let mut g = DiGraph::<(), ()>::from_edges((0..2 * depth).map(|i| (i, i + 1)));
+    g.extend_with_edges((1..=depth).map(|i| (2 * depth + 1 - i, i)));
+    let forest = loop_nesting_forest(&g, n(0));
+    assert_eq!(forest.loops().len(), depth as usize);
+    assert_eq!(forest.loop_depth(n(depth as usize)), depth as usize);
+    assert!(forest.loops().iter().all(|l| l.is_reducible()));
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn loop_nesting_stable_graph() {
+    let mut g =
+        StableDiGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 1), (0, 3), (3, 4), (4, 3)]);
+    g.remove_node(n(2));
+    let forest = loop_nesting_forest(&g, n(0));
+    assert_eq!(forest.loops().len(), 1);
+    assert_eq!(forest.loops()[0].header(), n(3));
+    assert_eq!(forest.innermost_loop(n(1)), None);
+}
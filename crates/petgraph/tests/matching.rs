@@ -1,10 +1,17 @@
+#[path = "utils/random.rs"]
+mod random;
+
 use core::hash::Hash;
 
 use hashbrown::HashSet;
 use petgraph::{
-    algo::{greedy_matching, maximum_matching},
+    algo::{
+        greedy_matching, maximum_matching, maximum_weight_matching, minimum_weight_perfect_matching,
+    },
     prelude::*,
 };
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::{random_graph, remove_parallel_edges};
 
 macro_rules! assert_one_of {
     ($actual:expr, [$($expected:expr),+]) => {
@@ -141,3 +148,158 @@ fn is_perfect_in_stable_graph() {
     assert_eq!(m.len(), 1);
     assert!(m.is_perfect());
 }
+
+/// The best matching weight over the nodes in `free`, by exhaustive search. With `perfect`, only
+/// perfect matchings are considered and `None` is returned if there is none.
+fn brute_force_matching(
+    adj: &[Vec<Option<i64>>],
+    free: u32,
+    perfect: bool,
+    minimize: bool,
+) -> Option<i64> {
+    if free == 0 {
+        return Some(0);
+    }
+    let v = free.trailing_zeros() as usize;
+    let rest = free & !(1 << v);
+    let mut best = if perfect {
+        None
+    } else {
+        brute_force_matching(adj, rest, perfect, minimize)
+    };
+    for (u, &w) in adj[v].iter().enumerate() {
+        if let Some(w) = w.filter(|_| rest & (1 << u) != 0)
+            && let Some(sub) = brute_force_matching(adj, rest & !(1 << u), perfect, minimize)
+        {
+            let total = sub + w;
+            best = Some(match best {
+                Some(b) if (minimize && b <= total) || (!minimize && b >= total) => b,
+                _ => total,
+            });
+        }
+    }
+    best
+}
+
+fn random_weighted_graph(rng: &mut ChaChaRng) -> UnGraph<(), i64> {
+    let node_count = rng.gen_range(0, 11);
+    let mut g = random_graph(rng, node_count, 0.4, |_| (), |rng| rng.gen_range(-5, 30));
+    // `adjacency` keeps one edge between every pair of nodes.
+    remove_parallel_edges(&mut g);
+    g
+}
+
+fn adjacency(g: &UnGraph<(), i64>) -> Vec<Vec<Option<i64>>> {
+    let mut adj = vec![vec![None; g.node_count()]; g.node_count()];
+    for e in g.edge_references() {
+        adj[e.source().index()][e.target().index()] = Some(*e.weight());
+        adj[e.target().index()][e.source().index()] = Some(*e.weight());
+    }
+    adj
+}
+
+fn matching_weight(
+    g: &UnGraph<(), i64>,
+    edges: impl Iterator<Item = (NodeIndex, NodeIndex)>,
+) -> i64 {
+    edges
+        .map(|(a, b)| *g.edge_weight(g.find_edge(a, b).unwrap()).unwrap())
+        .sum()
+}
+
+#[test]
+fn maximum_weight_path() {
+    let g: UnGraph<(), i32> = UnGraph::from_edges([(0, 1, 5), (1, 2, 11), (2, 3, 5)]);
+    let m = maximum_weight_matching(&g, |e| *e.weight());
+    assert_eq!(collect(m.edges()), set![(1, 2)]);
+
+    let g: UnGraph<(), i32> = UnGraph::from_edges([(0, 1, 5), (1, 2, 9), (2, 3, 5)]);
+    let m = maximum_weight_matching(&g, |e| *e.weight());
+    assert_eq!(collect(m.edges()), set![(0, 1), (2, 3)]);
+}
+
+#[test]
+fn maximum_weight_nested_blossoms() {
+    // Nested S-blossom, relabeled as T-blossom and expanded.
+    let g: UnGraph<(), f64> = UnGraph::from_edges([
+        (0, 1, 19.),
+        (0, 2, 20.),
+        (0, 7, 8.),
+        (1, 2, 25.),
+        (1, 3, 18.),
+        (2, 4, 18.),
+        (3, 4, 13.),
+        (3, 6, 7.),
+        (4, 5, 7.),
+    ]);
+    let m = maximum_weight_matching(&g, |e| *e.weight());
+    assert_eq!(collect(m.edges()), set![(0, 7), (1, 2), (3, 6), (4, 5)]);
+}
+
+#[test]
+fn maximum_weight_random() {
+    let mut rng = ChaChaRng::from_seed([7; 32]);
+    for _ in 0..300 {
+        let g = random_weighted_graph(&mut rng);
+        let free = (1u32 << g.node_count()) - 1;
+        let expected = brute_force_matching(&adjacency(&g), free, false, false).unwrap();
+
+        let m = maximum_weight_matching(&g, |e| *e.weight());
+        assert_eq!(matching_weight(&g, m.edges()), expected, "{g:?}");
+        assert_eq!(m.len(), m.edges().count());
+    }
+}
+
+#[test]
+fn minimum_weight_perfect_random() {
+    let mut rng = ChaChaRng::from_seed([11; 32]);
+    for _ in 0..300 {
+        let g = random_weighted_graph(&mut rng);
+        let free = (1u32 << g.node_count()) - 1;
+        let expected = brute_force_matching(&adjacency(&g), free, true, true);
+
+        let m = minimum_weight_perfect_matching(&g, |e| *e.weight());
+        assert_eq!(m.map(|m| matching_weight(&g, m.edges())), expected, "{g:?}");
+    }
+}
+
+#[test]
+fn minimum_weight_perfect_unsigned_random() {
+    let mut rng = ChaChaRng::from_seed([13; 32]);
+    for _ in 0..300 {
+        let g = random_weighted_graph(&mut rng).map(|_, _| (), |_, &w| w + 5);
+        let free = (1u32 << g.node_count()) - 1;
+        let expected = brute_force_matching(&adjacency(&g), free, true, true);
+
+        let unsigned = g.map(|_, _| (), |_, &w| w as u32);
+        let m = minimum_weight_perfect_matching(&unsigned, |e| *e.weight());
+        assert_eq!(m.map(|m| matching_weight(&g, m.edges())), expected, "{g:?}");
+    }
+}
+
+#[test]
+fn minimum_weight_perfect_largest_unsigned() {
+    // The mirrored costs add up to at most 2 · (1 + 4 · 31) = 250.
+    let g: UnGraph<(), u8> = UnGraph::from_edges([(0, 1, 31), (2, 3, 31), (0, 2, 0), (1, 3, 0)]);
+    let m = minimum_weight_perfect_matching(&g, |e| *e.weight()).unwrap();
+    assert_eq!(collect(m.edges()), set![(0, 2), (1, 3)]);
+}
+
+#[test]
+#[should_panic(expected = "edge weights are too large to be mirrored")]
+fn minimum_weight_perfect_overflow() {
+    let g: UnGraph<(), u8> = UnGraph::from_edges([(0, 1, 32), (2, 3, 32), (0, 2, 0), (1, 3, 0)]);
+    minimum_weight_perfect_matching(&g, |e| *e.weight());
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn minimum_weight_perfect_in_stable_graph() {
+    let mut g: StableUnGraph<(), u32> =
+        StableUnGraph::from_edges([(0, 1, 1), (1, 2, 4), (2, 3, 1), (3, 4, 1), (4, 1, 2)]);
+    assert!(minimum_weight_perfect_matching(&g, |e| *e.weight()).is_none());
+
+    g.remove_node(NodeIndex::new(0));
+    let m = minimum_weight_perfect_matching(&g, |e| *e.weight()).unwrap();
+    assert_eq!(collect(m.edges()), set![(2, 3), (1, 4)]);
+}
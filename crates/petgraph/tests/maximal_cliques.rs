@@ -4,7 +4,7 @@ use core::hash::Hash;
 use hashbrown::HashSet;
 use petgraph::{
     Undirected,
-    algo::maximal_cliques,
+    algo::{maximal_cliques, maximal_cliques_iter},
     graph::{DiGraph, Graph, UnGraph},
     visit::{GetAdjacencyMatrix, IntoNeighbors, IntoNodeIdentifiers},
 };
@@ -351,3 +351,48 @@ fn test_maximal_cliques_ref_directed() {
         assert!(cliques.contains(&v.iter().cloned().collect()));
     }
 }
+
+#[test]
+fn test_maximal_cliques_iter() {
+    // f - d - e - a
+    //     |   | /
+    //     c - b
+    let mut g = Graph::<i32, (), Undirected>::new_undirected();
+    let a = g.add_node(0);
+    let b = g.add_node(1);
+    let c = g.add_node(2);
+    let d = g.add_node(3);
+    let e = g.add_node(4);
+    let f = g.add_node(5);
+    g.extend_with_edges([(a, b), (a, e), (b, e), (b, c), (c, d), (d, e), (e, f)]);
+
+    let cliques = maximal_cliques_iter(&g).collect::<Vec<_>>();
+    assert_eq!(cliques.len(), 5);
+    for clique in maximal_cliques(&g) {
+        assert!(cliques.contains(&clique));
+    }
+
+    // The iterator can be stopped before all cliques are found.
+    let triangle = maximal_cliques_iter(&g).find(|clique| clique.len() == 3);
+    assert_eq!(triangle, Some([a, b, e].into_iter().collect()));
+
+    let empty = Graph::<i32, ()>::new();
+    assert_eq!(
+        maximal_cliques_iter(&empty).collect::<Vec<_>>(),
+        vec![HashSet::new()]
+    );
+}
+
+#[test]
+fn test_maximal_cliques_iter_complete_graph() {
+    let mut g = UnGraph::<(), ()>::default();
+    let nodes = (0..8).map(|_| g.add_node(())).collect::<Vec<_>>();
+    for (i, &a) in nodes.iter().enumerate() {
+        for &b in &nodes[i + 1..] {
+            g.add_edge(a, b, ());
+        }
+    }
+    let mut cliques = maximal_cliques_iter(&g);
+    assert_eq!(cliques.next(), Some(nodes.iter().cloned().collect()));
+    assert_eq!(cliques.next(), None);
+}
@@ -0,0 +1,169 @@
+#[path = "utils/random.rs"]
+mod random;
+
+use hashbrown::HashSet;
+use petgraph::{
+    algo::{
+        maximal_cliques, maximum_clique, maximum_independent_set, maximum_weight_clique,
+        minimum_vertex_cover,
+    },
+    graph::node_index as n,
+    operator::complement,
+    prelude::*,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::random_graph;
+
+fn is_clique(g: &UnGraph<(), ()>, nodes: &HashSet<NodeIndex>) -> bool {
+    nodes
+        .iter()
+        .all(|&a| nodes.iter().all(|&b| a == b || g.contains_edge(a, b)))
+}
+
+fn is_independent(g: &UnGraph<(), ()>, nodes: &HashSet<NodeIndex>) -> bool {
+    nodes
+        .iter()
+        .all(|&a| nodes.iter().all(|&b| a == b || !g.contains_edge(a, b)))
+}
+
+/// Enumerate all subsets of nodes, and return the largest weight of one that satisfies `f`.
+fn brute_force(
+    g: &UnGraph<(), ()>,
+    weights: &[u32],
+    mut f: impl FnMut(&HashSet<NodeIndex>) -> bool,
+) -> u32 {
+    (0..1u32 << g.node_count())
+        .map(|mask| {
+            g.node_indices()
+                .filter(|v| mask & (1 << v.index()) != 0)
+                .collect::<HashSet<_>>()
+        })
+        .filter(|nodes| f(nodes))
+        .map(|nodes| nodes.iter().map(|v| weights[v.index()]).sum())
+        .max()
+        .unwrap()
+}
+
+#[test]
+fn maximum_clique_known_graphs() {
+    let empty = UnGraph::<(), ()>::default();
+    assert!(maximum_clique(&empty).is_empty());
+    assert!(maximum_independent_set(&empty).is_empty());
+    assert!(minimum_vertex_cover(&empty).is_empty());
+    assert_eq!(
+        maximum_weight_clique(&empty, |_| 1.5),
+        (HashSet::new(), 0.0)
+    );
+
+    // Two disjoint copies of K4 joined by a path, and a K5 minus a perfect matching edge.
+    let mut g = UnGraph::<(), ()>::default();
+    for _ in 0..13 {
+        g.add_node(());
+    }
+    for offset in [0, 4] {
+        for a in 0..4 {
+            for b in a + 1..4 {
+                g.add_edge(n(offset + a), n(offset + b), ());
+            }
+        }
+    }
+    g.add_edge(n(3), n(4), ());
+    for a in 8..13 {
+        for b in a + 1..13 {
+            if (a, b) != (8, 9) {
+                g.add_edge(n(a), n(b), ());
+            }
+        }
+    }
+    let clique = maximum_clique(&g);
+    assert_eq!(clique.len(), 4);
+    assert!(is_clique(&g, &clique));
+
+    // One node of every K4, and two of the K5 minus an edge.
+    let independent = maximum_independent_set(&g);
+    assert_eq!(independent.len(), 4);
+    assert!(is_independent(&g, &independent));
+    assert_eq!(minimum_vertex_cover(&g).len(), 9);
+}
+
+#[test]
+fn maximum_clique_random() {
+    let mut rng = ChaChaRng::from_seed([19; 32]);
+    for _ in 0..300 {
+        let node_count = rng.gen_range(0, 11);
+        let density = rng.gen_range(0.05, 0.95);
+        let g: UnGraph<(), ()> = random_graph(&mut rng, node_count, density, |_| (), |_| ());
+        let weights = (0..g.node_count())
+            .map(|_| rng.gen_range(0, 10))
+            .collect::<Vec<u32>>();
+        let ones = vec![1; g.node_count()];
+
+        let clique = maximum_clique(&g);
+        assert!(is_clique(&g, &clique));
+        assert_eq!(
+            clique.len() as u32,
+            brute_force(&g, &ones, |c| is_clique(&g, c))
+        );
+        // Every maximum clique is maximal.
+        assert!(maximal_cliques(&g).contains(&clique));
+
+        let (weighted, weight) = maximum_weight_clique(&g, |v| weights[v.index()]);
+        assert!(is_clique(&g, &weighted));
+        assert_eq!(
+            weighted.iter().map(|v| weights[v.index()]).sum::<u32>(),
+            weight
+        );
+        assert_eq!(weight, brute_force(&g, &weights, |c| is_clique(&g, c)));
+
+        let independent = maximum_independent_set(&g);
+        assert!(is_independent(&g, &independent));
+        assert_eq!(
+            independent.len() as u32,
+            brute_force(&g, &ones, |c| is_independent(&g, c))
+        );
+
+        let cover = minimum_vertex_cover(&g);
+        assert_eq!(cover.len() + independent.len(), g.node_count());
+        assert!(g.edge_references().all(|e| {
+            e.source() == e.target() || cover.contains(&e.source()) || cover.contains(&e.target())
+        }));
+    }
+}
+
+#[test]
+fn maximum_clique_larger_graphs() {
+    let mut rng = ChaChaRng::from_seed([20; 32]);
+    for _ in 0..20 {
+        let node_count = rng.gen_range(0, 61);
+        let density = rng.gen_range(0.05, 0.95);
+        let g: UnGraph<(), ()> = random_graph(&mut rng, node_count, density, |_| (), |_| ());
+        let clique = maximum_clique(&g);
+        assert!(is_clique(&g, &clique));
+        if g.node_count() <= 30 {
+            let largest = maximal_cliques(&g).iter().map(HashSet::len).max();
+            assert_eq!(clique.len(), largest.unwrap());
+        }
+
+        // The independent sets of a graph are the cliques of its complement.
+        let independent = maximum_independent_set(&g);
+        assert!(is_independent(&g, &independent));
+        let mut h = UnGraph::default();
+        complement(&g, &mut h, ());
+        assert_eq!(maximum_clique(&h).len(), independent.len());
+        assert_eq!(maximum_independent_set(&h).len(), clique.len());
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn maximum_clique_stable_graph() {
+    let mut g = StableUnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (0, 3), (1, 3)]);
+    g.add_node(());
+    g.remove_node(n(2));
+    let clique = maximum_clique(&g);
+    assert_eq!(clique, HashSet::from_iter([n(0), n(1), n(3)]));
+    let independent = maximum_independent_set(&g);
+    assert_eq!(independent.len(), 2);
+    assert!(independent.contains(&n(4)));
+    assert_eq!(minimum_vertex_cover(&g).len(), 2);
+}
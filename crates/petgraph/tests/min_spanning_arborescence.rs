@@ -0,0 +1,300 @@
+#[path = "utils/random.rs"]
+mod random;
+
+use petgraph::{
+    algo::{min_branching, min_spanning_arborescence},
+    data::{Element, FromElements},
+    graph::node_index as n,
+    prelude::*,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::random_graph;
+
+/// Check that `edges` enter every node at most once, and every node but `root` exactly once if
+/// `root` is given, without cycles, and return their total weight.
+fn check_branching(g: &DiGraph<(), i32>, edges: &[EdgeIndex], root: Option<NodeIndex>) -> i32 {
+    let mut parent = vec![None; g.node_count()];
+    for &e in edges {
+        let (a, b) = g.edge_endpoints(e).unwrap();
+        assert_ne!(a, b);
+        assert!(parent[b.index()].is_none());
+        parent[b.index()] = Some(a);
+    }
+    for v in g.node_indices() {
+        if let Some(root) = root {
+            assert_eq!(parent[v.index()].is_none(), v == root);
+        }
+        // Every node reaches a node without parent.
+        let mut x = v;
+        for _ in 0..=g.node_count() {
+            match parent[x.index()] {
+                Some(p) => x = p,
+                None => break,
+            }
+        }
+        assert!(parent[x.index()].is_none());
+    }
+    edges.iter().map(|&e| g[e]).sum()
+}
+
+/// Find the weight of a minimum arborescence rooted at `root`, or of a minimum branching, by
+/// trying all choices of incoming edges.
+fn brute_force(g: &DiGraph<(), i32>, root: Option<NodeIndex>) -> Option<i32> {
+    fn search(
+        g: &DiGraph<(), i32>,
+        root: Option<NodeIndex>,
+        v: usize,
+        parent: &mut Vec<Option<NodeIndex>>,
+        weight: i32,
+        best: &mut Option<i32>,
+    ) {
+        if v == g.node_count() {
+            let acyclic = g.node_indices().all(|v| {
+                let mut x = v;
+                for _ in 0..=g.node_count() {
+                    match parent[x.index()] {
+                        Some(p) => x = p,
+                        None => return true,
+                    }
+                }
+                false
+            });
+            if acyclic && best.is_none_or(|best| weight < best) {
+                *best = Some(weight);
+            }
+            return;
+        }
+        if root.is_none() || root == Some(n(v)) {
+            search(g, root, v + 1, parent, weight, best);
+        }
+        if root == Some(n(v)) {
+            return;
+        }
+        for edge in g.edges_directed(n(v), Incoming) {
+            if edge.source() != n(v) {
+                parent[v] = Some(edge.source());
+                search(g, root, v + 1, parent, weight + edge.weight(), best);
+                parent[v] = None;
+            }
+        }
+    }
+    let mut best = None;
+    search(g, root, 0, &mut vec![None; g.node_count()], 0, &mut best);
+    best
+}
+
+/// Collect the edges of the `elements` as edges of `g`, with the cheapest of parallel edges.
+fn edges_of(
+    g: &DiGraph<(), i32>,
+    elements: impl Iterator<Item = Element<(), i32>>,
+) -> Vec<EdgeIndex> {
+    elements
+        .filter_map(|element| match element {
+            Element::Node { .. } => None,
+            Element::Edge {
+                source,
+                target,
+                weight,
+            } => Some(
+                g.edges_connecting(n(source), n(target))
+                    .find(|edge| *edge.weight() == weight)
+                    .unwrap()
+                    .id(),
+            ),
+        })
+        .collect()
+}
+
+#[test]
+fn arborescence_known_graph() {
+    let mut g = DiGraph::<&str, f64>::new();
+    let r = g.add_node("r");
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    let c = g.add_node("c");
+    let d = g.add_node("d");
+    // The cheapest edges into `a`, `b` and `c` make a cycle, which is entered through `b`.
+    g.extend_with_edges([
+        (r, a, 10.),
+        (r, b, 6.),
+        (r, c, 12.),
+        (a, b, 1.),
+        (b, c, 1.),
+        (c, a, 1.),
+        (c, d, 3.),
+        (a, d, 2.),
+        (d, a, 0.5),
+    ]);
+    let arborescence = min_spanning_arborescence(&g, Some(r)).unwrap();
+    assert_eq!(arborescence.root(), Some(r));
+    let tree = DiGraph::<_, _>::from_elements(arborescence);
+    assert_eq!(tree.node_count(), 5);
+    assert_eq!(tree.edge_count(), 4);
+    assert_eq!(tree.edge_weights().sum::<f64>(), 10.);
+    for (source, target) in [(r, b), (b, c), (c, a), (a, d)] {
+        assert!(tree.contains_edge(source, target));
+    }
+    assert_eq!(tree[n(2)], "b");
+
+    // Only `r` can be the root, as it has no incoming edges.
+    let best = min_spanning_arborescence(&g, None).unwrap();
+    assert_eq!(best.root(), Some(r));
+    assert!(min_spanning_arborescence(&g, Some(a)).is_none());
+}
+
+#[test]
+fn arborescence_empty_and_disconnected() {
+    let empty = DiGraph::<(), i32>::new();
+    assert!(min_spanning_arborescence(&empty, None).is_none());
+    assert_eq!(min_branching(&empty).count(), 0);
+
+    let single = DiGraph::<(), i32>::from_edges([(0, 0, 1)]);
+    let arborescence = min_spanning_arborescence(&single, None).unwrap();
+    assert_eq!(arborescence.root(), Some(n(0)));
+    assert_eq!(arborescence.count(), 1);
+
+    // Two components cannot be spanned from a single root.
+    let g = DiGraph::<(), i32>::from_edges([(0, 1, 1), (2, 3, 1)]);
+    assert!(min_spanning_arborescence(&g, None).is_none());
+    assert!(min_spanning_arborescence(&g, Some(n(0))).is_none());
+    let branching = min_branching(&g);
+    assert_eq!(branching.root(), None);
+    assert_eq!(branching.count(), 4);
+}
+
+#[test]
+fn arborescence_random() {
+    let mut rng = ChaChaRng::from_seed([26; 32]);
+    for _ in 0..500 {
+        let node_count = rng.gen_range(0, 7);
+        let density = rng.gen_range(0.1, 0.6);
+        let g: DiGraph<(), i32> = random_graph(
+            &mut rng,
+            node_count,
+            density,
+            |_| (),
+            |rng| rng.gen_range(0, 10),
+        );
+        let mut best_root = None;
+        for root in g.node_indices() {
+            let expected = brute_force(&g, Some(root));
+            let arborescence = min_spanning_arborescence(&g, Some(root));
+            assert_eq!(arborescence.is_some(), expected.is_some());
+            if let Some(arborescence) = arborescence {
+                assert_eq!(arborescence.root(), Some(root));
+                let edges = edges_of(&g, arborescence);
+                assert_eq!(Some(check_branching(&g, &edges, Some(root))), expected);
+            }
+            if let Some(weight) = expected {
+                best_root = Some(best_root.map_or(weight, |best: i32| best.min(weight)));
+            }
+        }
+
+        let best = min_spanning_arborescence(&g, None);
+        assert_eq!(best.is_some(), best_root.is_some());
+        if let Some(best) = best {
+            let root = best.root().unwrap();
+            let edges = edges_of(&g, best);
+            assert_eq!(Some(check_branching(&g, &edges, Some(root))), best_root);
+        }
+
+        // Reduced costs must not overflow unsigned weights.
+        let unsigned = g.map(|_, _| (), |_, &w| w as u32);
+        let best = min_spanning_arborescence(&unsigned, None).map(|best| {
+            DiGraph::<(), u32>::from_elements(best)
+                .edge_weights()
+                .sum::<u32>()
+        });
+        assert_eq!(best, best_root.map(|w| w as u32));
+    }
+}
+
+#[test]
+fn branching_random() {
+    let mut rng = ChaChaRng::from_seed([27; 32]);
+    for _ in 0..500 {
+        let node_count = rng.gen_range(0, 7);
+        let density = rng.gen_range(0.1, 0.6);
+        let g: DiGraph<(), i32> = random_graph(
+            &mut rng,
+            node_count,
+            density,
+            |_| (),
+            |rng| rng.gen_range(-10, 10),
+        );
+        let branching = min_branching(&g);
+        assert_eq!(branching.root(), None);
+        let elements = branching.collect::<Vec<_>>();
+        assert_eq!(
+            elements
+                .iter()
+                .take_while(|element| matches!(element, Element::Node { .. }))
+                .count(),
+            g.node_count()
+        );
+        let edges = edges_of(&g, elements.into_iter());
+        assert_eq!(
+            Some(check_branching(&g, &edges, None)),
+            brute_force(&g, None)
+        );
+    }
+}
+
+#[test]
+fn arborescence_larger_graphs() {
+    let mut rng = ChaChaRng::from_seed([28; 32]);
+    for _ in 0..20 {
+        let node_count = rng.gen_range(0, 301);
+        let density = rng.gen_range(0.1, 0.6);
+        let mut g: DiGraph<(), i32> = random_graph(
+            &mut rng,
+            node_count,
+            density,
+            |_| (),
+            |rng| rng.gen_range(0, 10),
+        );
+        // Make every node reachable from the first one.
+        for v in 1..g.node_count() {
+            let weight = rng.gen_range(10, 100);
+            g.add_edge(n(rng.gen_range(0, v)), n(v), weight);
+        }
+        if g.node_count() == 0 {
+            continue;
+        }
+        let arborescence = min_spanning_arborescence(&g, Some(n(0))).unwrap();
+        let edges = edges_of(&g, arborescence);
+        assert_eq!(edges.len(), g.node_count() - 1);
+        check_branching(&g, &edges, Some(n(0)));
+        let best = min_spanning_arborescence(&g, None).unwrap();
+        let root = best.root().unwrap();
+        let edges = edges_of(&g, best);
+        check_branching(&g, &edges, Some(root));
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn arborescence_stable_graph() {
+    let mut g = StableDiGraph::<u32, i32>::from_edges([
+        (0, 1, 5),
+        (0, 2, 1),
+        (1, 2, 1),
+        (2, 3, 1),
+        (3, 1, 1),
+    ]);
+    for (i, weight) in g.node_weights_mut().enumerate() {
+        *weight = i as u32;
+    }
+    g.remove_node(n(0));
+    // The cycle 1 -> 2 -> 3 -> 1 can be rooted at any of its nodes.
+    let arborescence = min_spanning_arborescence(&g, None).unwrap();
+    assert!(g.contains_node(arborescence.root().unwrap()));
+    let tree = DiGraph::<_, _>::from_elements(arborescence);
+    assert_eq!(tree.node_weights().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    assert_eq!(tree.edge_count(), 2);
+    assert_eq!(tree.edge_weights().sum::<i32>(), 2);
+
+    let tree = DiGraph::<_, _>::from_elements(min_spanning_arborescence(&g, Some(n(2))).unwrap());
+    // Nodes are numbered by their order in the stable graph.
+    assert!(tree.contains_edge(n(1), n(2)) && tree.contains_edge(n(2), n(0)));
+}
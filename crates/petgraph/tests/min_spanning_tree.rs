@@ -1,9 +1,18 @@
+#[path = "utils/random.rs"]
+mod random;
+
+#[cfg(feature = "rayon")]
+use petgraph::algo::parallel_min_spanning_tree_boruvka;
 use petgraph::{
     Graph, Undirected,
-    algo::{min_spanning_tree, min_spanning_tree_prim},
+    algo::{
+        connected_components, min_spanning_tree, min_spanning_tree_boruvka, min_spanning_tree_prim,
+    },
     dot::Dot,
-    graph::{NodeIndex, UnGraph},
+    graph::{NodeIndex, UnGraph, node_index as n},
 };
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::random_multigraph;
 
 #[test]
 fn mst_kruskal() {
@@ -207,6 +216,108 @@ fn mst_prim_test_cases() {
     }
 }
 
+#[test]
+fn mst_boruvka_test_cases() {
+    use petgraph::data::FromElements;
+
+    for (edges, expected_mst_edges) in TEST_CASES {
+        let mut gr = UnGraph::new_undirected();
+        gr.extend_with_edges(edges.to_vec());
+
+        let mst: Graph<(), u32, Undirected, u32> =
+            UnGraph::from_elements(min_spanning_tree_boruvka(&gr));
+
+        assert!(mst.node_count() == gr.node_count());
+        assert!(mst.edge_count() == expected_mst_edges.len());
+
+        for (source, target, _) in expected_mst_edges {
+            let a = NodeIndex::new(*source as usize);
+            let b = NodeIndex::new(*target as usize);
+            assert!(mst.contains_edge(a, b));
+        }
+
+        #[cfg(feature = "rayon")]
+        {
+            let parallel: Graph<(), u32, Undirected, u32> =
+                UnGraph::from_elements(parallel_min_spanning_tree_boruvka(&gr));
+            assert_eq!(parallel.edge_count(), mst.edge_count());
+            for (source, target, _) in expected_mst_edges {
+                let a = NodeIndex::new(*source as usize);
+                let b = NodeIndex::new(*target as usize);
+                assert!(parallel.contains_edge(a, b));
+            }
+        }
+    }
+}
+
+#[test]
+fn mst_boruvka_random() {
+    use petgraph::data::FromElements;
+
+    let mut rng = ChaChaRng::from_seed([29; 32]);
+    for _ in 0..300 {
+        let node_count = rng.gen_range(0, 41);
+        let edge_count = rng.gen_range(0, 3 * node_count + 1);
+        // Include many equal weights.
+        let g: UnGraph<(), u32> = random_multigraph(
+            &mut rng,
+            node_count,
+            edge_count,
+            |_| (),
+            |rng| rng.gen_range(0, 10),
+        );
+        let kruskal = UnGraph::<(), u32>::from_elements(min_spanning_tree(&g));
+        let boruvka = UnGraph::<(), u32>::from_elements(min_spanning_tree_boruvka(&g));
+        assert_eq!(boruvka.node_count(), g.node_count());
+        assert_eq!(
+            boruvka.edge_count(),
+            g.node_count() - connected_components(&g)
+        );
+        // A spanning forest of the same components.
+        assert_eq!(connected_components(&boruvka), connected_components(&g));
+        for e in boruvka.edge_indices() {
+            let (a, b) = boruvka.edge_endpoints(e).unwrap();
+            assert!(
+                g.edges_connecting(a, b)
+                    .any(|edge| *edge.weight() == boruvka[e])
+            );
+        }
+        assert_eq!(
+            boruvka.edge_weights().sum::<u32>(),
+            kruskal.edge_weights().sum::<u32>()
+        );
+
+        #[cfg(feature = "rayon")]
+        {
+            let parallel = parallel_min_spanning_tree_boruvka(&g).collect::<Vec<_>>();
+            assert_eq!(parallel, min_spanning_tree_boruvka(&g).collect::<Vec<_>>());
+        }
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn mst_boruvka_stable_graph() {
+    use petgraph::{data::FromElements, stable_graph::StableUnGraph};
+
+    let mut g = StableUnGraph::<u32, u32>::from_edges([
+        (0, 1, 1),
+        (1, 2, 5),
+        (2, 3, 1),
+        (3, 0, 2),
+        (0, 2, 3),
+    ]);
+    for (i, weight) in g.node_weights_mut().enumerate() {
+        *weight = i as u32;
+    }
+    g.remove_node(n(1));
+    let mst = UnGraph::<u32, u32>::from_elements(min_spanning_tree_boruvka(&g));
+    assert_eq!(mst.node_weights().copied().collect::<Vec<_>>(), [0, 2, 3]);
+    // Nodes are numbered by their order in the stable graph.
+    assert!(mst.contains_edge(n(1), n(2)) && mst.contains_edge(n(0), n(2)));
+    assert_eq!(mst.edge_weights().sum::<u32>(), 3);
+}
+
 // Test cases format: (graph order, graph edges, mst edges)
 #[rustfmt::skip]
 #[allow(clippy::type_complexity)]
@@ -1,6 +1,19 @@
+#[path = "utils/random.rs"]
+mod random;
+
 #[cfg(feature = "rayon")]
 use petgraph::algo::page_rank::parallel_page_rank;
-use petgraph::{Graph, algo::page_rank};
+use petgraph::{
+    EdgeType, Graph,
+    algo::{
+        page_rank,
+        page_rank::{DanglingNodes, PageRank},
+    },
+    graph::node_index as n,
+    prelude::*,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::{assert_close, random_graph};
 
 fn graph_example() -> Graph<String, f32> {
     // Taken and adapted from https://github.com/neo4j-labs/graph?tab=readme-ov-file#how-to-run-algorithms
@@ -81,3 +94,195 @@ fn test_par_page_rank() {
                 || expected.is_nan())
     );
 }
+
+/// A random edge weight, which is zero once in a while.
+fn edge_weight(rng: &mut ChaChaRng) -> f64 {
+    if rng.gen_bool(0.1) {
+        0.
+    } else {
+        rng.gen_range(0.5, 2.)
+    }
+}
+
+fn random_distribution(rng: &mut ChaChaRng, len: usize) -> Vec<f64> {
+    let mut distribution = (0..len).map(|_| rng.gen_range(0., 1.)).collect::<Vec<_>>();
+    distribution[rng.gen_range(0, len)] = 1.;
+    distribution
+}
+
+fn scaled(weights: &[f64]) -> Vec<f64> {
+    let sum = weights.iter().sum::<f64>();
+    weights.iter().map(|w| w / sum).collect()
+}
+
+/// One step of the random surfer from `ranks`, computed from the dense transition matrix.
+fn surf<Ty: EdgeType>(
+    g: &Graph<(), f64, Ty>,
+    ranks: &[f64],
+    damping_factor: f64,
+    teleport: &[f64],
+    dangling_nodes: &DanglingNodes<f64>,
+) -> Vec<f64> {
+    let node_count = g.node_count();
+    let mut matrix = vec![vec![0.; node_count]; node_count];
+    for e in g.edge_references() {
+        let (a, b) = (e.source().index(), e.target().index());
+        matrix[a][b] += e.weight();
+        if !g.is_directed() && a != b {
+            matrix[b][a] += e.weight();
+        }
+    }
+    for (a, row) in matrix.iter_mut().enumerate() {
+        if row.iter().sum::<f64>() == 0. {
+            *row = match dangling_nodes {
+                DanglingNodes::Teleport => teleport.to_vec(),
+                DanglingNodes::Uniform => vec![1.; node_count],
+                DanglingNodes::Distribution(jump) => jump.clone(),
+                DanglingNodes::SelfLoop => {
+                    let mut row = vec![0.; node_count];
+                    row[a] = 1.;
+                    row
+                }
+            };
+        }
+        *row = scaled(row);
+    }
+    (0..node_count)
+        .map(|b| {
+            let followed = (0..node_count)
+                .map(|a| ranks[a] * matrix[a][b])
+                .sum::<f64>();
+            damping_factor * followed + (1. - damping_factor) * teleport[b]
+        })
+        .collect()
+}
+
+fn assert_stationary<Ty: EdgeType>(rng: &mut ChaChaRng, g: &Graph<(), f64, Ty>) {
+    let node_count = g.node_count();
+    let damping_factor = rng.gen_range(0., 0.95);
+    let personalization = random_distribution(rng, node_count);
+    let dangling_nodes = match rng.gen_range(0, 4) {
+        0 => DanglingNodes::Teleport,
+        1 => DanglingNodes::Uniform,
+        2 => DanglingNodes::Distribution(random_distribution(rng, node_count)),
+        _ => DanglingNodes::SelfLoop,
+    };
+    let result = PageRank::new()
+        .damping_factor(damping_factor)
+        .max_iter(10_000)
+        .tolerance(1e-13)
+        .personalization(personalization.clone())
+        .dangling_nodes(dangling_nodes.clone())
+        .run(g, |e| *e.weight());
+    assert!(result.converged);
+    assert!(result.iterations <= 10_000);
+    assert!((result.ranks.iter().sum::<f64>() - 1.).abs() < 1e-9);
+    let next = surf(
+        g,
+        &result.ranks,
+        damping_factor,
+        &scaled(&personalization),
+        &dangling_nodes,
+    );
+    assert_close(&result.ranks, &next);
+}
+
+#[test]
+fn page_rank_builder_stationary() {
+    let mut rng = ChaChaRng::from_seed([5; 32]);
+    for _ in 0..100 {
+        let node_count = rng.gen_range(1, 12);
+        let g = random_graph::<_, _, Directed>(&mut rng, node_count, 0.2, |_| (), edge_weight);
+        assert_stationary(&mut rng, &g);
+        let node_count = rng.gen_range(1, 12);
+        let g = random_graph::<_, _, Undirected>(&mut rng, node_count, 0.2, |_| (), edge_weight);
+        assert_stationary(&mut rng, &g);
+    }
+}
+
+#[test]
+fn page_rank_builder_options() {
+    // `a` follows its heavier edge to `c` three times as often as the one to `b`, and the
+    // dangling nodes `b` and `c` teleport back to `a`.
+    let g = DiGraph::<(), f64>::from_edges([(0, 1, 1.), (0, 2, 3.)]);
+    let page_rank = PageRank::new()
+        .damping_factor(0.5)
+        .personalization(vec![1., 0., 0.])
+        .tolerance(1e-12);
+    let result = page_rank.run(&g, |e| *e.weight());
+    assert!(result.converged);
+    assert_close(&result.ranks, &[2. / 3., 1. / 12., 1. / 4.]);
+
+    // Without weights, both edges are followed equally often.
+    let result = page_rank.run(&g, |_| 1.);
+    assert_close(&result.ranks, &[2. / 3., 1. / 6., 1. / 6.]);
+
+    // Staying at the dangling nodes leaves all rank there after one step.
+    let result = PageRank::new()
+        .damping_factor(1.)
+        .dangling_nodes(DanglingNodes::SelfLoop)
+        .run(&g, |e| *e.weight());
+    assert_eq!(result.iterations, 2);
+    assert!(result.converged);
+    assert_close(&result.ranks, &[0., 5. / 12., 7. / 12.]);
+
+    // The iteration stops at the maximum number of iterations.
+    let g = DiGraph::<(), f64>::from_edges([(0, 1, 1.), (1, 2, 1.), (2, 0, 1.), (2, 1, 1.)]);
+    let result = PageRank::new().max_iter(3).run(&g, |e| *e.weight());
+    assert_eq!(result.iterations, 3);
+    assert!(!result.converged);
+    let result = PageRank::new().tolerance(1.).run(&g, |e| *e.weight());
+    assert_eq!(result.iterations, 1);
+    assert!(result.converged);
+
+    // An undirected cycle ranks all nodes equally.
+    let g = UnGraph::<(), f64>::from_edges([(0, 1, 1.), (1, 2, 1.), (2, 0, 1.)]);
+    let result = PageRank::new().run(&g, |e| *e.weight());
+    assert_eq!(result.ranks, [1. / 3.; 3]);
+
+    let g = DiGraph::<(), f64>::new();
+    assert!(PageRank::new().run(&g, |e| *e.weight()).ranks.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn page_rank_builder_personalization_length() {
+    let g = DiGraph::<(), f64>::from_edges([(0, 1, 1.)]);
+    PageRank::new()
+        .personalization(vec![1.])
+        .run(&g, |e| *e.weight());
+}
+
+#[test]
+#[should_panic]
+fn page_rank_builder_damping_factor() {
+    PageRank::new().damping_factor(1.5);
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn page_rank_builder_stable_graph() {
+    let mut g =
+        StableDiGraph::<(), f64>::from_edges([(0, 1, 1.), (1, 2, 1.), (2, 3, 1.), (3, 1, 1.)]);
+    g.remove_node(n(0));
+    let result = PageRank::new().run(&g, |e| *e.weight());
+    assert_eq!(result.ranks, [0., 1. / 3., 1. / 3., 1. / 3.]);
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn page_rank_builder_parallel() {
+    let mut rng = ChaChaRng::from_seed([9; 32]);
+    for _ in 0..50 {
+        let node_count = rng.gen_range(1, 12);
+        let g = random_graph::<_, _, Directed>(&mut rng, node_count, 0.2, |_| (), edge_weight);
+        let page_rank = PageRank::new()
+            .personalization(random_distribution(&mut rng, g.node_count()))
+            .dangling_nodes(DanglingNodes::Uniform)
+            .tolerance(1e-12);
+        assert_eq!(
+            page_rank.parallel_run(&g, |e| *e.weight()),
+            page_rank.run(&g, |e| *e.weight())
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use petgraph::{
+    algo::{
+        KuratowskiGraph, KuratowskiSubgraph, PlanarEmbedding, connected_components, is_planar,
+        planar_embedding,
+    },
+    graph::{EdgeIndex, node_index as n},
+    prelude::*,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+fn complete_graph(node_count: usize) -> UnGraph<(), ()> {
+    let mut g = UnGraph::default();
+    for _ in 0..node_count {
+        g.add_node(());
+    }
+    for a in 0..node_count {
+        for b in a + 1..node_count {
+            g.add_edge(n(a), n(b), ());
+        }
+    }
+    g
+}
+
+fn complete_bipartite_graph(left: usize, right: usize) -> UnGraph<(), ()> {
+    let mut g = UnGraph::default();
+    for _ in 0..left + right {
+        g.add_node(());
+    }
+    for a in 0..left {
+        for b in left..left + right {
+            g.add_edge(n(a), n(b), ());
+        }
+    }
+    g
+}
+
+/// Assert that `embedding` is a valid rotation system of `g` that has genus zero.
+fn assert_planar_embedding(g: &UnGraph<(), ()>, embedding: &PlanarEmbedding<NodeIndex, EdgeIndex>) {
+    for v in g.node_indices() {
+        let mut rotation = embedding
+            .rotation(v)
+            .unwrap()
+            .iter()
+            .map(|&(w, e)| {
+                let (a, b) = g.edge_endpoints(e).unwrap();
+                assert!((a, b) == (v, w) || (a, b) == (w, v));
+                e
+            })
+            .collect::<Vec<_>>();
+        rotation.sort();
+        let mut incident = g
+            .edges(v)
+            .flat_map(|e| {
+                let count = if e.source() == e.target() { 2 } else { 1 };
+                core::iter::repeat_n(e.id(), count)
+            })
+            .collect::<Vec<_>>();
+        incident.sort();
+        assert_eq!(rotation, incident);
+    }
+
+    let faces = embedding.faces();
+    assert_eq!(
+        faces.iter().map(|f| f.len()).sum::<usize>(),
+        2 * g.edge_count()
+    );
+    // Euler's formula, summed over the components. Isolated nodes have no faces.
+    let isolated = g
+        .node_indices()
+        .filter(|&v| g.edges(v).next().is_none())
+        .count();
+    let components = connected_components(g);
+    assert_eq!(
+        g.node_count() + faces.len(),
+        g.edge_count() + 2 * components - isolated,
+        "not a planar embedding"
+    );
+}
+
+/// Assert that `witness` is a subdivision of K5 or K3,3 in `g`.
+fn assert_kuratowski_subgraph(
+    g: &UnGraph<(), ()>,
+    witness: &KuratowskiSubgraph<NodeIndex, EdgeIndex>,
+) {
+    let branch = witness.branch_nodes();
+    let (branch_count, branch_degree) = match witness.kind() {
+        KuratowskiGraph::K5 => (5, 4),
+        KuratowskiGraph::K33 => (6, 3),
+    };
+    assert_eq!(branch.len(), branch_count);
+
+    let mut edges = witness.edges().to_vec();
+    edges.sort();
+    edges.dedup();
+    assert_eq!(edges.len(), witness.edges().len(), "duplicate edges");
+    let mut adjacency = vec![Vec::new(); g.node_count()];
+    for &e in &edges {
+        let (a, b) = g.edge_endpoints(e).unwrap();
+        assert_ne!(a, b);
+        adjacency[a.index()].push(b.index());
+        adjacency[b.index()].push(a.index());
+    }
+    for (v, neighbors) in adjacency.iter().enumerate() {
+        if branch.contains(&n(v)) {
+            assert_eq!(neighbors.len(), branch_degree);
+        } else {
+            assert!(neighbors.is_empty() || neighbors.len() == 2);
+        }
+    }
+
+    // Follow the paths between the branch nodes.
+    let mut path_edges = 0;
+    let mut paths = Vec::new();
+    for &start in branch {
+        for &next in &adjacency[start.index()] {
+            let (mut previous, mut current) = (start.index(), next);
+            path_edges += 1;
+            while !branch.contains(&n(current)) {
+                let following = *adjacency[current].iter().find(|&&w| w != previous).unwrap();
+                (previous, current) = (current, following);
+                path_edges += 1;
+            }
+            paths.push((start, n(current)));
+        }
+    }
+    // Every path was followed from both ends, and together they cover all edges.
+    assert_eq!(path_edges, 2 * edges.len());
+    paths.retain(|&(a, b)| a < b);
+    paths.sort();
+    let mut expected = Vec::new();
+    for i in 0..branch_count {
+        for j in i + 1..branch_count {
+            let (a, b) = (branch[i], branch[j]);
+            if witness.kind() == KuratowskiGraph::K5 || (i < 3) != (j < 3) {
+                expected.push((a.min(b), a.max(b)));
+            }
+        }
+    }
+    expected.sort();
+    assert_eq!(paths, expected);
+}
+
+fn assert_planarity(g: &UnGraph<(), ()>) -> bool {
+    let planar = is_planar(g);
+    match planar_embedding(g) {
+        Ok(embedding) => {
+            assert!(planar);
+            assert_planar_embedding(g, &embedding);
+        }
+        Err(witness) => {
+            assert!(!planar);
+            assert_kuratowski_subgraph(g, &witness);
+        }
+    }
+    planar
+}
+
+#[test]
+fn planarity_small_graphs() {
+    assert!(assert_planarity(&UnGraph::default()));
+    assert!(assert_planarity(&complete_graph(1)));
+    assert!(assert_planarity(&complete_graph(4)));
+    assert!(!assert_planarity(&complete_graph(5)));
+    assert!(!assert_planarity(&complete_graph(6)));
+    assert!(assert_planarity(&complete_bipartite_graph(2, 5)));
+    assert!(!assert_planarity(&complete_bipartite_graph(3, 3)));
+
+    let witness = planar_embedding(&complete_graph(5)).unwrap_err();
+    assert_eq!(witness.kind(), KuratowskiGraph::K5);
+    assert_eq!(witness.edges().len(), 10);
+    let witness = planar_embedding(&complete_bipartite_graph(3, 3)).unwrap_err();
+    assert_eq!(witness.kind(), KuratowskiGraph::K33);
+    let mut sides = [&witness.branch_nodes()[..3], &witness.branch_nodes()[3..]];
+    sides.sort();
+    assert_eq!(sides, [[n(0), n(1), n(2)], [n(3), n(4), n(5)]]);
+}
+
+#[test]
+fn planarity_petersen() {
+    // The Petersen graph contains a subdivision of K3,3 but not of K5.
+    let mut g = UnGraph::<(), ()>::from_edges((0..5).map(|i| (i, (i + 1) % 5)));
+    g.extend_with_edges((0..5).map(|i| (i, i + 5)));
+    g.extend_with_edges((0..5).map(|i| (i + 5, (i + 2) % 5 + 5)));
+    assert!(!assert_planarity(&g));
+    assert_eq!(
+        planar_embedding(&g).unwrap_err().kind(),
+        KuratowskiGraph::K33
+    );
+}
+
+#[test]
+fn planarity_multigraph() {
+    // A triangle with parallel edges and self-loops.
+    let mut g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (0, 1), (1, 0), (2, 2)]);
+    g.add_edge(n(2), n(2), ());
+    g.add_node(());
+    let embedding = planar_embedding(&g).unwrap();
+    assert_planar_embedding(&g, &embedding);
+    // Two digons between 0 and 1, and one face in each self-loop.
+    assert_eq!(embedding.faces().len(), 6);
+
+    // Parallel edges do not make K3,3 planar, and are not part of the witness.
+    let mut g = complete_bipartite_graph(3, 3);
+    g.add_edge(n(0), n(3), ());
+    g.add_edge(n(4), n(4), ());
+    let witness = planar_embedding(&g).unwrap_err();
+    assert_kuratowski_subgraph(&g, &witness);
+    assert_eq!(witness.edges().len(), 9);
+}
+
+/// Generate a random planar graph by stacking nodes into the faces of a triangulation, and
+/// removing edges.
+fn random_planar_graph(rng: &mut ChaChaRng, node_count: usize) -> UnGraph<(), ()> {
+    let mut g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+    let mut faces = vec![[0u32, 1, 2], [0, 2, 1]];
+    for v in 3..node_count.max(3) as u32 {
+        let [a, b, c] = faces.swap_remove(rng.gen_range(0, faces.len()));
+        g.extend_with_edges([(v, a), (v, b), (v, c)]);
+        faces.extend([[a, b, v], [b, c, v], [c, a, v]]);
+    }
+    g.retain_edges(|_, _| rng.gen_bool(0.85));
+    g
+}
+
+#[test]
+fn planarity_random_planar() {
+    let mut rng = ChaChaRng::from_seed([4; 32]);
+    for _ in 0..300 {
+        let node_count = rng.gen_range(3, 40);
+        let mut g = random_planar_graph(&mut rng, node_count);
+        // Shuffle the node order, which changes the depth-first search.
+        let mut permutation = (0..g.node_count()).collect::<Vec<_>>();
+        rng.shuffle(&mut permutation);
+        let mut h = UnGraph::<(), ()>::default();
+        for _ in 0..g.node_count() {
+            h.add_node(());
+        }
+        for e in g.edge_references() {
+            h.add_edge(
+                n(permutation[e.source().index()]),
+                n(permutation[e.target().index()]),
+                (),
+            );
+        }
+        assert!(assert_planarity(&h));
+
+        // An edge between two far away nodes of a triangulation usually makes it non-planar.
+        g.add_edge(n(0), n(node_count - 1), ());
+        assert_planarity(&g);
+    }
+}
+
+#[test]
+fn planarity_random() {
+    let mut rng = ChaChaRng::from_seed([6; 32]);
+    let mut planar = 0;
+    for _ in 0..1000 {
+        let node_count = rng.gen_range(1, 13);
+        let mut g = UnGraph::<(), ()>::default();
+        for _ in 0..node_count {
+            g.add_node(());
+        }
+        let edge_count = rng.gen_range(0, 4 * node_count);
+        for _ in 0..edge_count {
+            let (a, b) = (rng.gen_range(0, node_count), rng.gen_range(0, node_count));
+            g.add_edge(n(a), n(b), ());
+        }
+        if assert_planarity(&g) {
+            planar += 1;
+        }
+    }
+    assert!(planar > 100 && planar < 900, "{planar} planar graphs");
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn planarity_stable_graph() {
+    let mut g = StableUnGraph::<(), ()>::default();
+    let nodes = (0..6).map(|_| g.add_node(())).collect::<Vec<_>>();
+    for i in 0..6 {
+        for j in i + 1..6 {
+            g.add_edge(nodes[i], nodes[j], ());
+        }
+    }
+    assert!(!is_planar(&g));
+    let witness = planar_embedding(&g).unwrap_err();
+    assert!(witness.branch_nodes().len() >= 5);
+
+    g.remove_node(nodes[1]);
+    g.remove_node(nodes[4]);
+    assert!(is_planar(&g));
+    let embedding = planar_embedding(&g).unwrap();
+    assert!(embedding.rotation(nodes[1]).is_none());
+    assert_eq!(embedding.rotation(nodes[0]).unwrap().len(), 3);
+    assert_eq!(embedding.faces().len(), 4);
+}
@@ -23,8 +23,6 @@ use itertools::{assert_equal, cloned};
 use odds::prelude::*;
 #[cfg(feature = "rayon")]
 use petgraph::algo::parallel_johnson;
-#[cfg(feature = "stable_graph")]
-use petgraph::algo::steiner_tree;
 use petgraph::{
     EdgeType,
     algo::{
@@ -33,7 +31,7 @@ use petgraph::{
         ford_fulkerson, greedy_feedback_arc_set, greedy_matching, is_cyclic_directed,
         is_cyclic_undirected, is_isomorphic, is_isomorphic_matching, johnson, k_shortest_path,
         kosaraju_scc, maximal_cliques as maximal_cliques_algo, maximum_matching, min_spanning_tree,
-        page_rank, spfa, tarjan_scc, toposort,
+        page_rank, scc_condensation, spfa, steiner_tree, tarjan_scc, toposort,
     },
     data::FromElements,
     dot::{Config, Dot},
@@ -573,6 +571,24 @@ fn graph_condensation_acyclic() {
     quickcheck::quickcheck(prop as fn(_) -> bool);
 }
 
+#[test]
+fn graph_scc_condensation_topological() {
+    fn prop(g: Graph<(), ()>) -> bool {
+        let cond = scc_condensation(&g);
+        let dag = cond.graph();
+        let sccs = tarjan_scc(&g);
+        dag.node_count() == sccs.len()
+            && dag
+                .edge_references()
+                .all(|edge| edge.source() < edge.target())
+            && g.edge_references().all(|edge| {
+                let (a, b) = (cond.scc_of(edge.source()), cond.scc_of(edge.target()));
+                a == b || dag.contains_edge(a.unwrap(), b.unwrap())
+            })
+    }
+    quickcheck::quickcheck(prop as fn(_) -> bool);
+}
+
 #[derive(Debug, Clone)]
 struct Dag<N: Default + Clone + Send + 'static>(Graph<N, ()>);
 
@@ -1625,7 +1641,6 @@ quickcheck! {
     }
 }
 
-#[cfg(feature = "stable_graph")]
 #[test]
 fn steiner_tree_spans_terminals() {
     fn prop(g: UnGraph<(), u32>) -> bool {
@@ -1675,9 +1690,18 @@ fn steiner_tree_spans_terminals() {
                 );
 
                 let terminals = g.node_indices().take(5).collect::<Vec<_>>();
-                let m_steiner_tree = steiner_tree(&g, &terminals);
-
-                let steiner_tree_nodes: Vec<NodeIndex> = m_steiner_tree.node_indices().collect();
+                let Some((m_steiner_tree, _)) = steiner_tree(&g, &terminals, |e| *e.weight())
+                else {
+                    return false; // The terminals of a component are connected
+                };
+
+                let steiner_tree_nodes: Vec<NodeIndex> = m_steiner_tree
+                    .iter()
+                    .flat_map(|&e| {
+                        let (a, b) = g.edge_endpoints(e).unwrap();
+                        [a, b]
+                    })
+                    .collect();
 
                 let spans_terminals = terminals.iter().all(|&t| steiner_tree_nodes.contains(&t));
 
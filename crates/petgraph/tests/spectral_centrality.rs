@@ -0,0 +1,203 @@
+#[path = "utils/random.rs"]
+mod random;
+
+use petgraph::{
+    EdgeType,
+    algo::{eigenvector_centrality, hits, katz_centrality},
+    graph::node_index as n,
+    prelude::*,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::assert_close;
+
+/// A random connected graph: a cycle through all nodes plus random chords.
+fn random_connected_graph<Ty: EdgeType>(rng: &mut ChaChaRng) -> Graph<(), f64, Ty> {
+    let node_count = rng.gen_range(2, 12);
+    let mut g = Graph::with_capacity(node_count, 0);
+    for _ in 0..node_count {
+        g.add_node(());
+    }
+    for a in 0..node_count {
+        g.add_edge(n(a), n((a + 1) % node_count), rng.gen_range(0.5, 2.));
+        for b in 0..node_count {
+            if a != b && rng.gen_bool(0.2) {
+                g.add_edge(n(a), n(b), rng.gen_range(0.5, 2.));
+            }
+        }
+    }
+    g
+}
+
+/// The weighted in-neighbor sums `Aᵀx`, or the out-neighbor sums `Ax` if not `transpose`.
+fn multiply<Ty: EdgeType>(g: &Graph<(), f64, Ty>, x: &[f64], transpose: bool) -> Vec<f64> {
+    let mut y = vec![0.; x.len()];
+    for e in g.edge_references() {
+        let (mut a, mut b) = (e.source().index(), e.target().index());
+        if !transpose {
+            (a, b) = (b, a);
+        }
+        y[b] += e.weight() * x[a];
+        if !g.is_directed() && a != b {
+            y[a] += e.weight() * x[b];
+        }
+    }
+    y
+}
+
+/// Check that `x` is an eigenvector of `Aᵀ` or `A`, scaled to sum to one.
+fn assert_eigenvector<Ty: EdgeType>(g: &Graph<(), f64, Ty>, x: &[f64], transpose: bool) {
+    assert!((x.iter().sum::<f64>() - 1.).abs() < 1e-9);
+    let y = multiply(g, x, transpose);
+    let eigenvalue = y.iter().sum::<f64>();
+    let scaled = x.iter().map(|v| v * eigenvalue).collect::<Vec<_>>();
+    assert_close(&y, &scaled);
+}
+
+#[test]
+fn eigenvector_centrality_known_graphs() {
+    // A directed cycle ranks all nodes equally.
+    let g = DiGraph::<(), f64>::from_edges([(0, 1, 1.), (1, 2, 1.), (2, 0, 1.)]);
+    assert_close(
+        &eigenvector_centrality(&g, 100, None, |e| *e.weight()),
+        &[1. / 3.; 3],
+    );
+
+    // Doubling every weight does not change the scores.
+    let g = UnGraph::<(), f64>::from_edges([(0, 1, 1.), (1, 2, 3.), (2, 0, 2.), (2, 3, 1.)]);
+    let centrality = eigenvector_centrality(&g, 1000, Some(1e-12), |e| *e.weight());
+    assert_close(
+        &eigenvector_centrality(&g, 1000, Some(1e-12), |e| 2. * e.weight()),
+        &centrality,
+    );
+    assert_eigenvector(&g, &centrality, true);
+
+    // Unweighted, the path a - b - c ranks `b` √2 times higher than its neighbors.
+    let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+    let centrality = eigenvector_centrality(&g, 1000, Some(1e-12), |_| 1.);
+    let middle = 2f64.sqrt() / (2f64.sqrt() + 2.);
+    assert_close(
+        &centrality,
+        &[(1. - middle) / 2., middle, (1. - middle) / 2.],
+    );
+
+    let g = DiGraph::<(), f64>::new();
+    assert!(eigenvector_centrality(&g, 10, None, |e| *e.weight()).is_empty());
+}
+
+#[test]
+fn eigenvector_centrality_random() {
+    let mut rng = ChaChaRng::from_seed([7; 32]);
+    for _ in 0..50 {
+        let g = random_connected_graph::<Directed>(&mut rng);
+        let centrality = eigenvector_centrality(&g, 100_000, Some(1e-14), |e| *e.weight());
+        assert_eigenvector(&g, &centrality, true);
+
+        let g = random_connected_graph::<Undirected>(&mut rng);
+        let centrality = eigenvector_centrality(&g, 100_000, Some(1e-14), |e| *e.weight());
+        assert_eigenvector(&g, &centrality, true);
+    }
+}
+
+#[test]
+fn katz_centrality_known_graphs() {
+    // With `alpha` zero, every node has the base score.
+    let g = DiGraph::<(), f64>::from_edges([(0, 1, 1.), (1, 2, 1.), (2, 0, 1.)]);
+    assert_eq!(
+        katz_centrality(&g, 0., 2., 100, None, |e| *e.weight()),
+        [2.; 3]
+    );
+
+    // In a directed cycle, every node scores `beta / (1 - alpha)`.
+    assert_close(
+        &katz_centrality(&g, 0.5, 1., 1000, Some(1e-12), |e| *e.weight()),
+        &[2.; 3],
+    );
+
+    // A star: the center scores `1 + 3α` and the leaves `1 + α`, up to longer walks.
+    let g = UnGraph::<(), f64>::from_edges([(0, 1, 1.), (0, 2, 1.), (0, 3, 1.)]);
+    let alpha = 0.1;
+    let centrality = katz_centrality(&g, alpha, 1., 1000, Some(1e-12), |e| *e.weight());
+    // Solve x₀ = 1 + 3αx₁, x₁ = 1 + αx₀.
+    let leaf = (1. + alpha) / (1. - 3. * alpha * alpha);
+    let center = 1. + 3. * alpha * leaf;
+    assert_close(&centrality, &[center, leaf, leaf, leaf]);
+}
+
+#[test]
+fn katz_centrality_random() {
+    let mut rng = ChaChaRng::from_seed([11; 32]);
+    for _ in 0..50 {
+        let g = random_connected_graph::<Directed>(&mut rng);
+        // The largest eigenvalue is at most the largest weighted in-degree.
+        let bound = multiply(&g, &vec![1.; g.node_count()], true)
+            .into_iter()
+            .fold(0., f64::max);
+        let alpha = 0.9 / bound;
+        let x = katz_centrality(&g, alpha, 1., 100_000, Some(1e-13), |e| *e.weight());
+        let expected = multiply(&g, &x, true)
+            .into_iter()
+            .map(|y| alpha * y + 1.)
+            .collect::<Vec<_>>();
+        assert_close(&x, &expected);
+    }
+}
+
+#[test]
+fn hits_known_graphs() {
+    // In an undirected graph, hubs and authorities agree with each other.
+    let g = UnGraph::<(), f64>::from_edges([(0, 1, 1.), (1, 2, 1.), (2, 3, 1.), (3, 1, 1.)]);
+    let (hubs, authorities) = hits(&g, 1000, Some(1e-12), |e| *e.weight());
+    assert_close(&hubs, &authorities);
+
+    // Nodes without out-edges are no hubs, and nodes without in-edges are no authorities.
+    let g = DiGraph::<(), f64>::from_edges([(0, 1, 1.), (0, 2, 1.), (3, 1, 1.), (3, 2, 1.)]);
+    let (hubs, authorities) = hits(&g, 1000, None, |e| *e.weight());
+    assert_close(&hubs, &[0.5, 0., 0., 0.5]);
+    assert_close(&authorities, &[0., 0.5, 0.5, 0.]);
+
+    // Without edges, there are no hubs or authorities.
+    let mut g = DiGraph::<(), f64>::new();
+    g.add_node(());
+    g.add_node(());
+    assert_eq!(
+        hits(&g, 10, None, |e| *e.weight()),
+        (vec![0.; 2], vec![0.; 2])
+    );
+}
+
+#[test]
+fn hits_random() {
+    let mut rng = ChaChaRng::from_seed([13; 32]);
+    for _ in 0..50 {
+        let g = random_connected_graph::<Directed>(&mut rng);
+        let (hubs, authorities) = hits(&g, 100_000, Some(1e-14), |e| *e.weight());
+        // The hubs are `A` times the authorities, and the authorities `Aᵀ` times the hubs.
+        let mut expected_hubs = multiply(&g, &authorities, false);
+        let sum = expected_hubs.iter().sum::<f64>();
+        expected_hubs.iter_mut().for_each(|h| *h /= sum);
+        assert_close(&hubs, &expected_hubs);
+        let mut expected_authorities = multiply(&g, &hubs, true);
+        let sum = expected_authorities.iter().sum::<f64>();
+        expected_authorities.iter_mut().for_each(|a| *a /= sum);
+        assert_close(&authorities, &expected_authorities);
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn spectral_centrality_stable_graph() {
+    let mut g =
+        StableDiGraph::<(), f64>::from_edges([(0, 1, 1.), (1, 2, 1.), (2, 3, 1.), (3, 1, 1.)]);
+    g.remove_node(n(0));
+    assert_close(
+        &eigenvector_centrality(&g, 100, None, |e| *e.weight()),
+        &[0., 1. / 3., 1. / 3., 1. / 3.],
+    );
+    assert_close(
+        &katz_centrality(&g, 0., 1., 100, None, |e| *e.weight()),
+        &[0., 1., 1., 1.],
+    );
+    let (hubs, authorities) = hits(&g, 100, None, |e| *e.weight());
+    assert_close(&hubs, &[0., 1. / 3., 1. / 3., 1. / 3.]);
+    assert_close(&authorities, &[0., 1. / 3., 1. / 3., 1. / 3.]);
+}
@@ -1,12 +1,15 @@
-#[cfg(feature = "stable_graph")]
-#[cfg(test)]
+#[path = "utils/random.rs"]
+mod random;
+
+use hashbrown::HashSet;
 use petgraph::{
     Graph, Undirected,
-    graph::{NodeIndex, UnGraph},
+    algo::{Measure, steiner_tree, steiner_tree_dreyfus_wagner, steiner_tree_mehlhorn},
+    graph::{EdgeIndex, NodeIndex, UnGraph, node_index as n},
 };
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::random_multigraph;
 
-#[cfg(feature = "stable_graph")]
-#[cfg(test)]
 fn b01_example() -> (UnGraph<(), i32>, Vec<NodeIndex>) {
     // Implementing b01 case from Vienna test set B
     let mut graph = Graph::<(), i32, Undirected>::new_undirected();
@@ -137,8 +140,6 @@ fn b01_example() -> (UnGraph<(), i32>, Vec<NodeIndex>) {
     (graph, terminals)
 }
 
-#[cfg(feature = "stable_graph")]
-#[cfg(test)]
 fn b07_example() -> (UnGraph<(), i32>, Vec<NodeIndex>) {
     // Implementing b07 case from Vienna test set B
     let mut graph = Graph::<(), i32, Undirected>::new_undirected();
@@ -328,8 +329,6 @@ fn b07_example() -> (UnGraph<(), i32>, Vec<NodeIndex>) {
     (graph, terminals)
 }
 
-#[cfg(feature = "stable_graph")]
-#[cfg(test)]
 fn example_kou_paper() -> (UnGraph<(), usize>, Vec<NodeIndex>) {
     let mut graph = Graph::<(), usize, Undirected>::new_undirected();
     // Add nodes
@@ -360,51 +359,218 @@ fn example_kou_paper() -> (UnGraph<(), usize>, Vec<NodeIndex>) {
     (graph, vec![nodes[0], nodes[1], nodes[2], nodes[3]])
 }
 
-#[cfg(feature = "stable_graph")]
-#[cfg(test)]
-mod test {
-    use petgraph::{
-        algo::{connected_components, steiner_tree},
-        graph::UnGraph,
-    };
+type SteinerTreeFn<E> = fn(&UnGraph<(), E>, &[NodeIndex]) -> Option<(HashSet<EdgeIndex>, E)>;
 
-    use crate::{b01_example, b07_example, example_kou_paper};
-
-    #[test]
-    fn b01_vienna_test() {
-        let (graph, terminals) = b01_example();
-        let st = steiner_tree(&graph, &terminals);
-        let weights = st.edge_weights().cloned().sum::<i32>();
-        let steiner_tree_nodes: Vec<_> = st.node_indices().collect();
-        assert!(terminals.iter().all(|&t| steiner_tree_nodes.contains(&t)));
-        assert!(st.edge_count() == st.node_count() - 1);
-        assert_eq!(connected_components(&UnGraph::from(st)), 1);
-        assert_eq!(weights, 82);
+fn solvers<E: Measure + Copy>() -> [SteinerTreeFn<E>; 3] {
+    [
+        |g, t| steiner_tree(g, t, |e| *e.weight()),
+        |g, t| steiner_tree_mehlhorn(g, t, |e| *e.weight()),
+        |g, t| steiner_tree_dreyfus_wagner(g, t, |e| *e.weight()),
+    ]
+}
+
+/// Check that `tree` is a tree of `graph` that connects the `terminals`, whose leaves are
+/// terminals, and return its cost.
+fn check_steiner_tree<E>(
+    graph: &UnGraph<(), E>,
+    terminals: &[NodeIndex],
+    tree: &HashSet<EdgeIndex>,
+) -> E
+where
+    E: Copy + Default + core::ops::Add<Output = E>,
+{
+    let mut degree = vec![0; graph.node_count()];
+    let mut components = petgraph::unionfind::UnionFind::<usize>::new(graph.node_count());
+    let mut cost = E::default();
+    for &e in tree {
+        let (a, b) = graph.edge_endpoints(e).unwrap();
+        assert!(
+            components.union(a.index(), b.index()),
+            "the tree has a cycle"
+        );
+        degree[a.index()] += 1;
+        degree[b.index()] += 1;
+        cost = cost + graph[e];
+    }
+    for &t in terminals {
+        assert!(components.equiv(t.index(), terminals[0].index()));
     }
+    for v in graph.node_indices() {
+        if degree[v.index()] > 0 {
+            assert!(components.equiv(v.index(), terminals[0].index()));
+        }
+        if degree[v.index()] == 1 {
+            assert!(terminals.contains(&v), "non-terminal leaf {v:?}");
+        }
+    }
+    cost
+}
+
+#[test]
+fn b01_vienna_test() {
+    let (graph, terminals) = b01_example();
+    for solver in solvers() {
+        let (tree, cost) = solver(&graph, &terminals).unwrap();
+        assert_eq!(check_steiner_tree(&graph, &terminals, &tree), cost);
+        assert!(cost <= 2 * 82);
+    }
+    let (_, cost) = steiner_tree(&graph, &terminals, |e| *e.weight()).unwrap();
+    assert_eq!(cost, 82);
+    let (_, cost) = steiner_tree_dreyfus_wagner(&graph, &terminals, |e| *e.weight()).unwrap();
+    assert_eq!(cost, 82);
+}
 
-    #[test]
-    fn b07_vienna_test() {
-        let (graph, terminals) = b07_example();
-        let st = steiner_tree(&graph, &terminals);
-
-        let weights = st.edge_weights().cloned().sum::<i32>();
-        let steiner_tree_nodes: Vec<_> = st.node_indices().collect();
-        assert!(terminals.iter().all(|&t| steiner_tree_nodes.contains(&t)));
-        assert!(st.edge_count() == st.node_count() - 1);
-        assert_eq!(connected_components(&UnGraph::from(st)), 1);
-        assert_eq!(weights, 111);
+#[test]
+fn b07_vienna_test() {
+    let (graph, terminals) = b07_example();
+    for solver in solvers() {
+        let (tree, cost) = solver(&graph, &terminals).unwrap();
+        assert_eq!(check_steiner_tree(&graph, &terminals, &tree), cost);
+        assert!(cost <= 2 * 111);
     }
+    let (_, cost) = steiner_tree(&graph, &terminals, |e| *e.weight()).unwrap();
+    assert_eq!(cost, 111);
+    let (_, cost) = steiner_tree_dreyfus_wagner(&graph, &terminals, |e| *e.weight()).unwrap();
+    assert_eq!(cost, 111);
+}
+
+#[test]
+fn example_kous_paper() {
+    let (graph, terminals) = example_kou_paper();
+    for solver in solvers() {
+        let (tree, cost) = solver(&graph, &terminals).unwrap();
+        assert_eq!(check_steiner_tree(&graph, &terminals, &tree), cost);
+    }
+    let (tree, cost) = steiner_tree(&graph, &terminals, |e| *e.weight()).unwrap();
+    assert_eq!(cost, 8);
+    assert_eq!(tree.len(), 8);
+}
 
-    #[test]
-    fn example_kous_paper() {
-        let (graph, terminals) = example_kou_paper();
-        let st = steiner_tree(&graph, &terminals);
-
-        let weights = st.edge_weights().cloned().sum::<usize>();
-        let steiner_tree_nodes: Vec<_> = st.node_indices().collect();
-        assert!(terminals.iter().all(|&t| steiner_tree_nodes.contains(&t)));
-        assert!(st.edge_count() == st.node_count() - 1);
-        assert_eq!(connected_components(&UnGraph::from(st)), 1);
-        assert_eq!(weights, 8);
+#[test]
+fn steiner_tree_trivial_and_disconnected() {
+    let mut graph = UnGraph::<(), u32>::from_edges([(0, 1, 1), (1, 2, 1), (3, 4, 1)]);
+    graph.add_node(());
+    for solver in solvers() {
+        assert_eq!(solver(&graph, &[]), Some((HashSet::new(), 0)));
+        assert_eq!(solver(&graph, &[n(5)]), Some((HashSet::new(), 0)));
+        assert_eq!(solver(&graph, &[n(0), n(3)]), None);
+        assert_eq!(solver(&graph, &[n(0), n(5)]), None);
+        // Repeated terminals.
+        let (tree, cost) = solver(&graph, &[n(2), n(0), n(2)]).unwrap();
+        assert_eq!(cost, 2);
+        assert_eq!(tree.len(), 2);
     }
 }
+
+/// Find the cost of a minimum Steiner tree by trying all sets of edges.
+fn brute_force(graph: &UnGraph<(), u32>, terminals: &[NodeIndex]) -> Option<u32> {
+    let mut best = None;
+    for subset in 0..1usize << graph.edge_count() {
+        let mut components = petgraph::unionfind::UnionFind::<usize>::new(graph.node_count());
+        let mut cost = 0;
+        for e in graph.edge_indices() {
+            if subset >> e.index() & 1 == 1 {
+                let (a, b) = graph.edge_endpoints(e).unwrap();
+                components.union(a.index(), b.index());
+                cost += graph[e];
+            }
+        }
+        if terminals
+            .iter()
+            .all(|t| components.equiv(t.index(), terminals[0].index()))
+            && best.is_none_or(|best| cost < best)
+        {
+            best = Some(cost);
+        }
+    }
+    best
+}
+
+#[test]
+fn steiner_tree_random() {
+    let mut rng = ChaChaRng::from_seed([30; 32]);
+    for _ in 0..300 {
+        let node_count = rng.gen_range(1, 8);
+        let edge_count = rng.gen_range(0, 12);
+        // Include edges of cost zero.
+        let graph: UnGraph<(), u32> = random_multigraph(
+            &mut rng,
+            node_count,
+            edge_count,
+            |_| (),
+            |rng| rng.gen_range(0, 8),
+        );
+        let terminal_count = rng.gen_range(1, graph.node_count() + 1);
+        let terminals = (0..terminal_count)
+            .map(|_| n(rng.gen_range(0, graph.node_count())))
+            .collect::<Vec<_>>();
+        let optimum = brute_force(&graph, &terminals);
+        for (i, solver) in solvers().into_iter().enumerate() {
+            let result = solver(&graph, &terminals);
+            assert_eq!(result.is_some(), optimum.is_some());
+            if let (Some((tree, cost)), Some(optimum)) = (result, optimum) {
+                assert_eq!(check_steiner_tree(&graph, &terminals, &tree), cost);
+                assert!(cost <= 2 * optimum);
+                if i == 2 {
+                    assert_eq!(cost, optimum);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn steiner_tree_edge_cost() {
+    // Costs from a closure instead of the weights: a path of cheap edges and a direct edge.
+    let graph = UnGraph::<&str, &str>::from_edges([
+        (0, 1, "road"),
+        (1, 2, "road"),
+        (2, 3, "road"),
+        (0, 3, "ferry"),
+    ]);
+    let cost = |e: petgraph::graph::EdgeReference<&str>| match *e.weight() {
+        "road" => 1.0,
+        _ => 2.5,
+    };
+    let (tree, total) = steiner_tree_dreyfus_wagner(&graph, &[n(0), n(3)], cost).unwrap();
+    assert_eq!(total, 2.5);
+    assert_eq!(
+        tree,
+        HashSet::from_iter([graph.find_edge(n(0), n(3)).unwrap()])
+    );
+    let (tree, total) = steiner_tree_mehlhorn(&graph, &[n(0), n(2), n(3)], cost).unwrap();
+    assert_eq!(total, 3.0);
+    assert_eq!(tree.len(), 3);
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn steiner_tree_stable_graph() {
+    use petgraph::stable_graph::StableUnGraph;
+
+    let mut graph = StableUnGraph::<(), u32>::from_edges([
+        (0, 1, 1),
+        (1, 2, 1),
+        (0, 3, 1),
+        (3, 2, 1),
+        (1, 3, 5),
+    ]);
+    graph.remove_node(n(1));
+    for (tree, cost) in [
+        steiner_tree(&graph, &[n(0), n(2)], |e| *e.weight()).unwrap(),
+        steiner_tree_mehlhorn(&graph, &[n(0), n(2)], |e| *e.weight()).unwrap(),
+        steiner_tree_dreyfus_wagner(&graph, &[n(0), n(2)], |e| *e.weight()).unwrap(),
+    ] {
+        assert_eq!(cost, 2);
+        assert_eq!(tree.len(), 2);
+        assert!(tree.iter().all(|&e| graph.edge_weight(e).is_some()));
+    }
+}
+
+#[test]
+#[should_panic(expected = "steiner_tree_dreyfus_wagner supports at most 16 terminals")]
+fn dreyfus_wagner_too_many_terminals() {
+    let graph = UnGraph::<(), u32>::from_edges((0..16).map(|i| (i, i + 1, 1)));
+    let terminals = (0..=16).map(n).collect::<Vec<_>>();
+    steiner_tree_dreyfus_wagner(&graph, &terminals, |e| *e.weight());
+}
@@ -0,0 +1,181 @@
+use petgraph::{
+    algo::{
+        all_toposorts, kosaraju_scc, topological_generations, toposort, toposort_by,
+        toposort_by_key,
+    },
+    graph::node_index as n,
+    prelude::*,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+/// A random directed graph, which is acyclic unless `cycles` is set.
+fn random_graph(rng: &mut ChaChaRng, max_nodes: usize, cycles: bool) -> DiGraph<u32, ()> {
+    let node_count = rng.gen_range(0, max_nodes + 1);
+    let mut g = DiGraph::with_capacity(node_count, 0);
+    for _ in 0..node_count {
+        g.add_node(rng.gen_range(0, 4));
+    }
+    // Edges go between the nodes in a random order, so that the node order is no topological
+    // order in general.
+    let mut rank = (0..node_count).collect::<Vec<_>>();
+    rng.shuffle(&mut rank);
+    let density = rng.gen_range(0.1, 0.5);
+    for a in 0..node_count {
+        for b in 0..node_count {
+            if rng.gen_bool(density) && (rank[a] < rank[b] || cycles && rng.gen_bool(0.1)) {
+                g.add_edge(n(a), n(b), ());
+            }
+        }
+    }
+    g
+}
+
+fn is_toposort(g: &DiGraph<u32, ()>, order: &[NodeIndex]) -> bool {
+    let mut position = vec![usize::MAX; g.node_count()];
+    for (i, v) in order.iter().enumerate() {
+        position[v.index()] = i;
+    }
+    order.len() == g.node_count()
+        && position.iter().all(|&i| i != usize::MAX)
+        && g.edge_references()
+            .all(|e| position[e.source().index()] < position[e.target().index()])
+}
+
+/// All permutations of the nodes that are topological orders, in lexicographic order.
+fn brute_force(g: &DiGraph<u32, ()>) -> Vec<Vec<NodeIndex>> {
+    fn search(g: &DiGraph<u32, ()>, order: &mut Vec<NodeIndex>, orders: &mut Vec<Vec<NodeIndex>>) {
+        if order.len() == g.node_count() {
+            if is_toposort(g, order) {
+                orders.push(order.clone());
+            }
+            return;
+        }
+        for v in g.node_indices() {
+            if !order.contains(&v) {
+                order.push(v);
+                search(g, order, orders);
+                order.pop();
+            }
+        }
+    }
+    let mut orders = Vec::new();
+    search(g, &mut Vec::new(), &mut orders);
+    orders
+}
+
+/// Check that `v` is on a cycle.
+fn on_cycle(g: &DiGraph<u32, ()>, v: NodeIndex) -> bool {
+    g.contains_edge(v, v)
+        || kosaraju_scc(g)
+            .iter()
+            .any(|component| component.len() > 1 && component.contains(&v))
+}
+
+#[test]
+fn toposort_known_graph() {
+    let empty = DiGraph::<u32, ()>::new();
+    assert_eq!(toposort_by_key(&empty, |v| v), Ok(Vec::new()));
+    assert_eq!(topological_generations(&empty), Ok(Vec::new()));
+    assert_eq!(all_toposorts(&empty).collect::<Vec<_>>(), [Vec::new()]);
+
+    // Diamond 0 -> {1, 2} -> 3, and 4 -> 3.
+    let mut g = DiGraph::<u32, ()>::from_edges([(0, 1), (0, 2), (1, 3), (2, 3), (4, 3)]);
+    assert_eq!(
+        toposort_by_key(&g, |v| v).unwrap(),
+        [n(0), n(1), n(2), n(4), n(3)]
+    );
+    assert_eq!(
+        toposort_by(&g, |a, b| b.cmp(&a)).unwrap(),
+        [n(4), n(0), n(2), n(1), n(3)]
+    );
+    assert_eq!(
+        topological_generations(&g).unwrap(),
+        [vec![n(0), n(4)], vec![n(1), n(2)], vec![n(3)]]
+    );
+    assert_eq!(all_toposorts(&g).count(), 8);
+
+    g.add_edge(n(4), n(4), ());
+    assert_eq!(toposort_by_key(&g, |v| v).unwrap_err().node_id(), n(4));
+    assert_eq!(topological_generations(&g).unwrap_err().node_id(), n(4));
+    assert_eq!(all_toposorts(&g).next(), None);
+}
+
+#[test]
+fn toposort_random() {
+    let mut rng = ChaChaRng::from_seed([35; 32]);
+    for _ in 0..300 {
+        let cycles = rng.gen_bool(0.3);
+        let g = random_graph(&mut rng, 6, cycles);
+        let expected = brute_force(&g);
+        let all = all_toposorts(&g).collect::<Vec<_>>();
+        assert_eq!(all, expected);
+
+        let by_key = toposort_by_key(&g, |v| g[v]);
+        let by = toposort_by(&g, |a, b| g[a].cmp(&g[b]));
+        let generations = topological_generations(&g);
+        if expected.is_empty() {
+            assert!(toposort(&g, None).is_err());
+            for cycle in [
+                by_key.unwrap_err(),
+                by.unwrap_err(),
+                generations.unwrap_err(),
+            ] {
+                assert!(on_cycle(&g, cycle.node_id()));
+            }
+            continue;
+        }
+
+        // The smallest order by weight, with ties broken by index.
+        let key = |order: &Vec<NodeIndex>| order.iter().map(|&v| (g[v], v)).collect::<Vec<_>>();
+        let smallest = expected.iter().min_by_key(|order| key(order)).unwrap();
+        assert_eq!(&by_key.unwrap(), smallest);
+        assert_eq!(&by.unwrap(), smallest);
+
+        // Generation `k` holds the nodes whose longest incoming path has `k` edges.
+        let mut depth = vec![0; g.node_count()];
+        for &v in &expected[0] {
+            for w in g.neighbors(v) {
+                depth[w.index()] = depth[w.index()].max(depth[v.index()] + 1);
+            }
+        }
+        let generations = generations.unwrap();
+        for (k, generation) in generations.iter().enumerate() {
+            assert!(!generation.is_empty());
+            assert!(generation.windows(2).all(|w| w[0] < w[1]));
+            assert!(generation.iter().all(|v| depth[v.index()] == k));
+        }
+        assert!(is_toposort(&g, &generations.concat()));
+    }
+}
+
+#[test]
+fn toposort_larger_graphs() {
+    let mut rng = ChaChaRng::from_seed([36; 32]);
+    for _ in 0..20 {
+        let g = random_graph(&mut rng, 300, false);
+        let order = toposort_by_key(&g, |v| (g[v], v)).unwrap();
+        assert!(is_toposort(&g, &order));
+        assert_eq!(toposort_by(&g, |a, b| (g[a], a).cmp(&(g[b], b))), Ok(order));
+        assert!(is_toposort(
+            &g,
+            &topological_generations(&g).unwrap().concat()
+        ));
+        for order in all_toposorts(&g).take(10) {
+            assert!(is_toposort(&g, &order));
+        }
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn toposort_stable_graph() {
+    let mut g = StableDiGraph::<(), ()>::from_edges([(0, 1), (1, 2), (3, 2), (2, 0)]);
+    assert!(toposort_by_key(&g, |v| v).is_err());
+    g.remove_node(n(0));
+    assert_eq!(toposort_by_key(&g, |v| v).unwrap(), [n(1), n(3), n(2)]);
+    assert_eq!(
+        topological_generations(&g).unwrap(),
+        [vec![n(1), n(3)], vec![n(2)]]
+    );
+    assert_eq!(all_toposorts(&g).count(), 2);
+}
@@ -0,0 +1,229 @@
+#[path = "utils/random.rs"]
+mod random;
+
+use hashbrown::HashSet;
+use petgraph::{
+    algo::{
+        elimination_tree_decomposition, is_tree_decomposition, perfect_elimination_ordering,
+        treewidth_exact, treewidth_min_degree, treewidth_min_fill_in,
+    },
+    graph::node_index as n,
+    prelude::*,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::random_graph;
+
+fn grid(rows: usize, columns: usize) -> UnGraph<(), ()> {
+    let mut g = UnGraph::default();
+    for _ in 0..rows * columns {
+        g.add_node(());
+    }
+    for i in 0..rows {
+        for j in 0..columns {
+            if i + 1 < rows {
+                g.add_edge(n(i * columns + j), n((i + 1) * columns + j), ());
+            }
+            if j + 1 < columns {
+                g.add_edge(n(i * columns + j), n(i * columns + j + 1), ());
+            }
+        }
+    }
+    g
+}
+
+fn width(decomposition: &UnGraph<HashSet<NodeIndex>, ()>) -> usize {
+    decomposition
+        .node_weights()
+        .map(|bag| bag.len().saturating_sub(1))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Find the treewidth by trying all elimination orderings.
+fn brute_force_treewidth(g: &UnGraph<(), ()>) -> usize {
+    fn permutations(order: &mut Vec<NodeIndex>, k: usize, f: &mut impl FnMut(&[NodeIndex])) {
+        if k == order.len() {
+            f(order);
+            return;
+        }
+        for i in k..order.len() {
+            order.swap(k, i);
+            permutations(order, k + 1, f);
+            order.swap(k, i);
+        }
+    }
+    let mut best = usize::MAX;
+    let mut order = g.node_indices().collect::<Vec<_>>();
+    permutations(&mut order, 0, &mut |order| {
+        best = best.min(elimination_tree_decomposition(g, order).1);
+    });
+    if g.node_count() == 0 { 0 } else { best }
+}
+
+#[test]
+fn treewidth_known_graphs() {
+    let empty = UnGraph::<(), ()>::default();
+    for f in [treewidth_min_degree, treewidth_min_fill_in, treewidth_exact] {
+        let (decomposition, w) = f(&empty);
+        assert_eq!(w, 0);
+        assert_eq!(decomposition.node_count(), 0);
+        assert!(is_tree_decomposition(&empty, &decomposition));
+    }
+
+    // A forest of two paths and an isolated node.
+    let mut forest = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (3, 4), (4, 5), (4, 6)]);
+    forest.add_node(());
+    let mut complete = UnGraph::<(), ()>::default();
+    for _ in 0..6 {
+        complete.add_node(());
+    }
+    for a in 0..6 {
+        for b in a + 1..6 {
+            complete.add_edge(n(a), n(b), ());
+        }
+    }
+    let cycle = UnGraph::<(), ()>::from_edges((0..8).map(|i| (i, (i + 1) % 8)));
+    for (g, expected) in [
+        (&forest, 1),
+        (&complete, 5),
+        (&cycle, 2),
+        (&grid(3, 4), 3),
+        (&grid(4, 4), 4),
+    ] {
+        for f in [treewidth_min_degree, treewidth_min_fill_in, treewidth_exact] {
+            let (decomposition, w) = f(g);
+            assert!(is_tree_decomposition(g, &decomposition));
+            assert_eq!(decomposition.node_count(), g.node_count());
+            assert_eq!(width(&decomposition), w);
+            assert!(w >= expected);
+        }
+        assert_eq!(treewidth_exact(g).1, expected);
+    }
+}
+
+#[test]
+fn treewidth_random() {
+    let mut rng = ChaChaRng::from_seed([13; 32]);
+    for _ in 0..300 {
+        let node_count = rng.gen_range(0, 7);
+        let density = rng.gen_range(0.05, 0.7);
+        let g: UnGraph<(), ()> = random_graph(&mut rng, node_count, density, |_| (), |_| ());
+        let optimum = brute_force_treewidth(&g);
+        let (decomposition, w) = treewidth_exact(&g);
+        assert_eq!(w, optimum);
+        assert!(is_tree_decomposition(&g, &decomposition));
+        assert_eq!(width(&decomposition), w);
+        for f in [treewidth_min_degree, treewidth_min_fill_in] {
+            let (decomposition, w) = f(&g);
+            assert!(w >= optimum);
+            assert!(is_tree_decomposition(&g, &decomposition));
+            assert_eq!(width(&decomposition), w);
+        }
+    }
+}
+
+#[test]
+fn treewidth_heuristics_larger_graphs() {
+    let mut rng = ChaChaRng::from_seed([14; 32]);
+    for _ in 0..30 {
+        let node_count = rng.gen_range(0, 61);
+        let density = rng.gen_range(0.05, 0.7);
+        let g: UnGraph<(), ()> = random_graph(&mut rng, node_count, density, |_| (), |_| ());
+        for f in [treewidth_min_degree, treewidth_min_fill_in] {
+            let (decomposition, w) = f(&g);
+            assert!(is_tree_decomposition(&g, &decomposition));
+            assert_eq!(width(&decomposition), w);
+        }
+    }
+}
+
+#[test]
+fn treewidth_exact_largest_graph() {
+    let g = UnGraph::<(), ()>::from_edges((0..20).map(|i| (i, (i + 1) % 20)));
+    let (decomposition, w) = treewidth_exact(&g);
+    assert_eq!(w, 2);
+    assert!(is_tree_decomposition(&g, &decomposition));
+}
+
+#[test]
+#[should_panic(expected = "treewidth_exact supports at most 20 nodes")]
+fn treewidth_exact_too_many_nodes() {
+    let g = UnGraph::<(), ()>::from_edges((0..21).map(|i| (i, (i + 1) % 21)));
+    treewidth_exact(&g);
+}
+
+#[test]
+fn chordal_elimination_tree_decomposition() {
+    // A perfect elimination ordering of a chordal graph gives a decomposition of minimum width.
+    let g = UnGraph::<(), ()>::from_edges([
+        (0, 1),
+        (0, 2),
+        (1, 2),
+        (1, 3),
+        (2, 3),
+        (3, 4),
+        (4, 5),
+        (3, 5),
+    ]);
+    let order = perfect_elimination_ordering(&g).unwrap();
+    let (decomposition, w) = elimination_tree_decomposition(&g, &order);
+    assert_eq!(w, 2);
+    assert!(is_tree_decomposition(&g, &decomposition));
+    assert_eq!(treewidth_exact(&g).1, 2);
+}
+
+#[test]
+#[should_panic]
+fn elimination_tree_decomposition_missing_node() {
+    let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+    elimination_tree_decomposition(&g, &[n(0), n(1)]);
+}
+
+#[test]
+fn is_tree_decomposition_invalid() {
+    let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 0)]);
+    let (valid, _) = treewidth_min_fill_in(&g);
+    assert!(is_tree_decomposition(&g, &valid));
+
+    // A missing node.
+    let mut with_isolated = g.clone();
+    with_isolated.add_node(());
+    assert!(!is_tree_decomposition(&with_isolated, &valid));
+
+    // A node that is not in the graph.
+    let mut decomposition = valid.clone();
+    decomposition[n(0)].insert(n(7));
+    assert!(!is_tree_decomposition(&g, &decomposition));
+
+    // A missing edge.
+    let mut decomposition = UnGraph::<HashSet<NodeIndex>, ()>::default();
+    let a = decomposition.add_node(HashSet::from_iter([n(0), n(1), n(2)]));
+    let b = decomposition.add_node(HashSet::from_iter([n(1), n(2), n(3)]));
+    decomposition.add_edge(a, b, ());
+    assert!(!is_tree_decomposition(&g, &decomposition));
+    decomposition[b].insert(n(0));
+    assert!(is_tree_decomposition(&g, &decomposition));
+
+    // Not a tree.
+    let mut decomposition = valid.clone();
+    decomposition.add_edge(n(0), n(0), ());
+    assert!(!is_tree_decomposition(&g, &decomposition));
+    let mut decomposition = valid.clone();
+    let e = decomposition.edge_indices().next().unwrap();
+    decomposition.remove_edge(e);
+    assert!(!is_tree_decomposition(&g, &decomposition));
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn treewidth_stable_graph() {
+    let mut g = StableUnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 0), (0, 2)]);
+    g.add_node(());
+    g.remove_node(n(1));
+    // A triangle and an isolated node.
+    let (decomposition, w) = treewidth_exact(&g);
+    assert_eq!(w, 2);
+    assert_eq!(decomposition.node_count(), 4);
+    assert!(is_tree_decomposition(&g, &decomposition));
+    assert!(decomposition.node_weights().all(|bag| !bag.contains(&n(1))));
+}
@@ -0,0 +1,193 @@
+#[path = "utils/random.rs"]
+mod random;
+
+use petgraph::{
+    EdgeType,
+    algo::{
+        average_clustering, clustering_coefficients, node_triangle_counts, transitivity,
+        triangle_count, triangles,
+    },
+    graph::node_index as n,
+    prelude::*,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::{assert_close, random_graph};
+
+/// The adjacency matrix of the simple undirected graph underlying `g`.
+fn adjacency<Ty: EdgeType>(g: &Graph<(), (), Ty>) -> Vec<Vec<bool>> {
+    let mut adjacency = vec![vec![false; g.node_count()]; g.node_count()];
+    for e in g.edge_references() {
+        let (a, b) = (e.source().index(), e.target().index());
+        if a != b {
+            adjacency[a][b] = true;
+            adjacency[b][a] = true;
+        }
+    }
+    adjacency
+}
+
+fn brute_force_triangles(adjacency: &[Vec<bool>]) -> Vec<[usize; 3]> {
+    let node_count = adjacency.len();
+    let mut triangles = Vec::new();
+    for a in 0..node_count {
+        for b in a + 1..node_count {
+            for c in b + 1..node_count {
+                if adjacency[a][b] && adjacency[b][c] && adjacency[a][c] {
+                    triangles.push([a, b, c]);
+                }
+            }
+        }
+    }
+    triangles
+}
+
+fn check_random<Ty: EdgeType>(seed: u8) {
+    let mut rng = ChaChaRng::from_seed([seed; 32]);
+    for _ in 0..100 {
+        let node_count = rng.gen_range(0, 25);
+        let density = rng.gen_range(0.05, 0.6);
+        let g = random_graph::<_, _, Ty>(&mut rng, node_count, density, |_| (), |_| ());
+        let adjacency = adjacency(&g);
+        let expected = brute_force_triangles(&adjacency);
+
+        let mut found = triangles(&g)
+            .into_iter()
+            .map(|mut triangle| {
+                triangle.sort();
+                triangle.map(|v| v.index())
+            })
+            .collect::<Vec<_>>();
+        found.sort();
+        assert_eq!(found, expected);
+        assert_eq!(triangle_count(&g), expected.len());
+
+        let mut counts = vec![0; g.node_count()];
+        for triangle in &expected {
+            triangle.iter().for_each(|&v| counts[v] += 1);
+        }
+        assert_eq!(node_triangle_counts(&g), counts);
+
+        let degree = adjacency
+            .iter()
+            .map(|row| row.iter().filter(|&&adjacent| adjacent).count())
+            .collect::<Vec<_>>();
+        let clustering = (0..g.node_count())
+            .map(|v| {
+                let pairs = degree[v] * degree[v].saturating_sub(1) / 2;
+                if pairs == 0 {
+                    0.
+                } else {
+                    counts[v] as f64 / pairs as f64
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_close(&clustering_coefficients(&g), &clustering);
+        let average = if g.node_count() == 0 {
+            0.
+        } else {
+            clustering.iter().sum::<f64>() / g.node_count() as f64
+        };
+        assert_close(&[average_clustering(&g)], &[average]);
+
+        let paths = degree
+            .iter()
+            .map(|d| d * d.saturating_sub(1) / 2)
+            .sum::<usize>();
+        let expected_transitivity = if paths == 0 {
+            0.
+        } else {
+            3. * expected.len() as f64 / paths as f64
+        };
+        assert_close(&[transitivity(&g)], &[expected_transitivity]);
+    }
+}
+
+#[test]
+fn triangles_random_undirected() {
+    check_random::<Undirected>(1);
+}
+
+#[test]
+fn triangles_random_directed() {
+    check_random::<Directed>(2);
+}
+
+#[test]
+fn triangles_known_graphs() {
+    // The complete graph on five nodes.
+    let mut g = UnGraph::<(), ()>::new_undirected();
+    let nodes = (0..5).map(|_| g.add_node(())).collect::<Vec<_>>();
+    for (i, &a) in nodes.iter().enumerate() {
+        for &b in &nodes[i + 1..] {
+            g.add_edge(a, b, ());
+        }
+    }
+    assert_eq!(triangle_count(&g), 10);
+    assert_eq!(node_triangle_counts(&g), [6; 5]);
+    assert_eq!(clustering_coefficients(&g), [1.; 5]);
+    assert_eq!(transitivity(&g), 1.);
+
+    // A star has no triangles.
+    let g = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3)]);
+    assert!(triangles(&g).is_empty());
+    assert_eq!(clustering_coefficients(&g), [0.; 4]);
+    assert_eq!(average_clustering(&g), 0.);
+    assert_eq!(transitivity(&g), 0.);
+
+    let g = UnGraph::<(), ()>::new_undirected();
+    assert_eq!(triangle_count(&g), 0);
+    assert_eq!(average_clustering(&g), 0.);
+    assert_eq!(transitivity(&g), 0.);
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn triangles_stable_graph() {
+    let mut g = StableUnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (1, 3), (3, 2)]);
+    g.remove_node(n(0));
+    assert_eq!(triangles(&g).len(), 1);
+    assert_eq!(node_triangle_counts(&g), [0, 1, 1, 1]);
+    assert_eq!(clustering_coefficients(&g), [0., 1., 1., 1.]);
+    assert_eq!(average_clustering(&g), 1.);
+}
+
+#[cfg(feature = "graphmap")]
+#[test]
+fn triangles_graphmap() {
+    let g = UnGraphMap::<u32, ()>::from_edges([(10, 20), (20, 30), (30, 10), (30, 40)]);
+    assert_eq!(triangle_count(&g), 1);
+    assert_eq!(transitivity(&g), 3. / 5.);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn triangles_parallel() {
+    use petgraph::algo::{
+        parallel_average_clustering, parallel_clustering_coefficients,
+        parallel_node_triangle_counts, parallel_transitivity, parallel_triangle_count,
+        parallel_triangles,
+    };
+
+    let mut rng = ChaChaRng::from_seed([3; 32]);
+    for _ in 0..50 {
+        let node_count = rng.gen_range(0, 25);
+        let density = rng.gen_range(0.05, 0.6);
+        let g = random_graph::<_, _, Undirected>(&mut rng, node_count, density, |_| (), |_| ());
+        let mut sequential = triangles(&g);
+        let mut parallel = parallel_triangles(&g);
+        sequential.sort();
+        parallel.sort();
+        assert_eq!(parallel, sequential);
+        assert_eq!(parallel_triangle_count(&g), triangle_count(&g));
+        assert_eq!(parallel_node_triangle_counts(&g), node_triangle_counts(&g));
+        assert_eq!(
+            parallel_clustering_coefficients(&g),
+            clustering_coefficients(&g)
+        );
+        assert_close(
+            &[parallel_average_clustering(&g)],
+            &[average_clustering(&g)],
+        );
+        assert_eq!(parallel_transitivity(&g), transitivity(&g));
+    }
+}
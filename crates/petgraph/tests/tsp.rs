@@ -0,0 +1,248 @@
+use petgraph::{
+    EdgeType,
+    algo::{christofides, held_karp, nearest_neighbor, or_opt, two_opt},
+    graph::node_index as n,
+    prelude::*,
+    visit::{GraphProp, IntoEdgeReferences, NodeIndexable},
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+/// Return the cost of `tour` in `g`, using the cheapest edge between consecutive nodes.
+fn tour_cost<G>(g: G, tour: &[G::NodeId]) -> Option<u32>
+where
+    G: IntoEdgeReferences<EdgeWeight = u32> + NodeIndexable + GraphProp,
+{
+    let edge = |a, b| {
+        g.edge_references()
+            .filter(|e| {
+                (e.source() == a && e.target() == b)
+                    || (!g.is_directed() && e.source() == b && e.target() == a)
+            })
+            .map(|e| *e.weight())
+            .min()
+    };
+    if tour.len() <= 1 {
+        return Some(0);
+    }
+    let mut cost = edge(tour[tour.len() - 1], tour[0])?;
+    for pair in tour.windows(2) {
+        cost += edge(pair[0], pair[1])?;
+    }
+    Some(cost)
+}
+
+/// Assert that `tour` visits every node of `g` exactly once.
+fn assert_tour(g: &Graph<(), u32, impl EdgeType>, tour: &[NodeIndex]) {
+    let mut nodes = tour.to_vec();
+    nodes.sort();
+    assert_eq!(nodes, g.node_indices().collect::<Vec<_>>());
+}
+
+/// Find the cost of an optimal tour by trying all permutations.
+fn brute_force<Ty: EdgeType>(g: &Graph<(), u32, Ty>) -> Option<u32> {
+    fn extend<Ty: EdgeType>(
+        g: &Graph<(), u32, Ty>,
+        tour: &mut Vec<NodeIndex>,
+        best: &mut Option<u32>,
+    ) {
+        if tour.len() == g.node_count() {
+            if let Some(cost) = tour_cost(g, tour) {
+                *best = Some(best.map_or(cost, |b| b.min(cost)));
+            }
+            return;
+        }
+        for v in g.node_indices() {
+            if !tour.contains(&v) {
+                tour.push(v);
+                extend(g, tour, best);
+                tour.pop();
+            }
+        }
+    }
+    let mut best = None;
+    let mut tour = vec![n(0)];
+    extend(g, &mut tour, &mut best);
+    best
+}
+
+/// Generate a complete graph on random points in the plane, with the Manhattan distance as cost.
+fn random_metric_graph(rng: &mut ChaChaRng, node_count: usize) -> UnGraph<(), u32> {
+    let points = (0..node_count)
+        .map(|_| (rng.gen_range(0, 100i32), rng.gen_range(0, 100i32)))
+        .collect::<Vec<_>>();
+    let mut g = UnGraph::with_capacity(node_count, node_count * node_count);
+    for _ in 0..node_count {
+        g.add_node(());
+    }
+    for a in 0..node_count {
+        for b in a + 1..node_count {
+            let (p, q) = (points[a], points[b]);
+            g.add_edge(n(a), n(b), p.0.abs_diff(q.0) + p.1.abs_diff(q.1));
+        }
+    }
+    g
+}
+
+#[test]
+fn tsp_trivial_graphs() {
+    let mut g = UnGraph::<(), u32>::new_undirected();
+    assert_eq!(held_karp(&g, |e| *e.weight()), Some((0, vec![])));
+    assert_eq!(christofides(&g, |e| *e.weight()), Some((0, vec![])));
+    assert_eq!(nearest_neighbor(&g, |e| *e.weight()), Some((0, vec![])));
+
+    let a = g.add_node(());
+    assert_eq!(held_karp(&g, |e| *e.weight()), Some((0, vec![a])));
+    assert_eq!(christofides(&g, |e| *e.weight()), Some((0, vec![a])));
+
+    // Two nodes: the tour goes back and forth.
+    let b = g.add_node(());
+    g.add_edge(a, b, 3);
+    assert_eq!(held_karp(&g, |e| *e.weight()), Some((6, vec![a, b])));
+    assert_eq!(christofides(&g, |e| *e.weight()), Some((6, vec![a, b])));
+    assert_eq!(nearest_neighbor(&g, |e| *e.weight()), Some((6, vec![a, b])));
+}
+
+#[test]
+fn tsp_missing_edges() {
+    // A path has no tour.
+    let g = UnGraph::<(), u32>::from_edges([(0, 1, 1), (1, 2, 1), (2, 3, 1)]);
+    assert!(held_karp(&g, |e| *e.weight()).is_none());
+    assert!(christofides(&g, |e| *e.weight()).is_none());
+    assert!(nearest_neighbor(&g, |e| *e.weight()).is_none());
+    assert!(two_opt(&g, &[n(0), n(1), n(2), n(3)], |e| *e.weight()).is_none());
+    assert!(or_opt(&g, &[n(0), n(1), n(2), n(3)], |e| *e.weight()).is_none());
+
+    // A cycle has exactly one tour.
+    let g = UnGraph::<(), u32>::from_edges([(0, 2, 1), (2, 1, 1), (1, 3, 1), (3, 0, 1)]);
+    let (cost, tour) = held_karp(&g, |e| *e.weight()).unwrap();
+    assert_eq!(cost, 4);
+    assert!(tour == [n(0), n(2), n(1), n(3)] || tour == [n(0), n(3), n(1), n(2)]);
+}
+
+#[test]
+fn tsp_parallel_edges_and_self_loops() {
+    let mut g = UnGraph::<(), u32>::from_edges([(0, 1, 5), (1, 2, 5), (2, 0, 5)]);
+    g.add_edge(n(1), n(0), 1);
+    g.add_edge(n(1), n(1), 0);
+    let (cost, tour) = held_karp(&g, |e| *e.weight()).unwrap();
+    assert_eq!(cost, 11);
+    assert_tour(&g, &tour);
+    assert_eq!(nearest_neighbor(&g, |e| *e.weight()).unwrap().0, 11);
+}
+
+#[test]
+fn held_karp_directed_random() {
+    let mut rng = ChaChaRng::from_seed([5; 32]);
+    for _ in 0..100 {
+        let node_count = rng.gen_range(2, 7);
+        let mut g = DiGraph::<(), u32>::with_capacity(node_count, 0);
+        for _ in 0..node_count {
+            g.add_node(());
+        }
+        for _ in 0..rng.gen_range(0, node_count * node_count) {
+            let (a, b) = (rng.gen_range(0, node_count), rng.gen_range(0, node_count));
+            g.add_edge(n(a), n(b), rng.gen_range(0, 20));
+        }
+
+        let result = held_karp(&g, |e| *e.weight());
+        assert_eq!(result.as_ref().map(|r| r.0), brute_force(&g));
+        if let Some((cost, tour)) = result {
+            assert_tour(&g, &tour);
+            assert_eq!(tour[0], n(0));
+            assert_eq!(tour_cost(&g, &tour), Some(cost));
+        }
+    }
+}
+
+#[test]
+fn held_karp_largest_graph() {
+    let g = DiGraph::<(), u32>::from_edges((0..20).map(|i| (i, (i + 1) % 20, 1)));
+    let (cost, tour) = held_karp(&g, |e| *e.weight()).unwrap();
+    assert_eq!(cost, 20);
+    assert_eq!(tour, (0..20).map(n).collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic(expected = "held_karp supports at most 20 nodes")]
+fn held_karp_too_many_nodes() {
+    let g = DiGraph::<(), u32>::from_edges((0..21).map(|i| (i, (i + 1) % 21, 1)));
+    held_karp(&g, |e| *e.weight());
+}
+
+#[test]
+fn tsp_metric_random() {
+    let mut rng = ChaChaRng::from_seed([11; 32]);
+    for _ in 0..100 {
+        let node_count = rng.gen_range(3, 8);
+        let g = random_metric_graph(&mut rng, node_count);
+        let optimum = brute_force(&g).unwrap();
+
+        let (cost, tour) = held_karp(&g, |e| *e.weight()).unwrap();
+        assert_eq!(cost, optimum);
+        assert_tour(&g, &tour);
+
+        let (cost, tour) = christofides(&g, |e| *e.weight()).unwrap();
+        assert_tour(&g, &tour);
+        assert_eq!(tour_cost(&g, &tour), Some(cost));
+        assert!(2 * cost <= 3 * optimum, "{cost} exceeds 3/2 of {optimum}");
+
+        let (mut cost, mut tour) = nearest_neighbor(&g, |e| *e.weight()).unwrap();
+        assert_tour(&g, &tour);
+        assert_eq!(tour_cost(&g, &tour), Some(cost));
+        for step in 0..3 {
+            let (improved, improved_tour) = if step == 1 {
+                or_opt(&g, &tour, |e| *e.weight()).unwrap()
+            } else {
+                two_opt(&g, &tour, |e| *e.weight()).unwrap()
+            };
+            assert!(improved <= cost);
+            assert!(improved >= optimum);
+            assert_tour(&g, &improved_tour);
+            assert_eq!(tour_cost(&g, &improved_tour), Some(improved));
+            (cost, tour) = (improved, improved_tour);
+        }
+    }
+}
+
+#[test]
+fn two_opt_uncrosses_tour() {
+    // The corners of a unit square, visited along the diagonals.
+    let mut g = UnGraph::<(), f64>::new_undirected();
+    let corners = [(0., 0.), (1., 0.), (1., 1.), (0., 1.)];
+    let nodes = corners.map(|_| g.add_node(()));
+    for a in 0..4 {
+        for b in a + 1..4 {
+            let (p, q): ((f64, f64), (f64, f64)) = (corners[a], corners[b]);
+            g.add_edge(nodes[a], nodes[b], (p.0 - q.0).hypot(p.1 - q.1));
+        }
+    }
+    let crossed = [nodes[0], nodes[2], nodes[1], nodes[3]];
+    let (cost, tour) = two_opt(&g, &crossed, |e| *e.weight()).unwrap();
+    assert!((cost - 4.).abs() < 1e-9);
+    assert_eq!(tour, vec![nodes[0], nodes[1], nodes[2], nodes[3]]);
+
+    // Or-opt fixes it by moving a single node.
+    let (cost, _) = or_opt(&g, &crossed, |e| *e.weight()).unwrap();
+    assert!((cost - 4.).abs() < 1e-9);
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn tsp_stable_graph() {
+    let mut g = StableUnGraph::<(), u32>::default();
+    let nodes = (0..5).map(|_| g.add_node(())).collect::<Vec<_>>();
+    for (i, &a) in nodes.iter().enumerate() {
+        for (j, &b) in nodes.iter().enumerate().skip(i + 1) {
+            g.add_edge(a, b, (j - i) as u32);
+        }
+    }
+    g.remove_node(nodes[2]);
+
+    let (cost, tour) = held_karp(&g, |e| *e.weight()).unwrap();
+    assert_eq!(cost, 8);
+    assert_eq!(tour.len(), 4);
+    assert!(!tour.contains(&nodes[2]));
+    assert_eq!(christofides(&g, |e| *e.weight()).unwrap().0, 8);
+    let (_, tour) = nearest_neighbor(&g, |e| *e.weight()).unwrap();
+    assert_eq!(two_opt(&g, &tour, |e| *e.weight()).unwrap().0, 8);
+}
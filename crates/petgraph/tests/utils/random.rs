@@ -0,0 +1,125 @@
+// Random graph helpers shared by the tests, included with `#[path]` since each test only uses
+// some of them.
+
+use hashbrown::HashSet;
+use petgraph::{
+    EdgeType,
+    graph::{Graph, node_index as n},
+    visit::EdgeRef,
+};
+use rand::{ChaChaRng, Rng};
+
+/// Generate a graph with `node_count` nodes, where every pair of nodes is joined with probability
+/// `density`, and some pairs get a parallel edge. A node is joined to itself with a tenth of that
+/// probability.
+#[allow(dead_code)]
+pub fn random_graph<N, E, Ty: EdgeType>(
+    rng: &mut ChaChaRng,
+    node_count: usize,
+    density: f64,
+    mut node_weight: impl FnMut(&mut ChaChaRng) -> N,
+    mut edge_weight: impl FnMut(&mut ChaChaRng) -> E,
+) -> Graph<N, E, Ty> {
+    let mut g = Graph::with_capacity(node_count, 0);
+    for _ in 0..node_count {
+        g.add_node(node_weight(rng));
+    }
+    for a in 0..node_count {
+        for b in 0..node_count {
+            if (Ty::is_directed() || a <= b)
+                && rng.gen_bool(density)
+                && (a != b || rng.gen_bool(0.1))
+            {
+                let weight = edge_weight(rng);
+                g.add_edge(n(a), n(b), weight);
+                if rng.gen_bool(0.05) {
+                    // Undirected parallel edges go the other way.
+                    let (c, d) = if Ty::is_directed() { (a, b) } else { (b, a) };
+                    let weight = edge_weight(rng);
+                    g.add_edge(n(c), n(d), weight);
+                }
+            }
+        }
+    }
+    g
+}
+
+/// Generate a graph with `node_count` nodes and `edge_count` edges between random endpoints,
+/// which includes self-loops and parallel edges. There are no edges without nodes.
+#[allow(dead_code)]
+pub fn random_multigraph<N, E, Ty: EdgeType>(
+    rng: &mut ChaChaRng,
+    node_count: usize,
+    edge_count: usize,
+    mut node_weight: impl FnMut(&mut ChaChaRng) -> N,
+    mut edge_weight: impl FnMut(&mut ChaChaRng) -> E,
+) -> Graph<N, E, Ty> {
+    let mut g = Graph::with_capacity(node_count, 0);
+    for _ in 0..node_count {
+        g.add_node(node_weight(rng));
+    }
+    if node_count > 0 {
+        for _ in 0..edge_count {
+            let (a, b) = (rng.gen_range(0, node_count), rng.gen_range(0, node_count));
+            let weight = edge_weight(rng);
+            g.add_edge(n(a), n(b), weight);
+        }
+    }
+    g
+}
+
+/// Remove all edges but one between every pair of nodes.
+#[allow(dead_code)]
+pub fn remove_parallel_edges<N, E, Ty: EdgeType>(g: &mut Graph<N, E, Ty>) {
+    let mut seen = HashSet::new();
+    g.retain_edges(|g, e| {
+        let (a, b) = g.edge_endpoints(e).unwrap();
+        seen.insert(if Ty::is_directed() || a <= b {
+            (a, b)
+        } else {
+            (b, a)
+        })
+    });
+}
+
+/// Shuffle the nodes and edges of `g`, and the endpoints of undirected edges. Returns the new
+/// graph and the new index of every node.
+#[allow(dead_code)]
+pub fn permute<N: Clone, E: Clone, Ty: EdgeType>(
+    g: &Graph<N, E, Ty>,
+    rng: &mut ChaChaRng,
+) -> (Graph<N, E, Ty>, Vec<usize>) {
+    let mut order = (0..g.node_count()).collect::<Vec<_>>();
+    rng.shuffle(&mut order);
+    let mut position = vec![0; order.len()];
+    for (i, &v) in order.iter().enumerate() {
+        position[v] = i;
+    }
+    let mut h = Graph::with_capacity(g.node_count(), g.edge_count());
+    for &v in &order {
+        h.add_node(g[n(v)].clone());
+    }
+    let mut edges = g.edge_references().collect::<Vec<_>>();
+    rng.shuffle(&mut edges);
+    for e in edges {
+        let (a, b) = (position[e.source().index()], position[e.target().index()]);
+        if !Ty::is_directed() && rng.gen_bool(0.5) {
+            h.add_edge(n(b), n(a), e.weight().clone());
+        } else {
+            h.add_edge(n(a), n(b), e.weight().clone());
+        }
+    }
+    (h, position)
+}
+
+/// Assert that `actual` and `expected` only differ by rounding errors.
+#[allow(dead_code)]
+pub fn assert_close(actual: &[f64], expected: &[f64]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!(
+            (a - e).abs() <= 1e-9 * e.abs().max(1.),
+            "{actual:?} != {expected:?}"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+#[path = "utils/random.rs"]
+mod random;
+
+use petgraph::{
+    EdgeType,
+    algo::{weisfeiler_lehman_graph_hash, weisfeiler_lehman_subtree_hashes},
+    graph::node_index as n,
+    prelude::*,
+};
+use rand::{ChaChaRng, Rng, SeedableRng};
+use random::{permute, random_graph};
+
+fn hash<Ty: EdgeType>(g: &Graph<u8, u8, Ty>) -> u64 {
+    weisfeiler_lehman_graph_hash(g, 3, |v| g[v], |e| *e.weight())
+}
+
+fn check_permuted<Ty: EdgeType>(seed: u8) {
+    let mut rng = ChaChaRng::from_seed([seed; 32]);
+    for _ in 0..100 {
+        let node_count = rng.gen_range(0, 20);
+        let density = rng.gen_range(0.05, 0.5);
+        let g: Graph<u8, u8, Ty> = random_graph(
+            &mut rng,
+            node_count,
+            density,
+            |rng| rng.gen_range(0, 3),
+            |rng| rng.gen_range(0, 2),
+        );
+        let (h, position) = permute(&g, &mut rng);
+        assert_eq!(hash(&g), hash(&h));
+
+        let g_hashes = weisfeiler_lehman_subtree_hashes(&g, 3, |v| g[v], |e| *e.weight());
+        let h_hashes = weisfeiler_lehman_subtree_hashes(&h, 3, |v| h[v], |e| *e.weight());
+        for v in g.node_indices() {
+            assert_eq!(g_hashes[v.index()].len(), 4);
+            assert_eq!(g_hashes[v.index()], h_hashes[position[v.index()]]);
+        }
+    }
+}
+
+#[test]
+fn weisfeiler_lehman_permuted_undirected() {
+    check_permuted::<Undirected>(1);
+}
+
+#[test]
+fn weisfeiler_lehman_permuted_directed() {
+    check_permuted::<Directed>(2);
+}
+
+#[test]
+fn weisfeiler_lehman_distinguishes() {
+    // A path and a star.
+    let path = UnGraph::<u8, u8>::from_edges([(0, 1), (1, 2), (2, 3)]);
+    let star = UnGraph::<u8, u8>::from_edges([(0, 1), (0, 2), (0, 3)]);
+    assert_ne!(hash(&path), hash(&star));
+
+    // The same edges, directed and undirected.
+    let directed = DiGraph::<u8, u8>::from_edges([(0, 1), (1, 2), (2, 3)]);
+    assert_ne!(
+        weisfeiler_lehman_graph_hash(&path, 3, |_| (), |_| ()),
+        weisfeiler_lehman_graph_hash(&directed, 3, |_| (), |_| ())
+    );
+
+    // A directed path and its reverse are isomorphic, but the direction of the edges counts.
+    let reversed = DiGraph::<u8, u8>::from_edges([(3, 2), (2, 1), (1, 0)]);
+    let branching = DiGraph::<u8, u8>::from_edges([(0, 1), (1, 2), (3, 2)]);
+    assert_eq!(hash(&directed), hash(&reversed));
+    assert_ne!(hash(&directed), hash(&branching));
+
+    // Edge labels count.
+    let labeled = UnGraph::<u8, u8>::from_edges([(0, 1, 0), (1, 2, 1), (2, 3, 0)]);
+    let relabeled = UnGraph::<u8, u8>::from_edges([(0, 1, 1), (1, 2, 0), (2, 3, 0)]);
+    assert_ne!(hash(&labeled), hash(&relabeled));
+
+    // The number of isolated nodes counts.
+    let mut more = path.clone();
+    more.add_node(0);
+    assert_ne!(hash(&path), hash(&more));
+}
+
+#[test]
+fn weisfeiler_lehman_regular_graphs() {
+    // Two triangles and a hexagon are not isomorphic, but refinement cannot tell them apart.
+    let triangles = UnGraph::<u8, u8>::from_edges([(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)]);
+    let hexagon = UnGraph::<u8, u8>::from_edges([(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)]);
+    assert_eq!(hash(&triangles), hash(&hexagon));
+}
+
+#[test]
+fn weisfeiler_lehman_is_stable() {
+    // The hash does not depend on the run or the platform.
+    let g = UnGraph::<u8, u8>::from_edges([(0, 1), (1, 2), (2, 0), (2, 3)]);
+    assert_eq!(hash(&g), 16144097263039011656);
+
+    let empty = UnGraph::<u8, u8>::new_undirected();
+    assert_ne!(
+        hash(&empty),
+        weisfeiler_lehman_graph_hash(&empty, 0, |_| (), |_| ())
+    );
+    assert!(weisfeiler_lehman_subtree_hashes(&empty, 3, |_| (), |_| ()).is_empty());
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn weisfeiler_lehman_stable_graph() {
+    let mut g = StableUnGraph::<u8, u8>::from_edges([(0, 1), (1, 2), (2, 3), (3, 1)]);
+    g.remove_node(n(0));
+    let h = UnGraph::<u8, u8>::from_edges([(0, 1), (1, 2), (2, 0)]);
+    assert_eq!(
+        weisfeiler_lehman_graph_hash(&g, 3, |_| (), |_| ()),
+        weisfeiler_lehman_graph_hash(&h, 3, |_| (), |_| ())
+    );
+
+    let hashes = weisfeiler_lehman_subtree_hashes(&g, 2, |_| (), |_| ());
+    assert!(hashes[0].is_empty());
+    assert_eq!(hashes[1].len(), 3);
+    assert_eq!(hashes[1], hashes[3]);
+}